@@ -0,0 +1,24 @@
+#![no_main]
+
+use lalg::{Aabb, Bvh, Vec4};
+use libfuzzer_sys::fuzz_target;
+
+// A hierarchy over arbitrary boxes, including inverted, coincident and
+// non-finite ones. Building must terminate, and a range query must return
+// exactly the stored boxes that a linear scan finds.
+fuzz_target!(|data: &[u8]| {
+    let floats: Vec<f32> = data.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    let boxes: Vec<Aabb> = floats.chunks_exact(6)
+        .map(|b| Aabb::new(Vec4::new(b[0], b[1], b[2], 0.0), Vec4::new(b[3], b[4], b[5], 0.0)))
+        .collect();
+    let Some(region) = boxes.first().copied() else {
+        return;
+    };
+    let tree = Bvh::build(boxes.into_iter().enumerate().map(|(id, bounds)| (bounds, id)));
+    let mut found: Vec<usize> = tree.query_range(&region).into_iter().copied().collect();
+    found.sort();
+    let mut expected: Vec<usize> = tree.iter().filter(|(bounds, _)| bounds.intersects(&region)).map(|(_, &id)| id).collect();
+    expected.sort();
+    assert_eq!(found, expected);
+    let _ = tree.query_ray(&region.min, &(region.max - region.min), f32::INFINITY);
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use lalg::{convex_hull_2d, minimum_area_rect, minimum_enclosing_sphere, minimum_volume_obb, Vec4};
+use libfuzzer_sys::fuzz_target;
+
+// Point sets with NaNs, infinities, duplicates and collinear runs through
+// the hull and the bounding volumes built on it. The hull may only contain
+// finite input points.
+fuzz_target!(|data: &[u8]| {
+    let floats: Vec<f32> = data.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    let points: Vec<(f32, f32)> = floats.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+    let hull = convex_hull_2d(&points);
+    assert!(hull.len() <= points.len());
+    assert!(hull.iter().all(|p| p.0.is_finite() && p.1.is_finite() && points.contains(p)));
+    let _ = minimum_area_rect(&points);
+
+    let points: Vec<Vec4> = floats.chunks_exact(3).map(|p| Vec4::new(p[0], p[1], p[2], 0.0)).collect();
+    let _ = minimum_enclosing_sphere(&points);
+    let _ = minimum_volume_obb(&points);
+});
@@ -0,0 +1,25 @@
+#![no_main]
+
+use lalg::{Mat4, Transform};
+use libfuzzer_sys::fuzz_target;
+
+// Decomposing any affine matrix, degenerate or not, must not panic, and a
+// finite input must give a finite rotation.
+fuzz_target!(|data: &[u8]| {
+    let floats: Vec<f32> = data.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    let Some(elements) = floats.get(..12) else {
+        return;
+    };
+    let mut m = Mat4::identity();
+    for col in 0..4 {
+        for row in 0..3 {
+            m[(row, col)] = elements[col * 3 + row];
+        }
+    }
+    let (_, rotation, _) = m.decompose();
+    if m.is_finite() {
+        assert!(rotation.is_finite());
+    }
+    let _ = Transform::from_mat4(&m).to_mat4();
+    let _ = m.log();
+});
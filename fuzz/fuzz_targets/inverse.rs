@@ -0,0 +1,29 @@
+#![no_main]
+
+use lalg::{InverseMethod, Mat4};
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary matrices, including NaN, infinite and singular ones, through
+// every inverse. None may panic; whatever `try_inverse` accepts must be
+// finite.
+fuzz_target!(|data: &[u8]| {
+    let Some(m) = mat4(data) else {
+        return;
+    };
+    if let Ok(inverse) = m.try_inverse() {
+        assert!(inverse.is_finite());
+    }
+    if let Ok(inverse) = m.lu().inverse() {
+        let _ = inverse * m;
+    }
+    let _ = m.normal_matrix();
+    let (inverse, method) = m.robust_inverse();
+    if m.is_finite() && method == InverseMethod::Exact {
+        assert!(inverse.is_finite());
+    }
+});
+
+fn mat4(data: &[u8]) -> Option<Mat4> {
+    let floats: Vec<f32> = data.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    Some(Mat4::from_cols_array(floats.get(..16)?.try_into().ok()?))
+}
@@ -0,0 +1,24 @@
+#![no_main]
+
+use lalg::{Mat4, Vec4};
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary text through the FromStr parsers: malformed input must come
+// back as a ParseError, and whatever parses must print back to a string
+// that parses to the same value. Display prints the shortest string that
+// round-trips, so comparing the printed forms compares the values, NaN
+// included.
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(v) = s.parse::<Vec4>() {
+        let printed = v.to_string();
+        assert_eq!(printed.parse::<Vec4>().unwrap().to_string(), printed);
+    }
+    let _ = s.parse::<Vec4<f64>>();
+    if let Ok(m) = s.parse::<Mat4>() {
+        let printed = m.to_string();
+        assert_eq!(printed.parse::<Mat4>().unwrap().to_string(), printed);
+    }
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+use lalg::{read_snapshot, SnapshotView};
+use libfuzzer_sys::fuzz_target;
+
+// Untrusted snapshot bytes: both readers must reject malformed input with
+// an error rather than panicking, reading out of bounds or allocating
+// without bound.
+fuzz_target!(|data: &[u8]| {
+    let _ = read_snapshot(data);
+    if let Ok(view) = SnapshotView::new(data) {
+        for values in view.vec4s() {
+            let _ = values.iter().map(|v| v.x).sum::<f32>();
+        }
+        for values in view.mat4s() {
+            let _ = values.iter().map(|m| m[(0, 0)]).sum::<f32>();
+        }
+    }
+});
@@ -0,0 +1,45 @@
+#![no_main]
+
+use lalg::{DMat, DVec, Mat4, Matrix, Vec4, Vector};
+use libfuzzer_sys::fuzz_target;
+
+// The LU, QR and SVD paths plus the fixed and dynamic solvers on arbitrary
+// systems. Singular, NaN and mismatched inputs must come back as errors or
+// non-finite values, never as panics or hangs.
+fuzz_target!(|data: &[u8]| {
+    let Some((&size, data)) = data.split_first() else {
+        return;
+    };
+    let floats: Vec<f32> = data.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    if floats.len() < 20 {
+        return;
+    }
+    let m = Mat4::from_cols_array(floats[..16].try_into().unwrap());
+    let b = Vec4::new(floats[16], floats[17], floats[18], floats[19]);
+    let _ = m.lu().solve(&b);
+    let _ = m.solve(b);
+    let (q, r) = m.qr();
+    let _ = q * r;
+    let (u, sigma, v) = m.svd();
+    let _ = (u, sigma, v.transpose());
+    let _ = m.pinv(1e-6);
+
+    let fixed = Matrix::<f32, 4, 3>::from_cols([0, 1, 2].map(|col| std::array::from_fn(|row| floats[col * 4 + row])));
+    let _ = fixed.least_squares(&Vector::new([b.x, b.y, b.z, b.w]));
+    let square = Matrix::<f32, 3, 3>::from_cols([0, 1, 2].map(|col| std::array::from_fn(|row| floats[col * 3 + row])));
+    let _ = square.solve(&Vector::new([b.x, b.y, b.z]));
+    let _ = square.symmetric_eigen(1e-6);
+
+    // A dynamic system of the size picked by the first byte, filled from
+    // the floats, with a deliberately mismatched right-hand side half the
+    // time.
+    let n = (size % 8) as usize + 1;
+    let values: Vec<f64> = floats.iter().cycle().take(n * n).map(|&f| f as f64).collect();
+    let a = DMat::from_col_slice(n, n, &values);
+    let rhs_len = if size & 0x80 != 0 { n + 1 } else { n };
+    let rhs = DVec::new(values.iter().copied().cycle().take(rhs_len).collect());
+    let _ = a.solve(&rhs);
+    let _ = a.least_squares(&rhs);
+    let _ = a.cholesky();
+    let _ = a.pinv(1e-9);
+});
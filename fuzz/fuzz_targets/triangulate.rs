@@ -0,0 +1,16 @@
+#![no_main]
+
+use lalg::triangulate_polygon;
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary polygons, including self-intersecting, collinear and
+// non-finite ones. Triangulation must terminate, and a polygon of n
+// vertices gives at most n - 2 triangles indexing into it.
+fuzz_target!(|data: &[u8]| {
+    let floats: Vec<f32> = data.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    let polygon: Vec<(f32, f32)> = floats.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+    if let Some(triangles) = triangulate_polygon(&polygon) {
+        assert!(triangles.len() <= polygon.len().saturating_sub(2));
+        assert!(triangles.iter().flatten().all(|&i| i < polygon.len()));
+    }
+});
@@ -0,0 +1,128 @@
+use crate::closest_point::ClosestPoint;
+use crate::vec4::Vec4;
+
+// Axis-aligned bounding box. Only x, y and z are used; w is kept at zero.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb {
+    pub min: Vec4,
+    pub max: Vec4
+}
+
+fn xyz(v: &Vec4) -> Vec4 {
+    Vec4::new(v.x, v.y, v.z, 0.0)
+}
+
+#[allow(dead_code)]
+impl Aabb {
+    pub fn new(min: Vec4, max: Vec4) -> Self {
+        Self {
+            min: xyz(&min),
+            max: xyz(&max)
+        }
+    }
+
+    pub fn from_point(point: &Vec4) -> Self {
+        Self::new(*point, *point)
+    }
+
+    pub fn from_center_half_extents(center: &Vec4, half_extents: &Vec4) -> Self {
+        Self::new(*center - *half_extents, *center + *half_extents)
+    }
+
+    pub fn from_points(points: &[Vec4]) -> Option<Self> {
+        let (first, rest) = points.split_first()?;
+        let mut out = Self::from_point(first);
+        for p in rest {
+            out = out.union(&Self::from_point(p));
+        }
+        Some(out)
+    }
+
+    pub fn center(&self) -> Vec4 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec4 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self {
+            min: Vec4::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y), self.min.z.min(other.min.z), 0.0),
+            max: Vec4::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y), self.max.z.max(other.max.z), 0.0)
+        }
+    }
+
+    pub fn contains_point(&self, point: &Vec4) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x
+            && point.y >= self.min.y && point.y <= self.max.y
+            && point.z >= self.min.z && point.z <= self.max.z
+    }
+
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x
+            && self.min.y <= other.max.y && self.max.y >= other.min.y
+            && self.min.z <= other.max.z && self.max.z >= other.min.z
+    }
+
+    // Slab test. Returns the entry and exit distances along `direction`,
+    // with the entry clamped to zero when the origin is inside the box.
+    pub fn ray_intersection(&self, origin: &Vec4, direction: &Vec4) -> Option<(f32, f32)> {
+        let mut t_min = 0.0f32;
+        let mut t_max = f32::INFINITY;
+        for axis in 0..3 {
+            let inv = 1.0 / direction[axis];
+            let mut t0 = (self.min[axis] - origin[axis]) * inv;
+            let mut t1 = (self.max[axis] - origin[axis]) * inv;
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            if t0.is_nan() || t1.is_nan() {
+                // Ray parallel to and exactly on a slab plane.
+                continue;
+            }
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+            if t_min > t_max {
+                return None;
+            }
+        }
+        Some((t_min, t_max))
+    }
+
+    pub fn support(&self, direction: &Vec4) -> Vec4 {
+        Vec4::new(
+            if direction.x >= 0.0 { self.max.x } else { self.min.x },
+            if direction.y >= 0.0 { self.max.y } else { self.min.y },
+            if direction.z >= 0.0 { self.max.z } else { self.min.z },
+            1.0
+        )
+    }
+
+    pub fn volume(&self) -> f32 {
+        let size = self.max - self.min;
+        size.x * size.y * size.z
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        let size = self.max - self.min;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+}
+
+impl ClosestPoint for Aabb {
+    fn closest_point(&self, point: &Vec4) -> Vec4 {
+        Vec4::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z),
+            1.0
+        )
+    }
+}
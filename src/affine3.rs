@@ -0,0 +1,256 @@
+use crate::error::MathError;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// 3x4 affine transform stored column-major like Mat4, with the implicit
+// bottom row (0, 0, 0, 1) dropped: x axis, y axis, z axis, translation.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Affine3 {
+    elements: [f32; 12],
+}
+
+#[allow(dead_code)]
+impl Affine3 {
+    pub fn identity() -> Self {
+        let mut elements = [0.0; 12];
+        elements[0] = 1.0;
+        elements[4] = 1.0;
+        elements[8] = 1.0;
+        Self {
+            elements
+        }
+    }
+
+    pub fn from_cols(x_axis: Vec4, y_axis: Vec4, z_axis: Vec4, translation: Vec4) -> Self {
+        Self {
+            elements: [
+                x_axis.x, x_axis.y, x_axis.z,
+                y_axis.x, y_axis.y, y_axis.z,
+                z_axis.x, z_axis.y, z_axis.z,
+                translation.x, translation.y, translation.z
+            ]
+        }
+    }
+
+    // Drops the bottom row of `m`, which is assumed to be (0, 0, 0, 1).
+    pub fn from_mat4(m: &Mat4) -> Self {
+        let mut elements = [0.0; 12];
+        for col in 0..4 {
+            for row in 0..3 {
+                elements[col * 3 + row] = m.elements[col * 4 + row];
+            }
+        }
+        Self {
+            elements
+        }
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        let mut out = Mat4::zeroes();
+        for col in 0..4 {
+            for row in 0..3 {
+                out.elements[col * 4 + row] = self.elements[col * 3 + row];
+            }
+        }
+        out.elements[15] = 1.0;
+        out
+    }
+
+    pub fn x_vector(&self) -> Vec4 {
+        Vec4::new(self.elements[0], self.elements[1], self.elements[2], 0.0)
+    }
+
+    pub fn y_vector(&self) -> Vec4 {
+        Vec4::new(self.elements[3], self.elements[4], self.elements[5], 0.0)
+    }
+
+    pub fn z_vector(&self) -> Vec4 {
+        Vec4::new(self.elements[6], self.elements[7], self.elements[8], 0.0)
+    }
+
+    pub fn position(&self) -> Vec4 {
+        Vec4::new(self.elements[9], self.elements[10], self.elements[11], 1.0)
+    }
+
+    pub fn transform_point(&self, point: &Vec4) -> Vec4 {
+        *self * Vec4::new(point.x, point.y, point.z, 1.0)
+    }
+
+    pub fn transform_vector(&self, vector: &Vec4) -> Vec4 {
+        *self * Vec4::new(vector.x, vector.y, vector.z, 0.0)
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let e = &self.elements;
+        e[0] * (e[4] * e[8] - e[7] * e[5])
+            - e[3] * (e[1] * e[8] - e[7] * e[2])
+            + e[6] * (e[1] * e[5] - e[4] * e[2])
+    }
+
+    // General affine inverse through the 3x3 adjugate. Fails with
+    // `NotFinite` on a NaN or infinite element and with `Singular` when the
+    // determinant is rounding error next to the product of the axis lengths,
+    // its largest possible magnitude, so the test ignores overall scale.
+    pub fn inverse(&self) -> Result<Self, MathError> {
+        if !self.elements.iter().all(|e| e.is_finite()) {
+            return Err(MathError::NotFinite);
+        }
+        let det = self.determinant();
+        let bound = self.x_vector().length() * self.y_vector().length() * self.z_vector().length();
+        if det.abs() <= f32::EPSILON * 16.0 * bound {
+            return Err(MathError::Singular);
+        }
+        let e = &self.elements;
+        let inv_det = 1.0 / det;
+        let mut elements = [0.0; 12];
+        elements[0] = (e[4] * e[8] - e[7] * e[5]) * inv_det;
+        elements[1] = (e[7] * e[2] - e[1] * e[8]) * inv_det;
+        elements[2] = (e[1] * e[5] - e[4] * e[2]) * inv_det;
+        elements[3] = (e[6] * e[5] - e[3] * e[8]) * inv_det;
+        elements[4] = (e[0] * e[8] - e[6] * e[2]) * inv_det;
+        elements[5] = (e[3] * e[2] - e[0] * e[5]) * inv_det;
+        elements[6] = (e[3] * e[7] - e[6] * e[4]) * inv_det;
+        elements[7] = (e[6] * e[1] - e[0] * e[7]) * inv_det;
+        elements[8] = (e[0] * e[4] - e[3] * e[1]) * inv_det;
+        let mut out = Self {
+            elements
+        };
+        let t = out.transform_vector(&Vec4::new(e[9], e[10], e[11], 0.0));
+        out.elements[9] = -t.x;
+        out.elements[10] = -t.y;
+        out.elements[11] = -t.z;
+        Ok(out)
+    }
+
+    // Inverse of a rotation + translation transform, using the transpose of
+    // the linear part. Only valid when the axes are orthonormal.
+    pub fn rigid_inverse(&self) -> Self {
+        let e = &self.elements;
+        let mut elements = [
+            e[0], e[3], e[6],
+            e[1], e[4], e[7],
+            e[2], e[5], e[8],
+            0.0, 0.0, 0.0
+        ];
+        for row in 0..3 {
+            elements[9 + row] = -(elements[row] * e[9] + elements[3 + row] * e[10] + elements[6 + row] * e[11]);
+        }
+        Self {
+            elements
+        }
+    }
+}
+
+impl std::ops::MulAssign<Affine3> for Affine3 {
+    fn mul_assign(&mut self, rhs: Affine3) {
+        let mut result = [0.0; 12];
+        for col in 0..4 {
+            for row in 0..3 {
+                for k in 0..3 {
+                    result[col * 3 + row] += self.elements[k * 3 + row] * rhs.elements[col * 3 + k];
+                }
+            }
+        }
+        for row in 0..3 {
+            result[9 + row] += self.elements[9 + row];
+        }
+        self.elements = result;
+    }
+}
+
+impl std::ops::Mul<Affine3> for Affine3 {
+    type Output = Affine3;
+
+    fn mul(self, rhs: Affine3) -> Self::Output {
+        let mut out = self;
+        out *= rhs;
+        out
+    }
+}
+
+// Treats `rhs` as homogeneous, exactly like the equivalent Mat4 would.
+impl std::ops::Mul<Vec4> for Affine3 {
+    type Output = Vec4;
+
+    fn mul(self, rhs: Vec4) -> Self::Output {
+        let e = &self.elements;
+        let x = e[0] * rhs.x + e[3] * rhs.y + e[6] * rhs.z + e[9] * rhs.w;
+        let y = e[1] * rhs.x + e[4] * rhs.y + e[7] * rhs.z + e[10] * rhs.w;
+        let z = e[2] * rhs.x + e[5] * rhs.y + e[8] * rhs.z + e[11] * rhs.w;
+        Vec4 { x, y, z, w: rhs.w }
+    }
+}
+
+impl std::fmt::Display for Affine3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[{}, {}, {}, {}]\n[{}, {}, {}, {}]\n[{}, {}, {}, {}]",
+            self.elements[0], self.elements[3], self.elements[6], self.elements[9],
+            self.elements[1], self.elements[4], self.elements[7], self.elements[10],
+            self.elements[2], self.elements[5], self.elements[8], self.elements[11]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rigid() -> Mat4 {
+        Mat4::from_translation(&Vec4::new(1.0, -2.0, 3.0, 0.0))
+            * Mat4::from_axis_angle(Vec4::new(1.0, 2.0, -0.5, 0.0).normalize(), 0.8)
+    }
+
+    fn general() -> Mat4 {
+        rigid() * Mat4::from_shear(0.3, 0.0, -0.2, 0.1, 0.0, 0.4) * Mat4::from_scale(Vec4::new(2.0, 0.5, 1.5, 1.0))
+    }
+
+    #[test]
+    fn mat4_round_trip() {
+        let m = general();
+        assert_eq!(Affine3::from_mat4(&m).to_mat4(), m);
+        assert_eq!(Affine3::identity().to_mat4(), Mat4::identity());
+        let a = Affine3::from_mat4(&m);
+        let rebuilt = Affine3::from_cols(a.x_vector(), a.y_vector(), a.z_vector(), a.position());
+        assert_eq!(rebuilt.to_mat4(), m);
+    }
+
+    #[test]
+    fn products_match_mat4() {
+        let (m, n) = (general(), rigid());
+        let product = Affine3::from_mat4(&m) * Affine3::from_mat4(&n);
+        assert!(product.to_mat4().approx_eq(&(m * n), 1e-5));
+
+        let a = Affine3::from_mat4(&m);
+        for v in [Vec4::new(0.5, -1.0, 2.0, 1.0), Vec4::new(0.5, -1.0, 2.0, 0.0)] {
+            assert!((a * v).approx_eq(&(m * v), 1e-5));
+        }
+        let p = Vec4::new(3.0, 1.0, -1.0, 0.0);
+        assert!(a.transform_point(&p).approx_eq(&(m * Vec4::new(3.0, 1.0, -1.0, 1.0)), 1e-5));
+        assert!(a.transform_vector(&p).approx_eq(&(m * p), 1e-5));
+    }
+
+    #[test]
+    fn inverses_match_mat4() {
+        let m = general();
+        let inverse = Affine3::from_mat4(&m).inverse().unwrap();
+        assert!(inverse.to_mat4().approx_eq(&m.inverse(), 1e-5));
+        assert!((Affine3::from_mat4(&m).determinant() - m.determinant()).abs() < 1e-5);
+
+        let r = rigid();
+        assert!(Affine3::from_mat4(&r).rigid_inverse().to_mat4().approx_eq(&r.inverse(), 1e-5));
+
+        let singular = Mat4::from_scale(Vec4::new(1.0, 0.0, 1.0, 1.0));
+        assert_eq!(Affine3::from_mat4(&singular).inverse().err(), Some(MathError::Singular));
+        // Two axes equal up to rounding: the determinant isn't exactly zero.
+        let axis = Vec4::new(0.1, 0.7, 0.3, 0.0);
+        let nearly = Affine3::from_cols(axis, axis + Vec4::new(0.0, 3e-8, -2e-8, 0.0), Vec4::new(0.0, 0.0, 1.0, 0.0), Vec4::zero());
+        assert_eq!(nearly.inverse().err(), Some(MathError::Singular));
+        // A tiny but well-shaped transform still inverts.
+        let tiny = Affine3::from_mat4(&(m * Mat4::from_scale(Vec4::new(1e-6, 1e-6, 1e-6, 1.0))));
+        assert!((tiny * tiny.inverse().unwrap()).to_mat4().approx_eq(&Mat4::identity(), 1e-4));
+        let nan = Affine3::from_cols(Vec4::new(f32::NAN, 0.0, 0.0, 0.0), axis, axis, axis);
+        assert_eq!(nan.inverse().err(), Some(MathError::NotFinite));
+    }
+}
@@ -0,0 +1,62 @@
+use std::f32::consts::{PI, TAU};
+
+// Angles are in radians. Wrapped angles lie in (-pi, pi], so pi and -pi
+// both map to pi and a half-turn difference is always reported as +pi.
+
+#[allow(dead_code)]
+pub fn wrap_angle(angle: f32) -> f32 {
+    // Already in range: returned untouched so small angles keep their
+    // precision.
+    if angle > -PI && angle <= PI {
+        return angle;
+    }
+    let wrapped = (angle + PI).rem_euclid(TAU) - PI;
+    // rem_euclid can round up to exactly TAU for tiny negative inputs.
+    if wrapped <= -PI { wrapped + TAU } else { wrapped }
+}
+
+// Signed smallest rotation taking `from` to `to`.
+#[allow(dead_code)]
+pub fn shortest_angle_diff(from: f32, to: f32) -> f32 {
+    wrap_angle(to - from)
+}
+
+// Interpolates along the shorter arc; the result is wrapped.
+#[allow(dead_code)]
+pub fn lerp_angle(from: f32, to: f32, t: f32) -> f32 {
+    wrap_angle(from + shortest_angle_diff(from, to) * t)
+}
+
+// Removes the jumps of a sequence of wrapped angles, such as sampled
+// headings, by shifting each by whole turns so it lands within half a turn
+// of the previous one. The first angle is kept as is.
+#[allow(dead_code)]
+pub fn unwrap_angles(angles: &mut [f32]) {
+    for i in 1..angles.len() {
+        angles[i] = angles[i - 1] + shortest_angle_diff(angles[i - 1], angles[i]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_into_half_open_range() {
+        assert_eq!(wrap_angle(PI), PI);
+        assert_eq!(wrap_angle(-PI), PI);
+        assert!((wrap_angle(3.0 * PI) - PI).abs() < 1e-5);
+        assert!((wrap_angle(-1.0 - 4.0 * TAU) + 1.0).abs() < 1e-5);
+        assert_eq!(wrap_angle(-1e-9), -1e-9);
+        assert!(wrap_angle(f32::NAN).is_nan());
+
+        assert!((shortest_angle_diff(3.0, -3.0) - (TAU - 6.0)).abs() < 1e-6);
+        assert!((lerp_angle(3.0, -3.0, 0.5) - PI).abs() < 1e-5);
+        assert!((lerp_angle(0.5, 1.5, 0.25) - 0.75).abs() < 1e-6);
+
+        let mut headings = [3.0, -3.1, -2.9, 2.9, 0.1];
+        unwrap_angles(&mut headings);
+        let expected = [3.0, TAU - 3.1, TAU - 2.9, 2.9, 0.1];
+        assert!(headings.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-5));
+    }
+}
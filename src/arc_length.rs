@@ -0,0 +1,89 @@
+use crate::vec4::Vec4;
+
+// Cumulative chord length of a curve sampled at evenly spaced parameters
+// in [0, 1]. Lookups interpolate linearly between samples, so accuracy is
+// set by the sample count.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct ArcLengthTable {
+    distances: Vec<f32>
+}
+
+#[allow(dead_code)]
+impl ArcLengthTable {
+    // Samples `curve` at `samples + 1` parameters. Only x, y and z of the
+    // returned points are used.
+    pub fn new(curve: impl Fn(f32) -> Vec4, samples: usize) -> Self {
+        let samples = samples.max(1);
+        let mut distances = Vec::with_capacity(samples + 1);
+        let mut previous = curve(0.0);
+        let mut total = 0.0;
+        distances.push(0.0);
+        for i in 1..=samples {
+            let point = curve(i as f32 / samples as f32);
+            let d = point - previous;
+            total += (d.x * d.x + d.y * d.y + d.z * d.z).sqrt();
+            distances.push(total);
+            previous = point;
+        }
+        Self {
+            distances
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        *self.distances.last().unwrap()
+    }
+
+    // Distance along the curve at parameter `t`, clamped to [0, 1].
+    pub fn distance_at_t(&self, t: f32) -> f32 {
+        let segments = self.distances.len() - 1;
+        let x = t.clamp(0.0, 1.0) * segments as f32;
+        let i = (x.floor() as usize).min(segments - 1);
+        let f = x - i as f32;
+        self.distances[i] + (self.distances[i + 1] - self.distances[i]) * f
+    }
+
+    // Parameter at which the curve has covered `distance`, clamped to the
+    // curve's length. Walking `distance` at a constant rate gives constant
+    // speed motion.
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        let segments = self.distances.len() - 1;
+        let distance = distance.clamp(0.0, self.length());
+        let i = self.distances.partition_point(|&d| d < distance).clamp(1, segments) - 1;
+        let span = self.distances[i + 1] - self.distances[i];
+        let f = if span > 0.0 { (distance - self.distances[i]) / span } else { 0.0 };
+        (i as f32 + f) / segments as f32
+    }
+
+    // Parameter at `fraction` of the total length.
+    pub fn t_at_fraction(&self, fraction: f32) -> f32 {
+        self.t_at_distance(fraction * self.length())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quadratic_bezier_constant_speed() {
+        let (p0, p1, p2) = (Vec4::new(0.0, 0.0, 0.0, 1.0), Vec4::new(1.0, 4.0, 0.0, 1.0), Vec4::new(4.0, 0.0, 0.0, 1.0));
+        let curve = |t: f32| p0 * ((1.0 - t) * (1.0 - t)) + p1 * (2.0 * t * (1.0 - t)) + p2 * (t * t);
+        let table = ArcLengthTable::new(curve, 512);
+
+        let steps = 20;
+        let points: Vec<Vec4> = (0..=steps)
+            .map(|i| curve(table.t_at_fraction(i as f32 / steps as f32)))
+            .collect();
+        let expected = table.length() / steps as f32;
+        for pair in points.windows(2) {
+            // Chords are slightly shorter than the arcs they span.
+            let chord = (pair[1] - pair[0]).length();
+            assert!(chord <= expected + 1e-3 && chord > expected * 0.99);
+        }
+        for t in [0.0, 0.3, 0.77, 1.0] {
+            assert!((table.t_at_distance(table.distance_at_t(t)) - t).abs() < 1e-5);
+        }
+    }
+}
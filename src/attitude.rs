@@ -0,0 +1,104 @@
+use crate::mat4::Mat4;
+use crate::symmat4::SymMat4;
+use crate::transform::quat_to_rotation;
+use crate::vec4::Vec4;
+
+// Orthonormal triad from a primary and a secondary direction: the primary
+// itself, the normal of the plane they span, and the third axis completing
+// a right-handed frame. None when the two are parallel.
+fn basis(primary: &Vec4, secondary: &Vec4) -> Option<[Vec4; 3]> {
+    let t1 = Vec4::new(primary.x, primary.y, primary.z, 0.0).normalize();
+    let normal = t1.cross(secondary);
+    let length = normal.length();
+    if length <= 1e-6 * secondary.length() {
+        return None;
+    }
+    let t2 = normal / length;
+    Some([t1, t2, t1.cross(&t2)])
+}
+
+// TRIAD: the rotation taking `from[i]` onto `to[i]`. The first pair is
+// matched exactly and the second only fixes the roll about it, so put the
+// more trusted measurement first. None when either pair is parallel.
+#[allow(dead_code)]
+pub fn triad(from: [Vec4; 2], to: [Vec4; 2]) -> Option<Mat4> {
+    let t = basis(&from[0], &from[1])?;
+    let s = basis(&to[0], &to[1])?;
+    let mut out = Mat4::identity();
+    for col in 0..3 {
+        for row in 0..3 {
+            out.elements[col * 4 + row] = (0..3).map(|k| s[k][row] * t[k][col]).sum();
+        }
+    }
+    Some(out)
+}
+
+// Davenport's q-method: the rotation minimizing the weighted squared error
+// sum w * |to - R from|^2 over any number of (from, to, weight) pairs, as a
+// unit quaternion (x, y, z, w). Unlike TRIAD no pair is privileged. Needs at
+// least two non-parallel directions to be unique; returns None when there
+// are no pairs.
+#[allow(dead_code)]
+pub fn davenport_q(pairs: &[(Vec4, Vec4, f32)]) -> Option<Vec4> {
+    if pairs.is_empty() {
+        return None;
+    }
+    // B = sum w * to * from^T
+    let mut b = [[0.0f32; 3]; 3];
+    for (from, to, weight) in pairs {
+        let (from, to) = (Vec4::new(from.x, from.y, from.z, 0.0).normalize(), Vec4::new(to.x, to.y, to.z, 0.0).normalize());
+        for (row, b_row) in b.iter_mut().enumerate() {
+            for (col, value) in b_row.iter_mut().enumerate() {
+                *value += weight * to[row] * from[col];
+            }
+        }
+    }
+    let sigma = b[0][0] + b[1][1] + b[2][2];
+    let z = [b[2][1] - b[1][2], b[0][2] - b[2][0], b[1][0] - b[0][1]];
+    let mut k = SymMat4::zeroes();
+    for row in 0..3 {
+        for col in row..3 {
+            k[(row, col)] = b[row][col] + b[col][row] - if row == col { sigma } else { 0.0 };
+        }
+        k[(row, 3)] = z[row];
+    }
+    k[(3, 3)] = sigma;
+
+    let (_, vectors) = k.eigen(1e-7);
+    let q = vectors.x_vector();
+    Some(if q.w < 0.0 { -q } else { q })
+}
+
+// Same as `davenport_q`, as a rotation matrix.
+#[allow(dead_code)]
+pub fn davenport_rotation(pairs: &[(Vec4, Vec4, f32)]) -> Option<Mat4> {
+    davenport_q(pairs).map(|q| quat_to_rotation(&q))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn direction(x: f32, y: f32, z: f32) -> Vec4 {
+        Vec4::new(x, y, z, 0.0)
+    }
+
+    #[test]
+    fn recovers_a_known_rotation() {
+        let mut rotation = Mat4::identity();
+        rotation.rotate(direction(0.2, -1.0, 0.5).normalize(), 2.1);
+        let from = [direction(0.0, 0.0, -1.0), direction(1.0, 0.2, 0.0), direction(0.3, 1.0, 0.4)];
+        let to = from.map(|v| rotation * v);
+
+        let close = |m: &Mat4| (0..16).all(|i| (m.elements[i] - rotation.elements[i]).abs() < 1e-5);
+        assert!(close(&triad([from[0], from[1]], [to[0], to[1]]).unwrap()));
+        let pairs: Vec<_> = (0..3).map(|i| (from[i], to[i], 1.0)).collect();
+        assert!(close(&davenport_rotation(&pairs).unwrap()));
+        assert!(triad([from[0], from[0] * 2.0], [to[0], to[1]]).is_none());
+
+        // A noisy second pair bends TRIAD only around the first direction.
+        let noisy = to[1] + direction(0.0, 0.05, 0.0);
+        let m = triad([from[0], from[1]], [to[0], noisy]).unwrap();
+        assert!((m * from[0] - to[0]).length() < 1e-5);
+    }
+}
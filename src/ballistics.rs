@@ -0,0 +1,223 @@
+use crate::instrument::{trace_event, trace_span};
+use crate::vec4::Vec4;
+
+// Angles at which a projectile launched at `speed` in a vacuum passes
+// through a point `horizontal` away and `vertical` above the launch point,
+// under gravity of magnitude `gravity`. Returns the low and high arc
+// elevations in radians, equal when the target is at the edge of the
+// range, or None when it is out of reach.
+#[allow(dead_code)]
+pub fn vacuum_launch_angles(horizontal: f32, vertical: f32, speed: f32, gravity: f32) -> Option<(f32, f32)> {
+    let v2 = speed * speed;
+    let discriminant = v2 * v2 - gravity * (gravity * horizontal * horizontal + 2.0 * vertical * v2);
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let gx = gravity * horizontal;
+    Some(((v2 - root).atan2(gx), (v2 + root).atan2(gx)))
+}
+
+// Which of the two solutions `Ballistics::aim` returns: the flatter, faster
+// shot or the lob.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum AimArc {
+    #[default]
+    Low,
+    High
+}
+
+// Point-mass projectile under constant gravity and quadratic air drag,
+// a = gravity - drag * |v| * v, integrated numerically since there is no
+// closed form once drag is involved. `drag` folds together air density,
+// drag coefficient, cross-section and mass (0.5 rho Cd A / m).
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Ballistics {
+    pub gravity: Vec4,
+    pub drag: f32
+}
+
+// Elevations scanned for sign changes before bisecting; fine enough that
+// the two arcs of any reachable target land in separate brackets.
+const AIM_SAMPLES: usize = 128;
+const AIM_ITERATIONS: usize = 40;
+
+#[allow(dead_code)]
+impl Ballistics {
+    pub fn new(gravity: Vec4, drag: f32) -> Self {
+        Self {
+            gravity: Vec4::new(gravity.x, gravity.y, gravity.z, 0.0),
+            drag
+        }
+    }
+
+    pub fn acceleration(&self, velocity: &Vec4) -> Vec4 {
+        self.gravity - *velocity * (self.drag * velocity.length())
+    }
+
+    // One classic Runge-Kutta step of the position and velocity.
+    pub fn step(&self, position: &Vec4, velocity: &Vec4, dt: f32) -> (Vec4, Vec4) {
+        let (p, v) = (*position, *velocity);
+        let k1v = self.acceleration(&v);
+        let k1p = v;
+        let k2v = self.acceleration(&(v + k1v * (dt * 0.5)));
+        let k2p = v + k1v * (dt * 0.5);
+        let k3v = self.acceleration(&(v + k2v * (dt * 0.5)));
+        let k3p = v + k2v * (dt * 0.5);
+        let k4v = self.acceleration(&(v + k3v * dt));
+        let k4p = v + k3v * dt;
+        let sixth = dt / 6.0;
+        (
+            p + (k1p + k2p * 2.0 + k3p * 2.0 + k4p) * sixth,
+            v + (k1v + k2v * 2.0 + k3v * 2.0 + k4v) * sixth
+        )
+    }
+
+    // Positions after each of `steps` steps of `dt`, starting with the
+    // launch point.
+    pub fn trajectory(&self, position: &Vec4, velocity: &Vec4, dt: f32, steps: usize) -> Vec<Vec4> {
+        let (mut p, mut v) = (*position, Vec4::new(velocity.x, velocity.y, velocity.z, 0.0));
+        let mut out = Vec::with_capacity(steps + 1);
+        out.push(p);
+        for _ in 0..steps {
+            (p, v) = self.step(&p, &v, dt);
+            out.push(p);
+        }
+        out
+    }
+
+    // Launch velocity of magnitude `speed` that carries a projectile from
+    // `from` through `target`, found by scanning elevations for where the
+    // height at the target's horizontal distance crosses the target's and
+    // bisecting. Each trial is simulated with steps of `dt`. None when the
+    // target is out of reach at this speed.
+    pub fn aim(&self, from: &Vec4, target: &Vec4, speed: f32, dt: f32, arc: AimArc) -> Option<Vec4> {
+        trace_span!("ballistics_aim", speed = speed, dt = dt);
+        let up = (-self.gravity).normalize();
+        let offset = Vec4::new(target.x - from.x, target.y - from.y, target.z - from.z, 0.0);
+        let height = offset.dot(&up);
+        let flat = offset - up * height;
+        let distance = flat.length();
+        if distance <= 1e-6 {
+            return self.aim_vertically(&up, height, speed, dt);
+        }
+        let forward = flat * (1.0 / distance);
+        let launch = |elevation: f32| (forward * elevation.cos() + up * elevation.sin()) * speed;
+        let miss = |elevation: f32| self.miss(&launch(elevation), &up, &forward, distance, height, dt);
+
+        let limit = std::f32::consts::FRAC_PI_2 - 1e-3;
+        let angles: Vec<f32> = (0..=AIM_SAMPLES).map(|i| -limit + 2.0 * limit * i as f32 / AIM_SAMPLES as f32).collect();
+        let misses: Vec<f32> = angles.iter().map(|&a| miss(a)).collect();
+        let mut crossings = (0..AIM_SAMPLES).filter(|&i| (misses[i] < 0.0) != (misses[i + 1] < 0.0));
+        let bracket = match arc {
+            AimArc::Low => crossings.next(),
+            AimArc::High => crossings.next_back()
+        }?;
+
+        let (mut low, mut high) = (angles[bracket], angles[bracket + 1]);
+        let rising = misses[bracket] < 0.0;
+        for _ in 0..AIM_ITERATIONS {
+            let middle = 0.5 * (low + high);
+            if (miss(middle) < 0.0) == rising {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        let elevation = 0.5 * (low + high);
+        trace_event!("ballistics_aim finished", elevation = elevation, miss = miss(elevation));
+        Some(launch(elevation))
+    }
+
+    // Target straight above or below: fire along the vertical if the shot
+    // climbs high enough.
+    fn aim_vertically(&self, up: &Vec4, height: f32, speed: f32, dt: f32) -> Option<Vec4> {
+        if height <= 0.0 {
+            return Some(-*up * speed);
+        }
+        let (mut p, mut v) = (Vec4::zero(), *up * speed);
+        while v.dot(up) > 0.0 {
+            (p, v) = self.step(&p, &v, dt);
+            if p.dot(up) >= height {
+                return Some(*up * speed);
+            }
+        }
+        None
+    }
+
+    // Height above `height` at which the shot crosses horizontal `distance`.
+    // A shot that falls below the target before getting there, or stalls,
+    // reports minus the distance it still had to go, which keeps the sign
+    // right for bracketing.
+    fn miss(&self, velocity: &Vec4, up: &Vec4, forward: &Vec4, distance: f32, height: f32, dt: f32) -> f32 {
+        let (mut p, mut v) = (Vec4::zero(), *velocity);
+        let max_steps = 100_000;
+        for _ in 0..max_steps {
+            let (next_p, next_v) = self.step(&p, &v, dt);
+            let (along, next_along) = (p.dot(forward), next_p.dot(forward));
+            if next_along >= distance {
+                let t = if next_along > along { (distance - along) / (next_along - along) } else { 1.0 };
+                let y = p.dot(up) + (next_p.dot(up) - p.dot(up)) * t;
+                return y - height;
+            }
+            if next_v.dot(up) < 0.0 && next_p.dot(up) < height {
+                return -(distance - next_along).max(1e-6);
+            }
+            (p, v) = (next_p, next_v);
+        }
+        -(distance - p.dot(forward)).max(1e-6)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAVITY: f32 = 9.81;
+
+    // Closest the path comes to `target`.
+    fn closest_approach(ballistics: &Ballistics, from: &Vec4, velocity: &Vec4, target: &Vec4) -> f32 {
+        ballistics.trajectory(from, velocity, 1e-3, 20_000).windows(2).map(|w| {
+            let d = w[1] - w[0];
+            let t = ((*target - w[0]).dot(&d) / d.dot(&d)).clamp(0.0, 1.0);
+            (*target - (w[0] + d * t)).length()
+        }).fold(f32::INFINITY, f32::min)
+    }
+
+    #[test]
+    fn vacuum_aim_matches_closed_form() {
+        let vacuum = Ballistics::new(Vec4::new(0.0, -GRAVITY, 0.0, 0.0), 0.0);
+        let from = Vec4::new(1.0, 2.0, 3.0, 1.0);
+        let target = Vec4::new(31.0, 7.0, -37.0, 1.0);
+        let (low, high) = vacuum_launch_angles(50.0, 5.0, 30.0, GRAVITY).unwrap();
+        for (arc, expected) in [(AimArc::Low, low), (AimArc::High, high)] {
+            let velocity = vacuum.aim(&from, &target, 30.0, 1e-3, arc).unwrap();
+            assert!((velocity.length() - 30.0).abs() < 1e-3);
+            assert!(((velocity.y / 30.0).asin() - expected).abs() < 1e-3, "{:?}", arc);
+        }
+        assert!(vacuum_launch_angles(200.0, 0.0, 30.0, GRAVITY).is_none());
+        assert!(vacuum.aim(&from, &Vec4::new(201.0, 2.0, 3.0, 1.0), 30.0, 1e-2, AimArc::Low).is_none());
+    }
+
+    #[test]
+    fn drag_aim_hits_target() {
+        let ballistics = Ballistics::new(Vec4::new(0.0, -GRAVITY, 0.0, 0.0), 0.01);
+        let from = Vec4::new(0.0, 1.0, 0.0, 1.0);
+        let target = Vec4::new(40.0, 3.0, 20.0, 1.0);
+        let (vacuum_low, _) = vacuum_launch_angles((40.0f32).hypot(20.0), 2.0, 40.0, GRAVITY).unwrap();
+        let mut elevations = Vec::new();
+        for arc in [AimArc::Low, AimArc::High] {
+            let velocity = ballistics.aim(&from, &target, 40.0, 1e-3, arc).unwrap();
+            assert!(closest_approach(&ballistics, &from, &velocity, &target) < 0.05, "{:?}", arc);
+            elevations.push((velocity.y / 40.0).asin());
+        }
+        // Drag needs a steeper flat shot than a vacuum would.
+        assert!(elevations[0] > vacuum_low && elevations[1] > elevations[0]);
+
+        let up = ballistics.aim(&from, &Vec4::new(0.0, 20.0, 0.0, 1.0), 40.0, 1e-3, AimArc::Low).unwrap();
+        assert!((up - Vec4::new(0.0, 40.0, 0.0, 0.0)).length() < 1e-6);
+        assert!(ballistics.aim(&from, &Vec4::new(0.0, 500.0, 0.0, 1.0), 40.0, 1e-3, AimArc::Low).is_none());
+    }
+}
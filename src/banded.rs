@@ -0,0 +1,277 @@
+use crate::error::MathError;
+
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default)]
+pub struct Tridiagonal {
+    pub lower: Vec<f32>,
+    pub diagonal: Vec<f32>,
+    pub upper: Vec<f32>
+}
+
+#[allow(dead_code)]
+impl Tridiagonal {
+    // `lower` and `upper` hold the sub- and super-diagonals and must be one
+    // element shorter than `diagonal`.
+    pub fn new(lower: Vec<f32>, diagonal: Vec<f32>, upper: Vec<f32>) -> Self {
+        let n = diagonal.len();
+        assert!(lower.len() + 1 == n.max(1) && upper.len() + 1 == n.max(1), "Band size mismatch");
+        Self {
+            lower,
+            diagonal,
+            upper
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.diagonal.len()
+    }
+
+    pub fn mul_vec(&self, x: &[f32]) -> Vec<f32> {
+        let n = self.size();
+        assert!(x.len() == n, "Dimension mismatch");
+        let mut out = vec![0.0; n];
+        for i in 0..n {
+            out[i] = self.diagonal[i] * x[i];
+            if i > 0 {
+                out[i] += self.lower[i - 1] * x[i - 1];
+            }
+            if i + 1 < n {
+                out[i] += self.upper[i] * x[i + 1];
+            }
+        }
+        out
+    }
+
+    // Thomas algorithm. It does not pivot, so it is only stable for
+    // diagonally dominant or symmetric positive definite systems. Fails
+    // with `Singular` when a zero pivot is hit and `DimensionMismatch` when
+    // `rhs` has the wrong length.
+    pub fn solve(&self, rhs: &[f32]) -> Result<Vec<f32>, MathError> {
+        let n = self.size();
+        if rhs.len() != n {
+            return Err(MathError::DimensionMismatch);
+        }
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let mut c = vec![0.0; n];
+        let mut d = vec![0.0; n];
+        let mut m = self.diagonal[0];
+        if m == 0.0 {
+            return Err(MathError::Singular);
+        }
+        if n > 1 {
+            c[0] = self.upper[0] / m;
+        }
+        d[0] = rhs[0] / m;
+        for i in 1..n {
+            m = self.diagonal[i] - self.lower[i - 1] * c[i - 1];
+            if m == 0.0 {
+                return Err(MathError::Singular);
+            }
+            if i + 1 < n {
+                c[i] = self.upper[i] / m;
+            }
+            d[i] = (rhs[i] - self.lower[i - 1] * d[i - 1]) / m;
+        }
+        for i in (0..n - 1).rev() {
+            d[i] -= c[i] * d[i + 1];
+        }
+        Ok(d)
+    }
+
+    pub fn to_banded(&self) -> Banded {
+        let n = self.size();
+        let mut out = Banded::zeroes(n, 1, 1);
+        for i in 0..n {
+            out.set(i, i, self.diagonal[i]);
+            if i > 0 {
+                out.set(i, i - 1, self.lower[i - 1]);
+            }
+            if i + 1 < n {
+                out.set(i, i + 1, self.upper[i]);
+            }
+        }
+        out
+    }
+}
+
+// Square band matrix with `lower` sub-diagonals and `upper` super-diagonals.
+// Each row stores its `lower + upper + 1` band entries contiguously.
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default)]
+pub struct Banded {
+    size: usize,
+    lower: usize,
+    upper: usize,
+    data: Vec<f32>
+}
+
+#[allow(dead_code)]
+impl Banded {
+    pub fn zeroes(size: usize, lower: usize, upper: usize) -> Self {
+        Self {
+            size,
+            lower,
+            upper,
+            data: vec![0.0; size * (lower + upper + 1)]
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn bandwidths(&self) -> (usize, usize) {
+        (self.lower, self.upper)
+    }
+
+    fn in_band(&self, row: usize, col: usize) -> bool {
+        row < self.size && col < self.size && col + self.lower >= row && row + self.upper >= col
+    }
+
+    fn offset(&self, row: usize, col: usize) -> usize {
+        row * (self.lower + self.upper + 1) + (col + self.lower - row)
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> f32 {
+        if row >= self.size || col >= self.size {
+            panic!("Index out of bounds");
+        }
+        if self.in_band(row, col) {
+            self.data[self.offset(row, col)]
+        } else {
+            0.0
+        }
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: f32) {
+        if !self.in_band(row, col) {
+            panic!("Index out of band");
+        }
+        let offset = self.offset(row, col);
+        self.data[offset] = value;
+    }
+
+    fn band_cols(&self, row: usize) -> std::ops::Range<usize> {
+        row.saturating_sub(self.lower)..(row + self.upper + 1).min(self.size)
+    }
+
+    pub fn mul_vec(&self, x: &[f32]) -> Vec<f32> {
+        assert!(x.len() == self.size, "Dimension mismatch");
+        let mut out = vec![0.0; self.size];
+        for i in 0..self.size {
+            for j in self.band_cols(i) {
+                out[i] += self.get(i, j) * x[j];
+            }
+        }
+        out
+    }
+
+    // Banded LU without pivoting, which keeps the factors inside the
+    // original band. Fails with `Singular` on a zero pivot.
+    pub fn lu(&self) -> Result<BandedLu, MathError> {
+        let mut factors = self.clone();
+        for k in 0..self.size {
+            let pivot = factors.get(k, k);
+            if pivot == 0.0 {
+                return Err(MathError::Singular);
+            }
+            let last_row = (k + self.lower).min(self.size - 1);
+            let last_col = (k + self.upper).min(self.size - 1);
+            for i in (k + 1)..=last_row {
+                let l = factors.get(i, k) / pivot;
+                factors.set(i, k, l);
+                for j in (k + 1)..=last_col {
+                    let value = factors.get(i, j) - l * factors.get(k, j);
+                    factors.set(i, j, value);
+                }
+            }
+        }
+        Ok(BandedLu {
+            factors
+        })
+    }
+
+    pub fn solve(&self, rhs: &[f32]) -> Result<Vec<f32>, MathError> {
+        self.lu()?.solve(rhs)
+    }
+}
+
+// Packed L (unit diagonal, below) and U (on and above the diagonal).
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct BandedLu {
+    factors: Banded
+}
+
+#[allow(dead_code)]
+impl BandedLu {
+    // Fails with `DimensionMismatch` when `rhs` has the wrong length.
+    pub fn solve(&self, rhs: &[f32]) -> Result<Vec<f32>, MathError> {
+        let a = &self.factors;
+        let n = a.size;
+        if rhs.len() != n {
+            return Err(MathError::DimensionMismatch);
+        }
+        let mut x = rhs.to_vec();
+        for i in 0..n {
+            for j in i.saturating_sub(a.lower)..i {
+                x[i] -= a.get(i, j) * x[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in (i + 1)..(i + a.upper + 1).min(n) {
+                x[i] -= a.get(i, j) * x[j];
+            }
+            x[i] /= a.get(i, i);
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thomas_matches_banded_lu() {
+        let system = Tridiagonal::new(
+            vec![1.0, 1.0, 1.0, 1.0],
+            vec![4.0, 4.0, 4.0, 4.0, 4.0],
+            vec![1.0, 1.0, 1.0, 1.0]
+        );
+        let x = [1.0, -2.0, 3.0, 0.5, 2.0];
+        let b = system.mul_vec(&x);
+        let thomas = system.solve(&b).unwrap();
+        let banded = system.to_banded().solve(&b).unwrap();
+        for i in 0..5 {
+            assert!((thomas[i] - x[i]).abs() < 1e-5);
+            assert!((banded[i] - x[i]).abs() < 1e-5);
+        }
+        assert_eq!(system.solve(&b[..4]).err(), Some(MathError::DimensionMismatch));
+        assert_eq!(system.to_banded().lu().unwrap().solve(&[1.0; 6]).err(), Some(MathError::DimensionMismatch));
+    }
+
+    #[test]
+    fn wide_band_solve() {
+        let mut m = Banded::zeroes(6, 2, 1);
+        for i in 0..6 {
+            m.set(i, i, 5.0);
+            if i >= 1 {
+                m.set(i, i - 1, -1.0);
+            }
+            if i >= 2 {
+                m.set(i, i - 2, 0.5);
+            }
+            if i + 1 < 6 {
+                m.set(i, i + 1, 2.0);
+            }
+        }
+        let x = [0.5, 1.0, -1.0, 2.0, 0.0, 3.0];
+        let solved = m.solve(&m.mul_vec(&x)).unwrap();
+        for i in 0..6 {
+            assert!((solved[i] - x[i]).abs() < 1e-5);
+        }
+    }
+}
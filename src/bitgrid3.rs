@@ -0,0 +1,263 @@
+use crate::aabb::Aabb;
+use crate::grid3::Grid3;
+use crate::sphere::Sphere;
+use crate::vec4::Vec4;
+use crate::voxel::VoxelLayout;
+
+// Occupancy grid with one bit per cell, laid out like `Grid3` (x fastest,
+// then y, then z). Every x row starts on a fresh u64 word so box queries
+// and fills work on whole words with masks at the row ends.
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitGrid3 {
+    width: usize,
+    height: usize,
+    depth: usize,
+    words_per_row: usize,
+    words: Vec<u64>
+}
+
+// Bits `lo..=hi` of a word.
+fn span_mask(lo: usize, hi: usize) -> u64 {
+    (u64::MAX >> (63 - hi)) & (u64::MAX << lo)
+}
+
+#[allow(dead_code)]
+impl BitGrid3 {
+    // All cells clear.
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            depth,
+            words_per_row,
+            words: vec![0; words_per_row * height * depth]
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn in_bounds(&self, x: usize, y: usize, z: usize) -> bool {
+        x < self.width && y < self.height && z < self.depth
+    }
+
+    fn row_start(&self, y: usize, z: usize) -> usize {
+        (z * self.height + y) * self.words_per_row
+    }
+
+    // False outside the grid.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> bool {
+        self.in_bounds(x, y, z) && self.words[self.row_start(y, z) + x / 64] >> (x % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, z: usize, value: bool) {
+        assert!(self.in_bounds(x, y, z), "Index out of bounds");
+        let index = self.row_start(y, z) + x / 64;
+        let word = &mut self.words[index];
+        if value {
+            *word |= 1 << (x % 64);
+        } else {
+            *word &= !(1 << (x % 64));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    // Index of each word the inclusive cell box touches, with the mask of
+    // its bits inside the box. The box must already be clipped. Copies what
+    // it needs from `self` so fills can write while iterating.
+    fn word_spans(&self, min: [usize; 3], max: [usize; 3]) -> impl Iterator<Item = (usize, u64)> + use<> {
+        let (first, last) = (min[0] / 64, max[0] / 64);
+        let (height, words_per_row) = (self.height, self.words_per_row);
+        (min[2]..=max[2]).flat_map(move |z| (min[1]..=max[1]).map(move |y| (y, z))).flat_map(move |(y, z)| {
+            let start = (z * height + y) * words_per_row;
+            (first..=last).map(move |w| {
+                let lo = if w == first { min[0] % 64 } else { 0 };
+                let hi = if w == last { max[0] % 64 } else { 63 };
+                (start + w, span_mask(lo, hi))
+            })
+        })
+    }
+
+    // Sets every cell in the inclusive box `min..=max`, clipped to the grid.
+    pub fn fill_box(&mut self, min: [usize; 3], max: [usize; 3]) {
+        if let Some((min, max)) = self.clip(min, max) {
+            for (index, mask) in self.word_spans(min, max) {
+                self.words[index] |= mask;
+            }
+        }
+    }
+
+    fn clip(&self, min: [usize; 3], max: [usize; 3]) -> Option<([usize; 3], [usize; 3])> {
+        let dims = [self.width, self.height, self.depth];
+        let max = [0, 1, 2].map(|axis| max[axis].min(dims[axis].saturating_sub(1)));
+        if (0..3).any(|axis| dims[axis] == 0 || min[axis] > max[axis]) {
+            return None;
+        }
+        Some((min, max))
+    }
+
+    // Number of set cells, by population count over whole words.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // Set cells in the inclusive box `min..=max`, clipped to the grid.
+    pub fn count_in_box(&self, min: [usize; 3], max: [usize; 3]) -> usize {
+        match self.clip(min, max) {
+            Some((min, max)) => self.word_spans(min, max).map(|(index, mask)| (self.words[index] & mask).count_ones() as usize).sum(),
+            None => 0
+        }
+    }
+
+    // Fraction of cells in the clipped box that are set; zero for a box
+    // that misses the grid.
+    pub fn density(&self, min: [usize; 3], max: [usize; 3]) -> f32 {
+        match self.clip(min, max) {
+            Some((min, max)) => {
+                let volume: usize = (0..3).map(|axis| max[axis] - min[axis] + 1).product();
+                self.count_in_box(min, max) as f32 / volume as f32
+            }
+            None => 0.0
+        }
+    }
+
+    // Conservatively marks every cell of `layout` that overlaps `aabb`,
+    // including cells it only touches. `layout` must match the grid size.
+    pub fn rasterize_aabb(&mut self, layout: &VoxelLayout, aabb: &Aabb) {
+        assert_eq!(layout.dims, [self.width, self.height, self.depth], "Dimension mismatch");
+        if let Some(range) = layout.cell_range(aabb) {
+            self.fill_box(range.map(|r| r.0), range.map(|r| r.1));
+        }
+    }
+
+    // Conservatively marks every cell of `layout` that the ball overlaps:
+    // per row, the x span whose cells come within the radius, using the
+    // closest point of each row's y-z square to the center.
+    pub fn rasterize_sphere(&mut self, layout: &VoxelLayout, sphere: &Sphere) {
+        assert_eq!(layout.dims, [self.width, self.height, self.depth], "Dimension mismatch");
+        let r = sphere.radius;
+        let c = sphere.center;
+        let bounds = Aabb::from_center_half_extents(&c, &Vec4::new(r, r, r, 0.0));
+        let Some(range) = layout.cell_range(&bounds) else {
+            return;
+        };
+        let h = layout.cell_size;
+        // Distance from `v` to the cell interval [lo, lo + h] on one axis.
+        let gap = |v: f32, lo: f32| (lo - v).max(v - lo - h).max(0.0);
+        for z in range[2].0..=range[2].1 {
+            let dz = gap(c.z, layout.origin.z + z as f32 * h);
+            for y in range[1].0..=range[1].1 {
+                let dy = gap(c.y, layout.origin.y + y as f32 * h);
+                let reach_squared = r * r - dy * dy - dz * dz;
+                if reach_squared < 0.0 {
+                    continue;
+                }
+                let reach = reach_squared.sqrt();
+                let lo = ((c.x - reach - layout.origin.x) / h).floor().max(range[0].0 as f32) as usize;
+                let hi = (((c.x + reach - layout.origin.x) / h).floor() as usize).min(range[0].1);
+                if lo <= hi {
+                    self.fill_box([lo, y, z], [hi, y, z]);
+                }
+            }
+        }
+    }
+
+    pub fn to_grid3(&self) -> Grid3<bool> {
+        Grid3::from_fn(self.width, self.height, self.depth, |x, y, z| self.get(x, y, z))
+    }
+
+    pub fn from_grid3(grid: &Grid3<bool>) -> Self {
+        let mut out = Self::new(grid.width(), grid.height(), grid.depth());
+        for (index, &cell) in grid.as_slice().iter().enumerate() {
+            if cell {
+                let (x, y, z) = grid.coords_of(index);
+                out.set(x, y, z, true);
+            }
+        }
+        out
+    }
+}
+
+impl From<&Grid3<bool>> for BitGrid3 {
+    fn from(grid: &Grid3<bool>) -> Self {
+        Self::from_grid3(grid)
+    }
+}
+
+impl From<&BitGrid3> for Grid3<bool> {
+    fn from(grid: &BitGrid3) -> Self {
+        grid.to_grid3()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_get_and_counts_across_words() {
+        let mut grid = BitGrid3::new(130, 3, 2);
+        grid.set(0, 0, 0, true);
+        grid.set(64, 1, 1, true);
+        grid.set(129, 2, 1, true);
+        assert!(grid.get(64, 1, 1) && !grid.get(63, 1, 1) && !grid.get(130, 0, 0));
+        assert_eq!(grid.count_ones(), 3);
+        assert_eq!(grid.count_in_box([1, 0, 0], [129, 2, 1]), 2);
+        assert_eq!(grid.count_in_box([65, 0, 0], [128, 2, 1]), 0);
+
+        grid.fill_box([60, 0, 0], [200, 0, 0]);
+        assert_eq!(grid.count_in_box([0, 0, 0], [129, 0, 0]), 71);
+        assert!((grid.density([60, 0, 0], [69, 0, 0]) - 1.0).abs() < 1e-6);
+        grid.set(64, 1, 1, false);
+
+        let dense = grid.to_grid3();
+        assert_eq!(dense.as_slice().iter().filter(|&&c| c).count(), grid.count_ones());
+        assert_eq!(BitGrid3::from(&dense), grid);
+    }
+
+    #[test]
+    fn rasterization_is_conservative() {
+        let layout = VoxelLayout::new(&Aabb::new(Vec4::new(0.0, 0.0, 0.0, 0.0), Vec4::new(10.0, 10.0, 10.0, 0.0)), 1.0);
+        let mut grid = BitGrid3::new(10, 10, 10);
+        grid.rasterize_aabb(&layout, &Aabb::new(Vec4::new(1.5, 2.0, -5.0, 0.0), Vec4::new(3.2, 2.5, 0.5, 0.0)));
+        // x cells 1..=3, y cell 2, z cell 0.
+        assert_eq!(grid.count_ones(), 3);
+
+        grid.clear();
+        let sphere = Sphere::new(Vec4::new(5.0, 5.0, 5.0, 1.0), 2.5);
+        grid.rasterize_sphere(&layout, &sphere);
+        let exact = 4.0 / 3.0 * std::f32::consts::PI * 2.5f32.powi(3);
+        assert!(grid.count_ones() as f32 > exact);
+        for z in 0..10 {
+            for y in 0..10 {
+                for x in 0..10 {
+                    let center = layout.cell_center(x, y, z);
+                    // Cells whose center is inside must be set; cells wholly
+                    // outside (center further than radius + half diagonal)
+                    // must not.
+                    let d = (center - sphere.center).length();
+                    if d <= sphere.radius {
+                        assert!(grid.get(x, y, z));
+                    }
+                    if d > sphere.radius + 0.87 {
+                        assert!(!grid.get(x, y, z));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::vec4::Vec4;
+
+// Oriented plane element in 3D with components on the xy, xz and yz basis
+// planes. The wedge of two vectors spans the plane they lie in, with
+// magnitude equal to the area of their parallelogram.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bivector3 {
+    pub xy: f32,
+    pub xz: f32,
+    pub yz: f32
+}
+
+#[allow(dead_code)]
+impl Bivector3 {
+    pub fn new(xy: f32, xz: f32, yz: f32) -> Self {
+        Self {
+            xy,
+            xz,
+            yz
+        }
+    }
+
+    // Outer product u ^ v. Only x, y and z are used.
+    pub fn wedge(u: &Vec4, v: &Vec4) -> Self {
+        Self::new(u.x * v.y - u.y * v.x, u.x * v.z - u.z * v.x, u.y * v.z - u.z * v.y)
+    }
+
+    pub fn length(&self) -> f32 {
+        (self.xy * self.xy + self.xz * self.xz + self.yz * self.yz).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        *self * (1.0 / self.length())
+    }
+
+    // Hodge dual: the vector normal to the plane, equal to the cross product
+    // of the vectors that were wedged.
+    pub fn dual(&self) -> Vec4 {
+        Vec4::new(self.yz, -self.xz, self.xy, 0.0)
+    }
+}
+
+impl std::ops::Add<Bivector3> for Bivector3 {
+    type Output = Bivector3;
+
+    fn add(self, rhs: Bivector3) -> Self::Output {
+        Bivector3::new(self.xy + rhs.xy, self.xz + rhs.xz, self.yz + rhs.yz)
+    }
+}
+
+impl std::ops::Sub<Bivector3> for Bivector3 {
+    type Output = Bivector3;
+
+    fn sub(self, rhs: Bivector3) -> Self::Output {
+        Bivector3::new(self.xy - rhs.xy, self.xz - rhs.xz, self.yz - rhs.yz)
+    }
+}
+
+impl std::ops::Mul<f32> for Bivector3 {
+    type Output = Bivector3;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Bivector3::new(self.xy * rhs, self.xz * rhs, self.yz * rhs)
+    }
+}
+
+impl std::ops::Neg for Bivector3 {
+    type Output = Bivector3;
+
+    fn neg(self) -> Self::Output {
+        self * -1.0
+    }
+}
@@ -0,0 +1,368 @@
+use crate::dvec4::DVec4;
+use crate::obb::Obb;
+use crate::sphere::Sphere;
+use crate::pca::pca;
+use crate::vec4::Vec4;
+
+// Rectangle in the plane with unit `axis` along its first side and the
+// second side along `axis` turned a quarter counter-clockwise.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientedRect {
+    pub center: (f32, f32),
+    pub axis: (f32, f32),
+    pub half_extents: (f32, f32)
+}
+
+#[allow(dead_code)]
+impl OrientedRect {
+    pub fn area(&self) -> f32 {
+        4.0 * self.half_extents.0 * self.half_extents.1
+    }
+}
+
+// Evaluated in f64: with f32 coordinates near the top of the range the
+// differences and products would overflow to infinity and the sign to NaN.
+fn cross_2d(o: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f64 {
+    let (ox, oy) = (o.0 as f64, o.1 as f64);
+    (a.0 as f64 - ox) * (b.1 as f64 - oy) - (a.1 as f64 - oy) * (b.0 as f64 - ox)
+}
+
+fn is_finite_2d(p: &(f32, f32)) -> bool {
+    p.0.is_finite() && p.1.is_finite()
+}
+
+fn is_finite_3d(p: &Vec4) -> bool {
+    p.x.is_finite() && p.y.is_finite() && p.z.is_finite()
+}
+
+// Convex hull by Andrew's monotone chain, counter-clockwise without
+// collinear points. Points with a NaN or infinite coordinate are skipped.
+#[allow(dead_code)]
+pub fn convex_hull_2d(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut sorted: Vec<(f32, f32)> = points.iter().copied().filter(is_finite_2d).collect();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+    let half = |points: &mut dyn Iterator<Item = &(f32, f32)>| {
+        let mut chain: Vec<(f32, f32)> = Vec::new();
+        for &p in points {
+            while chain.len() >= 2 && cross_2d(chain[chain.len() - 2], chain[chain.len() - 1], p) <= 0.0 {
+                chain.pop();
+            }
+            chain.push(p);
+        }
+        chain.pop();
+        chain
+    };
+    let mut hull = half(&mut sorted.iter());
+    hull.extend(half(&mut sorted.iter().rev()));
+    hull
+}
+
+// Minimum-area enclosing rectangle by rotating calipers. One side of the
+// optimum is flush with a hull edge, so each edge is tried while the three
+// other extreme points advance monotonically around the hull.
+#[allow(dead_code)]
+pub fn minimum_area_rect(points: &[(f32, f32)]) -> Option<OrientedRect> {
+    let hull = convex_hull_2d(points);
+    let n = hull.len();
+    if n == 0 {
+        return None;
+    }
+    if n < 3 {
+        let (a, b) = (hull[0], hull[n - 1]);
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        let axis = if length > 0.0 { (dx / length, dy / length) } else { (1.0, 0.0) };
+        return Some(OrientedRect {
+            center: ((a.0 + b.0) * 0.5, (a.1 + b.1) * 0.5),
+            axis,
+            half_extents: (length * 0.5, 0.0)
+        });
+    }
+
+    let dot = |p: (f32, f32), d: (f32, f32)| p.0 * d.0 + p.1 * d.1;
+    let edge = |i: usize| {
+        let (a, b) = (hull[i], hull[(i + 1) % n]);
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        (dx / length, dy / length)
+    };
+    let (mut right, mut top, mut left) = (0, 0, 0);
+    let mut best: Option<OrientedRect> = None;
+    for i in 0..n {
+        let u = edge(i);
+        let v = (-u.1, u.0);
+        if i == 0 {
+            right = (0..n).max_by(|&a, &b| dot(hull[a], u).total_cmp(&dot(hull[b], u))).unwrap();
+            top = (0..n).max_by(|&a, &b| dot(hull[a], v).total_cmp(&dot(hull[b], v))).unwrap();
+            left = (0..n).min_by(|&a, &b| dot(hull[a], u).total_cmp(&dot(hull[b], u))).unwrap();
+        }
+        while dot(hull[(right + 1) % n], u) > dot(hull[right], u) {
+            right = (right + 1) % n;
+        }
+        while dot(hull[(top + 1) % n], v) > dot(hull[top], v) {
+            top = (top + 1) % n;
+        }
+        while dot(hull[(left + 1) % n], u) < dot(hull[left], u) {
+            left = (left + 1) % n;
+        }
+
+        let (min_u, max_u) = (dot(hull[left], u), dot(hull[right], u));
+        let (min_v, max_v) = (dot(hull[i], v), dot(hull[top], v));
+        let rect = OrientedRect {
+            center: (
+                u.0 * (min_u + max_u) * 0.5 + v.0 * (min_v + max_v) * 0.5,
+                u.1 * (min_u + max_u) * 0.5 + v.1 * (min_v + max_v) * 0.5
+            ),
+            axis: u,
+            half_extents: ((max_u - min_u) * 0.5, (max_v - min_v) * 0.5)
+        };
+        if best.is_none_or(|b| rect.area() < b.area()) {
+            best = Some(rect);
+        }
+    }
+    best
+}
+
+fn contains(center: &DVec4, radius_squared: f64, p: &DVec4) -> bool {
+    let d = *p - *center;
+    d.dot(&d) <= radius_squared * (1.0 + 1e-10) + 1e-20
+}
+
+// Smallest sphere with `points` on its boundary, which must have at most
+// four entries. Degenerate sets fall back to the best sphere through a
+// subset that still contains every point.
+fn boundary_sphere(points: &[DVec4]) -> (DVec4, f64) {
+    match points {
+        [a] => (*a, 0.0),
+        [a, b] => {
+            let center = (*a + *b) * 0.5;
+            let d = *a - center;
+            (center, d.dot(&d))
+        }
+        [a, b, c] => {
+            let (ab, ac) = (*b - *a, *c - *a);
+            let n = ab.cross(&ac);
+            let n2 = n.dot(&n);
+            if n2 <= 1e-24 * ab.dot(&ab) * ac.dot(&ac) {
+                return best_subset(points);
+            }
+            let offset = (n.cross(&ab) * ac.dot(&ac) + ac.cross(&n) * ab.dot(&ab)) / (2.0 * n2);
+            (*a + offset, offset.dot(&offset))
+        }
+        [a, b, c, d] => {
+            // Solve 2 (p - a) . x = |p - a|^2 for p in b, c, d.
+            let rows = [*b - *a, *c - *a, *d - *a];
+            let rhs = rows.map(|r| r.dot(&r) * 0.5);
+            let det = rows[0].dot(&rows[1].cross(&rows[2]));
+            let scale = rows.iter().map(|r| r.length()).product::<f64>();
+            if det.abs() <= 1e-12 * scale {
+                return best_subset(points);
+            }
+            let offset = (rows[1].cross(&rows[2]) * rhs[0] + rows[2].cross(&rows[0]) * rhs[1] + rows[0].cross(&rows[1]) * rhs[2]) / det;
+            (*a + offset, offset.dot(&offset))
+        }
+        _ => unreachable!()
+    }
+}
+
+// Smallest sphere through a proper subset of `points` that contains them all.
+fn best_subset(points: &[DVec4]) -> (DVec4, f64) {
+    let mut best = (DVec4::zero(), f64::INFINITY);
+    for skip in 0..points.len() {
+        let subset: Vec<DVec4> = points.iter().enumerate().filter(|&(i, _)| i != skip).map(|(_, p)| *p).collect();
+        let (center, r2) = boundary_sphere(&subset);
+        if r2 < best.1 && points.iter().all(|p| contains(&center, r2, p)) {
+            best = (center, r2);
+        }
+    }
+    best
+}
+
+// Exact minimum enclosing sphere by Welzl's algorithm in its iterative
+// move-to-front form, evaluated in f64. Points are visited in a fixed
+// pseudo-random order to get the expected linear running time. None when
+// empty or when any point has a NaN or infinite coordinate.
+#[allow(dead_code)]
+pub fn minimum_enclosing_sphere(points: &[Vec4]) -> Option<Sphere> {
+    if points.is_empty() || !points.iter().all(is_finite_3d) {
+        return None;
+    }
+    let mut p: Vec<DVec4> = points.iter().map(|v| DVec4::new(v.x as f64, v.y as f64, v.z as f64, 0.0)).collect();
+    let mut seed = 0x2545f491u32;
+    for i in (1..p.len()).rev() {
+        seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+        p.swap(i, (seed as usize) % (i + 1));
+    }
+
+    let (mut center, mut r2) = boundary_sphere(&p[..1]);
+    for i in 1..p.len() {
+        if contains(&center, r2, &p[i]) {
+            continue;
+        }
+        (center, r2) = boundary_sphere(&[p[i]]);
+        for j in 0..i {
+            if contains(&center, r2, &p[j]) {
+                continue;
+            }
+            (center, r2) = boundary_sphere(&[p[i], p[j]]);
+            for k in 0..j {
+                if contains(&center, r2, &p[k]) {
+                    continue;
+                }
+                (center, r2) = boundary_sphere(&[p[i], p[j], p[k]]);
+                for l in 0..k {
+                    if !contains(&center, r2, &p[l]) {
+                        (center, r2) = boundary_sphere(&[p[i], p[j], p[k], p[l]]);
+                    }
+                }
+            }
+        }
+    }
+    Some(Sphere::new(Vec4::new(center.x as f32, center.y as f32, center.z as f32, 1.0), r2.sqrt() as f32))
+}
+
+// Oriented box heuristic: for each principal axis of the points, fit the
+// minimum-area rectangle to the projection onto the perpendicular plane
+// and keep the smallest resulting box. Not guaranteed optimal, but exact
+// for boxes and close for most real data. None under the same conditions
+// as `pca`.
+#[allow(dead_code)]
+pub fn minimum_volume_obb(points: &[Vec4]) -> Option<Obb> {
+    let (_, principal, _) = pca(points)?;
+    let axes: Vec<Vec4> = (0..3).map(|col| {
+        let axis = principal.column(col);
+        Vec4::new(axis[0], axis[1], axis[2], 0.0)
+    }).collect();
+
+    let mut best: Option<Obb> = None;
+    for &up in &axes {
+        let helper = if up.x.abs() < 0.9 { Vec4::new(1.0, 0.0, 0.0, 0.0) } else { Vec4::new(0.0, 1.0, 0.0, 0.0) };
+        let e1 = (helper - up * up.dot(&helper)).normalize();
+        let e2 = up.cross(&e1);
+        let projected: Vec<(f32, f32)> = points.iter().map(|p| {
+            let p = Vec4::new(p.x, p.y, p.z, 0.0);
+            (p.dot(&e1), p.dot(&e2))
+        }).collect();
+        let rect = minimum_area_rect(&projected)?;
+        let heights = points.iter().map(|p| Vec4::new(p.x, p.y, p.z, 0.0).dot(&up));
+        let (low, high) = heights.fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), h| (lo.min(h), hi.max(h)));
+
+        let a0 = e1 * rect.axis.0 + e2 * rect.axis.1;
+        let a1 = up.cross(&a0);
+        let center = e1 * rect.center.0 + e2 * rect.center.1 + up * ((low + high) * 0.5);
+        let obb = Obb::new(
+            Vec4::new(center.x, center.y, center.z, 1.0),
+            [a0, a1, up],
+            Vec4::new(rect.half_extents.0, rect.half_extents.1, (high - low) * 0.5, 0.0)
+        );
+        if best.is_none_or(|b| obb.volume() < b.volume()) {
+            best = Some(obb);
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::closest_point::ClosestPoint;
+
+    fn random_points(count: usize) -> Vec<Vec4> {
+        let mut seed = 99u32;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0
+        };
+        (0..count).map(|_| Vec4::new(next() * 3.0, next(), next() * 2.0, 1.0)).collect()
+    }
+
+    #[test]
+    fn welzl_sphere_is_minimal() {
+        let points = random_points(400);
+        let sphere = minimum_enclosing_sphere(&points).unwrap();
+        assert!(points.iter().all(|p| sphere.distance(p) < 1e-5));
+        // The optimum is supported by at least two boundary points.
+        let touching = points.iter().filter(|p| ((**p - sphere.center).length() - sphere.radius).abs() < 1e-4).count();
+        assert!(touching >= 2);
+
+        // Regular tetrahedron: the circumsphere is the answer.
+        let tetra = [
+            Vec4::new(1.0, 1.0, 1.0, 1.0), Vec4::new(1.0, -1.0, -1.0, 1.0),
+            Vec4::new(-1.0, 1.0, -1.0, 1.0), Vec4::new(-1.0, -1.0, 1.0, 1.0),
+            Vec4::new(0.1, 0.2, -0.3, 1.0)
+        ];
+        let sphere = minimum_enclosing_sphere(&tetra).unwrap();
+        assert!((sphere.radius - 3f32.sqrt()).abs() < 1e-5);
+        assert!((sphere.center - Vec4::new(0.0, 0.0, 0.0, 1.0)).length() < 1e-5);
+        // Collinear input degenerates to the diameter sphere.
+        let line: Vec<Vec4> = (0..5).map(|i| Vec4::new(i as f32, 0.0, 0.0, 1.0)).collect();
+        assert!((minimum_enclosing_sphere(&line).unwrap().radius - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn calipers_find_rotated_rectangle() {
+        let angle = 0.4f32;
+        let (s, c) = angle.sin_cos();
+        let mut points = Vec::new();
+        for i in 0..=10 {
+            for j in 0..=4 {
+                let (x, y) = (i as f32 * 0.6 - 3.0, j as f32 * 0.5 - 1.0);
+                points.push((x * c - y * s + 5.0, x * s + y * c - 2.0));
+            }
+        }
+        let rect = minimum_area_rect(&points).unwrap();
+        assert!((rect.area() - 12.0).abs() < 1e-3);
+        assert!((rect.center.0 - 5.0).abs() < 1e-4 && (rect.center.1 + 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn obb_heuristic_recovers_rotated_box() {
+        let s = std::f32::consts::FRAC_1_SQRT_2;
+        let axes = [Vec4::new(s, s, 0.0, 0.0), Vec4::new(-s, s, 0.0, 0.0), Vec4::new(0.0, 0.0, 1.0, 0.0)];
+        let mut points = Vec::new();
+        for corner in 0..8 {
+            let signs = [1, 2, 4].map(|bit| if corner & bit != 0 { 1.0 } else { -1.0 });
+            points.push(Vec4::new(1.0, 2.0, 3.0, 1.0) + axes[0] * (3.0 * signs[0]) + axes[1] * signs[1] + axes[2] * (0.5 * signs[2]));
+        }
+        let obb = minimum_volume_obb(&points).unwrap();
+        assert!((obb.volume() - 12.0).abs() < 1e-3);
+        assert!(points.iter().all(|p| obb.distance(p) < 1e-4));
+    }
+
+    #[test]
+    fn degenerate_input_is_handled() {
+        let (nan, inf) = (f32::NAN, f32::INFINITY);
+        let flat: [&[(f32, f32)]; 5] = [
+            &[],
+            &[(1.0, 1.0); 5],
+            &[(0.0, 0.0), (1.0, 1.0), (3.0, 3.0), (2.0, 2.0)],
+            &[(nan, 0.0), (1.0, 0.0), (0.0, 1.0), (inf, 2.0), (0.0, 0.0)],
+            &[(nan, nan); 4]
+        ];
+        for points in flat {
+            let hull = convex_hull_2d(points);
+            assert!(hull.len() <= 3 && hull.iter().all(is_finite_2d), "{:?}", points);
+            let rect = minimum_area_rect(points);
+            assert_eq!(rect.is_none(), hull.is_empty());
+            assert!(rect.is_none_or(|r| r.area().is_finite()));
+        }
+        assert_eq!(convex_hull_2d(flat[2]), vec![(0.0, 0.0), (3.0, 3.0)]);
+        // Differences and cross products here overflow f32.
+        assert_eq!(convex_hull_2d(&[(0.0, 0.0), (3e38, -3e38), (-3e38, 3e38), (3e38, 3e38)]).len(), 3);
+
+        let spatial: Vec<Vec<Vec4>> = flat.iter().map(|points| points.iter().map(|p| Vec4::new(p.0, p.1, 0.5, 1.0)).collect()).collect();
+        for points in &spatial {
+            let finite = !points.is_empty() && points.iter().all(is_finite_3d);
+            assert_eq!(minimum_enclosing_sphere(points).is_some(), finite);
+            assert_eq!(pca(points).is_some(), finite);
+            let obb = minimum_volume_obb(points);
+            assert_eq!(obb.is_some(), finite);
+            assert!(obb.is_none_or(|b| b.volume().is_finite()));
+        }
+    }
+}
@@ -0,0 +1,146 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BVec2 {
+    pub x: bool,
+    pub y: bool
+}
+
+#[allow(dead_code)]
+impl BVec2 {
+    pub fn new(x: bool, y: bool) -> Self {
+        Self {
+            x,
+            y
+        }
+    }
+
+    pub fn splat(value: bool) -> Self {
+        Self {
+            x: value,
+            y: value
+        }
+    }
+
+    pub fn all(&self) -> bool {
+        self.x && self.y
+    }
+
+    pub fn any(&self) -> bool {
+        self.x || self.y
+    }
+
+    // Packs the lanes into the low bits, x first.
+    pub fn bitmask(&self) -> u32 {
+        (self.x as u32) | (self.y as u32) << 1
+    }
+}
+
+impl std::ops::BitAnd<BVec2> for BVec2 {
+    type Output = BVec2;
+
+    fn bitand(self, rhs: BVec2) -> Self::Output {
+        Self {
+            x: self.x & rhs.x,
+            y: self.y & rhs.y
+        }
+    }
+}
+
+impl std::ops::BitOr<BVec2> for BVec2 {
+    type Output = BVec2;
+
+    fn bitor(self, rhs: BVec2) -> Self::Output {
+        Self {
+            x: self.x | rhs.x,
+            y: self.y | rhs.y
+        }
+    }
+}
+
+impl std::ops::BitXor<BVec2> for BVec2 {
+    type Output = BVec2;
+
+    fn bitxor(self, rhs: BVec2) -> Self::Output {
+        Self {
+            x: self.x ^ rhs.x,
+            y: self.y ^ rhs.y
+        }
+    }
+}
+
+impl std::ops::Not for BVec2 {
+    type Output = BVec2;
+
+    fn not(self) -> Self::Output {
+        Self {
+            x: !self.x,
+            y: !self.y
+        }
+    }
+}
+
+impl std::ops::Index<usize> for BVec2 {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for BVec2 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for BVec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl std::fmt::Debug for BVec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "BVec2({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ivec2::IVec2;
+
+    #[test]
+    fn reductions_and_bitmask() {
+        for (mask, all, any, bits) in [
+            (BVec2::new(false, false), false, false, 0b00),
+            (BVec2::new(true, false), false, true, 0b01),
+            (BVec2::new(false, true), false, true, 0b10),
+            (BVec2::splat(true), true, true, 0b11)
+        ] {
+            assert_eq!((mask.all(), mask.any(), mask.bitmask()), (all, any, bits));
+        }
+        let a = BVec2::new(true, false);
+        let b = BVec2::new(true, true);
+        assert_eq!(a & b, a);
+        assert_eq!(a | b, b);
+        assert_eq!(a ^ b, !a);
+        assert!(a[0] && !a[1]);
+    }
+
+    #[test]
+    fn select() {
+        let a = IVec2::new(1, 2);
+        let b = IVec2::new(-1, -2);
+        assert_eq!(IVec2::select(&BVec2::new(true, false), &a, &b), IVec2::new(1, -2));
+        assert_eq!(IVec2::select(&a.cmplt(&b), &a, &b), a.min(&b));
+    }
+}
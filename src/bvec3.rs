@@ -0,0 +1,151 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BVec3 {
+    pub x: bool,
+    pub y: bool,
+    pub z: bool
+}
+
+#[allow(dead_code)]
+impl BVec3 {
+    pub fn new(x: bool, y: bool, z: bool) -> Self {
+        Self {
+            x,
+            y,
+            z
+        }
+    }
+
+    pub fn splat(value: bool) -> Self {
+        Self {
+            x: value,
+            y: value,
+            z: value
+        }
+    }
+
+    pub fn all(&self) -> bool {
+        self.x && self.y && self.z
+    }
+
+    pub fn any(&self) -> bool {
+        self.x || self.y || self.z
+    }
+
+    // Packs the lanes into the low bits, x first.
+    pub fn bitmask(&self) -> u32 {
+        (self.x as u32) | (self.y as u32) << 1 | (self.z as u32) << 2
+    }
+}
+
+impl std::ops::BitAnd<BVec3> for BVec3 {
+    type Output = BVec3;
+
+    fn bitand(self, rhs: BVec3) -> Self::Output {
+        Self {
+            x: self.x & rhs.x,
+            y: self.y & rhs.y,
+            z: self.z & rhs.z
+        }
+    }
+}
+
+impl std::ops::BitOr<BVec3> for BVec3 {
+    type Output = BVec3;
+
+    fn bitor(self, rhs: BVec3) -> Self::Output {
+        Self {
+            x: self.x | rhs.x,
+            y: self.y | rhs.y,
+            z: self.z | rhs.z
+        }
+    }
+}
+
+impl std::ops::BitXor<BVec3> for BVec3 {
+    type Output = BVec3;
+
+    fn bitxor(self, rhs: BVec3) -> Self::Output {
+        Self {
+            x: self.x ^ rhs.x,
+            y: self.y ^ rhs.y,
+            z: self.z ^ rhs.z
+        }
+    }
+}
+
+impl std::ops::Not for BVec3 {
+    type Output = BVec3;
+
+    fn not(self) -> Self::Output {
+        Self {
+            x: !self.x,
+            y: !self.y,
+            z: !self.z
+        }
+    }
+}
+
+impl std::ops::Index<usize> for BVec3 {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for BVec3 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for BVec3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl std::fmt::Debug for BVec3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "BVec3({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ivec3::IVec3;
+
+    #[test]
+    fn reductions_and_bitmask() {
+        let mask = BVec3::new(true, false, true);
+        assert!(!mask.all() && mask.any());
+        assert_eq!(mask.bitmask(), 0b101);
+        assert_eq!((!mask).bitmask(), 0b010);
+        assert!(BVec3::splat(true).all());
+        assert!(!BVec3::default().any());
+        assert_eq!(BVec3::default().bitmask(), 0);
+        let mut m = mask;
+        m[1] = true;
+        assert!(m.all());
+    }
+
+    #[test]
+    fn select() {
+        let a = IVec3::new(1, 2, 3);
+        let b = IVec3::new(3, 2, 1);
+        assert_eq!(IVec3::select(&a.cmpgt(&b), &a, &b), IVec3::new(3, 2, 3));
+        assert_eq!(IVec3::select(&BVec3::splat(false), &a, &b), b);
+    }
+}
@@ -0,0 +1,161 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BVec4 {
+    pub x: bool,
+    pub y: bool,
+    pub z: bool,
+    pub w: bool
+}
+
+#[allow(dead_code)]
+impl BVec4 {
+    pub fn new(x: bool, y: bool, z: bool, w: bool) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            w
+        }
+    }
+
+    pub fn splat(value: bool) -> Self {
+        Self {
+            x: value,
+            y: value,
+            z: value,
+            w: value
+        }
+    }
+
+    pub fn all(&self) -> bool {
+        self.x && self.y && self.z && self.w
+    }
+
+    pub fn any(&self) -> bool {
+        self.x || self.y || self.z || self.w
+    }
+
+    // Packs the lanes into the low bits, x first.
+    pub fn bitmask(&self) -> u32 {
+        (self.x as u32) | (self.y as u32) << 1 | (self.z as u32) << 2 | (self.w as u32) << 3
+    }
+}
+
+impl std::ops::BitAnd<BVec4> for BVec4 {
+    type Output = BVec4;
+
+    fn bitand(self, rhs: BVec4) -> Self::Output {
+        Self {
+            x: self.x & rhs.x,
+            y: self.y & rhs.y,
+            z: self.z & rhs.z,
+            w: self.w & rhs.w
+        }
+    }
+}
+
+impl std::ops::BitOr<BVec4> for BVec4 {
+    type Output = BVec4;
+
+    fn bitor(self, rhs: BVec4) -> Self::Output {
+        Self {
+            x: self.x | rhs.x,
+            y: self.y | rhs.y,
+            z: self.z | rhs.z,
+            w: self.w | rhs.w
+        }
+    }
+}
+
+impl std::ops::BitXor<BVec4> for BVec4 {
+    type Output = BVec4;
+
+    fn bitxor(self, rhs: BVec4) -> Self::Output {
+        Self {
+            x: self.x ^ rhs.x,
+            y: self.y ^ rhs.y,
+            z: self.z ^ rhs.z,
+            w: self.w ^ rhs.w
+        }
+    }
+}
+
+impl std::ops::Not for BVec4 {
+    type Output = BVec4;
+
+    fn not(self) -> Self::Output {
+        Self {
+            x: !self.x,
+            y: !self.y,
+            z: !self.z,
+            w: !self.w
+        }
+    }
+}
+
+impl std::ops::Index<usize> for BVec4 {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for BVec4 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for BVec4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+impl std::fmt::Debug for BVec4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "BVec4({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec4::Vec4;
+
+    #[test]
+    fn reductions_and_bitmask() {
+        let mask = BVec4::new(false, true, false, true);
+        assert!(!mask.all() && mask.any());
+        assert_eq!(mask.bitmask(), 0b1010);
+        assert_eq!((mask | !mask).bitmask(), 0b1111);
+        assert!((mask | !mask).all());
+        assert!(!(mask & !mask).any());
+        assert_eq!((mask ^ BVec4::splat(true)), !mask);
+    }
+
+    #[test]
+    fn select() {
+        let a = Vec4::new(1.0, 5.0, -2.0, 0.0);
+        let b = Vec4::new(2.0, 4.0, -3.0, 0.0);
+        let mask = a.cmpge(&b);
+        assert_eq!(mask, BVec4::new(false, true, true, true));
+        assert_eq!(Vec4::select(&mask, &a, &b), Vec4::new(2.0, 5.0, -2.0, 0.0));
+        // NaN is unequal to everything, itself included.
+        let nan = Vec4::new(f32::NAN, 0.0, 0.0, 0.0);
+        assert!(!nan.cmpeq(&nan).x && nan.cmpne(&nan).x);
+    }
+}
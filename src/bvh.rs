@@ -0,0 +1,202 @@
+use crate::aabb::Aabb;
+use crate::vec4::Vec4;
+
+// Leaves hold at most this many items.
+const MAX_LEAF_ITEMS: usize = 4;
+
+#[derive(Clone, Debug)]
+struct BvhNode {
+    bounds: Aabb,
+    // Index of the first of two consecutive children.
+    children: Option<usize>,
+    items: std::ops::Range<usize>
+}
+
+// Bounding volume hierarchy over items with bounding boxes, built once by
+// splitting at the median centroid along the longest axis. Unlike `Octree`
+// it needs no bounds up front and adapts to clustered items.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct Bvh<T> {
+    nodes: Vec<BvhNode>,
+    items: Vec<(Aabb, T)>
+}
+
+fn is_finite(bounds: &Aabb) -> bool {
+    (0..3).all(|axis| bounds.min[axis].is_finite() && bounds.max[axis].is_finite())
+}
+
+fn union_of<T>(items: &[(Aabb, T)]) -> Aabb {
+    items[1..].iter().fold(items[0].0, |bounds, (item, _)| bounds.union(item))
+}
+
+#[allow(dead_code)]
+impl<T> Bvh<T> {
+    // Items whose bounds have a NaN or infinite coordinate are dropped:
+    // they can't be ordered along an axis and no query could return them.
+    pub fn build(items: impl IntoIterator<Item = (Aabb, T)>) -> Self {
+        let mut out = Self {
+            nodes: Vec::new(),
+            items: items.into_iter().filter(|(bounds, _)| is_finite(bounds)).collect()
+        };
+        if !out.items.is_empty() {
+            out.nodes.push(BvhNode {
+                bounds: union_of(&out.items),
+                children: None,
+                items: 0..out.items.len()
+            });
+            out.split(0);
+        }
+        out
+    }
+
+    // Median splits halve the item count at every level, so the depth is
+    // logarithmic even when every centroid coincides.
+    fn split(&mut self, index: usize) {
+        let range = self.nodes[index].items.clone();
+        if range.len() <= MAX_LEAF_ITEMS {
+            return;
+        }
+        let extent = self.nodes[index].bounds.max - self.nodes[index].bounds.min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        self.items[range.clone()].sort_by(|a, b| a.0.center()[axis].total_cmp(&b.0.center()[axis]));
+        let middle = range.start + range.len() / 2;
+        let first = self.nodes.len();
+        for items in [range.start..middle, middle..range.end] {
+            self.nodes.push(BvhNode {
+                bounds: union_of(&self.items[items.clone()]),
+                children: None,
+                items
+            });
+        }
+        self.nodes[index].children = Some(first);
+        self.split(first);
+        self.split(first + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // None for an empty hierarchy.
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.nodes.first().map(|node| node.bounds)
+    }
+
+    // Depth-first walk over the nodes accepted by `visit_node`, calling
+    // `visit_item` for every item in the accepted leaves.
+    fn collect<'a>(&'a self, visit_node: impl Fn(&Aabb) -> bool, mut visit_item: impl FnMut(&Aabb, &'a T)) {
+        let mut stack: Vec<usize> = self.nodes.first().filter(|root| visit_node(&root.bounds)).map(|_| 0).into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            match node.children {
+                Some(first) => stack.extend((first..first + 2).filter(|&child| visit_node(&self.nodes[child].bounds))),
+                None => {
+                    for (bounds, value) in &self.items[node.items.clone()] {
+                        visit_item(bounds, value);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn query_range(&self, region: &Aabb) -> Vec<&T> {
+        let mut out = Vec::new();
+        self.collect(|bounds| bounds.intersects(region), |bounds, value| {
+            if bounds.intersects(region) {
+                out.push(value);
+            }
+        });
+        out
+    }
+
+    // Items whose bounds are hit by the ray within `max_distance`, sorted
+    // by the distance at which the ray enters their bounds.
+    pub fn query_ray(&self, origin: &Vec4, direction: &Vec4, max_distance: f32) -> Vec<(&T, f32)> {
+        let hit = |bounds: &Aabb| match bounds.ray_intersection(origin, direction) {
+            Some((entry, _)) if entry <= max_distance => Some(entry),
+            _ => None
+        };
+        let mut out = Vec::new();
+        self.collect(|bounds| hit(bounds).is_some(), |bounds, value| {
+            if let Some(entry) = hit(bounds) {
+                out.push((value, entry));
+            }
+        });
+        out.sort_by(|a, b| a.1.total_cmp(&b.1));
+        out
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Aabb, &T)> {
+        self.items.iter().map(|(bounds, value)| (bounds, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scattered() -> Bvh<usize> {
+        // A pseudo-random cloud plus a cluster of identical boxes and one
+        // box with a NaN corner.
+        let mut state = 12345u32;
+        let mut next = || {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            (state >> 8) as f32 / (1 << 24) as f32 * 20.0
+        };
+        let mut items = Vec::new();
+        for id in 0..200 {
+            let min = Vec4::new(next(), next(), next(), 0.0);
+            items.push((Aabb::new(min, min + Vec4::new(1.0, 0.5, 2.0, 0.0)), id));
+        }
+        for id in 200..220 {
+            items.push((Aabb::from_point(&Vec4::new(3.0, 3.0, 3.0, 0.0)), id));
+        }
+        items.push((Aabb::new(Vec4::new(f32::NAN, 0.0, 0.0, 0.0), Vec4::zero()), 999));
+        Bvh::build(items)
+    }
+
+    #[test]
+    fn range_query_matches_brute_force() {
+        let tree = scattered();
+        assert_eq!(tree.len(), 220);
+        for region in [
+            Aabb::new(Vec4::new(2.0, 2.5, 0.0, 0.0), Vec4::new(9.0, 7.0, 4.0, 0.0)),
+            Aabb::from_point(&Vec4::new(3.0, 3.0, 3.0, 0.0)),
+            Aabb::new(Vec4::new(-1.0, -1.0, -1.0, 0.0), Vec4::new(30.0, 30.0, 30.0, 0.0))
+        ] {
+            let mut found: Vec<usize> = tree.query_range(&region).into_iter().copied().collect();
+            found.sort();
+            let mut expected: Vec<usize> = tree.iter()
+                .filter(|(bounds, _)| region.intersects(bounds))
+                .map(|(_, &id)| id)
+                .collect();
+            expected.sort();
+            assert_eq!(found, expected);
+        }
+        assert!(Bvh::<usize>::build([]).query_range(&Aabb::from_point(&Vec4::zero())).is_empty());
+    }
+
+    #[test]
+    fn ray_query_sorted() {
+        let tree = scattered();
+        let (origin, direction) = (Vec4::new(-5.0, 3.0, 3.0, 0.0), Vec4::new(1.0, 0.0, 0.0, 0.0));
+        let hits = tree.query_ray(&origin, &direction, 100.0);
+        let expected = tree.iter()
+            .filter(|(bounds, _)| bounds.ray_intersection(&origin, &direction).is_some())
+            .count();
+        assert_eq!(hits.len(), expected);
+        assert!(hits.iter().any(|(&id, entry)| id == 200 && *entry == 8.0));
+        assert!(hits.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+}
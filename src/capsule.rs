@@ -0,0 +1,101 @@
+use crate::aabb::Aabb;
+use crate::closest_point::{direction, point, ClosestPoint};
+use crate::segment::Segment;
+use crate::sphere::Sphere;
+use crate::vec4::Vec4;
+
+// Points within `radius` of the segment from `start` to `end`.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Capsule {
+    pub start: Vec4,
+    pub end: Vec4,
+    pub radius: f32
+}
+
+#[allow(dead_code)]
+impl Capsule {
+    pub fn new(start: Vec4, end: Vec4, radius: f32) -> Self {
+        Self {
+            start: point(&start),
+            end: point(&end),
+            radius
+        }
+    }
+
+    pub fn segment(&self) -> Segment {
+        Segment::new(self.start, self.end)
+    }
+
+    pub fn contains_point(&self, p: &Vec4) -> bool {
+        self.segment().distance_squared(p) <= self.radius * self.radius
+    }
+
+    // Ray parameter of the first hit, zero when the origin is inside. The
+    // capsule is the union of a cylinder and two spheres, so the first hit
+    // is the nearest hit on any of them.
+    pub fn ray_cast(&self, origin: &Vec4, d: &Vec4) -> Option<f32> {
+        if self.contains_point(origin) {
+            return Some(0.0);
+        }
+        let d = direction(d);
+        let mut best = [self.start, self.end]
+            .iter()
+            .filter_map(|&center| Sphere::new(center, self.radius).ray_cast(origin, &d))
+            .fold(f32::INFINITY, f32::min);
+
+        let axis = self.end - self.start;
+        let axis_squared = axis.dot(&axis);
+        if axis_squared > 0.0 {
+            let oa = direction(&(*origin - self.start));
+            let d_perp = d - axis * (d.dot(&axis) / axis_squared);
+            let o_perp = oa - axis * (oa.dot(&axis) / axis_squared);
+            let a = d_perp.dot(&d_perp);
+            let b = o_perp.dot(&d_perp);
+            let c = o_perp.dot(&o_perp) - self.radius * self.radius;
+            let discriminant = b * b - a * c;
+            if a > 0.0 && discriminant >= 0.0 {
+                let t = (-b - discriminant.sqrt()) / a;
+                let along = (oa + d * t).dot(&axis);
+                if t >= 0.0 && along >= 0.0 && along <= axis_squared {
+                    best = best.min(t);
+                }
+            }
+        }
+        if best.is_finite() { Some(best) } else { None }
+    }
+
+    pub fn support(&self, d: &Vec4) -> Vec4 {
+        let end = if direction(&(self.end - self.start)).dot(&direction(d)) > 0.0 { self.end } else { self.start };
+        Sphere::new(end, self.radius).support(d)
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        Sphere::new(self.start, self.radius).aabb().union(&Sphere::new(self.end, self.radius).aabb())
+    }
+
+    pub fn volume(&self) -> f32 {
+        let pi = std::f32::consts::PI;
+        let r = self.radius;
+        pi * r * r * (self.segment().length() + 4.0 / 3.0 * r)
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        let pi = std::f32::consts::PI;
+        let r = self.radius;
+        2.0 * pi * r * (self.segment().length() + 2.0 * r)
+    }
+}
+
+impl ClosestPoint for Capsule {
+    fn closest_point(&self, p: &Vec4) -> Vec4 {
+        let axis_point = self.segment().closest_point(p);
+        let d = direction(&(*p - axis_point));
+        let length = d.length();
+        if length <= self.radius {
+            point(p)
+        } else {
+            axis_point + d * (self.radius / length)
+        }
+    }
+}
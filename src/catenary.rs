@@ -0,0 +1,192 @@
+use crate::vec4::Vec4;
+
+// Curve of a uniform rope or cable hanging between two points under
+// gravity along -y: y = a cosh((s - s0) / a) + c, where s is the distance
+// along the horizontal direction from `start` to `end`. Solved once on
+// construction; evaluation is closed form, so it is cheap enough to
+// rebuild every frame for rendering.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Catenary {
+    start: Vec4,
+    end: Vec4,
+    length: f32,
+    // Unit horizontal direction from start to end, and the span along it.
+    horizontal: Vec4,
+    span: f64,
+    // Shape parameter, lowest point's s and vertical offset. Zero `a` marks
+    // a taut rope that is just the straight segment.
+    a: f64,
+    s0: f64,
+    c: f64
+}
+
+#[allow(dead_code)]
+impl Catenary {
+    // Rope of `length` between two points. A rope no longer than the
+    // distance between them, or hanging between points stacked vertically,
+    // is taut and follows the straight segment.
+    pub fn new(start: Vec4, end: Vec4, length: f32) -> Self {
+        let start = Vec4::new(start.x, start.y, start.z, 1.0);
+        let end = Vec4::new(end.x, end.y, end.z, 1.0);
+        let flat = Vec4::new(end.x - start.x, 0.0, end.z - start.z, 0.0);
+        let span = (flat.x as f64).hypot(flat.z as f64);
+        let rise = (end.y - start.y) as f64;
+        let length_f64 = length as f64;
+        let mut out = Self {
+            start,
+            end,
+            length,
+            horizontal: Vec4::zero(),
+            span,
+            a: 0.0,
+            s0: 0.0,
+            c: 0.0
+        };
+        if span <= 1e-9 || length_f64 * length_f64 <= span * span + rise * rise {
+            out.length = (end - start).length();
+            return out;
+        }
+        out.horizontal = flat * (1.0 / span as f32);
+
+        // The rope fixes sinh(x) / x = r with x = span / (2a).
+        let r = (length_f64 * length_f64 - rise * rise).sqrt() / span;
+        let x = solve_sinhc(r);
+        let a = span / (2.0 * x);
+        out.a = a;
+        out.s0 = span * 0.5 - a * (rise / length_f64).atanh();
+        out.c = start.y as f64 - a * (out.s0 / a).cosh();
+        out
+    }
+
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    pub fn is_taut(&self) -> bool {
+        self.a == 0.0
+    }
+
+    // Point at horizontal distance `s` from `start` when hanging.
+    fn at_horizontal(&self, s: f64) -> Vec4 {
+        let y = self.a * ((s - self.s0) / self.a).cosh() + self.c;
+        let mut p = self.start + self.horizontal * s as f32;
+        p.y = y as f32;
+        p
+    }
+
+    // Arc length from the lowest point to horizontal distance `s`, signed.
+    fn arc_from_lowest(&self, s: f64) -> f64 {
+        self.a * ((s - self.s0) / self.a).sinh()
+    }
+
+    // Point at arc length `distance` from `start`, clamped to the rope.
+    pub fn point_at_length(&self, distance: f32) -> Vec4 {
+        let t = if self.length > 0.0 { (distance / self.length).clamp(0.0, 1.0) } else { 0.0 };
+        if self.is_taut() {
+            return self.start + (self.end - self.start) * t;
+        }
+        let arc = self.arc_from_lowest(0.0) + t as f64 * self.length as f64;
+        self.at_horizontal(self.s0 + self.a * (arc / self.a).asinh())
+    }
+
+    // Unit tangent at arc length `distance` from `start`, pointing towards
+    // `end`.
+    pub fn tangent_at_length(&self, distance: f32) -> Vec4 {
+        if self.is_taut() {
+            return (self.end - self.start).normalize();
+        }
+        let t = (distance / self.length).clamp(0.0, 1.0) as f64;
+        let arc = self.arc_from_lowest(0.0) + t * self.length as f64;
+        // dy/ds is arc / a, so the tangent is (1, arc / a) normalized.
+        let slope = arc / self.a;
+        let norm = (1.0 + slope * slope).sqrt();
+        let mut tangent = self.horizontal * (1.0 / norm) as f32;
+        tangent.y = (slope / norm) as f32;
+        tangent
+    }
+
+    // Lowest point along the rope, which is an endpoint when the rope
+    // never dips below it.
+    pub fn lowest_point(&self) -> Vec4 {
+        if self.is_taut() {
+            return if self.start.y <= self.end.y { self.start } else { self.end };
+        }
+        self.at_horizontal(self.s0.clamp(0.0, self.span))
+    }
+
+    // `count` points with unit tangents, evenly spaced along the rope from
+    // `start` to `end` inclusive.
+    pub fn sample(&self, count: usize) -> Vec<(Vec4, Vec4)> {
+        (0..count).map(|i| {
+            let distance = if count > 1 { self.length * i as f32 / (count - 1) as f32 } else { 0.0 };
+            (self.point_at_length(distance), self.tangent_at_length(distance))
+        }).collect()
+    }
+}
+
+// Positive root of sinh(x) / x = r for r > 1 by Newton's method. Both
+// starting guesses lie above the root and f(x) = sinh(x) - r x is convex
+// there, so the iteration decreases monotonically onto it.
+fn solve_sinhc(r: f64) -> f64 {
+    let mut x = if r < 3.0 {
+        (6.0 * (r - 1.0)).sqrt()
+    } else {
+        let l = (2.0 * r).ln();
+        l + l.ln() + 1.0
+    };
+    for _ in 0..100 {
+        let step = (x.sinh() - r * x) / (x.cosh() - r);
+        x -= step;
+        if step.abs() <= 1e-15 * x {
+            break;
+        }
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polyline_length(points: &[(Vec4, Vec4)]) -> f32 {
+        points.windows(2).map(|w| (w[1].0 - w[0].0).length()).sum()
+    }
+
+    #[test]
+    fn hangs_with_requested_length() {
+        for (start, end, length) in [
+            (Vec4::new(0.0, 10.0, 0.0, 1.0), Vec4::new(8.0, 10.0, 0.0, 1.0), 10.0),
+            (Vec4::new(1.0, 2.0, -3.0, 1.0), Vec4::new(4.0, 6.0, 1.0, 1.0), 12.0),
+            (Vec4::new(0.0, 0.0, 0.0, 1.0), Vec4::new(1.0, 0.0, 0.0, 1.0), 100.0)
+        ] {
+            let rope = Catenary::new(start, end, length);
+            assert!(!rope.is_taut());
+            let samples = rope.sample(2001);
+            assert!((samples[0].0 - start).length() < 1e-4);
+            assert!((samples[2000].0 - end).length() < 1e-3, "{:?}", samples[2000].0);
+            assert!((polyline_length(&samples) - length).abs() < 1e-3 * length);
+            assert!(samples.iter().all(|(_, t)| (t.length() - 1.0).abs() < 1e-5));
+            // Tangents match the direction between neighbouring samples.
+            let chord = (samples[1001].0 - samples[999].0).normalize();
+            assert!((chord - samples[1000].1).length() < 1e-3);
+            assert!(rope.lowest_point().y <= start.y.min(end.y));
+        }
+
+        // Level ends sag symmetrically with the lowest point mid-span.
+        let rope = Catenary::new(Vec4::new(0.0, 10.0, 0.0, 1.0), Vec4::new(8.0, 10.0, 0.0, 1.0), 10.0);
+        let lowest = rope.lowest_point();
+        assert!((lowest.x - 4.0).abs() < 1e-4 && lowest.y < 8.0);
+        assert!((rope.point_at_length(5.0) - lowest).length() < 1e-4);
+    }
+
+    #[test]
+    fn short_rope_is_taut() {
+        let (start, end) = (Vec4::new(0.0, 0.0, 0.0, 1.0), Vec4::new(3.0, 4.0, 0.0, 1.0));
+        let rope = Catenary::new(start, end, 4.0);
+        assert!(rope.is_taut());
+        assert_eq!(rope.length(), 5.0);
+        assert!((rope.point_at_length(2.5) - Vec4::new(1.5, 2.0, 0.0, 1.0)).length() < 1e-6);
+        assert!((rope.tangent_at_length(1.0) - Vec4::new(0.6, 0.8, 0.0, 0.0)).length() < 1e-6);
+    }
+}
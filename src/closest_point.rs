@@ -0,0 +1,89 @@
+use crate::vec4::Vec4;
+
+// Proximity queries against a primitive. Only x, y and z of the query are
+// used and results are points with w = 1. Solids count their interior, so a
+// query inside one is its own closest point.
+#[allow(dead_code)]
+pub trait ClosestPoint {
+    fn closest_point(&self, point: &Vec4) -> Vec4;
+
+    fn distance_squared(&self, point: &Vec4) -> f32 {
+        let d = self.closest_point(point) - *point;
+        d.x * d.x + d.y * d.y + d.z * d.z
+    }
+
+    fn distance(&self, point: &Vec4) -> f32 {
+        self.distance_squared(point).sqrt()
+    }
+}
+
+pub(crate) fn point(v: &Vec4) -> Vec4 {
+    Vec4::new(v.x, v.y, v.z, 1.0)
+}
+
+pub(crate) fn direction(v: &Vec4) -> Vec4 {
+    Vec4::new(v.x, v.y, v.z, 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aabb::Aabb;
+    use crate::capsule::Capsule;
+    use crate::obb::Obb;
+    use crate::plane::Plane;
+    use crate::segment::Segment;
+    use crate::sphere::Sphere;
+    use crate::triangle::Triangle;
+
+    fn p(x: f32, y: f32, z: f32) -> Vec4 {
+        Vec4::new(x, y, z, 1.0)
+    }
+
+    fn close(a: Vec4, b: Vec4) -> bool {
+        (a - b).length() < 1e-5
+    }
+
+    // Primitives are usable through the trait alone.
+    fn nearest<'a>(shapes: &'a [&'a dyn ClosestPoint], query: &Vec4) -> usize {
+        (0..shapes.len()).min_by(|&a, &b| shapes[a].distance(query).total_cmp(&shapes[b].distance(query))).unwrap()
+    }
+
+    #[test]
+    fn primitives() {
+        let sphere = Sphere::new(p(0.0, 0.0, 0.0), 2.0);
+        assert!(close(sphere.closest_point(&p(4.0, 0.0, 0.0)), p(2.0, 0.0, 0.0)));
+        assert!(close(sphere.closest_point(&p(0.5, 0.5, 0.0)), p(0.5, 0.5, 0.0)));
+
+        let aabb = Aabb::new(Vec4::new(-1.0, -1.0, -1.0, 0.0), Vec4::new(1.0, 1.0, 1.0, 0.0));
+        assert!(close(aabb.closest_point(&p(3.0, 0.5, -4.0)), p(1.0, 0.5, -1.0)));
+
+        let s = std::f32::consts::FRAC_1_SQRT_2;
+        let obb = Obb::new(p(0.0, 0.0, 0.0), [Vec4::new(s, s, 0.0, 0.0), Vec4::new(-s, s, 0.0, 0.0), Vec4::new(0.0, 0.0, 1.0, 0.0)], Vec4::new(1.0, 1.0, 1.0, 0.0));
+        assert!(close(obb.closest_point(&p(5.0, 5.0, 0.0)), p(s, s, 0.0)));
+        assert!(obb.contains_point(&p(0.0, 1.3, 0.0)) && !obb.contains_point(&p(1.3, 1.3, 0.0)));
+
+        let plane = Plane::from_point_normal(&p(0.0, 1.0, 0.0), &Vec4::new(0.0, 2.0, 0.0, 0.0));
+        assert!(close(plane.closest_point(&p(3.0, -2.0, 1.0)), p(3.0, 1.0, 1.0)));
+        assert_eq!(plane.signed_distance(&p(3.0, -2.0, 1.0)), -3.0);
+
+        let segment = Segment::new(p(0.0, 0.0, 0.0), p(2.0, 0.0, 0.0));
+        assert!(close(segment.closest_point(&p(-1.0, 1.0, 0.0)), p(0.0, 0.0, 0.0)));
+        assert!(close(segment.closest_point(&p(1.5, 1.0, 0.0)), p(1.5, 0.0, 0.0)));
+
+        let capsule = Capsule::new(p(0.0, 0.0, 0.0), p(2.0, 0.0, 0.0), 0.5);
+        assert!(close(capsule.closest_point(&p(1.0, 3.0, 0.0)), p(1.0, 0.5, 0.0)));
+        assert!((capsule.distance(&p(4.0, 0.0, 0.0)) - 1.5).abs() < 1e-6);
+
+        let triangle = Triangle::new(p(0.0, 0.0, 0.0), p(2.0, 0.0, 0.0), p(0.0, 2.0, 0.0));
+        assert!(close(triangle.closest_point(&p(0.5, 0.5, 3.0)), p(0.5, 0.5, 0.0)));
+        assert!(close(triangle.closest_point(&p(2.0, 2.0, 0.0)), p(1.0, 1.0, 0.0)));
+        assert!(close(triangle.closest_point(&p(-1.0, -1.0, 1.0)), p(0.0, 0.0, 0.0)));
+        assert!(close(triangle.closest_point(&p(1.0, -1.0, 0.0)), p(1.0, 0.0, 0.0)));
+
+        let far = Sphere::new(p(10.0, 0.0, 0.0), 1.0);
+        let shapes: [&dyn ClosestPoint; 3] = [&far, &segment, &triangle];
+        assert_eq!(nearest(&shapes, &p(0.5, 0.5, 0.1)), 2);
+        assert_eq!(nearest(&shapes, &p(12.0, 0.0, 0.0)), 0);
+    }
+}
@@ -0,0 +1,449 @@
+use crate::error::MathError;
+use crate::matrix::Matrix;
+use crate::vector::Vector;
+
+// Linear RGB, XYZ and white points are all `Vector<f32, 3>`; conversion
+// matrices are `Matrix<f32, 3, 3>` applied as `m * color`. The constants
+// below were derived in exact rational arithmetic from the primaries and
+// white points of each specification and rounded once to f32.
+
+// CIE 1931 xy chromaticities of common white points.
+pub const D50: [f32; 2] = [0.3457, 0.3585];
+pub const D65: [f32; 2] = [0.3127, 0.3290];
+// The ACES white, close to but not exactly D60.
+pub const ACES_WHITE: [f32; 2] = [0.32168, 0.33767];
+
+// Red, green and blue primaries as xy chromaticities.
+pub const SRGB_PRIMARIES: [[f32; 2]; 3] = [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]];
+pub const ACESCG_PRIMARIES: [[f32; 2]; 3] = [[0.713, 0.293], [0.165, 0.830], [0.128, 0.044]];
+
+const fn from_rows(r: [[f32; 3]; 3]) -> Matrix<f32, 3, 3> {
+    Matrix {
+        columns: [[r[0][0], r[1][0], r[2][0]], [r[0][1], r[1][1], r[2][1]], [r[0][2], r[1][2], r[2][2]]]
+    }
+}
+
+// Linear sRGB to XYZ, both relative to D65.
+pub const SRGB_TO_XYZ: Matrix<f32, 3, 3> = from_rows([
+    [0.4123908, 0.35758433, 0.1804808],
+    [0.212639, 0.71516865, 0.07219232],
+    [0.019330818, 0.11919478, 0.95053214]
+]);
+
+pub const XYZ_TO_SRGB: Matrix<f32, 3, 3> = from_rows([
+    [3.24097, -1.5373832, -0.49861076],
+    [-0.96924365, 1.8759675, 0.04155506],
+    [0.05563008, -0.20397696, 1.0569715]
+]);
+
+// ACEScg (AP1 primaries) to XYZ, both relative to the ACES white.
+pub const ACESCG_TO_XYZ: Matrix<f32, 3, 3> = from_rows([
+    [0.6624542, 0.1340042, 0.15618768],
+    [0.27222872, 0.67408174, 0.053689517],
+    [-0.0055746497, 0.0040607336, 1.0103391]
+]);
+
+pub const XYZ_TO_ACESCG: Matrix<f32, 3, 3> = from_rows([
+    [1.6410234, -0.3248033, -0.2364247],
+    [-0.66366285, 1.6153316, 0.016756348],
+    [0.011721894, -0.008284442, 0.98839486]
+]);
+
+// Linear sRGB to ACEScg with Bradford adaptation from D65 to the ACES
+// white, so sRGB white maps to ACEScg white.
+pub const SRGB_TO_ACESCG: Matrix<f32, 3, 3> = from_rows([
+    [0.6130974, 0.33952314, 0.047379453],
+    [0.07019372, 0.9163539, 0.013452399],
+    [0.020615593, 0.10956977, 0.86981463]
+]);
+
+pub const ACESCG_TO_SRGB: Matrix<f32, 3, 3> = from_rows([
+    [1.705051, -0.62179214, -0.083258875],
+    [-0.13025641, 1.1408048, -0.010548319],
+    [-0.024003357, -0.12896897, 1.1529723]
+]);
+
+// XYZ to the Bradford cone response space.
+pub const BRADFORD: Matrix<f32, 3, 3> = from_rows([
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296]
+]);
+
+const BRADFORD_INVERSE: Matrix<f32, 3, 3> = from_rows([
+    [0.9869929, -0.14705426, 0.15996265],
+    [0.43230528, 0.51836026, 0.049291227],
+    [-0.008528665, 0.04004282, 0.96848667]
+]);
+
+// XYZ of a white point with luminance Y = 1.
+#[allow(dead_code)]
+pub fn white_point_xyz(xy: [f32; 2]) -> Vector<f32, 3> {
+    let [x, y] = xy;
+    Vector::new([x / y, 1.0, (1.0 - x - y) / y])
+}
+
+// Matrix taking linear RGB with the given primaries and white to XYZ,
+// scaled so RGB (1, 1, 1) lands on the white with Y = 1. Fails when the
+// primaries are collinear.
+#[allow(dead_code)]
+pub fn rgb_to_xyz_matrix(primaries: [[f32; 2]; 3], white: [f32; 2]) -> Result<Matrix<f32, 3, 3>, MathError> {
+    let primaries = Matrix::from_cols(primaries.map(|p| white_point_xyz(p).to_array()));
+    let scale = primaries.solve(&white_point_xyz(white))?;
+    Ok(Matrix::from_cols([0, 1, 2].map(|i| (primaries.column(i) * scale[i]).to_array())))
+}
+
+// Bradford chromatic adaptation between two XYZ white points: scales the
+// cone responses so `source` maps exactly onto `destination`. Apply to XYZ
+// colors as `m * xyz`.
+#[allow(dead_code)]
+pub fn bradford_adaptation(source: &Vector<f32, 3>, destination: &Vector<f32, 3>) -> Matrix<f32, 3, 3> {
+    let gain = (BRADFORD * *destination).component_div(&(BRADFORD * *source));
+    let mut scale = Matrix::identity();
+    for i in 0..3 {
+        scale[(i, i)] = gain[i];
+    }
+    BRADFORD_INVERSE * scale * BRADFORD
+}
+
+// Electro-optical transfer function of sRGB: encoded value to linear.
+#[allow(dead_code)]
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+#[allow(dead_code)]
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+// Per-channel sRGB decode and encode.
+#[allow(dead_code)]
+pub fn decode_srgb(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    color.map(srgb_to_linear)
+}
+
+#[allow(dead_code)]
+pub fn encode_srgb(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    color.map(linear_to_srgb)
+}
+
+// The helpers below take and return linear values.
+#[allow(dead_code)]
+pub fn srgb_to_xyz(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    SRGB_TO_XYZ * *color
+}
+
+#[allow(dead_code)]
+pub fn xyz_to_srgb(xyz: &Vector<f32, 3>) -> Vector<f32, 3> {
+    XYZ_TO_SRGB * *xyz
+}
+
+#[allow(dead_code)]
+pub fn acescg_to_xyz(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    ACESCG_TO_XYZ * *color
+}
+
+#[allow(dead_code)]
+pub fn xyz_to_acescg(xyz: &Vector<f32, 3>) -> Vector<f32, 3> {
+    XYZ_TO_ACESCG * *xyz
+}
+
+#[allow(dead_code)]
+pub fn srgb_to_acescg(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    SRGB_TO_ACESCG * *color
+}
+
+#[allow(dead_code)]
+pub fn acescg_to_srgb(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    ACESCG_TO_SRGB * *color
+}
+
+// Linear sRGB to the LMS cone space Oklab is built on, and the cube-rooted
+// responses to Oklab (L, a, b); from Ottosson's reference implementation.
+const SRGB_TO_OKLAB_LMS: Matrix<f32, 3, 3> = from_rows([
+    [0.41222146, 0.53633255, 0.051445995],
+    [0.2119035, 0.6806995, 0.10739696],
+    [0.08830246, 0.28171885, 0.6299787]
+]);
+
+const OKLAB_LMS_TO_LAB: Matrix<f32, 3, 3> = from_rows([
+    [0.21045426, 0.7936178, -0.004072047],
+    [1.9779985, -2.4285922, 0.4505937],
+    [0.025904037, 0.78277177, -0.80867577]
+]);
+
+const OKLAB_LAB_TO_LMS: Matrix<f32, 3, 3> = from_rows([
+    [1.0, 0.39633778, 0.21580376],
+    [1.0, -0.105561346, -0.06385417],
+    [1.0, -0.08948418, -1.2914855]
+]);
+
+const OKLAB_LMS_TO_SRGB: Matrix<f32, 3, 3> = from_rows([
+    [4.0767417, -3.3077116, 0.23096994],
+    [-1.268438, 2.6097574, -0.34131938],
+    [-0.0041960864, -0.7034186, 1.7076147]
+]);
+
+// CIE Lab nonlinearity, linear near black so it stays invertible.
+fn lab_f(t: f32) -> f32 {
+    const DELTA: f32 = 6.0 / 29.0;
+    if t > DELTA * DELTA * DELTA {
+        t.cbrt()
+    } else {
+        t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+    }
+}
+
+fn lab_f_inverse(t: f32) -> f32 {
+    const DELTA: f32 = 6.0 / 29.0;
+    if t > DELTA {
+        t * t * t
+    } else {
+        3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+    }
+}
+
+// CIE L*a*b* relative to the XYZ `white` (usually `white_point_xyz(D50)`
+// or the white of the source space); L runs 0..100.
+#[allow(dead_code)]
+pub fn xyz_to_lab(xyz: &Vector<f32, 3>, white: &Vector<f32, 3>) -> Vector<f32, 3> {
+    let f = xyz.component_div(white).map(lab_f);
+    Vector::new([116.0 * f[1] - 16.0, 500.0 * (f[0] - f[1]), 200.0 * (f[1] - f[2])])
+}
+
+#[allow(dead_code)]
+pub fn lab_to_xyz(lab: &Vector<f32, 3>, white: &Vector<f32, 3>) -> Vector<f32, 3> {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let f = Vector::new([fy + lab[1] / 500.0, fy, fy - lab[2] / 200.0]);
+    f.map(lab_f_inverse).component_mul(white)
+}
+
+// Oklab (L, a, b) of a linear sRGB color; L runs 0..1.
+#[allow(dead_code)]
+pub fn srgb_to_oklab(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    OKLAB_LMS_TO_LAB * (SRGB_TO_OKLAB_LMS * *color).map(f32::cbrt)
+}
+
+#[allow(dead_code)]
+pub fn oklab_to_srgb(lab: &Vector<f32, 3>) -> Vector<f32, 3> {
+    OKLAB_LMS_TO_SRGB * (OKLAB_LAB_TO_LMS * *lab).map(|c| c * c * c)
+}
+
+// CIE76 color difference: distance in Lab.
+#[allow(dead_code)]
+pub fn delta_e76(a: &Vector<f32, 3>, b: &Vector<f32, 3>) -> f32 {
+    (*a - *b).norm()
+}
+
+// Distance in Oklab; about 0.02 is a just noticeable difference.
+#[allow(dead_code)]
+pub fn delta_e_ok(a: &Vector<f32, 3>, b: &Vector<f32, 3>) -> f32 {
+    (*a - *b).norm()
+}
+
+// CIEDE2000 color difference between two Lab colors, with the reference
+// weights kL = kC = kH = 1. Evaluated in f64; angles are in degrees as in
+// the standard.
+#[allow(dead_code)]
+pub fn delta_e2000(lab1: &Vector<f32, 3>, lab2: &Vector<f32, 3>) -> f32 {
+    let (l1, a1, b1) = (lab1[0] as f64, lab1[1] as f64, lab1[2] as f64);
+    let (l2, a2, b2) = (lab2[0] as f64, lab2[1] as f64, lab2[2] as f64);
+    let pow25_7 = 25.0f64.powi(7);
+    let c_mean = 0.5 * (a1.hypot(b1) + a2.hypot(b2));
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt());
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |b: f64, a: f64| if a == 0.0 && b == 0.0 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+    let (h1, h2) = (hue(b1, a1), hue(b2, a2));
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let chromatic = c1 * c2 != 0.0;
+    let delta_h = match h2 - h1 {
+        _ if !chromatic => 0.0,
+        d if d > 180.0 => d - 360.0,
+        d if d < -180.0 => d + 360.0,
+        d => d
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (0.5 * delta_h).to_radians().sin();
+
+    let l_mean = 0.5 * (l1 + l2);
+    let c_mean = 0.5 * (c1 + c2);
+    let h_mean = if !chromatic {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        0.5 * (h1 + h2)
+    } else if h1 + h2 < 360.0 {
+        0.5 * (h1 + h2 + 360.0)
+    } else {
+        0.5 * (h1 + h2 - 360.0)
+    };
+    let cos = |degrees: f64| degrees.to_radians().cos();
+    let t = 1.0 - 0.17 * cos(h_mean - 30.0) + 0.24 * cos(2.0 * h_mean) + 0.32 * cos(3.0 * h_mean + 6.0) - 0.20 * cos(4.0 * h_mean - 63.0);
+    let rotation = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt();
+    let l50 = (l_mean - 50.0).powi(2);
+    let s_l = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+    let r_t = -(2.0 * rotation).to_radians().sin() * r_c;
+    let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+    (l * l + c * c + h * h + r_t * c * h).sqrt() as f32
+}
+
+// Whether every channel of a linear RGB color lies in [0, 1].
+#[allow(dead_code)]
+pub fn is_in_gamut(color: &Vector<f32, 3>) -> bool {
+    color.as_slice().iter().all(|c| (0.0..=1.0).contains(c))
+}
+
+// Hard clip: clamps each channel to [0, 1]. Cheap, but shifts the hue of
+// saturated colors.
+#[allow(dead_code)]
+pub fn clip_to_gamut(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    color.map(|c| c.clamp(0.0, 1.0))
+}
+
+// Brings a linear sRGB color inside the sRGB gamut by reducing its Oklab
+// chroma at constant lightness and hue, finding the largest in-gamut
+// chroma by bisection. Lightness outside 0..1 is clamped first. Colors in
+// another space go through `acescg_to_srgb` (or similar) first.
+#[allow(dead_code)]
+pub fn compress_to_gamut(color: &Vector<f32, 3>) -> Vector<f32, 3> {
+    if is_in_gamut(color) {
+        return *color;
+    }
+    let lab = srgb_to_oklab(color);
+    let lightness = lab[0].clamp(0.0, 1.0);
+    let at = |chroma: f32| oklab_to_srgb(&Vector::new([lightness, lab[1] * chroma, lab[2] * chroma]));
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    for _ in 0..24 {
+        let mid = 0.5 * (lo + hi);
+        if is_in_gamut(&at(mid)) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    // The achromatic end can miss [0, 1] by rounding alone.
+    clip_to_gamut(&at(lo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &Matrix<f32, 3, 3>, b: &Matrix<f32, 3, 3>, tolerance: f32) -> bool {
+        (0..3).all(|r| (0..3).all(|c| (a[(r, c)] - b[(r, c)]).abs() < tolerance))
+    }
+
+    fn close_vec(a: &Vector<f32, 3>, b: &Vector<f32, 3>, tolerance: f32) -> bool {
+        (*a - *b).norm() < tolerance
+    }
+
+    #[test]
+    fn constants_match_their_derivation() {
+        let srgb = rgb_to_xyz_matrix(SRGB_PRIMARIES, D65).unwrap();
+        let aces = rgb_to_xyz_matrix(ACESCG_PRIMARIES, ACES_WHITE).unwrap();
+        assert!(close(&srgb, &SRGB_TO_XYZ, 1e-6));
+        assert!(close(&aces, &ACESCG_TO_XYZ, 1e-6));
+        assert!(close(&(XYZ_TO_SRGB * SRGB_TO_XYZ), &Matrix::identity(), 1e-6));
+        assert!(close(&(XYZ_TO_ACESCG * ACESCG_TO_XYZ), &Matrix::identity(), 1e-6));
+        assert!(close(&(ACESCG_TO_SRGB * SRGB_TO_ACESCG), &Matrix::identity(), 1e-6));
+        assert!(close(&(BRADFORD_INVERSE * BRADFORD), &Matrix::identity(), 1e-6));
+
+        let adapt = bradford_adaptation(&white_point_xyz(D65), &white_point_xyz(ACES_WHITE));
+        assert!(close(&(XYZ_TO_ACESCG * adapt * SRGB_TO_XYZ), &SRGB_TO_ACESCG, 1e-6));
+    }
+
+    #[test]
+    fn whites_map_to_whites() {
+        let white = Vector::new([1.0, 1.0, 1.0]);
+        assert!(close_vec(&srgb_to_xyz(&white), &Vector::new([0.95046, 1.0, 1.08906]), 1e-4));
+        assert!(close_vec(&srgb_to_acescg(&white), &white, 1e-6));
+        let d50 = white_point_xyz(D50);
+        assert!(close_vec(&(bradford_adaptation(&white_point_xyz(D65), &d50) * white_point_xyz(D65)), &d50, 1e-6));
+
+        let collinear = [[0.2, 0.2], [0.3, 0.3], [0.4, 0.4]];
+        assert_eq!(rgb_to_xyz_matrix(collinear, D65).err(), Some(MathError::Singular));
+    }
+
+    #[test]
+    fn srgb_transfer_round_trips() {
+        assert!((srgb_to_linear(0.5) - 0.21404114).abs() < 1e-6);
+        for i in 0..=20 {
+            let c = i as f32 / 20.0;
+            assert!((linear_to_srgb(srgb_to_linear(c)) - c).abs() < 1e-6);
+        }
+        let color = Vector::new([0.2, 0.5, 0.9]);
+        assert!(close_vec(&encode_srgb(&decode_srgb(&color)), &color, 1e-6));
+        assert!(close_vec(&xyz_to_acescg(&acescg_to_xyz(&color)), &color, 1e-6));
+        assert!(close_vec(&acescg_to_srgb(&srgb_to_acescg(&color)), &color, 1e-6));
+        assert!(close_vec(&xyz_to_srgb(&srgb_to_xyz(&color)), &color, 1e-6));
+    }
+
+    #[test]
+    fn lab_and_oklab_round_trip() {
+        let white = white_point_xyz(D65);
+        let lab = xyz_to_lab(&white, &white);
+        assert!(close_vec(&lab, &Vector::new([100.0, 0.0, 0.0]), 1e-4));
+        for color in [[0.2, 0.5, 0.9], [0.001, 0.002, 0.0005], [1.0, 0.0, 0.0]] {
+            let xyz = srgb_to_xyz(&Vector::new(color));
+            assert!(close_vec(&lab_to_xyz(&xyz_to_lab(&xyz, &white), &white), &xyz, 1e-5));
+            let color = Vector::new(color);
+            assert!(close_vec(&oklab_to_srgb(&srgb_to_oklab(&color)), &color, 1e-5));
+        }
+        // Reference values from the Oklab specification.
+        assert!(close_vec(&srgb_to_oklab(&Vector::new([1.0, 1.0, 1.0])), &Vector::new([1.0, 0.0, 0.0]), 1e-4));
+        assert!(close_vec(&srgb_to_oklab(&Vector::new([1.0, 0.0, 0.0])), &Vector::new([0.62796, 0.22486, 0.12585]), 1e-4));
+    }
+
+    #[test]
+    fn ciede2000_matches_reference_data() {
+        // Pairs from Sharma, Wu and Dalal's CIEDE2000 test data, including
+        // the hue wrap-around case.
+        for (a, b, expected) in [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+            ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0009], 7.1792),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082)
+        ] {
+            let (a, b) = (Vector::new(a), Vector::new(b));
+            assert!((delta_e2000(&a, &b) - expected).abs() < 1e-4, "{}", expected);
+            assert!((delta_e2000(&b, &a) - expected).abs() < 1e-4);
+        }
+        let lab = Vector::new([50.0, 3.0, 4.0]);
+        assert_eq!(delta_e2000(&lab, &lab), 0.0);
+        assert_eq!(delta_e76(&lab, &Vector::new([50.0, 0.0, 0.0])), 5.0);
+    }
+
+    #[test]
+    fn gamut_compression_keeps_lightness_and_hue() {
+        let inside = Vector::new([0.2, 0.5, 0.9]);
+        assert_eq!(compress_to_gamut(&inside).to_array(), inside.to_array());
+
+        // Saturated ACEScg green is outside sRGB.
+        let green = acescg_to_srgb(&Vector::new([0.0, 1.0, 0.0]));
+        assert!(!is_in_gamut(&green));
+        assert!(is_in_gamut(&clip_to_gamut(&green)));
+        let compressed = compress_to_gamut(&green);
+        assert!(is_in_gamut(&compressed));
+        let (before, after) = (srgb_to_oklab(&green), srgb_to_oklab(&compressed));
+        assert!((before[0] - after[0]).abs() < 1e-3);
+        assert!((before[2].atan2(before[1]) - after[2].atan2(after[1])).abs() < 1e-3);
+        // On the gamut boundary rather than needlessly desaturated.
+        assert!(compressed.as_slice().iter().any(|c| !(1e-3..=1.0 - 1e-3).contains(c)));
+
+        assert!(close_vec(&compress_to_gamut(&Vector::new([2.0, 2.0, 2.0])), &Vector::new([1.0, 1.0, 1.0]), 1e-5));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::float::Float;
+use crate::matrix::Matrix;
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Complex<T = f32> {
+    pub re: T,
+    pub im: T
+}
+
+#[allow(dead_code)]
+impl<T: Float> Complex<T> {
+    pub fn new(re: T, im: T) -> Self {
+        Self {
+            re,
+            im
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::new(T::one(), T::zero())
+    }
+
+    pub fn i() -> Self {
+        Self::new(T::zero(), T::one())
+    }
+
+    pub fn from_polar(radius: T, angle: T) -> Self {
+        Self::new(radius * angle.cos(), radius * angle.sin())
+    }
+
+    // Unit complex number rotating by `angle` radians when multiplied.
+    pub fn from_angle(angle: T) -> Self {
+        Self::from_polar(T::one(), angle)
+    }
+
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    pub fn norm_squared(&self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+
+    pub fn abs(&self) -> T {
+        self.norm_squared().sqrt()
+    }
+
+    pub fn arg(&self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    pub fn to_polar(self) -> (T, T) {
+        (self.abs(), self.arg())
+    }
+
+    pub fn exp(&self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+
+    // Principal branch, with the argument in (-pi, pi].
+    pub fn ln(&self) -> Self {
+        Self::new(self.abs().ln(), self.arg())
+    }
+
+    pub fn recip(&self) -> Self {
+        self.conj() / self.norm_squared()
+    }
+
+    // Rotates the 2D point (x, y) by this number's argument and scales it
+    // by its magnitude.
+    pub fn rotate(&self, x: T, y: T) -> (T, T) {
+        let out = *self * Self::new(x, y);
+        (out.re, out.im)
+    }
+
+    // The 2x2 matrix acting on (x, y) like multiplication by this number.
+    // For a unit number this is the rotation matrix by `arg`.
+    pub fn to_matrix(self) -> Matrix<T, 2, 2> {
+        Matrix::from_rows([[self.re, -self.im], [self.im, self.re]])
+    }
+
+    // Reads the first column of a rotation-scale matrix.
+    pub fn from_matrix(m: &Matrix<T, 2, 2>) -> Self {
+        Self::new(m[(0, 0)], m[(1, 0)])
+    }
+}
+
+impl<T: Float> std::ops::Add<Complex<T>> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn add(self, rhs: Complex<T>) -> Self::Output {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: Float> std::ops::AddAssign<Complex<T>> for Complex<T> {
+    fn add_assign(&mut self, rhs: Complex<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Float> std::ops::Sub<Complex<T>> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn sub(self, rhs: Complex<T>) -> Self::Output {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: Float> std::ops::SubAssign<Complex<T>> for Complex<T> {
+    fn sub_assign(&mut self, rhs: Complex<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Float> std::ops::Mul<Complex<T>> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, rhs: Complex<T>) -> Self::Output {
+        Complex::new(self.re * rhs.re - self.im * rhs.im, self.re * rhs.im + self.im * rhs.re)
+    }
+}
+
+impl<T: Float> std::ops::MulAssign<Complex<T>> for Complex<T> {
+    fn mul_assign(&mut self, rhs: Complex<T>) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Float> std::ops::Mul<T> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Complex::new(self.re * rhs, self.im * rhs)
+    }
+}
+
+impl<T: Float> std::ops::MulAssign<T> for Complex<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Float> std::ops::Div<Complex<T>> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn div(self, rhs: Complex<T>) -> Self::Output {
+        self * rhs.conj() / rhs.norm_squared()
+    }
+}
+
+impl<T: Float> std::ops::DivAssign<Complex<T>> for Complex<T> {
+    fn div_assign(&mut self, rhs: Complex<T>) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: Float> std::ops::Div<T> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Complex::new(self.re / rhs, self.im / rhs)
+    }
+}
+
+impl<T: Float> std::ops::DivAssign<T> for Complex<T> {
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: Float> std::ops::Neg for Complex<T> {
+    type Output = Complex<T>;
+
+    fn neg(self) -> Self::Output {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl<T: Float> std::fmt::Display for Complex<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.im < T::zero() {
+            write!(f, "{} - {}i", self.re, -self.im)
+        } else {
+            write!(f, "{} + {}i", self.re, self.im)
+        }
+    }
+}
+
+impl<T: Float> std::fmt::Debug for Complex<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Complex({}, {})", self.re, self.im)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn euler_identity_and_rotation() {
+        let pi = std::f64::consts::PI;
+        let z = (Complex::i() * pi).exp() + Complex::one();
+        assert!(z.abs() < 1e-12);
+
+        let a = Complex::new(3.0, -2.0);
+        let b = Complex::new(0.5, 4.0);
+        assert!((a * b / b - a).abs() < 1e-12);
+        assert!((a.ln().exp() - a).abs() < 1e-12);
+
+        let rotation = Complex::from_angle(pi / 2.0);
+        let (x, y) = rotation.rotate(1.0, 0.0);
+        assert!(x.abs() < 1e-12 && (y - 1.0).abs() < 1e-12);
+        let m = rotation.to_matrix() * crate::vector::Vector::new([1.0, 0.0]);
+        assert!(m[0].abs() < 1e-12 && (m[1] - 1.0).abs() < 1e-12);
+        assert_eq!(Complex::from_matrix(&rotation.to_matrix()), rotation);
+    }
+}
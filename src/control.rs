@@ -0,0 +1,181 @@
+use crate::error::MathError;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+fn max_abs_difference(a: &Mat4, b: &Mat4) -> f32 {
+    let mut out = 0.0f32;
+    for i in 0..16 {
+        out = out.max((a.elements[i] - b.elements[i]).abs());
+    }
+    out
+}
+
+// Solves X = A X A^T + Q with the doubling (Smith) iteration. Only converges
+// when every eigenvalue of A lies inside the unit circle; fails with
+// `NotFinite` when the iterates blow up and `NoConvergence` when
+// `max_iterations` is exhausted.
+#[allow(dead_code)]
+pub fn solve_discrete_lyapunov(a: &Mat4, q: &Mat4, tolerance: f32, max_iterations: usize) -> Result<Mat4, MathError> {
+    let mut x = *q;
+    let mut a_k = *a;
+    for _ in 0..max_iterations {
+        let next = x + a_k * x * a_k.transpose();
+        if !next.is_finite() {
+            return Err(MathError::NotFinite);
+        }
+        let delta = max_abs_difference(&next, &x);
+        x = next;
+        if delta <= tolerance {
+            return Ok(x);
+        }
+        a_k = a_k * a_k;
+    }
+    Err(MathError::NoConvergence)
+}
+
+// Solves the discrete algebraic Riccati equation
+// P = A^T P A - A^T P B (R + B^T P B)^-1 B^T P A + Q
+// by fixed-point iteration starting from P = Q.
+//
+// Systems with fewer than four states or inputs are padded with zeros in
+// A, B and Q; unused inputs need a non-zero diagonal entry in R so that
+// R + B^T P B stays invertible; otherwise this fails with `Singular`.
+#[allow(dead_code)]
+pub fn solve_discrete_riccati(a: &Mat4, b: &Mat4, q: &Mat4, r: &Mat4, tolerance: f32, max_iterations: usize) -> Result<Mat4, MathError> {
+    let a_t = a.transpose();
+    let b_t = b.transpose();
+    let mut p = *q;
+    for _ in 0..max_iterations {
+        let gain = (*r + b_t * p * *b).invert().ok_or(MathError::Singular)? * (b_t * p * *a);
+        let next = a_t * p * *a - a_t * p * *b * gain + *q;
+        if !next.is_finite() {
+            return Err(MathError::NotFinite);
+        }
+        let delta = max_abs_difference(&next, &p);
+        p = next;
+        if delta <= tolerance {
+            return Ok(p);
+        }
+    }
+    Err(MathError::NoConvergence)
+}
+
+// Infinite-horizon discrete LQR. Returns the feedback gain K, for the control
+// law u = -K x, together with the Riccati solution P.
+#[allow(dead_code)]
+pub fn discrete_lqr(a: &Mat4, b: &Mat4, q: &Mat4, r: &Mat4, tolerance: f32, max_iterations: usize) -> Result<(Mat4, Mat4), MathError> {
+    let p = solve_discrete_riccati(a, b, q, r, tolerance, max_iterations)?;
+    let b_t = b.transpose();
+    let gain = (*r + b_t * p * *b).invert().ok_or(MathError::Singular)? * (b_t * p * *a);
+    Ok((gain, p))
+}
+
+// Rotation vector (axis * angle, expressed in the body frame of `current`)
+// that rotates `current` onto `target` along the shortest path. Both are
+// unit quaternions (x, y, z, w); q and -q are the same attitude, so the
+// result never exceeds pi whichever sign either one comes with.
+#[allow(dead_code)]
+pub fn attitude_error(current: &Vec4, target: &Vec4) -> Vec4 {
+    // conj(current) * target
+    let (a, b) = (Vec4::new(-current.x, -current.y, -current.z, current.w), target);
+    let mut q = Vec4::new(
+        a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+        a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+        a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z
+    );
+    if q.w < 0.0 {
+        q = -q;
+    }
+    let axis = Vec4::new(q.x, q.y, q.z, 0.0);
+    let sin_half = axis.length();
+    if sin_half < 1e-7 {
+        return axis * 2.0;
+    }
+    axis * (2.0 * sin_half.atan2(q.w) / sin_half)
+}
+
+// PD attitude control: the torque that drives `current` towards `target`
+// while damping the body-frame `angular_velocity`.
+#[allow(dead_code)]
+pub fn attitude_torque(current: &Vec4, target: &Vec4, angular_velocity: &Vec4, kp: f32, kd: f32) -> Vec4 {
+    attitude_error(current, target) * kp - *angular_velocity * kd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stable_system() -> Mat4 {
+        let mut a = Mat4::zeroes();
+        a.elements = [
+            0.5, 0.1, 0.0, 0.0,
+            0.2, 0.4, 0.1, 0.0,
+            0.0, 0.0, 0.3, 0.2,
+            0.1, 0.0, 0.0, 0.6
+        ];
+        a
+    }
+
+    #[test]
+    fn lyapunov_satisfies_equation() {
+        let a = stable_system();
+        let q = Mat4::identity();
+        let x = solve_discrete_lyapunov(&a, &q, 1e-6, 64).unwrap();
+        let residual = a * x * a.transpose() + q;
+        assert!(max_abs_difference(&residual, &x) < 1e-4);
+    }
+
+    #[test]
+    fn lyapunov_rejects_unstable() {
+        let a = Mat4::identity() * 1.5;
+        assert_eq!(solve_discrete_lyapunov(&a, &Mat4::identity(), 1e-6, 64).err(), Some(MathError::NotFinite));
+    }
+
+    #[test]
+    fn riccati_satisfies_equation() {
+        // Double integrator with a single input, padded to 4x4.
+        let dt = 0.1;
+        let mut a = Mat4::identity();
+        a.elements[4] = dt;
+        let mut b = Mat4::zeroes();
+        b.elements[0] = 0.5 * dt * dt;
+        b.elements[1] = dt;
+        let mut q = Mat4::zeroes();
+        q.elements[0] = 1.0;
+        q.elements[5] = 1.0;
+        let r = Mat4::identity();
+        let (gain, p) = discrete_lqr(&a, &b, &q, &r, 1e-7, 10000).unwrap();
+        let a_t = a.transpose();
+        let residual = a_t * p * a - a_t * p * b * gain + q;
+        assert!(max_abs_difference(&residual, &p) < 1e-3);
+    }
+
+    fn about_z(angle: f32) -> Vec4 {
+        Vec4::new(0.0, 0.0, (angle * 0.5).sin(), (angle * 0.5).cos())
+    }
+
+    #[test]
+    fn attitude_error_about_z() {
+        let angle = 0.8f32;
+        let identity = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        let target = about_z(angle);
+        let error = attitude_error(&identity, &target);
+        assert!(error.x.abs() < 1e-5 && error.y.abs() < 1e-5);
+        assert!((error.z - angle).abs() < 1e-5);
+        let back = attitude_error(&target, &identity);
+        assert!((back.z + angle).abs() < 1e-5);
+
+        // Either sign of either quaternion gives the same error.
+        for (current, target) in [(identity, -target), (-identity, target), (-identity, -target)] {
+            assert!((attitude_error(&current, &target) - error).length() < 1e-5);
+        }
+        // From 170 to -170 degrees the short way is +20 degrees, not -340.
+        let (from, to) = (170.0f32.to_radians(), -170.0f32.to_radians());
+        let error = attitude_error(&about_z(from), &about_z(to));
+        assert!((error.z - 20.0f32.to_radians()).abs() < 1e-5);
+
+        let torque = attitude_torque(&identity, &target, &Vec4::new(0.0, 0.0, 0.5, 0.0), 2.0, 1.0);
+        assert!((torque.z - (2.0 * angle - 0.5)).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::matrix::Matrix;
+use crate::obb::Obb;
+use crate::vec4::Vec4;
+use crate::vector::Vector;
+
+// Running mean and covariance of 3D points, updated one point at a time
+// with Welford's method in f64. Memory stays constant however many points
+// arrive, and two accumulators over disjoint sets can be merged.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CovarianceAccumulator {
+    count: u64,
+    mean: [f64; 3],
+    // Sum of outer products of deviations from the running mean.
+    scatter: [[f64; 3]; 3]
+}
+
+#[allow(dead_code)]
+impl CovarianceAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_points(points: &[Vec4]) -> Self {
+        let mut out = Self::new();
+        out.extend(points);
+        out
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn add(&mut self, p: &Vec4) {
+        self.count += 1;
+        let n = self.count as f64;
+        let before = [0, 1, 2].map(|axis| p[axis] as f64 - self.mean[axis]);
+        for axis in 0..3 {
+            self.mean[axis] += before[axis] / n;
+        }
+        let after = [0, 1, 2].map(|axis| p[axis] as f64 - self.mean[axis]);
+        for row in 0..3 {
+            for col in 0..3 {
+                self.scatter[row][col] += before[row] * after[col];
+            }
+        }
+    }
+
+    pub fn extend(&mut self, points: &[Vec4]) {
+        for p in points {
+            self.add(p);
+        }
+    }
+
+    // Combines with the statistics of another, disjoint set of points.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        let total = self.count + other.count;
+        let (n, m, t) = (self.count as f64, other.count as f64, total as f64);
+        let delta = [0, 1, 2].map(|axis| other.mean[axis] - self.mean[axis]);
+        for row in 0..3 {
+            for col in 0..3 {
+                self.scatter[row][col] += other.scatter[row][col] + delta[row] * delta[col] * n * m / t;
+            }
+        }
+        for axis in 0..3 {
+            self.mean[axis] += delta[axis] * m / t;
+        }
+        self.count = total;
+    }
+
+    // Mean as a point (w = 1); the origin when empty.
+    pub fn mean(&self) -> Vec4 {
+        Vec4::new(self.mean[0] as f32, self.mean[1] as f32, self.mean[2] as f32, 1.0)
+    }
+
+    // Population covariance; zero when empty.
+    pub fn covariance(&self) -> Matrix<f64, 3, 3> {
+        let mut out = Matrix::zeroes();
+        if self.count > 0 {
+            for row in 0..3 {
+                for col in 0..3 {
+                    out[(row, col)] = self.scatter[row][col] / self.count as f64;
+                }
+            }
+        }
+        out
+    }
+
+    // Mean, principal axes and variances as returned by `pca::pca`, from
+    // the points seen so far. None when empty.
+    pub fn pca(&self) -> Option<(Vec4, Matrix<f32, 3, 3>, Vector<f32, 3>)> {
+        if self.count == 0 {
+            return None;
+        }
+        let covariance = self.covariance();
+        let total_variance = covariance[(0, 0)] + covariance[(1, 1)] + covariance[(2, 2)];
+        let (values, vectors) = covariance.symmetric_eigen(1e-12 * total_variance);
+
+        let mut axes = Matrix::<f32, 3, 3>::zeroes();
+        for col in 0..3 {
+            for row in 0..3 {
+                axes[(row, col)] = vectors[(row, col)] as f32;
+            }
+        }
+        let (x, y, z) = (axes.column(0), axes.column(1), axes.column(2));
+        let handedness = x[0] * (y[1] * z[2] - y[2] * z[1]) - x[1] * (y[0] * z[2] - y[2] * z[0]) + x[2] * (y[0] * z[1] - y[1] * z[0]);
+        if handedness < 0.0 {
+            for row in 0..3 {
+                axes[(row, 2)] = -axes[(row, 2)];
+            }
+        }
+        let variances = Vector::new([0, 1, 2].map(|i| values[i].max(0.0) as f32));
+        Some((self.mean(), axes, variances))
+    }
+
+    // Box along the principal axes with the same second moments as the
+    // points: a box uniformly filled has variance h^2 / 3 along an axis of
+    // half extent h. Matches exactly for points filling a box and is a
+    // cheap estimate otherwise, but unlike `bounding::minimum_volume_obb`
+    // it need not contain every point, since the points aren't kept.
+    pub fn obb(&self) -> Option<Obb> {
+        let (mean, axes, variances) = self.pca()?;
+        let axis = |col: usize| {
+            let a = axes.column(col);
+            Vec4::new(a[0], a[1], a[2], 0.0)
+        };
+        let half = |i: usize| (3.0 * variances[i]).sqrt();
+        Some(Obb::new(mean, [axis(0), axis(1), axis(2)], Vec4::new(half(0), half(1), half(2), 0.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaming_matches_batch() {
+        let points: Vec<Vec4> = (0..200)
+            .map(|i| {
+                let t = i as f32;
+                Vec4::new(1000.0 + (t * 0.37).sin() * 4.0, (t * 0.91).cos() * 2.0, (t * 0.13).sin(), 1.0)
+            })
+            .collect();
+        let batch = CovarianceAccumulator::from_points(&points);
+        let mut first = CovarianceAccumulator::from_points(&points[..70]);
+        first.merge(&CovarianceAccumulator::from_points(&points[70..]));
+        assert_eq!(first.count(), 200);
+        assert!((first.mean() - batch.mean()).length() < 1e-4);
+
+        // Two-pass reference.
+        let mean = points.iter().fold([0.0f64; 3], |m, p| [m[0] + p.x as f64 / 200.0, m[1] + p.y as f64 / 200.0, m[2] + p.z as f64 / 200.0]);
+        for row in 0..3 {
+            for col in 0..3 {
+                let reference: f64 = points.iter().map(|p| (p[row] as f64 - mean[row]) * (p[col] as f64 - mean[col])).sum::<f64>() / 200.0;
+                assert!((batch.covariance()[(row, col)] - reference).abs() < 1e-9);
+                assert!((first.covariance()[(row, col)] - reference).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn box_fill_gives_its_obb() {
+        let mut accumulator = CovarianceAccumulator::new();
+        assert!(accumulator.obb().is_none());
+        let n = 20;
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..n {
+                    let cell = |c: usize, size: f32| ((c as f32 + 0.5) / n as f32 - 0.5) * 2.0 * size;
+                    accumulator.add(&Vec4::new(5.0 + cell(i, 3.0), cell(j, 1.0), -2.0 + cell(k, 2.0), 1.0));
+                }
+            }
+        }
+        let obb = accumulator.obb().unwrap();
+        assert!((obb.center - Vec4::new(5.0, 0.0, -2.0, 1.0)).length() < 1e-4);
+        assert!((obb.half_extents - Vec4::new(3.0, 2.0, 1.0, 0.0)).length() < 0.02);
+        assert!((obb.axes[0].x.abs() - 1.0).abs() < 1e-4);
+    }
+}
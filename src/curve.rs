@@ -0,0 +1,159 @@
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// Frames along a curve are returned as Mat4s whose x axis is the normal,
+// y axis the binormal, z axis the tangent and position the curve point, so
+// geometry modelled in the xy plane can be placed by multiplying with them.
+fn frame(normal: &Vec4, binormal: &Vec4, tangent: &Vec4, point: &Vec4) -> Mat4 {
+    let mut out = Mat4::identity();
+    for (col, v) in [normal, binormal, tangent].into_iter().enumerate() {
+        out.elements[col * 4] = v.x;
+        out.elements[col * 4 + 1] = v.y;
+        out.elements[col * 4 + 2] = v.z;
+    }
+    out.elements[12] = point.x;
+    out.elements[13] = point.y;
+    out.elements[14] = point.z;
+    out
+}
+
+fn direction(from: &Vec4, to: &Vec4) -> Vec4 {
+    Vec4::new(to.x - from.x, to.y - from.y, to.z - from.z, 0.0)
+}
+
+// Unit tangents by central differences, one-sided at the ends.
+fn tangents(points: &[Vec4]) -> Vec<Vec4> {
+    let n = points.len();
+    (0..n).map(|i| {
+        let d = direction(&points[i.saturating_sub(1)], &points[(i + 1).min(n - 1)]);
+        if d.length() > 0.0 { d.normalize() } else { Vec4::new(0.0, 0.0, 1.0, 0.0) }
+    }).collect()
+}
+
+// Any unit vector perpendicular to `t`, built from the world axis least
+// aligned with it.
+fn perpendicular(t: &Vec4) -> Vec4 {
+    let axis = if t.x.abs() <= t.y.abs() && t.x.abs() <= t.z.abs() {
+        Vec4::new(1.0, 0.0, 0.0, 0.0)
+    } else if t.y.abs() <= t.z.abs() {
+        Vec4::new(0.0, 1.0, 0.0, 0.0)
+    } else {
+        Vec4::new(0.0, 0.0, 1.0, 0.0)
+    };
+    (axis - *t * t.dot(&axis)).normalize()
+}
+
+// Rotation-minimizing frames along a polyline, transported with the double
+// reflection method (Wang et al. 2008). Unlike Frenet frames they don't
+// flip at inflection points or spin around straight sections.
+#[allow(dead_code)]
+pub fn frames_along_curve(points: &[Vec4]) -> Vec<Mat4> {
+    let tangents = tangents(points);
+    let mut frames = Vec::with_capacity(points.len());
+    let Some(&first) = tangents.first() else {
+        return frames;
+    };
+    let mut normal = perpendicular(&first);
+    frames.push(frame(&normal, &first.cross(&normal), &first, &points[0]));
+
+    let reflect = |v: Vec4, axis: &Vec4, c: f32| v - *axis * (2.0 / c * axis.dot(&v));
+    for i in 1..points.len() {
+        let v1 = direction(&points[i - 1], &points[i]);
+        let c1 = v1.dot(&v1);
+        let (mut r, mut t) = (normal, tangents[i - 1]);
+        if c1 > 0.0 {
+            r = reflect(r, &v1, c1);
+            t = reflect(t, &v1, c1);
+        }
+        let v2 = tangents[i] - t;
+        let c2 = v2.dot(&v2);
+        if c2 > 0.0 {
+            r = reflect(r, &v2, c2);
+        }
+        // Re-orthogonalize against drift.
+        normal = (r - tangents[i] * tangents[i].dot(&r)).normalize();
+        frames.push(frame(&normal, &tangents[i].cross(&normal), &tangents[i], &points[i]));
+    }
+    frames
+}
+
+// Frenet frames along a polyline: the normal points towards the centre of
+// curvature. Where the curve is locally straight the previous normal is
+// carried over, and the first defined normal is used before it.
+#[allow(dead_code)]
+pub fn frenet_frames(points: &[Vec4]) -> Vec<Mat4> {
+    let n = points.len();
+    let tangents = tangents(points);
+    let mut normals: Vec<Option<Vec4>> = (0..n).map(|i| {
+        if n < 3 {
+            return None;
+        }
+        let i = i.clamp(1, n - 2);
+        let (ahead, behind) = (direction(&points[i], &points[i + 1]), direction(&points[i - 1], &points[i]));
+        let d2 = ahead - behind;
+        let t = tangents[i];
+        let normal = d2 - t * t.dot(&d2);
+        let length = normal.length();
+        if length > 1e-5 * ahead.length().max(behind.length()) {
+            Some(normal / length)
+        } else {
+            None
+        }
+    }).collect();
+
+    let mut previous = normals.iter().flatten().next().copied();
+    for normal in normals.iter_mut() {
+        match normal {
+            Some(v) => previous = Some(*v),
+            None => *normal = previous
+        }
+    }
+    (0..n).map(|i| {
+        let t = tangents[i];
+        let normal = match normals[i] {
+            Some(v) => (v - t * t.dot(&v)).normalize(),
+            None => perpendicular(&t)
+        };
+        frame(&normal, &t.cross(&normal), &t, &points[i])
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orthonormal(m: &Mat4) -> bool {
+        let (x, y, z) = (m.x_vector(), m.y_vector(), m.z_vector());
+        (x.length() - 1.0).abs() < 1e-4 && (y.length() - 1.0).abs() < 1e-4
+            && x.dot(&y).abs() < 1e-4 && (x.cross(&y) - z).length() < 1e-4
+    }
+
+    #[test]
+    fn transport_does_not_flip_at_inflection() {
+        // S-curve in the xy plane: Frenet normals flip sign at the
+        // inflection, transported normals stay continuous.
+        let points: Vec<Vec4> = (0..=64).map(|i| {
+            let t = i as f32 / 64.0 * std::f32::consts::TAU;
+            Vec4::new(t, t.sin(), 0.0, 1.0)
+        }).collect();
+        let transported = frames_along_curve(&points);
+        let frenet = frenet_frames(&points);
+        assert_eq!(transported.len(), points.len());
+        assert!(transported.iter().chain(&frenet).all(orthonormal));
+        assert!(transported.windows(2).all(|w| w[0].x_vector().dot(&w[1].x_vector()) > 0.9));
+        assert!(frenet.windows(2).any(|w| w[0].x_vector().dot(&w[1].x_vector()) < 0.0));
+        assert!((transported[10].position() - points[10]).length() < 1e-6);
+    }
+
+    #[test]
+    fn frenet_normal_points_inward_on_circle() {
+        let points: Vec<Vec4> = (0..16).map(|i| {
+            let a = i as f32 * 0.3;
+            Vec4::new(a.cos(), a.sin(), 0.0, 1.0)
+        }).collect();
+        for (m, p) in frenet_frames(&points).iter().zip(&points) {
+            let inward = Vec4::new(-p.x, -p.y, 0.0, 0.0);
+            assert!(m.x_vector().dot(&inward) > 0.95);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+// Curves and animation: arc-length tables, moving frames, keyframe tracks
+// and, together with `geometry`, parametric surfaces and sweeps.
+pub use crate::arc_length::ArcLengthTable;
+pub use crate::curve::{frames_along_curve, frenet_frames};
+pub use crate::keyframe::{compress_track, CompressedTrack, KeyInterpolation, KeyTolerance, Keyframe};
+#[cfg(feature = "geometry")]
+pub use crate::surface::{ParametricSurface, RoundedBox, Superellipsoid, Torus};
+#[cfg(feature = "geometry")]
+pub use crate::sweep::{extrude, lathe};
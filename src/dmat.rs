@@ -0,0 +1,537 @@
+use crate::dvec::DVec;
+use crate::error::MathError;
+use crate::float::Float;
+use crate::instrument::trace_span;
+use crate::mat4::Mat4;
+use crate::matrix::Matrix;
+
+// Heap-backed matrix whose size is chosen at runtime, stored column-major
+// like `Matrix`: element (row, col) lives at `elements[col * rows + row]`.
+// Binary operations panic when the shapes don't match.
+#[derive(Clone, PartialEq)]
+pub struct DMat<T = f64> {
+    rows: usize,
+    cols: usize,
+    pub(crate) elements: Vec<T>,
+}
+
+#[allow(dead_code)]
+impl<T: Float> DMat<T> {
+    pub fn zeroes(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            elements: vec![T::zero(); rows * cols]
+        }
+    }
+
+    pub fn identity(size: usize) -> Self {
+        let mut out = Self::zeroes(size, size);
+        for i in 0..size {
+            out[(i, i)] = T::one();
+        }
+        out
+    }
+
+    // Builds a matrix from column-major data.
+    pub fn from_col_slice(rows: usize, cols: usize, elements: &[T]) -> Self {
+        assert_eq!(elements.len(), rows * cols, "Dimension mismatch");
+        Self {
+            rows,
+            cols,
+            elements: elements.to_vec()
+        }
+    }
+
+    // Builds a matrix from row-major data.
+    pub fn from_row_slice(rows: usize, cols: usize, elements: &[T]) -> Self {
+        assert_eq!(elements.len(), rows * cols, "Dimension mismatch");
+        let mut out = Self::zeroes(rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                out[(row, col)] = elements[row * cols + col];
+            }
+        }
+        out
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, f: impl Fn(usize, usize) -> T) -> Self {
+        let mut out = Self::zeroes(rows, cols);
+        for col in 0..cols {
+            for row in 0..rows {
+                out[(row, col)] = f(row, col);
+            }
+        }
+        out
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    pub fn column(&self, col: usize) -> DVec<T> {
+        DVec::from_slice(&self.elements[col * self.rows..(col + 1) * self.rows])
+    }
+
+    pub fn row(&self, row: usize) -> DVec<T> {
+        DVec::new((0..self.cols).map(|col| self[(row, col)]).collect())
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |row, col| self[(col, row)])
+    }
+
+    // Kronecker product: the block matrix whose (i, j) block is
+    // self[(i, j)] * other.
+    pub fn kron(&self, other: &Self) -> Self {
+        Self::from_fn(self.rows * other.rows, self.cols * other.cols, |row, col| {
+            self[(row / other.rows, col / other.cols)] * other[(row % other.rows, col % other.cols)]
+        })
+    }
+
+    // Columns stacked into one vector, so vec(A X B) = (B^T kron A) vec(X)
+    // turns a matrix equation like A X + X B = C into a linear system.
+    pub fn vec(&self) -> DVec<T> {
+        DVec::from_slice(&self.elements)
+    }
+
+    // Inverse of `vec`: a rows x cols matrix filled column by column.
+    pub fn unvec(v: &DVec<T>, rows: usize, cols: usize) -> Self {
+        Self::from_col_slice(rows, cols, v.as_slice())
+    }
+
+    pub fn mul_vec(&self, v: &DVec<T>) -> DVec<T> {
+        assert_eq!(self.cols, v.len(), "Dimension mismatch");
+        let mut out = DVec::zero(self.rows);
+        for col in 0..self.cols {
+            let x = v[col];
+            for row in 0..self.rows {
+                out[row] += self[(row, col)] * x;
+            }
+        }
+        out
+    }
+
+    pub fn mul_mat(&self, other: &Self) -> Self {
+        assert_eq!(self.cols, other.rows, "Dimension mismatch");
+        let mut out = Self::zeroes(self.rows, other.cols);
+        for col in 0..other.cols {
+            for k in 0..self.cols {
+                let x = other[(k, col)];
+                for row in 0..self.rows {
+                    out[(row, col)] += self[(row, k)] * x;
+                }
+            }
+        }
+        out
+    }
+
+    // Gaussian elimination with partial pivoting. Fails with `Singular`
+    // when the matrix is singular and `DimensionMismatch` when it is not
+    // square or `b` has the wrong length.
+    pub fn solve(&self, b: &DVec<T>) -> Result<DVec<T>, MathError> {
+        if !self.is_square() || self.rows != b.len() {
+            return Err(MathError::DimensionMismatch);
+        }
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut x = b.clone();
+        for col in 0..n {
+            let mut pivot = col;
+            for row in (col + 1)..n {
+                if a[(row, col)].abs() > a[(pivot, col)].abs() {
+                    pivot = row;
+                }
+            }
+            if a[(pivot, col)] == T::zero() {
+                return Err(MathError::Singular);
+            }
+            if pivot != col {
+                for k in 0..n {
+                    a.elements.swap(k * n + col, k * n + pivot);
+                }
+                x.elements.swap(col, pivot);
+            }
+            for row in (col + 1)..n {
+                let factor = a[(row, col)] / a[(col, col)];
+                for k in col..n {
+                    let value = a[(col, k)];
+                    a[(row, k)] -= factor * value;
+                }
+                let value = x[col];
+                x[row] -= factor * value;
+            }
+        }
+        for row in (0..n).rev() {
+            let mut sum = x[row];
+            for k in (row + 1)..n {
+                sum -= a[(row, k)] * x[k];
+            }
+            x[row] = sum / a[(row, row)];
+        }
+        Ok(x)
+    }
+
+    // Least-squares solution of self * x = b through the normal equations.
+    // Fails with `Singular` when the columns are linearly dependent.
+    pub fn least_squares(&self, b: &DVec<T>) -> Result<DVec<T>, MathError> {
+        if self.rows != b.len() {
+            return Err(MathError::DimensionMismatch);
+        }
+        let transposed = self.transpose();
+        transposed.mul_mat(self).solve(&transposed.mul_vec(b))
+    }
+
+    // Returns the lower triangular L with self = L * L^T. Only the lower
+    // triangle is read. Fails with `Singular` when the matrix is not
+    // positive definite and `DimensionMismatch` when it is not square.
+    pub fn cholesky(&self) -> Result<Self, MathError> {
+        if !self.is_square() {
+            return Err(MathError::DimensionMismatch);
+        }
+        trace_span!("cholesky", size = self.rows);
+        let n = self.rows;
+        let mut l = Self::zeroes(n, n);
+        for j in 0..n {
+            let mut d = self[(j, j)];
+            for k in 0..j {
+                d -= l[(j, k)] * l[(j, k)];
+            }
+            if d <= T::zero() {
+                return Err(MathError::Singular);
+            }
+            l[(j, j)] = d.sqrt();
+            for i in (j + 1)..n {
+                let mut s = self[(i, j)];
+                for k in 0..j {
+                    s -= l[(i, k)] * l[(j, k)];
+                }
+                l[(i, j)] = s / l[(j, j)];
+            }
+        }
+        Ok(l)
+    }
+
+    // Solves self * x = b through the Cholesky factorization, failing like
+    // `cholesky` or with `DimensionMismatch` when `b` has the wrong length.
+    pub fn solve_cholesky(&self, b: &DVec<T>) -> Result<DVec<T>, MathError> {
+        if self.rows != b.len() {
+            return Err(MathError::DimensionMismatch);
+        }
+        let l = self.cholesky()?;
+        let n = self.rows;
+        let mut x = b.clone();
+        for i in 0..n {
+            let mut s = x[i];
+            for k in 0..i {
+                s -= l[(i, k)] * x[k];
+            }
+            x[i] = s / l[(i, i)];
+        }
+        for i in (0..n).rev() {
+            let mut s = x[i];
+            for k in (i + 1)..n {
+                s -= l[(k, i)] * x[k];
+            }
+            x[i] = s / l[(i, i)];
+        }
+        Ok(x)
+    }
+}
+
+// Normal equations of the ridge-regularized least-squares problem
+// min |A x - b|^2 + lambda |x|^2: returns A^T A + lambda I and A^T b.
+// A^T A is only formed once, so several right-hand sides or lambdas can
+// reuse it.
+#[allow(dead_code)]
+pub fn normal_equations<T: Float>(a: &DMat<T>, b: &DVec<T>, lambda: T) -> (DMat<T>, DVec<T>) {
+    assert_eq!(a.rows, b.len(), "Dimension mismatch");
+    let transposed = a.transpose();
+    let mut gram = transposed.mul_mat(a);
+    for i in 0..a.cols {
+        gram[(i, i)] += lambda;
+    }
+    (gram, transposed.mul_vec(b))
+}
+
+// Ridge regression: solves the normal equations by Cholesky. Any positive
+// lambda makes the system positive definite, so this only fails with
+// `Singular` for lambda = 0 with linearly dependent columns, and with
+// `DimensionMismatch` when `b` has the wrong length.
+#[allow(dead_code)]
+pub fn ridge_least_squares<T: Float>(a: &DMat<T>, b: &DVec<T>, lambda: T) -> Result<DVec<T>, MathError> {
+    if a.rows != b.len() {
+        return Err(MathError::DimensionMismatch);
+    }
+    let (gram, rhs) = normal_equations(a, b, lambda);
+    gram.solve_cholesky(&rhs)
+}
+
+// The Kronecker product of two 4x4 matrices is 16x16, which has no fixed
+// size type, so it lands in a DMat.
+#[allow(dead_code)]
+impl<T: Float> Mat4<T> {
+    pub fn to_dmat(self) -> DMat<T> {
+        DMat::from_col_slice(4, 4, &self.elements)
+    }
+
+    pub fn kron(&self, other: &Mat4<T>) -> DMat<T> {
+        self.to_dmat().kron(&other.to_dmat())
+    }
+}
+
+// Dynamic counterparts for fixed-size matrices, whose Kronecker products and
+// stacked columns have sizes the type system can't spell.
+#[allow(dead_code)]
+impl<T: Float, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn to_dmat(self) -> DMat<T> {
+        DMat::from_col_slice(R, C, self.columns.as_flattened())
+    }
+
+    pub fn kron<const P: usize, const Q: usize>(&self, other: &Matrix<T, P, Q>) -> DMat<T> {
+        self.to_dmat().kron(&other.to_dmat())
+    }
+
+    pub fn vec(&self) -> DVec<T> {
+        DVec::from_slice(self.columns.as_flattened())
+    }
+
+    // Panics unless `v` has R * C elements.
+    pub fn unvec(v: &DVec<T>) -> Self {
+        assert_eq!(v.len(), R * C, "Dimension mismatch");
+        Self::from_cols(std::array::from_fn(|col| std::array::from_fn(|row| v[col * R + row])))
+    }
+}
+
+impl<T: Float> std::ops::Index<(usize, usize)> for DMat<T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        assert!(index.0 < self.rows && index.1 < self.cols, "Index out of bounds");
+        &self.elements[index.1 * self.rows + index.0]
+    }
+}
+
+impl<T: Float> std::ops::IndexMut<(usize, usize)> for DMat<T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        assert!(index.0 < self.rows && index.1 < self.cols, "Index out of bounds");
+        &mut self.elements[index.1 * self.rows + index.0]
+    }
+}
+
+impl<T: Float> std::ops::Mul<DMat<T>> for DMat<T> {
+    type Output = DMat<T>;
+
+    fn mul(self, rhs: DMat<T>) -> Self::Output {
+        self.mul_mat(&rhs)
+    }
+}
+
+impl<T: Float> std::ops::Mul<DVec<T>> for DMat<T> {
+    type Output = DVec<T>;
+
+    fn mul(self, rhs: DVec<T>) -> Self::Output {
+        self.mul_vec(&rhs)
+    }
+}
+
+impl<T: Float> std::ops::MulAssign<T> for DMat<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        for x in self.elements.iter_mut() {
+            *x *= rhs;
+        }
+    }
+}
+
+impl<T: Float> std::ops::Mul<T> for DMat<T> {
+    type Output = DMat<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut out = self;
+        out *= rhs;
+        out
+    }
+}
+
+impl<T: Float> std::ops::AddAssign<DMat<T>> for DMat<T> {
+    fn add_assign(&mut self, rhs: DMat<T>) {
+        assert!(self.rows == rhs.rows && self.cols == rhs.cols, "Dimension mismatch");
+        for i in 0..self.elements.len() {
+            self.elements[i] += rhs.elements[i];
+        }
+    }
+}
+
+impl<T: Float> std::ops::Add<DMat<T>> for DMat<T> {
+    type Output = DMat<T>;
+
+    fn add(self, rhs: DMat<T>) -> Self::Output {
+        let mut out = self;
+        out += rhs;
+        out
+    }
+}
+
+impl<T: Float> std::ops::SubAssign<DMat<T>> for DMat<T> {
+    fn sub_assign(&mut self, rhs: DMat<T>) {
+        assert!(self.rows == rhs.rows && self.cols == rhs.cols, "Dimension mismatch");
+        for i in 0..self.elements.len() {
+            self.elements[i] -= rhs.elements[i];
+        }
+    }
+}
+
+impl<T: Float> std::ops::Sub<DMat<T>> for DMat<T> {
+    type Output = DMat<T>;
+
+    fn sub(self, rhs: DMat<T>) -> Self::Output {
+        let mut out = self;
+        out -= rhs;
+        out
+    }
+}
+
+impl<T: Float> std::ops::Neg for DMat<T> {
+    type Output = DMat<T>;
+
+    fn neg(self) -> Self::Output {
+        self * -T::one()
+    }
+}
+
+impl<T: Float> std::fmt::Display for DMat<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in 0..self.rows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", self.row(row))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Float> std::fmt::Debug for DMat<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "DMat{}x{}[", self.rows, self.cols)?;
+        for row in 0..self.rows {
+            if row > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", self.row(row))?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn product_transpose_and_solve() {
+        let a = DMat::from_row_slice(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let b = DMat::from_row_slice(3, 2, &[7.0, 8.0, 9.0, 10.0, 11.0, 12.0]);
+        let c = a.mul_mat(&b);
+        assert_eq!(c.as_slice(), &[58.0, 139.0, 64.0, 154.0]);
+        assert_eq!(a.transpose(), DMat::from_col_slice(3, 2, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+
+        // 1D Laplacian with Dirichlet boundaries.
+        let n = 8;
+        let laplacian = DMat::from_fn(n, n, |row, col| match row.abs_diff(col) {
+            0 => 2.0,
+            1 => -1.0,
+            _ => 0.0
+        });
+        let x = DVec::new((0..n).map(|i| (i as f64).sin()).collect());
+        let solved = laplacian.solve(&laplacian.mul_vec(&x)).unwrap();
+        assert!((solved - x.clone()).norm() < 1e-12);
+        assert_eq!(DMat::<f64>::zeroes(3, 3).solve(&DVec::one(3)).err(), Some(MathError::Singular));
+        assert_eq!(laplacian.solve(&DVec::one(2)).err(), Some(MathError::DimensionMismatch));
+
+        let l = laplacian.cholesky().unwrap();
+        assert!((l.mul_mat(&l.transpose()) - laplacian.clone()).as_slice().iter().all(|e| e.abs() < 1e-12));
+        assert!((laplacian.solve_cholesky(&laplacian.mul_vec(&x)).unwrap() - x).norm() < 1e-12);
+        assert_eq!((-laplacian.clone()).cholesky().err(), Some(MathError::Singular));
+        assert_eq!(DMat::<f64>::zeroes(2, 3).cholesky().err(), Some(MathError::DimensionMismatch));
+        assert_eq!(laplacian.solve_cholesky(&DVec::one(2)).err(), Some(MathError::DimensionMismatch));
+    }
+
+    #[test]
+    fn ridge_regression() {
+        // Fit y = 1 + 2x exactly, then shrink the coefficients with lambda.
+        let a = DMat::from_fn(5, 2, |row, col| if col == 0 { 1.0 } else { row as f64 });
+        let b = DVec::new((0..5).map(|i| 1.0 + 2.0 * i as f64).collect());
+        let exact = ridge_least_squares(&a, &b, 0.0).unwrap();
+        assert!((exact.clone() - DVec::from_slice(&[1.0, 2.0])).norm() < 1e-12);
+        let shrunk = ridge_least_squares(&a, &b, 10.0).unwrap();
+        assert!(shrunk.norm() < exact.norm());
+
+        // Dependent columns only solve with regularization.
+        let dependent = DMat::from_fn(3, 2, |row, _| row as f64 + 1.0);
+        let y = DVec::from_slice(&[2.0, 4.0, 6.0]);
+        assert_eq!(ridge_least_squares(&dependent, &y, 0.0).err(), Some(MathError::Singular));
+        assert_eq!(ridge_least_squares(&dependent, &DVec::one(2), 1.0).err(), Some(MathError::DimensionMismatch));
+        let x = ridge_least_squares(&dependent, &y, 1e-9).unwrap();
+        assert!((dependent.mul_vec(&x) - y).norm() < 1e-6);
+    }
+
+    #[test]
+    fn kronecker_product_and_vec() {
+        let a = DMat::from_row_slice(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+        let b = DMat::from_row_slice(2, 3, &[0.0, 5.0, -1.0, 6.0, 7.0, 2.0]);
+        let k = a.kron(&b);
+        assert_eq!((k.rows(), k.cols()), (4, 6));
+        assert_eq!(k.row(3).as_slice(), &[18.0, 21.0, 6.0, 24.0, 28.0, 8.0]);
+
+        // vec(A X B) = (B^T kron A) vec(X).
+        let x = DMat::from_row_slice(2, 2, &[1.0, -2.0, 0.5, 3.0]);
+        let c = DMat::from_row_slice(2, 2, &[2.0, 1.0, 0.0, -1.0]);
+        let lhs = a.mul_mat(&x).mul_mat(&c).vec();
+        let rhs = c.transpose().kron(&a).mul_vec(&x.vec());
+        assert!((lhs - rhs).norm() < 1e-12);
+        assert_eq!(DMat::unvec(&x.vec(), 2, 2), x);
+
+        // Sylvester equation A X + X C = Q through (I kron A + C^T kron I).
+        let q = a.mul_mat(&x) + x.mul_mat(&c);
+        let identity = DMat::identity(2);
+        let system = identity.kron(&a) + c.transpose().kron(&identity);
+        let solved = DMat::unvec(&system.solve(&q.vec()).unwrap(), 2, 2);
+        assert!((solved - x).as_slice().iter().all(|e| e.abs() < 1e-12));
+
+        let m = Matrix::<f64, 3, 3>::from_rows([[1.0, 2.0, 0.0], [0.0, 1.0, 4.0], [5.0, 0.0, 1.0]]);
+        let n = Matrix::<f64, 2, 1>::from_rows([[2.0], [-1.0]]);
+        let mn = m.kron(&n);
+        assert_eq!((mn.rows(), mn.cols()), (6, 3));
+        assert_eq!(mn[(3, 2)], -4.0);
+        assert_eq!(m.vec().as_slice(), m.to_dmat().vec().as_slice());
+        assert_eq!(Matrix::<f64, 3, 3>::unvec(&m.vec()).row(2).to_array(), [5.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn mat4_kronecker_product() {
+        let mut m = Mat4::<f64>::zeroes();
+        m.elements[0] = 2.0;
+        m.elements[4 * 3 + 1] = -1.0;
+        let k = m.kron(&m);
+        assert_eq!((k.rows(), k.cols()), (16, 16));
+        assert_eq!(k[(0, 0)], 4.0);
+        // (0, 0) block is 2 * m; the (1, 3) block is -1 * m.
+        assert_eq!(k[(1, 3)], -2.0);
+        assert_eq!(k[(4 + 1, 12 + 3)], 1.0);
+        assert_eq!(k.as_slice().iter().filter(|e| **e != 0.0).count(), 4);
+        assert_eq!(m.to_dmat()[(1, 3)], -1.0);
+    }
+}
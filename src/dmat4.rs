@@ -0,0 +1,68 @@
+use crate::mat4::Mat4;
+
+#[allow(dead_code)]
+pub type DMat4 = Mat4<f64>;
+
+#[allow(dead_code)]
+impl Mat4<f64> {
+    pub fn from_mat4(m: &Mat4) -> Self {
+        let mut elements = [0.0; 16];
+        for i in 0..16 {
+            elements[i] = m.elements[i] as f64;
+        }
+        Self {
+            elements
+        }
+    }
+
+    // Lossy, rounds each element to the nearest f32.
+    pub fn to_mat4(self) -> Mat4 {
+        let mut out = Mat4::zeroes();
+        for i in 0..16 {
+            out.elements[i] = self.elements[i] as f32;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec4::Vec4;
+
+    #[test]
+    fn f32_round_trip_is_exact() {
+        let m = Mat4::from_axis_angle(Vec4::new(0.3, -0.8, 0.5, 0.0).normalize(), 1.1)
+            * Mat4::from_translation(&Vec4::new(0.1, 1e-7, 3e5, 0.0));
+        let d = DMat4::from_mat4(&m);
+        for i in 0..16 {
+            assert_eq!(d.elements[i], m.elements[i] as f64);
+        }
+        assert_eq!(d.to_mat4(), m);
+    }
+
+    #[test]
+    fn narrowing_rounds_to_nearest() {
+        let mut d = DMat4::identity();
+        d.elements[12] = 0.1;
+        d.elements[13] = 1e300;
+        d.elements[14] = 16_777_217.0;
+        let m = d.to_mat4();
+        assert_eq!(m.elements[12], 0.1f32);
+        assert_eq!(m.elements[13], f32::INFINITY);
+        assert_eq!(m.elements[14], 16_777_216.0);
+    }
+
+    #[test]
+    fn keeps_precision_far_from_origin() {
+        // A small offset next to a large translation survives in f64 but
+        // rounds away in f32.
+        let far = 1.0e8f32;
+        let step = 0.25f32;
+        let single = Mat4::from_translation(&Vec4::new(far, 0.0, 0.0, 0.0)) * Vec4::new(step, 0.0, 0.0, 1.0);
+        let double: Mat4<f64> = DMat4::from_translation(&Vec4::new(far as f64, 0.0, 0.0, 0.0));
+        let moved = double * Vec4::new(step as f64, 0.0, 0.0, 1.0);
+        assert_eq!(single.x, far);
+        assert_eq!(moved.x - far as f64, step as f64);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::float::Float;
+
+// Dual number value + derivative * e with e^2 = 0. Implements `Float`, so
+// Vec4, Mat4, Vector and Matrix can be instantiated over it: seed the input
+// of interest with `Dual::variable` and every result carries its exact
+// derivative with respect to that input (forward-mode autodiff).
+//
+// Comparisons only look at the value, so branches in generic code follow
+// the same path as they would for the plain scalar.
+#[derive(Clone, Copy, Default)]
+pub struct Dual<T = f64> {
+    pub value: T,
+    pub derivative: T
+}
+
+#[allow(dead_code)]
+impl<T: Float> Dual<T> {
+    pub fn new(value: T, derivative: T) -> Self {
+        Self {
+            value,
+            derivative
+        }
+    }
+
+    // A constant: its derivative is zero.
+    pub fn constant(value: T) -> Self {
+        Self::new(value, T::zero())
+    }
+
+    // The variable being differentiated against: its derivative is one.
+    pub fn variable(value: T) -> Self {
+        Self::new(value, T::one())
+    }
+
+    // Applies a scalar function given its value and derivative at
+    // `self.value`, using the chain rule.
+    fn chain(self, value: T, derivative: T) -> Self {
+        Self::new(value, derivative * self.derivative)
+    }
+}
+
+impl<T: Float> Float for Dual<T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+
+    fn from_f64(value: f64) -> Self {
+        Self::constant(T::from_f64(value))
+    }
+
+    fn to_f64(self) -> f64 {
+        self.value.to_f64()
+    }
+
+    fn sqrt(self) -> Self {
+        let root = self.value.sqrt();
+        self.chain(root, T::one() / (T::from_f64(2.0) * root))
+    }
+
+    fn abs(self) -> Self {
+        if self.value < T::zero() { -self } else { self }
+    }
+
+    fn sin(self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+
+    fn cos(self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+
+    fn tan(self) -> Self {
+        let tan = self.value.tan();
+        self.chain(tan, T::one() + tan * tan)
+    }
+
+    fn exp(self) -> Self {
+        let exp = self.value.exp();
+        self.chain(exp, exp)
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), T::one() / self.value)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        let (y, x) = (self.value, other.value);
+        let denominator = x * x + y * y;
+        Self::new(y.atan2(x), (x * self.derivative - y * other.derivative) / denominator)
+    }
+
+    fn epsilon() -> Self {
+        Self::constant(T::epsilon())
+    }
+}
+
+impl<T: Float> PartialEq for Dual<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Float> PartialOrd for Dual<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Float> std::ops::Add<Dual<T>> for Dual<T> {
+    type Output = Dual<T>;
+
+    fn add(self, rhs: Dual<T>) -> Self::Output {
+        Dual::new(self.value + rhs.value, self.derivative + rhs.derivative)
+    }
+}
+
+impl<T: Float> std::ops::AddAssign<Dual<T>> for Dual<T> {
+    fn add_assign(&mut self, rhs: Dual<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Float> std::ops::Sub<Dual<T>> for Dual<T> {
+    type Output = Dual<T>;
+
+    fn sub(self, rhs: Dual<T>) -> Self::Output {
+        Dual::new(self.value - rhs.value, self.derivative - rhs.derivative)
+    }
+}
+
+impl<T: Float> std::ops::SubAssign<Dual<T>> for Dual<T> {
+    fn sub_assign(&mut self, rhs: Dual<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Float> std::ops::Mul<Dual<T>> for Dual<T> {
+    type Output = Dual<T>;
+
+    fn mul(self, rhs: Dual<T>) -> Self::Output {
+        Dual::new(self.value * rhs.value, self.derivative * rhs.value + self.value * rhs.derivative)
+    }
+}
+
+impl<T: Float> std::ops::MulAssign<Dual<T>> for Dual<T> {
+    fn mul_assign(&mut self, rhs: Dual<T>) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Float> std::ops::Div<Dual<T>> for Dual<T> {
+    type Output = Dual<T>;
+
+    fn div(self, rhs: Dual<T>) -> Self::Output {
+        Dual::new(
+            self.value / rhs.value,
+            (self.derivative * rhs.value - self.value * rhs.derivative) / (rhs.value * rhs.value)
+        )
+    }
+}
+
+impl<T: Float> std::ops::DivAssign<Dual<T>> for Dual<T> {
+    fn div_assign(&mut self, rhs: Dual<T>) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: Float> std::ops::Neg for Dual<T> {
+    type Output = Dual<T>;
+
+    fn neg(self) -> Self::Output {
+        Dual::new(-self.value, -self.derivative)
+    }
+}
+
+impl<T: Float> std::fmt::Display for Dual<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.derivative < T::zero() {
+            write!(f, "{} - {}e", self.value, -self.derivative)
+        } else {
+            write!(f, "{} + {}e", self.value, self.derivative)
+        }
+    }
+}
+
+impl<T: Float> std::fmt::Debug for Dual<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Dual({}, {})", self.value, self.derivative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mat4::Mat4;
+    use crate::vec4::Vec4;
+
+    #[test]
+    fn scalar_derivatives() {
+        let x = Dual::variable(0.7f64);
+        let y = (x * x).sin() / x.exp() + x.sqrt().ln() - x.tan();
+        let f = |x: f64| (x * x).sin() / x.exp() + x.sqrt().ln() - x.tan();
+        let h = 1e-6;
+        assert!((y.value - f(0.7)).abs() < 1e-12);
+        assert!((y.derivative - (f(0.7 + h) - f(0.7 - h)) / (2.0 * h)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn derivative_through_transform_chain() {
+        // d(position)/d(angle) of a point rotated then translated.
+        let point = |angle: Dual<f64>| {
+            let mut m: Mat4<Dual<f64>> = Mat4::identity().translate(&Vec4::new(1.0, 2.0, 3.0, 0.0).cast());
+            m.rotate(Vec4::new(0.0, 0.0, 1.0, 0.0).cast(), angle);
+            m * Vec4::new(2.0, 0.0, 1.0, 1.0).cast()
+        };
+        let p = point(Dual::variable(0.4));
+        let h = 1e-6;
+        let ahead = point(Dual::constant(0.4 + h));
+        let behind = point(Dual::constant(0.4 - h));
+        for i in 0..4 {
+            let numeric = (ahead[i].value - behind[i].value) / (2.0 * h);
+            assert!((p[i].derivative - numeric).abs() < 1e-6);
+        }
+        // Rotating (2, 0) about z moves it at speed 2.
+        assert!(((p.x.derivative.powi(2) + p.y.derivative.powi(2)).sqrt() - 2.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::float::Float;
+
+// Heap-backed vector whose length is chosen at runtime. Mirrors `Vector`;
+// binary operations panic when the lengths differ.
+#[derive(Clone, PartialEq)]
+pub struct DVec<T = f64> {
+    pub(crate) elements: Vec<T>,
+}
+
+#[allow(dead_code)]
+impl<T: Float> DVec<T> {
+    pub fn new(elements: Vec<T>) -> Self {
+        Self {
+            elements
+        }
+    }
+
+    pub fn from_slice(elements: &[T]) -> Self {
+        Self::new(elements.to_vec())
+    }
+
+    pub fn zero(len: usize) -> Self {
+        Self::splat(len, T::zero())
+    }
+
+    pub fn one(len: usize) -> Self {
+        Self::splat(len, T::one())
+    }
+
+    pub fn splat(len: usize, value: T) -> Self {
+        Self {
+            elements: vec![value; len]
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.elements
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.elements
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        assert_eq!(self.len(), other.len(), "Dimension mismatch");
+        let mut out = T::zero();
+        for i in 0..self.len() {
+            out += self.elements[i] * other.elements[i];
+        }
+        out
+    }
+
+    pub fn norm_squared(&self) -> T {
+        self.dot(self)
+    }
+
+    pub fn norm(&self) -> T {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        self.clone() / self.norm()
+    }
+
+    pub fn component_mul(&self, other: &Self) -> Self {
+        assert_eq!(self.len(), other.len(), "Dimension mismatch");
+        let mut out = self.clone();
+        for i in 0..self.len() {
+            out.elements[i] *= other.elements[i];
+        }
+        out
+    }
+
+    pub fn map(&self, f: impl Fn(T) -> T) -> Self {
+        Self::new(self.elements.iter().map(|&x| f(x)).collect())
+    }
+}
+
+impl<T: Float> std::ops::Add<DVec<T>> for DVec<T> {
+    type Output = DVec<T>;
+
+    fn add(self, rhs: DVec<T>) -> Self::Output {
+        let mut out = self;
+        out += rhs;
+        out
+    }
+}
+
+impl<T: Float> std::ops::AddAssign<DVec<T>> for DVec<T> {
+    fn add_assign(&mut self, rhs: DVec<T>) {
+        assert_eq!(self.len(), rhs.len(), "Dimension mismatch");
+        for i in 0..self.len() {
+            self.elements[i] += rhs.elements[i];
+        }
+    }
+}
+
+impl<T: Float> std::ops::Sub<DVec<T>> for DVec<T> {
+    type Output = DVec<T>;
+
+    fn sub(self, rhs: DVec<T>) -> Self::Output {
+        let mut out = self;
+        out -= rhs;
+        out
+    }
+}
+
+impl<T: Float> std::ops::SubAssign<DVec<T>> for DVec<T> {
+    fn sub_assign(&mut self, rhs: DVec<T>) {
+        assert_eq!(self.len(), rhs.len(), "Dimension mismatch");
+        for i in 0..self.len() {
+            self.elements[i] -= rhs.elements[i];
+        }
+    }
+}
+
+impl<T: Float> std::ops::Mul<T> for DVec<T> {
+    type Output = DVec<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut out = self;
+        out *= rhs;
+        out
+    }
+}
+
+impl<T: Float> std::ops::MulAssign<T> for DVec<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        for x in self.elements.iter_mut() {
+            *x *= rhs;
+        }
+    }
+}
+
+impl<T: Float> std::ops::Div<T> for DVec<T> {
+    type Output = DVec<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        let mut out = self;
+        out /= rhs;
+        out
+    }
+}
+
+impl<T: Float> std::ops::DivAssign<T> for DVec<T> {
+    fn div_assign(&mut self, rhs: T) {
+        for x in self.elements.iter_mut() {
+            *x /= rhs;
+        }
+    }
+}
+
+impl<T: Float> std::ops::Neg for DVec<T> {
+    type Output = DVec<T>;
+
+    fn neg(self) -> Self::Output {
+        self * -T::one()
+    }
+}
+
+impl<T: Float> std::ops::Index<usize> for DVec<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.elements[index]
+    }
+}
+
+impl<T: Float> std::ops::IndexMut<usize> for DVec<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.elements[index]
+    }
+}
+
+impl<T: Float> std::fmt::Display for DVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "(")?;
+        for i in 0..self.len() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", self.elements[i])?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T: Float> std::fmt::Debug for DVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "DVec{}", self)
+    }
+}
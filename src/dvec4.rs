@@ -0,0 +1,52 @@
+use crate::vec4::Vec4;
+
+pub type DVec4 = Vec4<f64>;
+
+#[allow(dead_code)]
+impl Vec4<f64> {
+    pub fn from_vec4(v: &Vec4) -> Self {
+        Self {
+            x: v.x as f64,
+            y: v.y as f64,
+            z: v.z as f64,
+            w: v.w as f64
+        }
+    }
+
+    // Lossy, rounds each component to the nearest f32.
+    pub fn to_vec4(self) -> Vec4 {
+        Vec4 {
+            x: self.x as f32,
+            y: self.y as f32,
+            z: self.z as f32,
+            w: self.w as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let v = Vec4::new(0.1f32, -3.5, 1e-30, f32::MAX);
+        let d: Vec4<f64> = DVec4::from_vec4(&v);
+        assert_eq!(d.x, 0.1f32 as f64);
+        assert_ne!(d.x, 0.1);
+        assert_eq!(d.to_vec4(), v);
+
+        let narrowed = DVec4::new(0.1, 1e300, -1e-50, 16_777_217.0).to_vec4();
+        assert_eq!(narrowed, Vec4::new(0.1f32, f32::INFINITY, -0.0, 16_777_216.0));
+        assert!(DVec4::new(f64::NAN, 0.0, 0.0, 0.0).to_vec4().x.is_nan());
+    }
+
+    #[test]
+    fn precision() {
+        let a = DVec4::new(1.0e8, 0.0, 0.0, 0.0);
+        let b = a + DVec4::new(1e-3, 0.0, 0.0, 0.0);
+        assert!(((b - a).x - 1e-3).abs() < 1e-7);
+        let single = b.to_vec4() - a.to_vec4();
+        assert_eq!(single.x, 0.0);
+    }
+}
@@ -0,0 +1,10 @@
+// Simulation, estimation and control.
+pub use crate::attitude::{davenport_q, davenport_rotation, triad};
+pub use crate::ballistics::{vacuum_launch_angles, AimArc, Ballistics};
+pub use crate::catenary::Catenary;
+pub use crate::control::{attitude_error, attitude_torque, discrete_lqr, solve_discrete_lyapunov, solve_discrete_riccati};
+pub use crate::imu::{Madgwick, Mahony};
+pub use crate::orbit::{mean_to_true_anomaly, propagate_state, solve_kepler, true_to_mean_anomaly, OrbitalElements};
+pub use crate::orca::{orca_step, orca_velocity, OrcaAgent};
+pub use crate::perception::{can_see, in_fov, in_fov_batch, visible_targets};
+pub use crate::pid::{Pid, PidValue};
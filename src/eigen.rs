@@ -0,0 +1,165 @@
+use crate::float::Float;
+use crate::instrument::{trace_event, trace_span};
+use crate::mat4::Mat4;
+use crate::matrix::Matrix;
+use crate::vec4::Vec4;
+use crate::vector::Vector;
+
+const MAX_JACOBI_SWEEPS: usize = 32;
+
+// Cyclic Jacobi eigendecomposition of the symmetric matrix `a`, given as
+// a[row][col]. Only the upper triangle is read. Returns the eigenvalues in
+// descending order and the matching unit eigenvectors as columns,
+// v[row][col]. Iteration stops once the off-diagonal norm falls below
+// `tolerance`.
+pub(crate) fn jacobi<T: Float, const N: usize>(a: &[[T; N]; N], tolerance: T) -> ([T; N], [[T; N]; N]) {
+    let mut a = *a;
+    for row in 0..N {
+        for col in 0..row {
+            a[row][col] = a[col][row];
+        }
+    }
+    let mut v = [[T::zero(); N]; N];
+    for (i, v_row) in v.iter_mut().enumerate() {
+        v_row[i] = T::one();
+    }
+
+    trace_span!("symmetric_eigen", size = N);
+    let (mut sweeps, mut residual) = (0, T::zero());
+    for _ in 0..MAX_JACOBI_SWEEPS {
+        let mut off = T::zero();
+        for p in 0..N {
+            for q in (p + 1)..N {
+                off += a[p][q] * a[p][q];
+            }
+        }
+        residual = off.sqrt();
+        if residual <= tolerance {
+            break;
+        }
+        sweeps += 1;
+        for p in 0..N {
+            for q in (p + 1)..N {
+                let apq = a[p][q];
+                if apq == T::zero() {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (T::from_f64(2.0) * apq);
+                let sign = if theta < T::zero() { -T::one() } else { T::one() };
+                let t = sign / (theta.abs() + (theta * theta + T::one()).sqrt());
+                let c = T::one() / (t * t + T::one()).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                for k in 0..N {
+                    let (apk, aqk) = (a[p][k], a[q][k]);
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    trace_event!("symmetric_eigen finished", sweeps = sweeps, residual = residual.to_f64());
+
+    let mut order = [0; N];
+    for (i, o) in order.iter_mut().enumerate() {
+        *o = i;
+    }
+    order.sort_by(|&i, &j| a[j][j].partial_cmp(&a[i][i]).unwrap_or(std::cmp::Ordering::Equal));
+    let mut values = [T::zero(); N];
+    let mut vectors = [[T::zero(); N]; N];
+    for (col, &i) in order.iter().enumerate() {
+        values[col] = a[i][i];
+        for row in 0..N {
+            vectors[row][col] = v[row][i];
+        }
+    }
+    (values, vectors)
+}
+
+#[allow(dead_code)]
+impl<T: Float> Mat4<T> {
+    // Eigenvalues in descending order and a matrix whose columns are the
+    // matching unit eigenvectors, for a symmetric matrix such as a
+    // covariance. Only the upper triangle is read.
+    pub fn symmetric_eigen(&self, tolerance: T) -> (Vec4<T>, Self) {
+        let mut a = [[T::zero(); 4]; 4];
+        for (row, a_row) in a.iter_mut().enumerate() {
+            for (col, value) in a_row.iter_mut().enumerate() {
+                *value = self.elements[col * 4 + row];
+            }
+        }
+        let (values, vectors) = jacobi(&a, tolerance);
+        let mut out = Self::zeroes();
+        for (row, v_row) in vectors.iter().enumerate() {
+            for (col, &value) in v_row.iter().enumerate() {
+                out.elements[col * 4 + row] = value;
+            }
+        }
+        (Vec4::new(values[0], values[1], values[2], values[3]), out)
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+    // Same as `Mat4::symmetric_eigen`; Matrix<T, 3, 3> covers inertia
+    // tensors and 3D covariances.
+    pub fn symmetric_eigen(&self, tolerance: T) -> (Vector<T, N>, Self) {
+        let mut a = [[T::zero(); N]; N];
+        for (row, a_row) in a.iter_mut().enumerate() {
+            for (col, value) in a_row.iter_mut().enumerate() {
+                *value = self.columns[col][row];
+            }
+        }
+        let (values, vectors) = jacobi(&a, tolerance);
+        (Vector::new(values), Self::from_rows(vectors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inertia_tensor_axes() {
+        // Inertia tensor of a box rotated 30 degrees about z.
+        let (s, c) = (std::f64::consts::FRAC_PI_6.sin(), std::f64::consts::FRAC_PI_6.cos());
+        let rotation = Matrix::<f64, 3, 3>::from_rows([[c, -s, 0.0], [s, c, 0.0], [0.0, 0.0, 1.0]]);
+        let diagonal = Matrix::<f64, 3, 3>::from_rows([[5.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 3.0]]);
+        let tensor = rotation * diagonal * rotation.transpose();
+        let (values, vectors) = tensor.symmetric_eigen(1e-14);
+        assert!((values[0] - 5.0).abs() < 1e-12 && (values[1] - 3.0).abs() < 1e-12 && (values[2] - 2.0).abs() < 1e-12);
+        let axis = vectors.column(0);
+        assert!((axis[0].abs() - c).abs() < 1e-12 && (axis[1].abs() - s).abs() < 1e-12);
+        let reconstructed = vectors * Matrix::from_rows([[5.0, 0.0, 0.0], [0.0, 3.0, 0.0], [0.0, 0.0, 2.0]]) * vectors.transpose();
+        for row in 0..3 {
+            for col in 0..3 {
+                assert!((reconstructed[(row, col)] - tensor[(row, col)]).abs() < 1e-12);
+            }
+        }
+
+        let mut m = Mat4::<f64>::zeroes();
+        for row in 0..4 {
+            for col in row..4 {
+                m.elements[col * 4 + row] = (row + 2 * col) as f64;
+            }
+        }
+        let (values, vectors) = m.symmetric_eigen(1e-14);
+        for i in 0..4 {
+            let v = Vec4::new(vectors.elements[i * 4], vectors.elements[i * 4 + 1], vectors.elements[i * 4 + 2], vectors.elements[i * 4 + 3]);
+            let full = Mat4 {
+                elements: std::array::from_fn(|k| m.elements[(k / 4).max(k % 4) * 4 + (k / 4).min(k % 4)])
+            };
+            assert!((full * v - v * values[i]).length() < 1e-12);
+        }
+    }
+}
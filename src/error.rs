@@ -0,0 +1,31 @@
+// Why a fallible operation failed, for APIs where the caller can act on
+// the reason: perturb a singular matrix, sanitize non-finite input, fix a
+// size bug or give an iteration more room.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MathError {
+    // The matrix has no inverse, or is too close to singular for the result
+    // to be more than rounding error.
+    Singular,
+    // An input or intermediate value is NaN or infinite.
+    NotFinite,
+    // Operand sizes do not fit together.
+    DimensionMismatch,
+    // An iterative method did not reach its tolerance within its iteration
+    // budget.
+    NoConvergence
+}
+
+impl std::fmt::Display for MathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            MathError::Singular => "Matrix is singular",
+            MathError::NotFinite => "Value is not finite",
+            MathError::DimensionMismatch => "Dimension mismatch",
+            MathError::NoConvergence => "Iteration did not converge"
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for MathError {}
@@ -0,0 +1,190 @@
+use crate::mat4::Mat4;
+use crate::transform::{quat_to_rotation, rotation_to_quat};
+use crate::vec4::Vec4;
+
+// Order in which the three rotations are applied, about fixed world axes.
+// `Xyz` rotates about x first, then y, then z, so the matrix is Rz * Ry * Rx;
+// read right to left this is also intrinsic z-y'-x''.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EulerOrder {
+    #[default]
+    Xyz,
+    Xzy,
+    Yxz,
+    Yzx,
+    Zxy,
+    Zyx
+}
+
+#[allow(dead_code)]
+impl EulerOrder {
+    pub const ALL: [EulerOrder; 6] = [EulerOrder::Xyz, EulerOrder::Xzy, EulerOrder::Yxz, EulerOrder::Yzx, EulerOrder::Zxy, EulerOrder::Zyx];
+
+    // Axis indices in application order.
+    pub fn axes(&self) -> [usize; 3] {
+        match self {
+            EulerOrder::Xyz => [0, 1, 2],
+            EulerOrder::Xzy => [0, 2, 1],
+            EulerOrder::Yxz => [1, 0, 2],
+            EulerOrder::Yzx => [1, 2, 0],
+            EulerOrder::Zxy => [2, 0, 1],
+            EulerOrder::Zyx => [2, 1, 0]
+        }
+    }
+
+    fn is_even(&self) -> bool {
+        matches!(self, EulerOrder::Xyz | EulerOrder::Yzx | EulerOrder::Zxy)
+    }
+}
+
+// Rotation angles in radians about the x, y and z axes, applied in `order`.
+// Rotations are right-handed: a positive angle turns counter-clockwise when
+// looking down the axis towards the origin.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EulerAngles {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub order: EulerOrder
+}
+
+#[allow(dead_code)]
+impl EulerAngles {
+    pub fn new(x: f32, y: f32, z: f32, order: EulerOrder) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            order
+        }
+    }
+
+    fn angle(&self, axis: usize) -> f32 {
+        [self.x, self.y, self.z][axis]
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        let mut out = Mat4::identity();
+        for axis in self.order.axes() {
+            out = axis_rotation(axis, self.angle(axis)) * out;
+        }
+        out
+    }
+
+    // Extracts angles from the rotation part of `m`, which must be
+    // orthonormal. The middle angle lands in [-pi/2, pi/2]; at gimbal lock
+    // the last rotation is set to zero and the first absorbs it.
+    pub fn from_mat4(m: &Mat4, order: EulerOrder) -> Self {
+        let at = |row: usize, col: usize| m.elements[col * 4 + row];
+        let [a, b, c] = order.axes();
+        let s = if order.is_even() { 1.0 } else { -1.0 };
+        let sin_middle = (-s * at(c, a)).clamp(-1.0, 1.0);
+        let middle = sin_middle.asin();
+        let (first, last) = if sin_middle.abs() < 1.0 - 1e-6 {
+            ((s * at(c, b)).atan2(at(c, c)), (s * at(b, a)).atan2(at(a, a)))
+        } else {
+            ((-s * at(b, c)).atan2(at(b, b)), 0.0)
+        };
+        let mut angles = [0.0; 3];
+        angles[a] = first;
+        angles[b] = middle;
+        angles[c] = last;
+        Self::new(angles[0], angles[1], angles[2], order)
+    }
+
+    // Unit quaternion stored as (x, y, z, w).
+    pub fn to_quat(self) -> Vec4 {
+        rotation_to_quat(&self.to_mat4())
+    }
+
+    pub fn from_quat(q: &Vec4, order: EulerOrder) -> Self {
+        Self::from_mat4(&quat_to_rotation(q), order)
+    }
+
+    // Same rotation expressed in another order.
+    pub fn reorder(&self, order: EulerOrder) -> Self {
+        Self::from_mat4(&self.to_mat4(), order)
+    }
+}
+
+#[allow(dead_code)]
+impl Mat4 {
+    // Rotation from angles in radians about x, y and z, applied in `order`.
+    pub fn from_euler(x: f32, y: f32, z: f32, order: EulerOrder) -> Self {
+        EulerAngles::new(x, y, z, order).to_mat4()
+    }
+
+    // Angles of the rotation part, which must be orthonormal; see
+    // `EulerAngles::from_mat4` for the ranges and the gimbal lock case.
+    pub fn to_euler(self, order: EulerOrder) -> EulerAngles {
+        EulerAngles::from_mat4(&self, order)
+    }
+}
+
+// Right-handed rotation about a single coordinate axis.
+fn axis_rotation(axis: usize, angle: f32) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    let (i, j) = ((axis + 1) % 3, (axis + 2) % 3);
+    let mut out = Mat4::identity();
+    out.elements[i * 4 + i] = c;
+    out.elements[j * 4 + j] = c;
+    out.elements[i * 4 + j] = s;
+    out.elements[j * 4 + i] = -s;
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &Mat4, b: &Mat4) -> bool {
+        (0..16).all(|i| (a.elements[i] - b.elements[i]).abs() < 1e-5)
+    }
+
+    #[test]
+    fn round_trip_every_order() {
+        for order in EulerOrder::ALL {
+            for angles in [(0.3, -0.7, 1.9), (-2.5, 1.2, 0.1), (0.4, std::f32::consts::FRAC_PI_2, -0.8)] {
+                let euler = EulerAngles::new(angles.0, angles.1, angles.2, order);
+                let m = euler.to_mat4();
+                let back = EulerAngles::from_mat4(&m, order);
+                assert!(close(&back.to_mat4(), &m), "{:?}", order);
+                assert!(close(&EulerAngles::from_quat(&euler.to_quat(), order).to_mat4(), &m));
+                assert!(close(&euler.reorder(EulerOrder::Zyx).to_mat4(), &m));
+            }
+        }
+    }
+
+    #[test]
+    fn order_is_explicit() {
+        // 90 degrees about x then z takes +y to +z; about z then x takes
+        // it to -x.
+        let y = Vec4::new(0.0, 1.0, 0.0, 0.0);
+        let q = std::f32::consts::FRAC_PI_2;
+        let xz = Mat4::from_euler(q, 0.0, q, EulerOrder::Xzy) * y;
+        let zx = EulerAngles::new(q, 0.0, q, EulerOrder::Zxy).to_mat4() * y;
+        assert!((xz - Vec4::new(0.0, 0.0, 1.0, 0.0)).length() < 1e-6);
+        assert!((zx - Vec4::new(-1.0, 0.0, 0.0, 0.0)).length() < 1e-6);
+    }
+
+    #[test]
+    fn gimbal_lock_is_deterministic() {
+        let half_pi = std::f32::consts::FRAC_PI_2;
+        for order in EulerOrder::ALL {
+            let middle = order.axes()[1];
+            let mut angles = [0.4, 0.4, 0.4];
+            angles[middle] = half_pi;
+            let m = Mat4::from_euler(angles[0], angles[1], angles[2], order);
+            let euler = m.to_euler(order);
+            let last = order.axes()[2];
+            assert_eq!([euler.x, euler.y, euler.z][last], 0.0, "{:?}", order);
+            assert!(close(&euler.to_mat4(), &m), "{:?}", order);
+            let again = euler.to_mat4().to_euler(order);
+            assert!((again.x - euler.x).abs() < 1e-5 && (again.y - euler.y).abs() < 1e-5 && (again.z - euler.z).abs() < 1e-5);
+        }
+    }
+}
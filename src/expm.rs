@@ -0,0 +1,174 @@
+use crate::float::Float;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// Below this angle the closed forms divide by nearly zero and the series
+// expansions take over.
+const SMALL_ANGLE: f64 = 1e-4;
+
+fn at<T: Float>(m: &Mat4<T>, row: usize, col: usize) -> T {
+    m.elements[col * 4 + row]
+}
+
+// Skew-symmetric matrix of `w`, so skew(w) * v = w x v.
+fn skew<T: Float>(w: &Vec4<T>) -> Mat4<T> {
+    let mut out = Mat4::zeroes();
+    out.elements[4] = -w.z;
+    out.elements[8] = w.y;
+    out.elements[1] = w.z;
+    out.elements[9] = -w.x;
+    out.elements[2] = -w.y;
+    out.elements[6] = w.x;
+    out
+}
+
+// Rotation vector (axis times angle in [0, pi]) of the rotation part.
+fn rotation_log<T: Float>(m: &Mat4<T>) -> Vec4<T> {
+    let half = T::from_f64(0.5);
+    let cos = (at(m, 0, 0) + at(m, 1, 1) + at(m, 2, 2) - T::one()) * half;
+    let sin_axis = Vec4::new(at(m, 2, 1) - at(m, 1, 2), at(m, 0, 2) - at(m, 2, 0), at(m, 1, 0) - at(m, 0, 1), T::zero()) * half;
+    let sin = sin_axis.length();
+    let angle = sin.atan2(cos);
+    if angle.to_f64() < SMALL_ANGLE {
+        return sin_axis;
+    }
+    if sin.to_f64() > 1e-3 * angle.to_f64() {
+        return sin_axis * (angle / sin);
+    }
+    // Near pi the antisymmetric part vanishes; recover the axis from the
+    // symmetric part R + R^T = 2 cos I + 2 (1 - cos) a a^T, anchored on
+    // its largest diagonal entry and signed to agree with `sin_axis`.
+    let one_minus_cos = T::one() - cos;
+    let i = (0..3).fold(0, |best, i| if at(m, i, i) > at(m, best, best) { i } else { best });
+    let mut axis = [T::zero(); 3];
+    axis[i] = ((at(m, i, i) - cos) / one_minus_cos).sqrt();
+    for j in 0..3 {
+        if j != i {
+            axis[j] = (at(m, i, j) + at(m, j, i)) * half / (one_minus_cos * axis[i]);
+        }
+    }
+    let mut axis = Vec4::new(axis[0], axis[1], axis[2], T::zero());
+    if axis.dot(&sin_axis) < T::zero() {
+        axis = -axis;
+    }
+    axis * (angle / axis.length())
+}
+
+#[allow(dead_code)]
+impl<T: Float> Mat4<T> {
+    // Matrix exponential of any matrix by scaling and squaring: the
+    // Taylor series of self / 2^s is summed until it stops changing and
+    // squared s times. For a twist from `log` this is a rigid transform;
+    // for A dt it is the state transition of x' = A x. A NaN or infinite
+    // element gives an all-NaN result; it would otherwise ask for an
+    // unbounded number of squarings.
+    pub fn exp(&self) -> Self {
+        if !self.is_finite() {
+            return Self::from_cols_array(&[T::from_f64(f64::NAN); 16]);
+        }
+        let norm = (0..4)
+            .map(|row| (0..4).map(|col| at(self, row, col).abs().to_f64()).sum::<f64>())
+            .fold(0.0, f64::max);
+        let squarings = if norm > 0.5 { (norm / 0.5).log2().ceil() as i32 } else { 0 };
+        let a = *self * T::from_f64(0.5f64.powi(squarings));
+        let mut out = Self::identity();
+        let mut term = Self::identity();
+        for k in 1..=30 {
+            term = term * a * T::from_f64(1.0 / k as f64);
+            out += term;
+            if term.elements.iter().all(|e| e.abs() <= T::epsilon() * T::from_f64(0.5)) {
+                break;
+            }
+        }
+        for _ in 0..squarings {
+            out = out * out;
+        }
+        out
+    }
+
+    // Logarithm of a rigid transform (rotation plus translation): the
+    // twist with the skew-symmetric rotation generator in the upper 3x3,
+    // the translational velocity in the last column and a zero bottom row,
+    // so `exp` inverts it. The rotation angle comes out in [0, pi]; at
+    // exactly pi either direction is a valid logarithm.
+    pub fn log(&self) -> Self {
+        let w = rotation_log(self);
+        let angle = w.length();
+        let generator = skew(&w);
+        let generator_squared = generator * generator;
+        // V^-1 = I - W / 2 + c W^2, undoing the coupling between rotation
+        // and translation in exp.
+        let c = if angle.to_f64() < SMALL_ANGLE {
+            T::from_f64(1.0 / 12.0)
+        } else {
+            let (sin, cos) = (angle.sin(), angle.cos());
+            (T::one() - angle * sin / (T::from_f64(2.0) * (T::one() - cos))) / (angle * angle)
+        };
+        let v_inverse = Self::identity() - generator * T::from_f64(0.5) + generator_squared * c;
+        let translation = v_inverse * Vec4::new(self.elements[12], self.elements[13], self.elements[14], T::zero());
+        let mut out = generator;
+        out.elements[12] = translation.x;
+        out.elements[13] = translation.y;
+        out.elements[14] = translation.z;
+        out
+    }
+
+    // Rigid transform raised to the power t, moving along the screw motion
+    // between the identity (t = 0) and self (t = 1). Blend between two
+    // rigid transforms as a * (a^-1 * b).powf(t).
+    pub fn powf(&self, t: T) -> Self {
+        (self.log() * t).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &Mat4<f64>, b: &Mat4<f64>, tolerance: f64) -> bool {
+        (0..16).all(|i| (a.elements[i] - b.elements[i]).abs() < tolerance)
+    }
+
+    #[test]
+    fn exp_and_log_round_trip_rigid_transforms() {
+        for (axis, angle) in [((0.3, -1.0, 0.5), 1.2), ((1.0, 0.0, 0.0), 1e-6), ((0.0, 0.6, 0.8), std::f64::consts::PI - 1e-9), ((1.0, 2.0, 2.0), std::f64::consts::PI)] {
+            let axis = Vec4::new(axis.0, axis.1, axis.2, 0.0).normalize();
+            let m = Mat4::from_translation(&Vec4::new(1.0, -2.0, 0.5, 0.0)) * Mat4::from_axis_angle(axis, angle);
+            let twist = m.log();
+            assert!((0..4).all(|i| twist.elements[i * 4 + 3] == 0.0), "{}", angle);
+            assert!(close(&twist.exp(), &m, 1e-9), "{}", angle);
+        }
+        assert!(close(&Mat4::<f64>::identity().log(), &Mat4::zeroes(), 1e-15));
+    }
+
+    #[test]
+    fn powf_moves_along_the_screw() {
+        let m = Mat4::<f64>::from_translation(&Vec4::new(2.0, 0.0, 4.0, 0.0)) * Mat4::from_axis_angle(Vec4::new(0.0, 0.0, 1.0, 0.0), 1.0);
+        let half = m.powf(0.5);
+        assert!(close(&(half * half), &m, 1e-12));
+        assert!(close(&m.powf(0.0), &Mat4::identity(), 1e-12));
+        assert!(close(&m.powf(1.0), &m, 1e-12));
+        assert!(close(&m.powf(-1.0), &m.inverse(), 1e-12));
+        // Translation along the axis grows linearly with t.
+        assert!((m.powf(0.25).elements[14] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn exp_of_general_matrix() {
+        // exp([[0, 1], [-1, 0]] * t) rotates the plane; exp of a diagonal
+        // exponentiates the diagonal, even for large entries.
+        let mut a = Mat4::<f64>::zeroes();
+        a.elements[4] = 3.0;
+        a.elements[1] = -3.0;
+        a.elements[10] = 5.0;
+        let e = a.exp();
+        assert!((e.elements[0] - 3.0f64.cos()).abs() < 1e-12 && (e.elements[4] - 3.0f64.sin()).abs() < 1e-12);
+        assert!((e.elements[10] / 5.0f64.exp() - 1.0).abs() < 1e-12);
+        assert_eq!(e.elements[15], 1.0);
+
+        for bad in [f64::INFINITY, f64::NAN] {
+            a.elements[7] = bad;
+            assert!(a.exp().elements.iter().all(|e| e.is_nan()));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::vec4::Vec4;
+
+// Four IEEE 754 binary16 values stored as raw bits, for compact vertex
+// attributes. Converting from f32 rounds to nearest, ties to even; values
+// beyond the f16 range become infinity and NaN stays NaN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct F16Vec4 {
+    pub x: u16,
+    pub y: u16,
+    pub z: u16,
+    pub w: u16
+}
+
+#[allow(dead_code)]
+impl F16Vec4 {
+    pub fn from_bits(bits: [u16; 4]) -> Self {
+        Self {
+            x: bits[0],
+            y: bits[1],
+            z: bits[2],
+            w: bits[3]
+        }
+    }
+
+    pub fn to_bits(self) -> [u16; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    pub fn from_vec4(v: &Vec4) -> Self {
+        Self::from_bits([v.x, v.y, v.z, v.w].map(f32_to_f16))
+    }
+
+    pub fn to_vec4(self) -> Vec4 {
+        Vec4::new(f16_to_f32(self.x), f16_to_f32(self.y), f16_to_f32(self.z), f16_to_f32(self.w))
+    }
+
+    // Little-endian, x first, as laid out in a vertex buffer.
+    pub fn to_le_bytes(self) -> [u8; 8] {
+        let mut out = [0; 8];
+        for (i, bits) in self.to_bits().iter().enumerate() {
+            out[i * 2..i * 2 + 2].copy_from_slice(&bits.to_le_bytes());
+        }
+        out
+    }
+
+    pub fn from_le_bytes(bytes: [u8; 8]) -> Self {
+        let lane = |i: usize| u16::from_le_bytes([bytes[i * 2], bytes[i * 2 + 1]]);
+        Self::from_bits([lane(0), lane(1), lane(2), lane(3)])
+    }
+
+    // All four lanes in one word, x in the low bits.
+    pub fn pack(self) -> u64 {
+        u64::from_le_bytes(self.to_le_bytes())
+    }
+
+    pub fn unpack(packed: u64) -> Self {
+        Self::from_le_bytes(packed.to_le_bytes())
+    }
+}
+
+#[allow(dead_code)]
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        // Keep NaNs quiet and non-zero after dropping the low mantissa bits.
+        let nan = if mantissa != 0 { 0x200 | (mantissa >> 13) as u16 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exponent <= 0 {
+        // Subnormal in f16, or too small and flushed to zero.
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - exponent) as u32;
+        let half = (mantissa >> shift) as u16;
+        let rest = mantissa & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        let round = rest > halfway || (rest == halfway && half & 1 == 1);
+        return sign | (half + round as u16);
+    }
+
+    // A carry out of the mantissa correctly bumps the exponent, up to
+    // infinity.
+    let half = ((exponent as u16) << 10) | (mantissa >> 13) as u16;
+    let rest = mantissa & 0x1fff;
+    let round = rest > 0x1000 || (rest == 0x1000 && half & 1 == 1);
+    sign | (half + round as u16)
+}
+
+#[allow(dead_code)]
+pub fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x3ff) as u32;
+    match exponent {
+        0 => {
+            let magnitude = mantissa as f32 * (1.0 / (1 << 24) as f32);
+            if sign != 0 { -magnitude } else { magnitude }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 112) << 23) | (mantissa << 13))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_and_rounds() {
+        assert_eq!(f32_to_f16(1.0), 0x3c00);
+        assert_eq!(f32_to_f16(-2.0), 0xc000);
+        assert_eq!(f32_to_f16(65504.0), 0x7bff);
+        assert_eq!(f32_to_f16(65520.0), 0x7c00);
+        assert_eq!(f32_to_f16(1e-8), 0);
+        assert_eq!(f32_to_f16(-0.0), 0x8000);
+        assert_eq!(f32_to_f16(5.960_464_5e-8), 0x0001);
+        assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+        // 1 + 2^-11 is halfway between 1 and the next f16 and ties to even.
+        assert_eq!(f32_to_f16(1.0 + 1.0 / 2048.0), 0x3c00);
+        assert_eq!(f32_to_f16(1.0 + 3.0 / 2048.0), 0x3c02);
+
+        for bits in 0..=u16::MAX {
+            let value = f16_to_f32(bits);
+            if !value.is_nan() {
+                assert_eq!(f32_to_f16(value), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn vector_round_trip() {
+        let v = Vec4::new(0.5, -1.25, 3.0, 1.0);
+        let h = F16Vec4::from_vec4(&v);
+        assert_eq!((h.to_vec4() - v).length(), 0.0);
+        assert_eq!(F16Vec4::unpack(h.pack()), h);
+        assert_eq!(h.pack() & 0xffff, 0x3800);
+    }
+}
@@ -0,0 +1,180 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// Scalar type the vector and matrix types are generic over. Implemented for
+// f32 and f64; downstream crates can implement it for their own number types.
+pub trait Float:
+    Copy
+    + Default
+    + PartialOrd
+    + std::fmt::Debug
+    + std::fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn sqrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    // Gap between 1 and the next representable value.
+    fn epsilon() -> Self;
+
+    // How many representable values apart two numbers are; zero for equal
+    // values (including 0 and -0) and u64::MAX when either is NaN. The
+    // default counts in f64 steps; f32 and f64 count in their own.
+    fn ulps_between(self, other: Self) -> u64 {
+        self.to_f64().ulps_between(other.to_f64())
+    }
+
+    // Column-major 4x4 kernels behind `Mat4 * Mat4` and `Mat4 * Vec4`. The
+    // defaults are plain scalar loops; with the `simd` feature f32 overrides
+    // them with SSE on x86_64 and keeps these everywhere else.
+    fn mat4_mul(lhs: &[Self; 16], rhs: &[Self; 16]) -> [Self; 16] {
+        let mut result = [Self::zero(); 16];
+        for col in 0..4 {
+            for row in 0..4 {
+                for k in 0..4 {
+                    result[col * 4 + row] += lhs[k * 4 + row] * rhs[col * 4 + k];
+                }
+            }
+        }
+        result
+    }
+
+    fn mat4_mul_vec4(m: &[Self; 16], v: [Self; 4]) -> [Self; 4] {
+        std::array::from_fn(|row| m[row] * v[0] + m[4 + row] * v[1] + m[8 + row] * v[2] + m[12 + row] * v[3])
+    }
+}
+
+// With the `libm` feature these functions come from the portable libm
+// crate instead of the platform's math library, so code generic over
+// `Float` gives bit-identical results across targets (lockstep simulation,
+// WASM vs native). Code written directly against f32 still uses std.
+#[cfg(not(feature = "libm"))]
+macro_rules! math {
+    ($t:ty, $std:ident, $libm:ident, $($arg:expr),+) => {
+        <$t>::$std($($arg),+)
+    };
+}
+
+#[cfg(feature = "libm")]
+macro_rules! math {
+    ($t:ty, $std:ident, $libm:ident, $($arg:expr),+) => {
+        libm::$libm($($arg),+)
+    };
+}
+
+macro_rules! impl_float {
+    ($t:ty, $bits:ty, $sqrt:ident, $sin:ident, $cos:ident, $tan:ident, $exp:ident, $ln:ident, $atan2:ident $(, { $($kernels:tt)* })?) => {
+        impl Float for $t {
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn one() -> Self {
+                1.0
+            }
+
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn sqrt(self) -> Self {
+                math!($t, sqrt, $sqrt, self)
+            }
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn sin(self) -> Self {
+                math!($t, sin, $sin, self)
+            }
+
+            fn cos(self) -> Self {
+                math!($t, cos, $cos, self)
+            }
+
+            fn tan(self) -> Self {
+                math!($t, tan, $tan, self)
+            }
+
+            fn exp(self) -> Self {
+                math!($t, exp, $exp, self)
+            }
+
+            fn ln(self) -> Self {
+                math!($t, ln, $ln, self)
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                math!($t, atan2, $atan2, self, other)
+            }
+
+            fn epsilon() -> Self {
+                <$t>::EPSILON
+            }
+
+            fn ulps_between(self, other: Self) -> u64 {
+                if self.is_nan() || other.is_nan() {
+                    return u64::MAX;
+                }
+                // Reorders the sign-magnitude bit patterns so consecutive
+                // floats map to consecutive integers across zero.
+                let ordered = |x: $t| {
+                    let bits = x.to_bits() as $bits;
+                    (if bits < 0 { <$bits>::MIN - bits } else { bits }) as i128
+                };
+                (ordered(self) - ordered(other)).unsigned_abs().min(u64::MAX as u128) as u64
+            }
+
+            $($($kernels)*)?
+        }
+    };
+}
+
+impl_float!(f32, i32, sqrtf, sinf, cosf, tanf, expf, logf, atan2f, {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    fn mat4_mul(lhs: &[f32; 16], rhs: &[f32; 16]) -> [f32; 16] {
+        crate::simd::mat4_mul(lhs, rhs)
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    fn mat4_mul_vec4(m: &[f32; 16], v: [f32; 4]) -> [f32; 4] {
+        crate::simd::mat4_mul_vec4(m, v)
+    }
+});
+impl_float!(f64, i64, sqrt, sin, cos, tan, exp, log, atan2);
+
+#[cfg(all(test, feature = "libm"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn libm_agrees_with_std() {
+        for i in 0..100 {
+            let x = i as f64 * 0.173 - 8.0;
+            assert!((Float::sin(x) - x.sin()).abs() < 1e-15);
+            assert!((Float::atan2(x, 1.5) - x.atan2(1.5)).abs() < 1e-15);
+            assert!((Float::exp(x as f32) - (x as f32).exp()).abs() <= (x as f32).exp() * 1e-6);
+        }
+    }
+}
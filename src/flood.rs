@@ -0,0 +1,223 @@
+use std::collections::VecDeque;
+
+use crate::grid2::Grid2;
+use crate::grid3::Grid3;
+
+// Which neighbours of a 2D cell are connected to it: the four sharing an
+// edge, or also the four sharing only a corner.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Connectivity2 {
+    #[default]
+    Four,
+    Eight
+}
+
+// Which neighbours of a 3D cell are connected to it: the six sharing a
+// face, the eighteen sharing at least an edge, or all twenty-six.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Connectivity3 {
+    #[default]
+    Six,
+    Eighteen,
+    TwentySix
+}
+
+// Connected region found by labeling, with the inclusive bounding box of
+// its cells.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region2 {
+    pub label: u32,
+    pub cell_count: usize,
+    pub min: (usize, usize),
+    pub max: (usize, usize)
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region3 {
+    pub label: u32,
+    pub cell_count: usize,
+    pub min: (usize, usize, usize),
+    pub max: (usize, usize, usize)
+}
+
+// Neighbour offsets differing in at most `max_axes` coordinates, over the
+// first `dimensions` axes.
+fn offsets(dimensions: usize, max_axes: usize) -> Vec<[isize; 3]> {
+    let mut out = Vec::new();
+    for dz in -1..=1 {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let offset = [dx, dy, dz];
+                let moved = offset.iter().filter(|&&d| d != 0).count();
+                if moved > 0 && moved <= max_axes && offset[dimensions..].iter().all(|&d| d == 0) {
+                    out.push(offset);
+                }
+            }
+        }
+    }
+    out
+}
+
+impl Connectivity2 {
+    fn offsets(&self) -> Vec<[isize; 3]> {
+        offsets(2, if *self == Connectivity2::Four { 1 } else { 2 })
+    }
+}
+
+impl Connectivity3 {
+    fn offsets(&self) -> Vec<[isize; 3]> {
+        let max_axes = match self {
+            Connectivity3::Six => 1,
+            Connectivity3::Eighteen => 2,
+            Connectivity3::TwentySix => 3
+        };
+        offsets(3, max_axes)
+    }
+}
+
+// Cell count and inclusive bounding box of a region.
+type Extent = (usize, [usize; 3], [usize; 3]);
+
+// Breadth-first search over a dense x-fastest grid of `dims`, visiting the
+// included cells connected to `start` and marking them with `label`.
+fn fill(dims: [usize; 3], start: usize, include: &impl Fn(usize) -> bool, offsets: &[[isize; 3]], labels: &mut [u32], label: u32) -> Extent {
+    let coords = |index: usize| [index % dims[0], (index / dims[0]) % dims[1], index / (dims[0] * dims[1])];
+    let mut queue = VecDeque::from([start]);
+    labels[start] = label;
+    let (mut count, mut min, mut max) = (0, coords(start), coords(start));
+    while let Some(index) = queue.pop_front() {
+        let c = coords(index);
+        count += 1;
+        for axis in 0..3 {
+            min[axis] = min[axis].min(c[axis]);
+            max[axis] = max[axis].max(c[axis]);
+        }
+        for offset in offsets {
+            let mut n = [0; 3];
+            let mut inside = true;
+            for axis in 0..3 {
+                let moved = c[axis] as isize + offset[axis];
+                inside &= moved >= 0 && (moved as usize) < dims[axis];
+                n[axis] = moved as usize;
+            }
+            if !inside {
+                continue;
+            }
+            let neighbour = (n[2] * dims[1] + n[1]) * dims[0] + n[0];
+            if labels[neighbour] == 0 && include(neighbour) {
+                labels[neighbour] = label;
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    (count, min, max)
+}
+
+// Labels every connected region of included cells, numbering them from 1
+// in scan order; excluded cells get 0.
+fn label(dims: [usize; 3], include: impl Fn(usize) -> bool, offsets: &[[isize; 3]]) -> (Vec<u32>, Vec<Extent>) {
+    let mut labels = vec![0; dims[0] * dims[1] * dims[2]];
+    let mut regions = Vec::new();
+    for index in 0..labels.len() {
+        if labels[index] == 0 && include(index) {
+            let next = regions.len() as u32 + 1;
+            regions.push(fill(dims, index, &include, offsets, &mut labels, next));
+        }
+    }
+    (labels, regions)
+}
+
+// Cells connected to `start` for which `include` holds. Empty if `start`
+// itself is excluded or out of bounds.
+#[allow(dead_code)]
+pub fn flood_fill_2d<T>(grid: &Grid2<T>, start: (usize, usize), connectivity: Connectivity2, include: impl Fn(&T) -> bool) -> Grid2<bool> {
+    let mut labels = vec![0; grid.len()];
+    if grid.get(start.0, start.1).is_some_and(&include) {
+        let dims = [grid.width(), grid.height(), 1];
+        fill(dims, grid.index_of(start.0, start.1), &|i| include(&grid.cells[i]), &connectivity.offsets(), &mut labels, 1);
+    }
+    Grid2::from_fn(grid.width(), grid.height(), |x, y| labels[grid.index_of(x, y)] != 0)
+}
+
+#[allow(dead_code)]
+pub fn flood_fill_3d<T>(grid: &Grid3<T>, start: (usize, usize, usize), connectivity: Connectivity3, include: impl Fn(&T) -> bool) -> Grid3<bool> {
+    let mut labels = vec![0; grid.len()];
+    if grid.get(start.0, start.1, start.2).is_some_and(&include) {
+        let dims = [grid.width(), grid.height(), grid.depth()];
+        fill(dims, grid.index_of(start.0, start.1, start.2), &|i| include(&grid.cells[i]), &connectivity.offsets(), &mut labels, 1);
+    }
+    Grid3::from_fn(grid.width(), grid.height(), grid.depth(), |x, y, z| labels[grid.index_of(x, y, z)] != 0)
+}
+
+// Labels connected regions of cells for which `include` holds. Region `i`
+// carries label `i + 1`; excluded cells are labeled 0.
+#[allow(dead_code)]
+pub fn connected_components_2d<T>(grid: &Grid2<T>, connectivity: Connectivity2, include: impl Fn(&T) -> bool) -> (Grid2<u32>, Vec<Region2>) {
+    let dims = [grid.width(), grid.height(), 1];
+    let (labels, regions) = label(dims, |i| include(&grid.cells[i]), &connectivity.offsets());
+    let regions = regions.iter().enumerate().map(|(i, &(cell_count, min, max))| Region2 {
+        label: i as u32 + 1,
+        cell_count,
+        min: (min[0], min[1]),
+        max: (max[0], max[1])
+    }).collect();
+    (Grid2::from_fn(grid.width(), grid.height(), |x, y| labels[grid.index_of(x, y)]), regions)
+}
+
+#[allow(dead_code)]
+pub fn connected_components_3d<T>(grid: &Grid3<T>, connectivity: Connectivity3, include: impl Fn(&T) -> bool) -> (Grid3<u32>, Vec<Region3>) {
+    let dims = [grid.width(), grid.height(), grid.depth()];
+    let (labels, regions) = label(dims, |i| include(&grid.cells[i]), &connectivity.offsets());
+    let regions = regions.iter().enumerate().map(|(i, &(cell_count, min, max))| Region3 {
+        label: i as u32 + 1,
+        cell_count,
+        min: (min[0], min[1], min[2]),
+        max: (max[0], max[1], max[2])
+    }).collect();
+    (Grid3::from_fn(grid.width(), grid.height(), grid.depth(), |x, y, z| labels[grid.index_of(x, y, z)]), regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagonal_cells_join_only_with_eight_connectivity() {
+        let mask = [
+            "##..#",
+            "..#.#",
+            "....."
+        ];
+        let grid = Grid2::from_fn(5, 3, |x, y| mask[y].as_bytes()[x] == b'#');
+        let (labels, regions) = connected_components_2d(&grid, Connectivity2::Four, |&c| c);
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[0], Region2 { label: 1, cell_count: 2, min: (0, 0), max: (1, 0) });
+        assert_eq!(labels[(2, 1)], 3);
+        assert_eq!(labels[(3, 2)], 0);
+
+        let (_, regions) = connected_components_2d(&grid, Connectivity2::Eight, |&c| c);
+        assert_eq!(regions.len(), 2);
+        assert_eq!((regions[0].cell_count, regions[0].max), (3, (2, 1)));
+
+        let filled = flood_fill_2d(&grid, (0, 2), Connectivity2::Four, |&c| !c);
+        assert_eq!(filled.as_slice().iter().filter(|&&c| c).count(), 10);
+        assert!(!flood_fill_2d(&grid, (0, 0), Connectivity2::Four, |&c| !c)[(0, 2)]);
+    }
+
+    #[test]
+    fn corner_touching_voxels() {
+        let grid = Grid3::from_fn(3, 3, 3, |x, y, z| (x, y, z) == (0, 0, 0) || (x, y, z) == (1, 1, 0) || (x, y, z) == (2, 2, 2) || (x, y, z) == (1, 1, 1));
+        let count = |connectivity| connected_components_3d(&grid, connectivity, |&c| c).1.len();
+        assert_eq!(count(Connectivity3::Six), 3);
+        assert_eq!(count(Connectivity3::Eighteen), 2);
+        assert_eq!(count(Connectivity3::TwentySix), 1);
+
+        let (_, regions) = connected_components_3d(&grid, Connectivity3::TwentySix, |&c| c);
+        assert_eq!(regions[0], Region3 { label: 1, cell_count: 4, min: (0, 0, 0), max: (2, 2, 2) });
+        assert_eq!(flood_fill_3d(&grid, (1, 1, 1), Connectivity3::Eighteen, |&c| c).as_slice().iter().filter(|&&c| c).count(), 3);
+    }
+}
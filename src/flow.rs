@@ -0,0 +1,215 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::flood::Connectivity2;
+use crate::grid2::Grid2;
+use crate::instrument::trace_span;
+
+const EDGE_NEIGHBOURS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const CORNER_NEIGHBOURS: [(isize, isize); 4] = [(1, 1), (-1, 1), (1, -1), (-1, -1)];
+
+// Tentative distance of a cell, ordered so the heap pops the nearest first.
+#[derive(PartialEq)]
+struct Frontier(f32, usize);
+
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then(other.1.cmp(&self.1))
+    }
+}
+
+// Flow field over a cost grid: the travel distance from every cell to the
+// nearest goal, and a unit direction per cell pointing downhill towards it,
+// so any number of agents can steer by looking up the cell they stand in.
+// Directions are (x, y) in cell units; goals and cells that cannot reach a
+// goal get (0, 0).
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct FlowField {
+    distances: Grid2<f32>,
+    directions: Grid2<(f32, f32)>
+}
+
+#[allow(dead_code)]
+impl FlowField {
+    // Dijkstra from all `goals` at once. `costs` is the cost of entering
+    // each cell per unit of distance, so diagonal steps cost sqrt(2) times
+    // as much; a negative or non-finite cost marks a wall. With
+    // `Connectivity2::Eight`, diagonal steps never cut past a wall's corner.
+    pub fn new(costs: &Grid2<f32>, goals: &[(usize, usize)], connectivity: Connectivity2) -> Self {
+        let (width, height) = (costs.width(), costs.height());
+        trace_span!("flow_field", width = width, height = height, goals = goals.len());
+        let passable = |x: usize, y: usize| costs[(x, y)].is_finite() && costs[(x, y)] >= 0.0;
+        let mut distances = Grid2::new(width, height, f32::INFINITY);
+        let mut heap = BinaryHeap::new();
+        for &(x, y) in goals {
+            if costs.in_bounds(x, y) && passable(x, y) {
+                distances[(x, y)] = 0.0;
+                heap.push(Frontier(0.0, costs.index_of(x, y)));
+            }
+        }
+        while let Some(Frontier(distance, index)) = heap.pop() {
+            if distance > distances.cells[index] {
+                continue;
+            }
+            let (x, y) = costs.coords_of(index);
+            for (nx, ny, step) in Self::neighbours(costs, x, y, connectivity, &passable) {
+                let candidate = distance + costs[(nx, ny)] * step;
+                if candidate < distances[(nx, ny)] {
+                    distances[(nx, ny)] = candidate;
+                    heap.push(Frontier(candidate, costs.index_of(nx, ny)));
+                }
+            }
+        }
+
+        let directions = Grid2::from_fn(width, height, |x, y| {
+            let here = distances[(x, y)];
+            if !here.is_finite() || here == 0.0 {
+                return (0.0, 0.0);
+            }
+            let mut best = (here, (0.0, 0.0));
+            for (nx, ny, step) in Self::neighbours(costs, x, y, connectivity, &passable) {
+                if distances[(nx, ny)] < best.0 {
+                    let d = (nx as f32 - x as f32, ny as f32 - y as f32);
+                    best = (distances[(nx, ny)], (d.0 / step, d.1 / step));
+                }
+            }
+            best.1
+        });
+        Self {
+            distances,
+            directions
+        }
+    }
+
+    // Passable neighbours of a cell with the length of the step to each.
+    fn neighbours<'a>(costs: &'a Grid2<f32>, x: usize, y: usize, connectivity: Connectivity2, passable: &'a impl Fn(usize, usize) -> bool) -> impl Iterator<Item = (usize, usize, f32)> + 'a {
+        let offset = move |(dx, dy): (isize, isize)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            (nx >= 0 && ny >= 0 && costs.in_bounds(nx as usize, ny as usize)).then_some((nx as usize, ny as usize))
+        };
+        let edges = EDGE_NEIGHBOURS.into_iter()
+            .filter_map(offset)
+            .filter(|&(nx, ny)| passable(nx, ny))
+            .map(|(nx, ny)| (nx, ny, 1.0));
+        let corners = CORNER_NEIGHBOURS.into_iter()
+            .filter(move |_| connectivity == Connectivity2::Eight)
+            .filter_map(move |(dx, dy)| {
+                let (nx, ny) = offset((dx, dy))?;
+                let clear = passable(nx, ny) && passable(nx, y) && passable(x, ny);
+                clear.then_some((nx, ny, std::f32::consts::SQRT_2))
+            });
+        edges.chain(corners)
+    }
+
+    pub fn width(&self) -> usize {
+        self.distances.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.distances.height()
+    }
+
+    // Travel cost from the cell to the nearest goal; infinite when no goal
+    // can be reached.
+    pub fn distance(&self, x: usize, y: usize) -> f32 {
+        self.distances[(x, y)]
+    }
+
+    pub fn direction(&self, x: usize, y: usize) -> (f32, f32) {
+        self.directions[(x, y)]
+    }
+
+    pub fn distances(&self) -> &Grid2<f32> {
+        &self.distances
+    }
+
+    pub fn directions(&self) -> &Grid2<(f32, f32)> {
+        &self.directions
+    }
+
+    pub fn is_reachable(&self, x: usize, y: usize) -> bool {
+        self.distances[(x, y)].is_finite()
+    }
+
+    // Blends each reachable cell's direction with those of its reachable
+    // edge neighbours and renormalizes, `iterations` times. Removes the
+    // 45 degree staircase of the raw field so crowds take straighter,
+    // smoother paths; goals and unreachable cells stay (0, 0).
+    pub fn smooth(&mut self, iterations: usize) {
+        for _ in 0..iterations {
+            let previous = self.directions.clone();
+            for index in 0..previous.len() {
+                let (x, y) = previous.coords_of(index);
+                if previous.cells[index] == (0.0, 0.0) {
+                    continue;
+                }
+                let mut sum = previous.cells[index];
+                for (dx, dy) in EDGE_NEIGHBOURS {
+                    let (nx, ny) = (x as isize + dx, y as isize + dy);
+                    if nx >= 0 && ny >= 0 && previous.in_bounds(nx as usize, ny as usize) {
+                        let d = previous[(nx as usize, ny as usize)];
+                        sum = (sum.0 + d.0, sum.1 + d.1);
+                    }
+                }
+                let length = (sum.0 * sum.0 + sum.1 * sum.1).sqrt();
+                if length > 0.0 {
+                    self.directions.cells[index] = (sum.0 / length, sum.1 / length);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_around_wall() {
+        // Open 10x10 field with a wall along x = 5 for y < 8.
+        let costs = Grid2::from_fn(10, 10, |x, y| if x == 5 && y < 8 { f32::INFINITY } else { 1.0 });
+        let field = FlowField::new(&costs, &[(9, 0)], Connectivity2::Four);
+        assert_eq!(field.distance(9, 0), 0.0);
+        assert_eq!(field.direction(9, 0), (0.0, 0.0));
+        assert!(!field.is_reachable(5, 0));
+        // Down the left side to y = 8, across and back up.
+        assert_eq!(field.distance(4, 0), 8.0 + 2.0 + 8.0 + 3.0);
+        assert_eq!(field.direction(4, 0), (0.0, 1.0));
+        assert_eq!(field.direction(6, 0), (1.0, 0.0));
+
+        // Stepping to the neighbour each raw direction names reaches the
+        // goal by the shortest path.
+        let eight = FlowField::new(&costs, &[(9, 0)], Connectivity2::Eight);
+        let (mut x, mut y, mut steps) = (0, 0, 0);
+        while eight.distance(x, y) > 0.0 {
+            let d = eight.direction(x, y);
+            x = (x as f32 + d.0 * std::f32::consts::SQRT_2).round() as usize;
+            y = (y as f32 + d.1 * std::f32::consts::SQRT_2).round() as usize;
+            assert!(eight.is_reachable(x, y));
+            steps += 1;
+        }
+        assert_eq!((x, y), (9, 0));
+        assert!(steps <= 18, "{}", steps);
+
+        // Smoothing keeps directions unit length and still downhill.
+        let mut smooth = eight.clone();
+        smooth.smooth(3);
+        for index in 0..costs.len() {
+            let (x, y) = costs.coords_of(index);
+            let (raw, blended) = (eight.direction(x, y), smooth.direction(x, y));
+            if raw != (0.0, 0.0) {
+                assert!(((blended.0 * blended.0 + blended.1 * blended.1) - 1.0).abs() < 1e-5);
+                assert!(raw.0 * blended.0 + raw.1 * blended.1 > 0.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// Shapes, bounding volumes, queries and mesh generation.
+pub use crate::aabb::Aabb;
+pub use crate::bounding::{convex_hull_2d, minimum_area_rect, minimum_enclosing_sphere, minimum_volume_obb, OrientedRect};
+pub use crate::capsule::Capsule;
+pub use crate::closest_point::ClosestPoint;
+pub use crate::mesh::Mesh;
+pub use crate::obb::Obb;
+pub use crate::plane::Plane;
+pub use crate::polyhedron::ConvexPolyhedron;
+pub use crate::predicates::{incircle, orient2d, orient3d};
+pub use crate::rect::Rect;
+pub use crate::segment::Segment;
+pub use crate::shape::Shape;
+pub use crate::simplify::{simplify_rdp, simplify_visvalingam};
+pub use crate::sphere::Sphere;
+pub use crate::triangle::Triangle;
+pub use crate::triangulate::triangulate_polygon;
+pub use crate::visibility::{visibility_polygon, Occluder};
+#[cfg(feature = "spatial")]
+pub use crate::voxel::{signed_distance_field, voxelize, VoxelLayout};
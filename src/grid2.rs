@@ -0,0 +1,104 @@
+// Dense 2D grid of cells stored row by row, x fastest.
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid2<T> {
+    width: usize,
+    height: usize,
+    pub(crate) cells: Vec<T>
+}
+
+#[allow(dead_code)]
+impl<T: Clone> Grid2<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height]
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid2<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+        Self {
+            width,
+            height,
+            cells
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn index_of(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    // Inverse of `index_of`.
+    pub fn coords_of(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.in_bounds(x, y) { Some(&self.cells[self.index_of(x, y)]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.in_bounds(x, y) {
+            let index = self.index_of(x, y);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid2<U> {
+        Grid2 {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect()
+        }
+    }
+}
+
+impl<T> std::ops::Index<(usize, usize)> for Grid2<T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        self.get(index.0, index.1).expect("Index out of bounds")
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Grid2<T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(index.0, index.1).expect("Index out of bounds")
+    }
+}
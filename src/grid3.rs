@@ -0,0 +1,114 @@
+// Dense 3D grid of cells stored x-fastest, then y, then z.
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid3<T> {
+    width: usize,
+    height: usize,
+    depth: usize,
+    pub(crate) cells: Vec<T>
+}
+
+#[allow(dead_code)]
+impl<T: Clone> Grid3<T> {
+    pub fn new(width: usize, height: usize, depth: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            depth,
+            cells: vec![fill; width * height * depth]
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid3<T> {
+    pub fn from_fn(width: usize, height: usize, depth: usize, mut f: impl FnMut(usize, usize, usize) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height * depth);
+        for z in 0..depth {
+            for y in 0..height {
+                for x in 0..width {
+                    cells.push(f(x, y, z));
+                }
+            }
+        }
+        Self {
+            width,
+            height,
+            depth,
+            cells
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn in_bounds(&self, x: usize, y: usize, z: usize) -> bool {
+        x < self.width && y < self.height && z < self.depth
+    }
+
+    pub fn index_of(&self, x: usize, y: usize, z: usize) -> usize {
+        (z * self.height + y) * self.width + x
+    }
+
+    // Inverse of `index_of`.
+    pub fn coords_of(&self, index: usize) -> (usize, usize, usize) {
+        (index % self.width, (index / self.width) % self.height, index / (self.width * self.height))
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<&T> {
+        if self.in_bounds(x, y, z) { Some(&self.cells[self.index_of(x, y, z)]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize, z: usize) -> Option<&mut T> {
+        if self.in_bounds(x, y, z) {
+            let index = self.index_of(x, y, z);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid3<U> {
+        Grid3 {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            cells: self.cells.iter().map(f).collect()
+        }
+    }
+}
+
+impl<T> std::ops::Index<(usize, usize, usize)> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize, usize)) -> &Self::Output {
+        self.get(index.0, index.1, index.2).expect("Index out of bounds")
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize, usize)> for Grid3<T> {
+    fn index_mut(&mut self, index: (usize, usize, usize)) -> &mut Self::Output {
+        self.get_mut(index.0, index.1, index.2).expect("Index out of bounds")
+    }
+}
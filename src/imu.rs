@@ -0,0 +1,262 @@
+use crate::mat4::Mat4;
+use crate::transform::quat_to_rotation;
+use crate::vec4::Vec4;
+
+// Orientation filters fusing gyroscope, accelerometer and optionally
+// magnetometer samples. The orientation is a unit quaternion (x, y, z, w)
+// rotating sensor-frame vectors into the earth frame, whose z axis points
+// up and x axis towards magnetic north. Gyro rates are in rad/s; the
+// accelerometer and magnetometer only need the right direction.
+
+fn direction(v: &Vec4) -> Vec4 {
+    Vec4::new(v.x, v.y, v.z, 0.0)
+}
+
+// Unit vector along `v`, or None for a zero (missing) reading.
+fn unit(v: &Vec4) -> Option<Vec4> {
+    let v = direction(v);
+    let length = v.length();
+    if length > 0.0 { Some(v / length) } else { None }
+}
+
+// Rate of change of `q` rotating at body rate `gyro`: q * (gyro, 0) / 2.
+fn quat_derivative(q: &Vec4, gyro: &Vec4) -> Vec4 {
+    Vec4::new(
+        q.w * gyro.x + q.y * gyro.z - q.z * gyro.y,
+        q.w * gyro.y - q.x * gyro.z + q.z * gyro.x,
+        q.w * gyro.z + q.x * gyro.y - q.y * gyro.x,
+        -q.x * gyro.x - q.y * gyro.y - q.z * gyro.z
+    ) * 0.5
+}
+
+// Earth-frame reference for the magnetic field: the measured field rotated
+// into the earth frame, flattened onto the x-z plane so that only heading
+// is corrected by it and the local dip angle is learned from the data.
+fn magnetic_reference(rotation: &Mat4, mag: &Vec4) -> Vec4 {
+    let h = *rotation * *mag;
+    Vec4::new((h.x * h.x + h.y * h.y).sqrt(), 0.0, h.z, 0.0)
+}
+
+// Madgwick's gradient descent filter. `beta` is the rate in rad/s at which
+// gyro drift is corrected: higher converges faster but lets more
+// accelerometer noise and linear acceleration through.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Madgwick {
+    pub beta: f32,
+    orientation: Vec4
+}
+
+#[allow(dead_code)]
+impl Madgwick {
+    pub fn new(beta: f32) -> Self {
+        Self {
+            beta,
+            orientation: Vec4::new(0.0, 0.0, 0.0, 1.0)
+        }
+    }
+
+    pub fn orientation(&self) -> Vec4 {
+        self.orientation
+    }
+
+    pub fn set_orientation(&mut self, q: &Vec4) {
+        self.orientation = q.normalize();
+    }
+
+    pub fn rotation(&self) -> Mat4 {
+        quat_to_rotation(&self.orientation)
+    }
+
+    // Gyro and accelerometer only; heading drifts freely.
+    pub fn update_imu(&mut self, gyro: &Vec4, accel: &Vec4, dt: f32) {
+        self.update(gyro, accel, &Vec4::zero(), dt);
+    }
+
+    // A zero accelerometer or magnetometer reading skips that correction.
+    pub fn update(&mut self, gyro: &Vec4, accel: &Vec4, mag: &Vec4, dt: f32) {
+        let q = self.orientation;
+        let mut rate = quat_derivative(&q, &direction(gyro));
+        if let Some(a) = unit(accel) {
+            let (q0, q1, q2, q3) = (q.w, q.x, q.y, q.z);
+            // Objective: predicted gravity in the sensor frame minus the
+            // measurement, and its Jacobian with respect to (w, x, y, z).
+            let f = [
+                2.0 * (q1 * q3 - q0 * q2) - a.x,
+                2.0 * (q0 * q1 + q2 * q3) - a.y,
+                2.0 * (0.5 - q1 * q1 - q2 * q2) - a.z
+            ];
+            let j = [
+                [-2.0 * q2, 2.0 * q3, -2.0 * q0, 2.0 * q1],
+                [2.0 * q1, 2.0 * q0, 2.0 * q3, 2.0 * q2],
+                [0.0, -4.0 * q1, -4.0 * q2, 0.0]
+            ];
+            let mut gradient = [0.0; 4];
+            for (row, f_row) in f.iter().enumerate() {
+                for k in 0..4 {
+                    gradient[k] += j[row][k] * f_row;
+                }
+            }
+
+            if let Some(m) = unit(mag) {
+                let b = magnetic_reference(&quat_to_rotation(&q), &m);
+                let (bx, bz) = (b.x, b.z);
+                let f = [
+                    2.0 * bx * (0.5 - q2 * q2 - q3 * q3) + 2.0 * bz * (q1 * q3 - q0 * q2) - m.x,
+                    2.0 * bx * (q1 * q2 - q0 * q3) + 2.0 * bz * (q0 * q1 + q2 * q3) - m.y,
+                    2.0 * bx * (q0 * q2 + q1 * q3) + 2.0 * bz * (0.5 - q1 * q1 - q2 * q2) - m.z
+                ];
+                let j = [
+                    [-2.0 * bz * q2, 2.0 * bz * q3, -4.0 * bx * q2 - 2.0 * bz * q0, -4.0 * bx * q3 + 2.0 * bz * q1],
+                    [-2.0 * bx * q3 + 2.0 * bz * q1, 2.0 * bx * q2 + 2.0 * bz * q0, 2.0 * bx * q1 + 2.0 * bz * q3, -2.0 * bx * q0 + 2.0 * bz * q2],
+                    [2.0 * bx * q2, 2.0 * bx * q3 - 4.0 * bz * q1, 2.0 * bx * q0 - 4.0 * bz * q2, 2.0 * bx * q1]
+                ];
+                for (row, f_row) in f.iter().enumerate() {
+                    for k in 0..4 {
+                        gradient[k] += j[row][k] * f_row;
+                    }
+                }
+            }
+
+            let step = Vec4::new(gradient[1], gradient[2], gradient[3], gradient[0]);
+            let length = step.length();
+            if length > 0.0 {
+                rate -= step * (self.beta / length);
+            }
+        }
+        self.orientation = (q + rate * dt).normalize();
+    }
+}
+
+// Mahony's complementary filter: a PI controller on the angle between the
+// measured and predicted reference directions, fed back into the gyro
+// rate. `kp` sets how fast the estimate follows the references and `ki`
+// how fast gyro bias is learned; zero `ki` disables bias estimation.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Mahony {
+    pub kp: f32,
+    pub ki: f32,
+    orientation: Vec4,
+    bias: Vec4
+}
+
+#[allow(dead_code)]
+impl Mahony {
+    pub fn new(kp: f32, ki: f32) -> Self {
+        Self {
+            kp,
+            ki,
+            orientation: Vec4::new(0.0, 0.0, 0.0, 1.0),
+            bias: Vec4::zero()
+        }
+    }
+
+    pub fn orientation(&self) -> Vec4 {
+        self.orientation
+    }
+
+    pub fn set_orientation(&mut self, q: &Vec4) {
+        self.orientation = q.normalize();
+    }
+
+    pub fn rotation(&self) -> Mat4 {
+        quat_to_rotation(&self.orientation)
+    }
+
+    // Correction currently added to the gyro rate by the integral term,
+    // the negated estimate of the gyro bias.
+    pub fn bias_correction(&self) -> Vec4 {
+        self.bias
+    }
+
+    // Gyro and accelerometer only; heading drifts freely.
+    pub fn update_imu(&mut self, gyro: &Vec4, accel: &Vec4, dt: f32) {
+        self.update(gyro, accel, &Vec4::zero(), dt);
+    }
+
+    // A zero accelerometer or magnetometer reading skips that correction.
+    pub fn update(&mut self, gyro: &Vec4, accel: &Vec4, mag: &Vec4, dt: f32) {
+        let mut gyro = direction(gyro);
+        if let Some(a) = unit(accel) {
+            let rotation = quat_to_rotation(&self.orientation);
+            let to_sensor = rotation.transpose();
+            let mut error = a.cross(&(to_sensor * Vec4::new(0.0, 0.0, 1.0, 0.0)));
+            if let Some(m) = unit(mag) {
+                let b = magnetic_reference(&rotation, &m);
+                error += m.cross(&(to_sensor * b));
+            }
+            if self.ki > 0.0 {
+                self.bias += error * (self.ki * dt);
+                gyro += self.bias;
+            }
+            gyro += error * self.kp;
+        }
+        let q = self.orientation;
+        self.orientation = (q + quat_derivative(&q, &gyro) * dt).normalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn axis_angle(axis: Vec4, angle: f32) -> Mat4 {
+        let (s, c) = (angle * 0.5).sin_cos();
+        let axis = axis.normalize() * s;
+        quat_to_rotation(&Vec4::new(axis.x, axis.y, axis.z, c))
+    }
+
+    fn close(a: &Mat4, b: &Mat4, tolerance: f32) -> bool {
+        (0..16).all(|i| (a.elements[i] - b.elements[i]).abs() < tolerance)
+    }
+
+    // Readings of a stationary sensor with orientation `truth`, under a
+    // field dipping 60 degrees below north.
+    fn readings(truth: &Mat4) -> (Vec4, Vec4) {
+        let to_sensor = truth.transpose();
+        let field = Vec4::new(0.5, 0.0, -(0.75f32).sqrt(), 0.0);
+        (to_sensor * Vec4::new(0.0, 0.0, 9.81, 0.0), to_sensor * (field * 48.0))
+    }
+
+    #[test]
+    fn converges_to_a_stationary_orientation() {
+        let truth = axis_angle(Vec4::new(0.3, -0.5, 0.8, 0.0), 1.2);
+        let (accel, mag) = readings(&truth);
+
+        let mut madgwick = Madgwick::new(0.5);
+        let mut mahony = Mahony::new(2.0, 0.0);
+        for _ in 0..4000 {
+            madgwick.update(&Vec4::zero(), &accel, &mag, 0.01);
+            mahony.update(&Vec4::zero(), &accel, &mag, 0.01);
+        }
+        assert!(close(&madgwick.rotation(), &truth, 1e-3));
+        assert!(close(&mahony.rotation(), &truth, 1e-3));
+
+        // Without the magnetometer only the tilt is observable.
+        let mut tilt = Madgwick::new(0.5);
+        for _ in 0..4000 {
+            tilt.update_imu(&Vec4::zero(), &accel, 0.01);
+        }
+        let up = Vec4::new(0.0, 0.0, 1.0, 0.0);
+        assert!((tilt.rotation() * accel.normalize() - up).length() < 1e-3);
+    }
+
+    #[test]
+    fn integrates_gyro_and_learns_bias() {
+        let mut filter = Madgwick::new(0.0);
+        for _ in 0..1000 {
+            filter.update_imu(&Vec4::new(0.0, 0.0, 1.0, 0.0), &Vec4::zero(), 0.001);
+        }
+        assert!(close(&filter.rotation(), &axis_angle(Vec4::new(0.0, 0.0, 1.0, 0.0), 1.0), 1e-4));
+
+        let (accel, mag) = readings(&Mat4::identity());
+        let bias = Vec4::new(0.02, -0.01, 0.03, 0.0);
+        let mut mahony = Mahony::new(1.0, 0.5);
+        for _ in 0..20000 {
+            mahony.update(&bias, &accel, &mag, 0.01);
+        }
+        assert!((mahony.bias_correction() + bias).length() < 1e-3);
+        assert!(close(&mahony.rotation(), &Mat4::identity(), 1e-3));
+    }
+}
@@ -0,0 +1,37 @@
+// Instrumentation for the `tracing` and `stats` features. Without them the
+// macros expand to nothing beyond borrowing their field values, so
+// instrumented code costs nothing and needs no cfg of its own.
+
+// Enters a debug-level span that lasts until the end of the enclosing
+// block.
+macro_rules! trace_span {
+    ($name:literal $(, $field:ident = $value:expr)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name $(, $field = $value)*).entered();
+        #[cfg(not(feature = "tracing"))]
+        { $(let _ = &$value;)* }
+    };
+}
+
+// Emits a debug-level event, typically iteration counts and residuals once
+// a solver finishes.
+macro_rules! trace_event {
+    ($message:literal $(, $field:ident = $value:expr)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($field = $value,)* $message);
+        #[cfg(not(feature = "tracing"))]
+        { $(let _ = &$value;)* }
+    };
+}
+
+// Bumps one `OpStats` counter on the current thread.
+macro_rules! count_op {
+    ($counter:ident) => {
+        #[cfg(feature = "stats")]
+        crate::stats::record(|stats| stats.$counter += 1);
+    };
+}
+
+pub(crate) use count_op;
+pub(crate) use trace_event;
+pub(crate) use trace_span;
@@ -0,0 +1,2 @@
+// Persisting math data.
+pub use crate::snapshot::{read_snapshot, SnapshotChunk, SnapshotError, SnapshotView, SnapshotWriter, SNAPSHOT_MAGIC, SNAPSHOT_MAJOR, SNAPSHOT_MINOR};
@@ -0,0 +1,356 @@
+use crate::bvec2::BVec2;
+use crate::vec4::Vec4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IVec2 {
+    pub x: i32,
+    pub y: i32
+}
+
+#[allow(dead_code)]
+impl IVec2 {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self {
+            x,
+            y
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: 0,
+            y: 0
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            x: 1,
+            y: 1
+        }
+    }
+
+    pub fn splat(value: i32) -> Self {
+        Self {
+            x: value,
+            y: value
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> i32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y)
+        }
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y)
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs()
+        }
+    }
+
+    pub fn cmpeq(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x == other.x,
+            y: self.y == other.y
+        }
+    }
+
+    pub fn cmpne(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x != other.x,
+            y: self.y != other.y
+        }
+    }
+
+    pub fn cmplt(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x < other.x,
+            y: self.y < other.y
+        }
+    }
+
+    pub fn cmple(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x <= other.x,
+            y: self.y <= other.y
+        }
+    }
+
+    pub fn cmpgt(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x > other.x,
+            y: self.y > other.y
+        }
+    }
+
+    pub fn cmpge(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x >= other.x,
+            y: self.y >= other.y
+        }
+    }
+
+    // Picks each lane from `a` where `mask` is set and from `b` otherwise.
+    pub fn select(mask: &BVec2, a: &Self, b: &Self) -> Self {
+        Self {
+            x: if mask.x { a.x } else { b.x },
+            y: if mask.y { a.y } else { b.y }
+        }
+    }
+
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_add(other.x),
+            y: self.y.wrapping_add(other.y)
+        }
+    }
+
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_sub(other.x),
+            y: self.y.wrapping_sub(other.y)
+        }
+    }
+
+    pub fn wrapping_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_mul(other.x),
+            y: self.y.wrapping_mul(other.y)
+        }
+    }
+
+    pub fn saturating_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y)
+        }
+    }
+
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y)
+        }
+    }
+
+    pub fn saturating_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_mul(other.x),
+            y: self.y.saturating_mul(other.y)
+        }
+    }
+
+    pub fn as_vec4(&self) -> Vec4 {
+        Vec4::new(self.x as f32, self.y as f32, 0.0, 0.0)
+    }
+
+    // Truncates toward zero, saturating at the integer range.
+    pub fn from_vec4(v: &Vec4) -> Self {
+        Self {
+            x: v.x as i32,
+            y: v.y as i32
+        }
+    }
+}
+
+impl std::ops::Add<IVec2> for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, rhs: IVec2) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y
+        }
+    }
+}
+
+impl std::ops::AddAssign<IVec2> for IVec2 {
+    fn add_assign(&mut self, rhs: IVec2) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl std::ops::Sub<IVec2> for IVec2 {
+    type Output = IVec2;
+
+    fn sub(self, rhs: IVec2) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y
+        }
+    }
+}
+
+impl std::ops::SubAssign<IVec2> for IVec2 {
+    fn sub_assign(&mut self, rhs: IVec2) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl std::ops::Mul<i32> for IVec2 {
+    type Output = IVec2;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs
+        }
+    }
+}
+
+impl std::ops::MulAssign<i32> for IVec2 {
+    fn mul_assign(&mut self, rhs: i32) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl std::ops::Div<i32> for IVec2 {
+    type Output = IVec2;
+
+    fn div(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs
+        }
+    }
+}
+
+impl std::ops::DivAssign<i32> for IVec2 {
+    fn div_assign(&mut self, rhs: i32) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
+impl std::ops::Rem<i32> for IVec2 {
+    type Output = IVec2;
+
+    fn rem(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x % rhs,
+            y: self.y % rhs
+        }
+    }
+}
+
+impl std::ops::RemAssign<i32> for IVec2 {
+    fn rem_assign(&mut self, rhs: i32) {
+        self.x %= rhs;
+        self.y %= rhs;
+    }
+}
+
+impl std::ops::Neg for IVec2 {
+    type Output = IVec2;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y
+        }
+    }
+}
+
+impl std::ops::Index<usize> for IVec2 {
+    type Output = i32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for IVec2 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for IVec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl std::fmt::Debug for IVec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "IVec2({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = IVec2::new(7, -3);
+        let b = IVec2::new(2, 5);
+        assert_eq!(a + b, IVec2::new(9, 2));
+        assert_eq!(a - b, IVec2::new(5, -8));
+        assert_eq!(a * 3, IVec2::new(21, -9));
+        assert_eq!(a / 2, IVec2::new(3, -1));
+        assert_eq!(a % 2, IVec2::new(1, -1));
+        assert_eq!(-a, IVec2::new(-7, 3));
+        assert_eq!(a.dot(&b), -1);
+        assert_eq!(a.min(&b), IVec2::new(2, -3));
+        assert_eq!(a.abs(), IVec2::new(7, 3));
+        let mut c = a;
+        c += b;
+        c *= 2;
+        assert_eq!(c, IVec2::new(18, 4));
+        assert_eq!(c[1], 4);
+    }
+
+    #[test]
+    fn wrapping_and_saturating() {
+        let a = IVec2::new(i32::MAX, i32::MIN);
+        let one = IVec2::one();
+        assert_eq!(a.wrapping_add(&one), IVec2::new(i32::MIN, i32::MIN + 1));
+        assert_eq!(a.wrapping_sub(&one), IVec2::new(i32::MAX - 1, i32::MAX));
+        assert_eq!(a.wrapping_mul(&IVec2::splat(2)), IVec2::new(-2, 0));
+        assert_eq!(a.saturating_add(&one), IVec2::new(i32::MAX, i32::MIN + 1));
+        assert_eq!(a.saturating_sub(&one), IVec2::new(i32::MAX - 1, i32::MIN));
+        assert_eq!(a.saturating_mul(&IVec2::splat(-2)), IVec2::new(i32::MIN, i32::MAX));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn plain_add_overflows() {
+        let _ = IVec2::new(i32::MAX, 0) + IVec2::one();
+    }
+
+    #[test]
+    fn float_conversions() {
+        assert_eq!(IVec2::new(-4, 9).as_vec4(), Vec4::new(-4.0, 9.0, 0.0, 0.0));
+        assert_eq!(IVec2::from_vec4(&Vec4::new(-2.7, 2.7, 5.0, 5.0)), IVec2::new(-2, 2));
+        assert_eq!(IVec2::from_vec4(&Vec4::new(1e20, -1e20, 0.0, 0.0)), IVec2::new(i32::MAX, i32::MIN));
+        assert_eq!(IVec2::from_vec4(&Vec4::new(f32::NAN, f32::INFINITY, 0.0, 0.0)), IVec2::new(0, i32::MAX));
+    }
+}
@@ -0,0 +1,379 @@
+use crate::bvec3::BVec3;
+use crate::vec4::Vec4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IVec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32
+}
+
+#[allow(dead_code)]
+impl IVec3 {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self {
+            x,
+            y,
+            z
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            z: 0
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            x: 1,
+            y: 1,
+            z: 1
+        }
+    }
+
+    pub fn splat(value: i32) -> Self {
+        Self {
+            x: value,
+            y: value,
+            z: value
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> i32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z)
+        }
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z)
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs()
+        }
+    }
+
+    pub fn cmpeq(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x == other.x,
+            y: self.y == other.y,
+            z: self.z == other.z
+        }
+    }
+
+    pub fn cmpne(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x != other.x,
+            y: self.y != other.y,
+            z: self.z != other.z
+        }
+    }
+
+    pub fn cmplt(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x < other.x,
+            y: self.y < other.y,
+            z: self.z < other.z
+        }
+    }
+
+    pub fn cmple(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x <= other.x,
+            y: self.y <= other.y,
+            z: self.z <= other.z
+        }
+    }
+
+    pub fn cmpgt(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x > other.x,
+            y: self.y > other.y,
+            z: self.z > other.z
+        }
+    }
+
+    pub fn cmpge(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x >= other.x,
+            y: self.y >= other.y,
+            z: self.z >= other.z
+        }
+    }
+
+    // Picks each lane from `a` where `mask` is set and from `b` otherwise.
+    pub fn select(mask: &BVec3, a: &Self, b: &Self) -> Self {
+        Self {
+            x: if mask.x { a.x } else { b.x },
+            y: if mask.y { a.y } else { b.y },
+            z: if mask.z { a.z } else { b.z }
+        }
+    }
+
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_add(other.x),
+            y: self.y.wrapping_add(other.y),
+            z: self.z.wrapping_add(other.z)
+        }
+    }
+
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_sub(other.x),
+            y: self.y.wrapping_sub(other.y),
+            z: self.z.wrapping_sub(other.z)
+        }
+    }
+
+    pub fn wrapping_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_mul(other.x),
+            y: self.y.wrapping_mul(other.y),
+            z: self.z.wrapping_mul(other.z)
+        }
+    }
+
+    pub fn saturating_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+            z: self.z.saturating_add(other.z)
+        }
+    }
+
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y),
+            z: self.z.saturating_sub(other.z)
+        }
+    }
+
+    pub fn saturating_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_mul(other.x),
+            y: self.y.saturating_mul(other.y),
+            z: self.z.saturating_mul(other.z)
+        }
+    }
+
+    pub fn as_vec4(&self) -> Vec4 {
+        Vec4::new(self.x as f32, self.y as f32, self.z as f32, 0.0)
+    }
+
+    // Truncates toward zero, saturating at the integer range.
+    pub fn from_vec4(v: &Vec4) -> Self {
+        Self {
+            x: v.x as i32,
+            y: v.y as i32,
+            z: v.z as i32
+        }
+    }
+}
+
+impl std::ops::Add<IVec3> for IVec3 {
+    type Output = IVec3;
+
+    fn add(self, rhs: IVec3) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z
+        }
+    }
+}
+
+impl std::ops::AddAssign<IVec3> for IVec3 {
+    fn add_assign(&mut self, rhs: IVec3) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl std::ops::Sub<IVec3> for IVec3 {
+    type Output = IVec3;
+
+    fn sub(self, rhs: IVec3) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z
+        }
+    }
+}
+
+impl std::ops::SubAssign<IVec3> for IVec3 {
+    fn sub_assign(&mut self, rhs: IVec3) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl std::ops::Mul<i32> for IVec3 {
+    type Output = IVec3;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs
+        }
+    }
+}
+
+impl std::ops::MulAssign<i32> for IVec3 {
+    fn mul_assign(&mut self, rhs: i32) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+
+impl std::ops::Div<i32> for IVec3 {
+    type Output = IVec3;
+
+    fn div(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs
+        }
+    }
+}
+
+impl std::ops::DivAssign<i32> for IVec3 {
+    fn div_assign(&mut self, rhs: i32) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+    }
+}
+
+impl std::ops::Rem<i32> for IVec3 {
+    type Output = IVec3;
+
+    fn rem(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x % rhs,
+            y: self.y % rhs,
+            z: self.z % rhs
+        }
+    }
+}
+
+impl std::ops::RemAssign<i32> for IVec3 {
+    fn rem_assign(&mut self, rhs: i32) {
+        self.x %= rhs;
+        self.y %= rhs;
+        self.z %= rhs;
+    }
+}
+
+impl std::ops::Neg for IVec3 {
+    type Output = IVec3;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z
+        }
+    }
+}
+
+impl std::ops::Index<usize> for IVec3 {
+    type Output = i32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for IVec3 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for IVec3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl std::fmt::Debug for IVec3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "IVec3({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = IVec3::new(7, -3, 4);
+        let b = IVec3::new(2, 5, -1);
+        assert_eq!(a + b, IVec3::new(9, 2, 3));
+        assert_eq!(a - b, IVec3::new(5, -8, 5));
+        assert_eq!(a * -2, IVec3::new(-14, 6, -8));
+        assert_eq!(a / 2, IVec3::new(3, -1, 2));
+        assert_eq!(a % 3, IVec3::new(1, 0, 1));
+        assert_eq!(a.dot(&b), -5);
+        assert_eq!(a.max(&b), IVec3::new(7, 5, 4));
+        let mut c = a;
+        c -= b;
+        c /= 5;
+        assert_eq!(c, IVec3::new(1, -1, 1));
+    }
+
+    #[test]
+    fn wrapping_and_saturating() {
+        let a = IVec3::new(i32::MAX, i32::MIN, 0);
+        let one = IVec3::one();
+        assert_eq!(a.wrapping_add(&one), IVec3::new(i32::MIN, i32::MIN + 1, 1));
+        assert_eq!(a.wrapping_sub(&one), IVec3::new(i32::MAX - 1, i32::MAX, -1));
+        assert_eq!(a.saturating_add(&one), IVec3::new(i32::MAX, i32::MIN + 1, 1));
+        assert_eq!(a.saturating_sub(&one), IVec3::new(i32::MAX - 1, i32::MIN, -1));
+        assert_eq!(a.saturating_mul(&IVec3::splat(3)), IVec3::new(i32::MAX, i32::MIN, 0));
+    }
+
+    #[test]
+    fn float_conversions() {
+        assert_eq!(IVec3::new(1, -2, 3).as_vec4(), Vec4::new(1.0, -2.0, 3.0, 0.0));
+        assert_eq!(IVec3::from_vec4(&Vec4::new(0.9, -0.9, 3e10, 1.0)), IVec3::new(0, 0, i32::MAX));
+        assert_eq!(IVec3::from_vec4(&Vec4::new(f32::NAN, f32::NEG_INFINITY, -1.5, 0.0)), IVec3::new(0, i32::MIN, -1));
+    }
+}
@@ -0,0 +1,417 @@
+use crate::bvec4::BVec4;
+use crate::vec4::Vec4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IVec4 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub w: i32
+}
+
+#[allow(dead_code)]
+impl IVec4 {
+    pub fn new(x: i32, y: i32, z: i32, w: i32) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            w
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            z: 0,
+            w: 0
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            x: 1,
+            y: 1,
+            z: 1,
+            w: 1
+        }
+    }
+
+    pub fn splat(value: i32) -> Self {
+        Self {
+            x: value,
+            y: value,
+            z: value,
+            w: value
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> i32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z),
+            w: self.w.min(other.w)
+        }
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+            w: self.w.max(other.w)
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+            w: self.w.abs()
+        }
+    }
+
+    pub fn cmpeq(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x == other.x,
+            y: self.y == other.y,
+            z: self.z == other.z,
+            w: self.w == other.w
+        }
+    }
+
+    pub fn cmpne(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x != other.x,
+            y: self.y != other.y,
+            z: self.z != other.z,
+            w: self.w != other.w
+        }
+    }
+
+    pub fn cmplt(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x < other.x,
+            y: self.y < other.y,
+            z: self.z < other.z,
+            w: self.w < other.w
+        }
+    }
+
+    pub fn cmple(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x <= other.x,
+            y: self.y <= other.y,
+            z: self.z <= other.z,
+            w: self.w <= other.w
+        }
+    }
+
+    pub fn cmpgt(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x > other.x,
+            y: self.y > other.y,
+            z: self.z > other.z,
+            w: self.w > other.w
+        }
+    }
+
+    pub fn cmpge(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x >= other.x,
+            y: self.y >= other.y,
+            z: self.z >= other.z,
+            w: self.w >= other.w
+        }
+    }
+
+    // Picks each lane from `a` where `mask` is set and from `b` otherwise.
+    pub fn select(mask: &BVec4, a: &Self, b: &Self) -> Self {
+        Self {
+            x: if mask.x { a.x } else { b.x },
+            y: if mask.y { a.y } else { b.y },
+            z: if mask.z { a.z } else { b.z },
+            w: if mask.w { a.w } else { b.w }
+        }
+    }
+
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_add(other.x),
+            y: self.y.wrapping_add(other.y),
+            z: self.z.wrapping_add(other.z),
+            w: self.w.wrapping_add(other.w)
+        }
+    }
+
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_sub(other.x),
+            y: self.y.wrapping_sub(other.y),
+            z: self.z.wrapping_sub(other.z),
+            w: self.w.wrapping_sub(other.w)
+        }
+    }
+
+    pub fn wrapping_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_mul(other.x),
+            y: self.y.wrapping_mul(other.y),
+            z: self.z.wrapping_mul(other.z),
+            w: self.w.wrapping_mul(other.w)
+        }
+    }
+
+    pub fn saturating_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+            z: self.z.saturating_add(other.z),
+            w: self.w.saturating_add(other.w)
+        }
+    }
+
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y),
+            z: self.z.saturating_sub(other.z),
+            w: self.w.saturating_sub(other.w)
+        }
+    }
+
+    pub fn saturating_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_mul(other.x),
+            y: self.y.saturating_mul(other.y),
+            z: self.z.saturating_mul(other.z),
+            w: self.w.saturating_mul(other.w)
+        }
+    }
+
+    pub fn as_vec4(&self) -> Vec4 {
+        Vec4::new(self.x as f32, self.y as f32, self.z as f32, self.w as f32)
+    }
+
+    // Truncates toward zero, saturating at the integer range.
+    pub fn from_vec4(v: &Vec4) -> Self {
+        Self {
+            x: v.x as i32,
+            y: v.y as i32,
+            z: v.z as i32,
+            w: v.w as i32
+        }
+    }
+}
+
+impl std::ops::Add<IVec4> for IVec4 {
+    type Output = IVec4;
+
+    fn add(self, rhs: IVec4) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+            w: self.w + rhs.w
+        }
+    }
+}
+
+impl std::ops::AddAssign<IVec4> for IVec4 {
+    fn add_assign(&mut self, rhs: IVec4) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+        self.w += rhs.w;
+    }
+}
+
+impl std::ops::Sub<IVec4> for IVec4 {
+    type Output = IVec4;
+
+    fn sub(self, rhs: IVec4) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+            w: self.w - rhs.w
+        }
+    }
+}
+
+impl std::ops::SubAssign<IVec4> for IVec4 {
+    fn sub_assign(&mut self, rhs: IVec4) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+        self.w -= rhs.w;
+    }
+}
+
+impl std::ops::Mul<i32> for IVec4 {
+    type Output = IVec4;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+            w: self.w * rhs
+        }
+    }
+}
+
+impl std::ops::MulAssign<i32> for IVec4 {
+    fn mul_assign(&mut self, rhs: i32) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+        self.w *= rhs;
+    }
+}
+
+impl std::ops::Div<i32> for IVec4 {
+    type Output = IVec4;
+
+    fn div(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+            w: self.w / rhs
+        }
+    }
+}
+
+impl std::ops::DivAssign<i32> for IVec4 {
+    fn div_assign(&mut self, rhs: i32) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+        self.w /= rhs;
+    }
+}
+
+impl std::ops::Rem<i32> for IVec4 {
+    type Output = IVec4;
+
+    fn rem(self, rhs: i32) -> Self::Output {
+        Self {
+            x: self.x % rhs,
+            y: self.y % rhs,
+            z: self.z % rhs,
+            w: self.w % rhs
+        }
+    }
+}
+
+impl std::ops::RemAssign<i32> for IVec4 {
+    fn rem_assign(&mut self, rhs: i32) {
+        self.x %= rhs;
+        self.y %= rhs;
+        self.z %= rhs;
+        self.w %= rhs;
+    }
+}
+
+impl std::ops::Neg for IVec4 {
+    type Output = IVec4;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w
+        }
+    }
+}
+
+impl std::ops::Index<usize> for IVec4 {
+    type Output = i32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for IVec4 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for IVec4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+impl std::fmt::Debug for IVec4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "IVec4({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = IVec4::new(7, -3, 4, 0);
+        let b = IVec4::new(2, 5, -1, 6);
+        assert_eq!(a + b, IVec4::new(9, 2, 3, 6));
+        assert_eq!(a - b, IVec4::new(5, -8, 5, -6));
+        assert_eq!(b * 2, IVec4::new(4, 10, -2, 12));
+        assert_eq!(b / 2, IVec4::new(1, 2, 0, 3));
+        assert_eq!(b % 4, IVec4::new(2, 1, -1, 2));
+        assert_eq!(a.dot(&b), -5);
+        assert_eq!(IVec4::select(&a.cmpgt(&b), &a, &b), a.max(&b));
+        let mut c = b;
+        c %= 3;
+        assert_eq!(c, IVec4::new(2, 2, -1, 0));
+        c[3] = 9;
+        assert_eq!(c.w, 9);
+    }
+
+    #[test]
+    fn wrapping_and_saturating() {
+        let a = IVec4::new(i32::MAX, i32::MIN, -1, 1);
+        let one = IVec4::one();
+        assert_eq!(a.wrapping_add(&one), IVec4::new(i32::MIN, i32::MIN + 1, 0, 2));
+        assert_eq!(a.wrapping_mul(&IVec4::splat(-1)), IVec4::new(-i32::MAX, i32::MIN, 1, -1));
+        assert_eq!(a.saturating_add(&one), IVec4::new(i32::MAX, i32::MIN + 1, 0, 2));
+        assert_eq!(a.saturating_mul(&IVec4::splat(-1)), IVec4::new(-i32::MAX, i32::MAX, 1, -1));
+    }
+
+    #[test]
+    fn float_conversions() {
+        let v = IVec4::new(1, -2, 3, 16_777_217);
+        // Integers past 2^24 do not fit in f32 exactly.
+        assert_eq!(v.as_vec4(), Vec4::new(1.0, -2.0, 3.0, 16_777_216.0));
+        assert_eq!(IVec4::from_vec4(&Vec4::new(-0.5, 2.99, -3e9, f32::NAN)), IVec4::new(0, 2, i32::MIN, 0));
+        let round_trip = IVec4::new(-100, 0, 42, 1 << 20);
+        assert_eq!(IVec4::from_vec4(&round_trip.as_vec4()), round_trip);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::transform::{quat_to_rotation, rotation_to_quat, slerp, Transform};
+use crate::vec4::Vec4;
+
+// How the decompressor fills in between keys. `Cubic` stores a time
+// derivative per channel and evaluates Hermite splines, which usually needs
+// far fewer keys for smooth motion.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum KeyInterpolation {
+    #[default]
+    Linear,
+    Cubic
+}
+
+// Largest error the compressor may introduce at any input sample: distance
+// for translation, angle in radians for rotation and per-axis difference
+// for scale.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyTolerance {
+    pub translation: f32,
+    pub rotation: f32,
+    pub scale: f32
+}
+
+// A retained pose. The rotation is a unit quaternion (x, y, z, w) kept in
+// the same hemisphere as its neighbours; tangents are derivatives with
+// respect to time and stay zero for linear tracks.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Keyframe {
+    pub time: f32,
+    pub translation: Vec4,
+    pub rotation: Vec4,
+    pub scale: Vec4,
+    pub translation_tangent: Vec4,
+    pub rotation_tangent: Vec4,
+    pub scale_tangent: Vec4
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct CompressedTrack {
+    pub interpolation: KeyInterpolation,
+    pub keys: Vec<Keyframe>
+}
+
+// Channels of one input sample.
+#[derive(Clone, Copy)]
+struct Pose {
+    translation: Vec4,
+    rotation: Vec4,
+    scale: Vec4
+}
+
+fn xyz(v: &Vec4) -> Vec4 {
+    Vec4::new(v.x, v.y, v.z, 0.0)
+}
+
+// Angle between the rotations of two unit quaternions. The chord
+// |a - b| is 2 sin(angle / 4), which stays accurate for tiny angles where
+// acos of the dot product loses most of its digits.
+fn rotation_angle(a: &Vec4, b: &Vec4) -> f32 {
+    let chord = (*a - *b).length().min((*a + *b).length());
+    4.0 * (0.5 * chord).min(1.0).asin()
+}
+
+fn hermite(p0: Vec4, m0: Vec4, p1: Vec4, m1: Vec4, u: f32) -> Vec4 {
+    let (u2, u3) = (u * u, u * u * u);
+    p0 * (2.0 * u3 - 3.0 * u2 + 1.0) + m0 * (u3 - 2.0 * u2 + u) + p1 * (3.0 * u2 - 2.0 * u3) + m1 * (u3 - u2)
+}
+
+fn evaluate(interpolation: KeyInterpolation, a: &Keyframe, b: &Keyframe, time: f32) -> Pose {
+    let span = b.time - a.time;
+    let u = if span > 0.0 { ((time - a.time) / span).clamp(0.0, 1.0) } else { 0.0 };
+    match interpolation {
+        KeyInterpolation::Linear => Pose {
+            translation: a.translation + (b.translation - a.translation) * u,
+            rotation: slerp(a.rotation, b.rotation, u),
+            scale: a.scale + (b.scale - a.scale) * u
+        },
+        KeyInterpolation::Cubic => Pose {
+            translation: hermite(a.translation, a.translation_tangent * span, b.translation, b.translation_tangent * span, u),
+            rotation: hermite(a.rotation, a.rotation_tangent * span, b.rotation, b.rotation_tangent * span, u).normalize(),
+            scale: hermite(a.scale, a.scale_tangent * span, b.scale, b.scale_tangent * span, u)
+        }
+    }
+}
+
+// Error at a sample over its tolerance; above one means out of tolerance.
+fn error_ratio(pose: &Pose, sample: &Pose, tolerance: &KeyTolerance) -> f32 {
+    let translation = xyz(&(pose.translation - sample.translation)).length() / tolerance.translation;
+    let rotation = rotation_angle(&pose.rotation, &sample.rotation) / tolerance.rotation;
+    let d = pose.scale - sample.scale;
+    let scale = d.x.abs().max(d.y.abs()).max(d.z.abs()) / tolerance.scale;
+    translation.max(rotation).max(scale)
+}
+
+// Reduces a uniformly or irregularly sampled track to the keys needed to
+// reproduce every sample within `tolerance`. `times` must be increasing and
+// as long as `samples`. The first and last samples are always kept; the
+// rest are chosen by recursive subdivision at the worst-fitting sample, as
+// in Ramer-Douglas-Peucker. Cubic tangents are finite differences of the
+// samples around each key.
+#[allow(dead_code)]
+pub fn compress_track(times: &[f32], samples: &[Transform], tolerance: &KeyTolerance, interpolation: KeyInterpolation) -> CompressedTrack {
+    assert_eq!(times.len(), samples.len(), "Dimension mismatch");
+    let mut poses: Vec<Pose> = samples.iter().map(|s| Pose {
+        translation: xyz(&s.translation),
+        rotation: rotation_to_quat(&s.rotation),
+        scale: Vec4::new(s.scale.x, s.scale.y, s.scale.z, 1.0)
+    }).collect();
+    for i in 1..poses.len() {
+        if poses[i].rotation.dot(&poses[i - 1].rotation) < 0.0 {
+            poses[i].rotation = -poses[i].rotation;
+        }
+    }
+
+    let key = |i: usize| {
+        let pose = poses[i];
+        let (mut translation_tangent, mut rotation_tangent, mut scale_tangent) = (Vec4::zero(), Vec4::zero(), Vec4::zero());
+        if interpolation == KeyInterpolation::Cubic && poses.len() > 1 {
+            let (before, after) = (i.saturating_sub(1), (i + 1).min(poses.len() - 1));
+            let dt = times[after] - times[before];
+            translation_tangent = (poses[after].translation - poses[before].translation) / dt;
+            rotation_tangent = (poses[after].rotation - poses[before].rotation) / dt;
+            scale_tangent = (poses[after].scale - poses[before].scale) / dt;
+        }
+        Keyframe {
+            time: times[i],
+            translation: pose.translation,
+            rotation: pose.rotation,
+            scale: pose.scale,
+            translation_tangent,
+            rotation_tangent,
+            scale_tangent
+        }
+    };
+
+    let mut keep = vec![false; poses.len()];
+    if !poses.is_empty() {
+        let last = poses.len() - 1;
+        keep[0] = true;
+        keep[last] = true;
+        let mut stack = vec![(0, last)];
+        while let Some((start, end)) = stack.pop() {
+            let (a, b) = (key(start), key(end));
+            let worst = (start + 1..end)
+                .map(|i| (i, error_ratio(&evaluate(interpolation, &a, &b, times[i]), &poses[i], tolerance)))
+                .max_by(|x, y| x.1.total_cmp(&y.1));
+            if let Some((i, e)) = worst {
+                if e > 1.0 {
+                    keep[i] = true;
+                    stack.push((start, i));
+                    stack.push((i, end));
+                }
+            }
+        }
+    }
+    CompressedTrack {
+        interpolation,
+        keys: (0..poses.len()).filter(|&i| keep[i]).map(key).collect()
+    }
+}
+
+#[allow(dead_code)]
+impl CompressedTrack {
+    // Pose at `time`, held constant before the first and after the last
+    // key. Panics on an empty track.
+    pub fn sample(&self, time: f32) -> Transform {
+        let next = self.keys.partition_point(|k| k.time <= time).clamp(1, self.keys.len().max(1));
+        let a = &self.keys[next - 1];
+        let b = self.keys.get(next).unwrap_or(a);
+        let pose = evaluate(self.interpolation, a, b, time);
+        Transform::new(pose.translation, quat_to_rotation(&pose.rotation), pose.scale)
+    }
+
+    pub fn decompress(&self, times: &[f32]) -> Vec<Transform> {
+        times.iter().map(|&t| self.sample(t)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mat4::Mat4;
+
+    const TOLERANCE: KeyTolerance = KeyTolerance {
+        translation: 1e-3,
+        rotation: 1e-3,
+        scale: 1e-3
+    };
+
+    fn within_tolerance(track: &CompressedTrack, times: &[f32], samples: &[Transform]) -> bool {
+        track.decompress(times).iter().zip(samples).all(|(got, want)| {
+            let got_q = rotation_to_quat(&got.rotation);
+            let want_q = rotation_to_quat(&want.rotation);
+            (got.translation - want.translation).length() <= TOLERANCE.translation * 1.01
+                && rotation_angle(&got_q, &want_q) <= TOLERANCE.rotation * 1.01
+                && (got.scale - want.scale).length() <= TOLERANCE.scale * 1.01
+        })
+    }
+
+    #[test]
+    fn constant_motion_needs_two_keys() {
+        let times: Vec<f32> = (0..60).map(|i| i as f32 / 30.0).collect();
+        let axis = Vec4::new(0.0, 1.0, 0.0, 0.0);
+        let samples: Vec<Transform> = times.iter().map(|&t| {
+            Transform::new(Vec4::new(t, 2.0 * t, 0.0, 0.0), Mat4::from_axis_angle(axis, 0.8 * t), Vec4::new(1.0, 1.0, 1.0, 1.0))
+        }).collect();
+        let track = compress_track(&times, &samples, &TOLERANCE, KeyInterpolation::Linear);
+        assert_eq!(track.keys.len(), 2);
+        assert!(within_tolerance(&track, &times, &samples));
+    }
+
+    #[test]
+    fn cubic_needs_fewer_keys_for_smooth_motion() {
+        let times: Vec<f32> = (0..=240).map(|i| i as f32 / 60.0).collect();
+        let axis = Vec4::new(1.0, 1.0, 0.0, 0.0).normalize();
+        let samples: Vec<Transform> = times.iter().map(|&t| {
+            Transform::new(
+                Vec4::new(t.sin(), (0.5 * t).cos(), 0.1 * t * t, 0.0),
+                Mat4::from_axis_angle(axis, 1.5 * t.sin()),
+                Vec4::new(1.0 + 0.2 * t.sin(), 1.0, 1.0, 1.0)
+            )
+        }).collect();
+        let linear = compress_track(&times, &samples, &TOLERANCE, KeyInterpolation::Linear);
+        let cubic = compress_track(&times, &samples, &TOLERANCE, KeyInterpolation::Cubic);
+        assert!(within_tolerance(&linear, &times, &samples));
+        assert!(within_tolerance(&cubic, &times, &samples));
+        assert!(linear.keys.len() < times.len() / 3);
+        assert!(cubic.keys.len() * 2 < linear.keys.len(), "{} {}", cubic.keys.len(), linear.keys.len());
+        // Clamped outside the sampled range.
+        assert!((cubic.sample(100.0).translation - samples[240].translation).length() < 1e-5);
+    }
+}
@@ -1,17 +1,189 @@
+#![allow(clippy::needless_range_loop)]
+
+mod affine3;
+mod angle;
+mod bivector3;
+mod bvec2;
+mod bvec3;
+mod bvec4;
+pub mod color;
+mod complex;
+mod dmat4;
+mod dual;
+mod dvec4;
+mod eigen;
+mod error;
+mod euler;
+mod expm;
+mod f16vec4;
+mod float;
+mod instrument;
+mod ivec2;
+mod ivec3;
+mod ivec4;
+mod lu;
+mod mat3x4;
 mod mat4;
+mod mat4x3;
+mod matrix;
+mod parse;
+mod point3;
+mod project;
+mod qr;
+mod rotor3;
+mod snapshot;
+mod symmat;
+mod symmat3;
+mod symmat4;
+pub mod transform;
+mod uvec2;
+mod uvec3;
+mod uvec4;
 mod vec4;
+mod vector;
+
+#[cfg(feature = "geometry")]
+mod aabb;
+#[cfg(feature = "geometry")]
+mod bounding;
+#[cfg(feature = "geometry")]
+mod capsule;
+#[cfg(feature = "geometry")]
+mod closest_point;
+#[cfg(feature = "geometry")]
+mod covariance;
+#[cfg(feature = "geometry")]
+mod mesh;
+#[cfg(feature = "geometry")]
+mod obb;
+#[cfg(feature = "geometry")]
+mod pca;
+#[cfg(feature = "geometry")]
+mod plane;
+#[cfg(feature = "geometry")]
+mod polyhedron;
+#[cfg(feature = "geometry")]
+mod predicates;
+#[cfg(feature = "geometry")]
+mod rect;
+#[cfg(feature = "geometry")]
+mod segment;
+#[cfg(feature = "geometry")]
+mod shape;
+#[cfg(feature = "geometry")]
+mod simplify;
+#[cfg(feature = "geometry")]
+mod sphere;
+#[cfg(feature = "geometry")]
+mod triangle;
+#[cfg(feature = "geometry")]
+mod triangulate;
+#[cfg(feature = "geometry")]
+mod visibility;
+
+#[cfg(feature = "curves")]
+mod arc_length;
+#[cfg(feature = "curves")]
+mod curve;
+#[cfg(feature = "curves")]
+mod keyframe;
+
+#[cfg(all(feature = "curves", feature = "geometry"))]
+mod surface;
+#[cfg(all(feature = "curves", feature = "geometry"))]
+mod sweep;
+
+#[cfg(feature = "solvers")]
+mod banded;
+#[cfg(feature = "solvers")]
+mod dmat;
+#[cfg(feature = "solvers")]
+mod dvec;
+#[cfg(feature = "solvers")]
+mod sparse;
+#[cfg(feature = "solvers")]
+mod svd;
+
+#[cfg(feature = "spatial")]
+mod bitgrid3;
+#[cfg(feature = "spatial")]
+mod bvh;
+#[cfg(feature = "spatial")]
+mod flood;
+#[cfg(feature = "spatial")]
+mod flow;
+#[cfg(feature = "spatial")]
+mod grid2;
+#[cfg(feature = "spatial")]
+mod grid3;
+#[cfg(feature = "spatial")]
+mod nbody;
+#[cfg(feature = "spatial")]
+mod octree;
+#[cfg(feature = "spatial")]
+mod quadtree;
+#[cfg(feature = "spatial")]
+mod summed_area;
+#[cfg(feature = "spatial")]
+mod voxel;
+
+#[cfg(feature = "dynamics")]
+mod attitude;
+#[cfg(feature = "dynamics")]
+mod ballistics;
+#[cfg(feature = "dynamics")]
+mod catenary;
+#[cfg(feature = "dynamics")]
+mod control;
+#[cfg(feature = "dynamics")]
+mod imu;
+#[cfg(feature = "dynamics")]
+mod orbit;
+#[cfg(feature = "dynamics")]
+mod orca;
+#[cfg(feature = "dynamics")]
+mod perception;
+#[cfg(feature = "dynamics")]
+mod pid;
+
+#[cfg(feature = "rand")]
+mod random;
+
+#[cfg(feature = "stats")]
+pub mod stats;
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
 
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+#[cfg(feature = "curves")]
+pub mod curves;
+#[cfg(feature = "dynamics")]
+pub mod dynamics;
+#[cfg(feature = "geometry")]
+pub mod geometry;
+pub mod io;
+pub mod linear;
+pub mod num;
+pub mod prelude;
+#[cfg(feature = "spatial")]
+pub mod spatial;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Flat re-exports so `lalg::Mat4` keeps working next to `lalg::linear::Mat4`.
+pub use color::*;
+#[cfg(feature = "curves")]
+pub use curves::*;
+#[cfg(feature = "dynamics")]
+pub use dynamics::*;
+#[cfg(feature = "geometry")]
+pub use geometry::*;
+pub use io::*;
+pub use linear::*;
+pub use num::*;
+#[cfg(feature = "spatial")]
+pub use spatial::*;
+#[cfg(feature = "stats")]
+pub use stats::*;
+pub use transform::*;
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+#[cfg(all(test, feature = "oracle-tests"))]
+mod oracle_tests;
@@ -0,0 +1,38 @@
+// Vectors, matrices, decompositions and linear solvers.
+#[cfg(feature = "solvers")]
+pub use crate::banded::{Banded, BandedLu, Tridiagonal};
+pub use crate::bvec2::BVec2;
+pub use crate::bvec3::BVec3;
+pub use crate::bvec4::BVec4;
+#[cfg(feature = "geometry")]
+pub use crate::covariance::CovarianceAccumulator;
+#[cfg(feature = "solvers")]
+pub use crate::dmat::{normal_equations, ridge_least_squares, DMat};
+pub use crate::dmat4::DMat4;
+#[cfg(feature = "solvers")]
+pub use crate::dvec::DVec;
+pub use crate::dvec4::DVec4;
+pub use crate::f16vec4::F16Vec4;
+pub use crate::ivec2::IVec2;
+pub use crate::ivec3::IVec3;
+pub use crate::ivec4::IVec4;
+pub use crate::lu::Lu;
+pub use crate::mat3x4::Mat3x4;
+pub use crate::mat4::Mat4;
+pub use crate::mat4x3::Mat4x3;
+pub use crate::matrix::Matrix;
+#[cfg(feature = "geometry")]
+pub use crate::pca::pca;
+pub use crate::point3::Point3;
+#[cfg(feature = "solvers")]
+pub use crate::sparse::SparseMat;
+#[cfg(feature = "solvers")]
+pub use crate::svd::InverseMethod;
+pub use crate::symmat::SymMat;
+pub use crate::symmat3::SymMat3;
+pub use crate::symmat4::SymMat4;
+pub use crate::uvec2::UVec2;
+pub use crate::uvec3::UVec3;
+pub use crate::uvec4::UVec4;
+pub use crate::vec4::Vec4;
+pub use crate::vector::Vector;
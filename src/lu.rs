@@ -0,0 +1,174 @@
+use crate::error::MathError;
+use crate::float::Float;
+use crate::instrument::count_op;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// LU decomposition with partial pivoting: P * A = L * U, with L unit lower
+// triangular, U upper triangular and P the row permutation. Row `i` of
+// P * A is row `permutation[i]` of A.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Lu<T = f32> {
+    pub l: Mat4<T>,
+    pub u: Mat4<T>,
+    pub permutation: [usize; 4],
+    // Number of row swaps was even.
+    even: bool
+}
+
+#[allow(dead_code)]
+impl<T: Float> Lu<T> {
+    // Always succeeds; a singular matrix leaves a zero on U's diagonal.
+    pub fn new(a: &Mat4<T>) -> Self {
+        let mut u = *a;
+        let mut l = Mat4::identity();
+        let mut permutation = [0, 1, 2, 3];
+        let mut even = true;
+        for col in 0..4 {
+            let mut pivot = col;
+            for row in (col + 1)..4 {
+                if u.elements[col * 4 + row].abs() > u.elements[col * 4 + pivot].abs() {
+                    pivot = row;
+                }
+            }
+            if pivot != col {
+                for k in 0..4 {
+                    u.elements.swap(k * 4 + col, k * 4 + pivot);
+                }
+                for k in 0..col {
+                    l.elements.swap(k * 4 + col, k * 4 + pivot);
+                }
+                permutation.swap(col, pivot);
+                even = !even;
+            }
+            let diagonal = u.elements[col * 4 + col];
+            if diagonal == T::zero() {
+                continue;
+            }
+            for row in (col + 1)..4 {
+                let factor = u.elements[col * 4 + row] / diagonal;
+                l.elements[col * 4 + row] = factor;
+                for k in col..4 {
+                    let value = u.elements[k * 4 + col];
+                    u.elements[k * 4 + row] -= factor * value;
+                }
+            }
+        }
+        Self {
+            l,
+            u,
+            permutation,
+            even
+        }
+    }
+
+    pub fn permutation_matrix(&self) -> Mat4<T> {
+        let mut out = Mat4::zeroes();
+        for (row, &source) in self.permutation.iter().enumerate() {
+            out.elements[source * 4 + row] = T::one();
+        }
+        out
+    }
+
+    // Product of U's diagonal, negated for an odd number of row swaps.
+    pub fn determinant(&self) -> T {
+        let det = (0..4).fold(T::one(), |det, i| det * self.u.elements[i * 5]);
+        if self.even { det } else { -det }
+    }
+
+    pub fn is_singular(&self) -> bool {
+        (0..4).any(|i| self.u.elements[i * 5] == T::zero())
+    }
+
+    // Solves A * x = b by forward then back substitution. Fails with
+    // `Singular` when A is.
+    pub fn solve(&self, b: &Vec4<T>) -> Result<Vec4<T>, MathError> {
+        if self.is_singular() {
+            return Err(MathError::Singular);
+        }
+        let mut x = Vec4::zero();
+        for row in 0..4 {
+            let mut sum = b[self.permutation[row]];
+            for k in 0..row {
+                sum -= self.l.elements[k * 4 + row] * x[k];
+            }
+            x[row] = sum;
+        }
+        for row in (0..4).rev() {
+            let mut sum = x[row];
+            for k in (row + 1)..4 {
+                sum -= self.u.elements[k * 4 + row] * x[k];
+            }
+            x[row] = sum / self.u.elements[row * 5];
+        }
+        Ok(x)
+    }
+
+    // Inverse by solving for each column of the identity.
+    pub fn inverse(&self) -> Result<Mat4<T>, MathError> {
+        count_op!(inversions);
+        let mut out = Mat4::zeroes();
+        for col in 0..4 {
+            let mut e = Vec4::zero();
+            e[col] = T::one();
+            let x = self.solve(&e)?;
+            for row in 0..4 {
+                out.elements[col * 4 + row] = x[row];
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> Mat4<T> {
+    pub fn lu(&self) -> Lu<T> {
+        Lu::new(self)
+    }
+
+    // Solves self * x = b through the LU decomposition, cheaper and more
+    // accurate than multiplying by the inverse. Fails with `Singular` when
+    // the matrix is.
+    pub fn solve(&self, b: Vec4<T>) -> Result<Vec4<T>, MathError> {
+        self.lu().solve(&b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factors_and_solves() {
+        let mut a = Mat4::<f64>::zeroes();
+        for i in 0..16 {
+            a.elements[i] = ((i * 7 + 3) % 11) as f64 - 4.0;
+        }
+        let lu = a.lu();
+        let (pa, product) = (lu.permutation_matrix() * a, lu.l * lu.u);
+        assert!((0..16).all(|i| (pa.elements[i] - product.elements[i]).abs() < 1e-12));
+        for col in 0..4 {
+            for row in 0..col {
+                assert_eq!(lu.l.elements[col * 4 + row], 0.0);
+                assert_eq!(lu.u.elements[row * 4 + col], 0.0);
+            }
+        }
+        assert!((lu.determinant() - a.determinant()).abs() < 1e-9);
+
+        let b = Vec4::new(1.0, -2.0, 0.5, 3.0);
+        let x = lu.solve(&b).unwrap();
+        assert!((a * x - b).length() < 1e-12);
+        assert!((a.solve(b).unwrap() - x).length() == 0.0);
+        let inverse = lu.inverse().unwrap();
+        let expected = a.inverse();
+        assert!((0..16).all(|i| (inverse.elements[i] - expected.elements[i]).abs() < 1e-12));
+
+        let mut singular = a;
+        for row in 0..4 {
+            singular.elements[12 + row] = 0.0;
+        }
+        assert_eq!(singular.lu().solve(&b).err(), Some(MathError::Singular));
+        assert_eq!(singular.solve(b).err(), Some(MathError::Singular));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::affine3::Affine3;
+use crate::float::Float;
+use crate::mat4::Mat4;
+use crate::matrix::Matrix;
+use crate::vec4::Vec4;
+use crate::vector::Vector;
+
+// Three rows by four columns: the top of an affine Mat4 without its
+// constant bottom row, as used for skinning palettes.
+#[allow(dead_code)]
+pub type Mat3x4<T = f32> = Matrix<T, 3, 4>;
+
+#[allow(dead_code)]
+impl<T: Float> Matrix<T, 3, 4> {
+    // Drops the bottom row of `m`.
+    pub fn from_mat4(m: &Mat4<T>) -> Self {
+        let mut out = Self::zeroes();
+        for col in 0..4 {
+            for row in 0..3 {
+                out.columns[col][row] = m.elements[col * 4 + row];
+            }
+        }
+        out
+    }
+
+    // Restores the bottom row as (0, 0, 0, 1).
+    pub fn to_mat4(self) -> Mat4<T> {
+        let mut out = Mat4::identity();
+        for col in 0..4 {
+            for row in 0..3 {
+                out.elements[col * 4 + row] = self.columns[col][row];
+            }
+        }
+        out
+    }
+
+    // Row-major packing: three rows of four, the layout GPU shaders expect
+    // for a 3x4 transform uploaded as three vec4s.
+    pub fn to_rows_array(self) -> [[T; 4]; 3] {
+        let mut out = [[T::zero(); 4]; 3];
+        for row in 0..3 {
+            for col in 0..4 {
+                out[row][col] = self.columns[col][row];
+            }
+        }
+        out
+    }
+}
+
+#[allow(dead_code)]
+impl Matrix<f32, 3, 4> {
+    pub fn from_affine3(a: &Affine3) -> Self {
+        Self::from_mat4(&a.to_mat4())
+    }
+
+    pub fn to_affine3(self) -> Affine3 {
+        Affine3::from_mat4(&self.to_mat4())
+    }
+}
+
+impl<T: Float> std::ops::Mul<Mat4<T>> for Matrix<T, 3, 4> {
+    type Output = Matrix<T, 3, 4>;
+
+    fn mul(self, rhs: Mat4<T>) -> Self::Output {
+        self * Matrix::<T, 4, 4>::from_mat4(&rhs)
+    }
+}
+
+impl<T: Float> std::ops::Mul<Vec4<T>> for Matrix<T, 3, 4> {
+    type Output = Vector<T, 3>;
+
+    fn mul(self, rhs: Vec4<T>) -> Self::Output {
+        self * Vector::new([rhs.x, rhs.y, rhs.z, rhs.w])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mat4x3::Mat4x3;
+
+    #[test]
+    fn packs_and_multiplies_like_mat4() {
+        let mut m = Mat4::identity();
+        for i in 0..16 {
+            if i % 4 != 3 {
+                m.elements[i] = i as f32 + 1.0;
+            }
+        }
+        let packed = Mat3x4::from_mat4(&m);
+        assert_eq!(packed.to_mat4().elements, m.elements);
+        assert_eq!(packed.to_rows_array()[1], [2.0, 6.0, 10.0, 14.0]);
+
+        let v = Vec4::new(1.0, -2.0, 0.5, 1.0);
+        let full = m * v;
+        let short = packed * v;
+        assert_eq!([short[0], short[1], short[2]], [full.x, full.y, full.z]);
+        assert_eq!((packed * m).to_mat4().elements, (m * m).elements);
+
+        let tall = Mat4x3::from_mat4(&m);
+        assert_eq!((m * tall).to_mat4().elements, Mat4x3::from_mat4(&(m * m)).to_mat4().elements);
+        assert_eq!(Mat3x4::from_affine3(&Affine3::from_mat4(&m)).to_affine3().to_mat4().elements, m.elements);
+    }
+}
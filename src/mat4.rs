@@ -1,30 +1,71 @@
+use crate::error::MathError;
+use crate::float::Float;
+use crate::instrument::count_op;
 use crate::vec4::Vec4;
 
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, Default)]
-pub struct Mat4 {
-    elements: [f32; 16],
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Mat4<T = f32> {
+    pub(crate) elements: [T; 16],
 }
 
 #[allow(dead_code)]
-impl Mat4 {
+impl<T: Float> Mat4<T> {
     pub fn zeroes() -> Self {
         Self {
-            elements: [0.0; 16]
+            elements: [T::zero(); 16]
         }
     }
 
     pub fn identity() -> Self {
-        let mut elements = [0.0; 16];
-        elements[0] = 1.0;
-        elements[5] = 1.0;
-        elements[9] = 1.0;
-        elements[13] = 1.0;
+        let mut elements = [T::zero(); 16];
+        elements[0] = T::one();
+        elements[5] = T::one();
+        elements[10] = T::one();
+        elements[15] = T::one();
         Self {
             elements
         }
     }
-    pub fn x_vector(&self) -> Vec4 {
+
+    // Converts every element to another scalar type through f64.
+    pub fn cast<U: Float>(&self) -> Mat4<U> {
+        let mut out = Mat4::zeroes();
+        for i in 0..16 {
+            out.elements[i] = U::from_f64(self.elements[i].to_f64());
+        }
+        out
+    }
+
+    // Column-major, matching `as_slice` and the layout GPU uniform buffers
+    // expect for a column-major mat4.
+    pub fn from_cols_array(elements: &[T; 16]) -> Self {
+        Self {
+            elements: *elements
+        }
+    }
+
+    pub fn from_cols_array_2d(columns: &[[T; 4]; 4]) -> Self {
+        Self {
+            elements: std::array::from_fn(|i| columns[i / 4][i % 4])
+        }
+    }
+
+    pub fn to_cols_array(self) -> [T; 16] {
+        self.elements
+    }
+
+    pub fn to_cols_array_2d(self) -> [[T; 4]; 4] {
+        std::array::from_fn(|col| std::array::from_fn(|row| self.elements[col * 4 + row]))
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    pub fn x_vector(&self) -> Vec4<T> {
         Vec4 {
             x: self.elements[0],
             y: self.elements[1],
@@ -33,7 +74,7 @@ impl Mat4 {
         }
     }
 
-    pub fn y_vector(&self) -> Vec4 {
+    pub fn y_vector(&self) -> Vec4<T> {
         Vec4 {
             x: self.elements[4],
             y: self.elements[5],
@@ -42,7 +83,7 @@ impl Mat4 {
         }
     }
 
-    pub fn z_vector(&self) -> Vec4 {
+    pub fn z_vector(&self) -> Vec4<T> {
         Vec4 {
             x: self.elements[8],
             y: self.elements[9],
@@ -51,7 +92,7 @@ impl Mat4 {
         }
     }
 
-    pub fn position(&self) -> Vec4 {
+    pub fn position(&self) -> Vec4<T> {
         Vec4 {
             x: self.elements[12],
             y: self.elements[13],
@@ -60,8 +101,57 @@ impl Mat4 {
         }
     }
 
+    // Column `i`; columns 0..=3 are `x_vector` through `position`.
+    pub fn column(&self, i: usize) -> Vec4<T> {
+        assert!(i < 4, "Index out of bounds");
+        Vec4::new(self.elements[i * 4], self.elements[i * 4 + 1], self.elements[i * 4 + 2], self.elements[i * 4 + 3])
+    }
+
+    pub fn set_column(&mut self, i: usize, v: Vec4<T>) {
+        assert!(i < 4, "Index out of bounds");
+        self.elements[i * 4..i * 4 + 4].copy_from_slice(&[v.x, v.y, v.z, v.w]);
+    }
+
+    pub fn row(&self, i: usize) -> Vec4<T> {
+        assert!(i < 4, "Index out of bounds");
+        Vec4::new(self.elements[i], self.elements[4 + i], self.elements[8 + i], self.elements[12 + i])
+    }
+
+    pub fn set_row(&mut self, i: usize, v: Vec4<T>) {
+        assert!(i < 4, "Index out of bounds");
+        for (col, value) in [v.x, v.y, v.z, v.w].into_iter().enumerate() {
+            self.elements[col * 4 + i] = value;
+        }
+    }
+
+    // No NaN or infinite element.
+    pub fn is_finite(&self) -> bool {
+        self.elements.iter().all(|e| e.to_f64().is_finite())
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.elements.iter().any(|e| e.to_f64().is_nan())
+    }
+
+    // Every element within `epsilon` of the identity; false with any NaN.
+    pub fn is_identity(&self, epsilon: T) -> bool {
+        let identity = Self::identity();
+        self.elements.iter().zip(identity.elements.iter()).all(|(&e, &i)| (e - i).abs() <= epsilon)
+    }
+
+    // Every element within `epsilon` of the other's. Exact comparison is
+    // `==`.
+    pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.elements.iter().zip(other.elements.iter()).all(|(&a, &b)| (a - b).abs() <= epsilon)
+    }
+
+    // Every element at most `max_ulps` representable values away.
+    pub fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+        self.elements.iter().zip(other.elements.iter()).all(|(&a, &b)| a.ulps_between(b) <= max_ulps)
+    }
+
     pub fn transpose(&self) -> Self {
-        let mut elements = [0.0; 16];
+        let mut elements = [T::zero(); 16];
         for i in 0..4 {
             for j in 0..4 {
                 elements[i * 4 + j] = self.elements[j * 4 + i];
@@ -72,7 +162,19 @@ impl Mat4 {
         }
     }
 
-    pub fn translate(&self, translation: &Vec4) -> Self {
+    // Element-wise blend, exact at t = 0 and 1. Between them a rotating
+    // transform shrinks and shears; use `interpolate` for those.
+    pub fn lerp(&self, other: &Self, t: T) -> Self {
+        let mut elements = self.elements;
+        for (e, &o) in elements.iter_mut().zip(other.elements.iter()) {
+            *e += (o - *e) * t;
+        }
+        Self {
+            elements
+        }
+    }
+
+    pub fn translate(&self, translation: &Vec4<T>) -> Self {
         let mut elements = self.elements;
         elements[12] += translation.x;
         elements[13] += translation.y;
@@ -82,7 +184,7 @@ impl Mat4 {
         }
     }
 
-    pub fn translate_local(&self, translation: &Vec4) -> Self {
+    pub fn translate_local(&self, translation: &Vec4<T>) -> Self {
         let mut elements = self.elements;
         elements[12] += self.elements[0] * translation.x + self.elements[4] * translation.y + self.elements[8] * translation.z;
         elements[13] += self.elements[1] * translation.x + self.elements[5] * translation.y + self.elements[9] * translation.z;
@@ -92,7 +194,7 @@ impl Mat4 {
         }
     }
 
-    pub fn scale(&self, scale: Vec4) -> Self {
+    pub fn scale(&self, scale: Vec4<T>) -> Self {
         let mut elements = self.elements;
         elements[0] *= scale.x;
         elements[5] *= scale.y;
@@ -102,61 +204,231 @@ impl Mat4 {
         }
     }
 
-    fn rodrigues(&self, axis: Vec4, angle: f32) -> Self {
-        let mut elements = [0.0; 16];
+    pub fn from_translation(translation: &Vec4<T>) -> Self {
+        Self::identity().translate(translation)
+    }
+
+    pub fn from_scale(scale: Vec4<T>) -> Self {
+        Self::identity().scale(scale)
+    }
+
+    // Shear where each coordinate gains a multiple of the others: `xy` is
+    // how far x moves per unit of y, and so on, so
+    // x' = x + xy y + xz z, y' = yx x + y + yz z, z' = zx x + zy y + z.
+    pub fn from_shear(xy: T, xz: T, yx: T, yz: T, zx: T, zy: T) -> Self {
+        let mut out = Self::identity();
+        out.elements[4] = xy;
+        out.elements[8] = xz;
+        out.elements[1] = yx;
+        out.elements[9] = yz;
+        out.elements[2] = zx;
+        out.elements[6] = zy;
+        out
+    }
+
+    // Mirror across the plane of points p with normal . p = distance, the
+    // same convention as `Plane`; the normal need not be unit length. The
+    // determinant is -1, so triangle winding flips: swap the cull face (or
+    // front-face winding) while rendering with it. Panics on a zero normal,
+    // like `Plane::new`.
+    pub fn reflection(plane_normal: Vec4<T>, plane_distance: T) -> Self {
+        let axis = Vec4::new(plane_normal.x, plane_normal.y, plane_normal.z, T::zero());
+        let length = axis.length();
+        assert!(length > T::zero(), "Plane normal is zero");
+        let n = axis / length;
+        let d = plane_distance / length;
+        let two = T::one() + T::one();
+        let n = [n.x, n.y, n.z];
+        let mut out = Self::identity();
+        for col in 0..3 {
+            for row in 0..3 {
+                out.elements[col * 4 + row] -= two * n[row] * n[col];
+            }
+            out.elements[12 + col] = two * d * n[col];
+        }
+        out
+    }
+
+    // Rotation about a unit `axis` by Rodrigues' formula, the same matrix
+    // `rotate` and `rotate_local` apply. Rotations are right-handed like
+    // `EulerAngles` and quaternions: a positive angle turns counter-clockwise
+    // when looking down the axis towards the origin.
+    pub fn from_axis_angle(axis: Vec4<T>, angle: T) -> Self {
+        let mut elements = [T::zero(); 16];
         let c = angle.cos();
         let s = angle.sin();
-        let t = 1.0 - c;
+        let t = T::one() - c;
         let x = axis.x;
         let y = axis.y;
         let z = axis.z;
         elements[0] = t * x * x + c;
-        elements[1] = t * x * y - s * z;
-        elements[2] = t * x * z + s * y;
-        elements[3] = 0.0;
-        elements[4] = t * x * y + s * z;
+        elements[1] = t * x * y + s * z;
+        elements[2] = t * x * z - s * y;
+        elements[3] = T::zero();
+        elements[4] = t * x * y - s * z;
         elements[5] = t * y * y + c;
-        elements[6] = t * y * z - s * x;
-        elements[7] = 0.0;
-        elements[8] = t * x * z - s * y;
-        elements[9] = t * y * z + s * x;
+        elements[6] = t * y * z + s * x;
+        elements[7] = T::zero();
+        elements[8] = t * x * z + s * y;
+        elements[9] = t * y * z - s * x;
         elements[10] = t * z * z + c;
-        elements[11] = 0.0;
-        elements[12] = 0.0;
-        elements[13] = 0.0;
-        elements[14] = 0.0;
-        elements[15] = 1.0;
+        elements[11] = T::zero();
+        elements[12] = T::zero();
+        elements[13] = T::zero();
+        elements[14] = T::zero();
+        elements[15] = T::one();
         Self {
             elements
         }
     }
 
-    pub fn rotate(&mut self, axis: Vec4, angle: f32) {
-        *self *= self.rodrigues(axis, angle);
+    pub fn rotate(&mut self, axis: Vec4<T>, angle: T) {
+        *self *= Self::from_axis_angle(axis, angle);
     }
 
-    pub fn rotate_local(&mut self, axis: Vec4, angle: f32) {
-        *self = self.rodrigues(axis, angle) * *self;
+    pub fn rotate_local(&mut self, axis: Vec4<T>, angle: T) {
+        *self = Self::from_axis_angle(axis, angle) * *self;
+    }
+
+
+    pub fn perspective(fov: T, aspect_ratio: T, near: T, far: T) -> Self {
+        let f = T::one() / (fov / T::from_f64(2.0)).tan();
+        let mut elements = [T::zero(); 16];
+        elements[0] = f / aspect_ratio;
+        elements[5] = f;
+        elements[10] = (far + near) / (near - far);
+        elements[11] = -T::one();
+        elements[14] = (T::from_f64(2.0) * far * near) / (near - far);
+        Self {
+            elements
+        }
     }
 
+    // `perspective` with depth mapped to [0, 1] instead of [-1, 1], the
+    // clip space of Vulkan, D3D and WebGPU.
+    pub fn perspective_zo(fov: T, aspect_ratio: T, near: T, far: T) -> Self {
+        let mut out = Self::perspective(fov, aspect_ratio, near, far);
+        out.elements[10] = far / (near - far);
+        out.elements[14] = far * near / (near - far);
+        out
+    }
 
-    pub fn perspective(fov: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
-        let f = 1.0 / (fov / 2.0).tan();
-        let mut elements = [0.0; 16];
+    // `perspective` in the limit of an infinitely distant far plane: the
+    // near plane still maps to -1 and depth approaches 1 towards infinity,
+    // so nothing is ever clipped for being too far.
+    pub fn perspective_infinite(fov: T, aspect_ratio: T, near: T) -> Self {
+        let f = T::one() / (fov / T::from_f64(2.0)).tan();
+        let mut elements = [T::zero(); 16];
         elements[0] = f / aspect_ratio;
         elements[5] = f;
+        elements[10] = -T::one();
+        elements[11] = -T::one();
+        elements[14] = -T::from_f64(2.0) * near;
+        Self {
+            elements
+        }
+    }
+
+    // `perspective` with reversed depth: near maps to 1 and far to 0, in a
+    // [0, 1] clip depth range (Vulkan, D3D, or OpenGL with
+    // glClipControl(GL_ZERO_TO_ONE)). With a float depth buffer and a
+    // greater-than depth test this spreads precision evenly with distance.
+    pub fn perspective_reversed(fov: T, aspect_ratio: T, near: T, far: T) -> Self {
+        let mut out = Self::perspective(fov, aspect_ratio, near, far);
+        out.elements[10] = near / (far - near);
+        out.elements[14] = far * near / (far - near);
+        out
+    }
+
+    // Off-center perspective projection with the near plane spanning
+    // [left, right] x [bottom, top], in the same OpenGL clip space as
+    // `perspective`: the camera looks down -z and depth maps to [-1, 1].
+    pub fn frustum(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let two = T::from_f64(2.0);
+        let mut elements = [T::zero(); 16];
+        elements[0] = two * near / (right - left);
+        elements[5] = two * near / (top - bottom);
+        elements[8] = (right + left) / (right - left);
+        elements[9] = (top + bottom) / (top - bottom);
         elements[10] = (far + near) / (near - far);
-        elements[11] = -1.0;
-        elements[14] = (2.0 * far * near) / (near - far);
+        elements[11] = -T::one();
+        elements[14] = (two * far * near) / (near - far);
+        Self {
+            elements
+        }
+    }
+
+    // `frustum` with depth mapped to [0, 1].
+    pub fn frustum_zo(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let mut out = Self::frustum(left, right, bottom, top, near, far);
+        out.elements[10] = far / (near - far);
+        out.elements[14] = far * near / (near - far);
+        out
+    }
+
+    // Left-handed projections, as used by D3D: the camera looks down +z
+    // with +x right and +y up. The plain `_lh` versions keep the [-1, 1]
+    // depth of `perspective` and `orthographic`; `_lh_zo` map depth to
+    // [0, 1] as D3D expects. Each is the right-handed matrix with the sign
+    // of its z input flipped.
+    fn flip_z_input(mut self) -> Self {
+        for row in 0..4 {
+            self.elements[8 + row] = -self.elements[8 + row];
+        }
+        self
+    }
+
+    pub fn perspective_lh(fov: T, aspect_ratio: T, near: T, far: T) -> Self {
+        Self::perspective(fov, aspect_ratio, near, far).flip_z_input()
+    }
+
+    pub fn perspective_lh_zo(fov: T, aspect_ratio: T, near: T, far: T) -> Self {
+        Self::perspective_zo(fov, aspect_ratio, near, far).flip_z_input()
+    }
+
+    pub fn orthographic_lh(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        Self::orthographic(left, right, bottom, top, near, far).flip_z_input()
+    }
+
+    pub fn orthographic_lh_zo(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        Self::orthographic_zo(left, right, bottom, top, near, far).flip_z_input()
+    }
+
+    // Left-handed view matrix: the camera looks down its +z axis, with +y
+    // towards `up` and +x to the right as seen in a left-handed world.
+    pub fn view_lh(position: Vec4<T>, forward: Vec4<T>, up: Vec4<T>) -> Self {
+        let right = up.cross(&forward).normalize();
+        let up = forward.cross(&right).normalize();
+        let forward = forward.normalize();
+        let mut elements = [T::zero(); 16];
+        elements[0] = right.x;
+        elements[4] = right.y;
+        elements[8] = right.z;
+        elements[1] = up.x;
+        elements[5] = up.y;
+        elements[9] = up.z;
+        elements[2] = forward.x;
+        elements[6] = forward.y;
+        elements[10] = forward.z;
+        elements[12] = -right.dot(&position);
+        elements[13] = -up.dot(&position);
+        elements[14] = -forward.dot(&position);
+        elements[15] = T::one();
         Self {
             elements
         }
     }
-    pub fn view(position: Vec4, forward: Vec4, up: Vec4) -> Self {
+
+    // `look_at` for a left-handed world; see `view_lh`.
+    pub fn look_at_lh(eye: Vec4<T>, target: Vec4<T>, up: Vec4<T>) -> Self {
+        Self::view_lh(eye, target - eye, up)
+    }
+
+    pub fn view(position: Vec4<T>, forward: Vec4<T>, up: Vec4<T>) -> Self {
         let right = forward.cross(&up).normalize();
         let up = right.cross(&forward).normalize();
         let forward = forward.normalize();
-        let mut elements = [0.0; 16];
+        let mut elements = [T::zero(); 16];
         elements[0] = right.x;
         elements[4] = right.y;
         elements[8] = right.z;
@@ -169,66 +441,273 @@ impl Mat4 {
         elements[12] = -right.dot(&position);
         elements[13] = -up.dot(&position);
         elements[14] = forward.dot(&position);
-        elements[15] = 1.0;
+        elements[15] = T::one();
         Self {
             elements
         }
     }
-    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
-        let mut elements = [0.0; 16];
-        elements[0] = 2.0 / (right - left);
-        elements[5] = 2.0 / (top - bottom);
-        elements[10] = -2.0 / (far - near);
+
+    // Right-handed view matrix for a camera at `eye` looking at `target`:
+    // the camera looks down its -z axis with +y towards `up` and +x to the
+    // right. `up` must not be parallel to the viewing direction.
+    pub fn look_at(eye: Vec4<T>, target: Vec4<T>, up: Vec4<T>) -> Self {
+        Self::view(eye, target - eye, up)
+    }
+
+    pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let mut elements = [T::zero(); 16];
+        elements[0] = T::from_f64(2.0) / (right - left);
+        elements[5] = T::from_f64(2.0) / (top - bottom);
+        elements[10] = -T::from_f64(2.0) / (far - near);
         elements[12] = -(right + left) / (right - left);
         elements[13] = -(top + bottom) / (top - bottom);
         elements[14] = -(far + near) / (far - near);
-        elements[15] = 1.0;
+        elements[15] = T::one();
         Self {
             elements
         }
     }
 
-}
+    // `orthographic` with depth mapped to [0, 1].
+    pub fn orthographic_zo(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let mut out = Self::orthographic(left, right, bottom, top, near, far);
+        out.elements[10] = T::one() / (near - far);
+        out.elements[14] = near / (near - far);
+        out
+    }
+
+    // `orthographic` with reversed depth: near maps to 1 and far to 0 in a
+    // [0, 1] clip depth range, matching `perspective_reversed`.
+    pub fn orthographic_reversed(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let mut out = Self::orthographic(left, right, bottom, top, near, far);
+        out.elements[10] = T::one() / (far - near);
+        out.elements[14] = far / (far - near);
+        out
+    }
 
-impl std::ops::MulAssign<Mat4> for Mat4 {
-    fn mul_assign(&mut self, rhs: Mat4) {
-        let mut result = [0.0; 16];
-        for col in 0..4 { // Iterate over columns of the result
-            for row in 0..4 { // Iterate over rows of the result
-                for k in 0..4 { // Accumulate the dot product
-                    result[col * 4 + row] += self.elements[k * 4 + row] * rhs.elements[col * 4 + k];
+    // Re-orthogonalizes the upper 3x3 block by Gram-Schmidt on its columns:
+    // x is normalized, y loses its x component, z its x and y components.
+    // Any scale is removed along with the drift; translation and the bottom
+    // row are kept.
+    pub fn orthonormalize(&self) -> Self {
+        let column = |m: &Self, col: usize| Vec4::new(m.elements[col * 4], m.elements[col * 4 + 1], m.elements[col * 4 + 2], T::zero());
+        let x = column(self, 0).normalize();
+        let mut y = column(self, 1);
+        y = (y - x * x.dot(&y)).normalize();
+        let mut z = column(self, 2);
+        z = z - x * x.dot(&z);
+        z = (z - y * y.dot(&z)).normalize();
+        let mut out = *self;
+        for (col, axis) in [x, y, z].iter().enumerate() {
+            for row in 0..3 {
+                out.elements[col * 4 + row] = axis[row];
+            }
+        }
+        out
+    }
+
+    // Sum of the diagonal. For a rotation the upper 3x3 part has trace
+    // 1 + 2 cos(angle), so this is 2 + 2 cos(angle) on an affine matrix.
+    pub fn trace(&self) -> T {
+        self.elements[0] + self.elements[5] + self.elements[10] + self.elements[15]
+    }
+
+    // Laplace expansion over complementary 2x2 minors of the first two and
+    // last two columns. Negative for transforms that mirror.
+    pub fn determinant(&self) -> T {
+        let e = |row: usize, col: usize| self.elements[col * 4 + row];
+        let minor = |c0: usize, c1: usize, r0: usize, r1: usize| e(r0, c0) * e(r1, c1) - e(r1, c0) * e(r0, c1);
+        let mut det = T::zero();
+        let pairs = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+        for (i, &(r0, r1)) in pairs.iter().enumerate() {
+            let (r2, r3) = pairs[5 - i];
+            let sign = if (r0 + r1) % 2 == 0 { -T::one() } else { T::one() };
+            det += sign * minor(0, 1, r0, r1) * minor(2, 3, r2, r3);
+        }
+        det
+    }
+
+    // Matrix of signed 3x3 minors: entry (row, col) is (-1)^(row + col)
+    // times the determinant left after deleting that row and column.
+    pub fn cofactor_matrix(&self) -> Self {
+        let mut out = Self::zeroes();
+        for col in 0..4 {
+            for row in 0..4 {
+                let skip = |deleted: usize, k: usize| if k < deleted { k } else { k + 1 };
+                let e = |r: usize, c: usize| self.elements[skip(col, c) * 4 + skip(row, r)];
+                let minor = e(0, 0) * (e(1, 1) * e(2, 2) - e(2, 1) * e(1, 2))
+                    - e(0, 1) * (e(1, 0) * e(2, 2) - e(2, 0) * e(1, 2))
+                    + e(0, 2) * (e(1, 0) * e(2, 1) - e(2, 0) * e(1, 1));
+                out.elements[col * 4 + row] = if (row + col) % 2 == 0 { minor } else { -minor };
+            }
+        }
+        out
+    }
+
+    // Transposed cofactor matrix, so that A * adj(A) = det(A) * I. Defined
+    // even for singular matrices.
+    pub fn adjugate(&self) -> Self {
+        self.cofactor_matrix().transpose()
+    }
+
+    pub(crate) fn invert(&self) -> Option<Self> {
+        self.invert_with_tolerance(T::zero())
+    }
+
+    // Gauss-Jordan elimination with partial pivoting. Fails when the best
+    // pivot of a column is no larger than `tolerance`.
+    fn invert_with_tolerance(&self, tolerance: T) -> Option<Self> {
+        let mut a = *self;
+        let mut inv = Self::identity();
+        for col in 0..4 {
+            let mut pivot = col;
+            for row in (col + 1)..4 {
+                if a.elements[col * 4 + row].abs() > a.elements[col * 4 + pivot].abs() {
+                    pivot = row;
                 }
             }
+            if a.elements[col * 4 + pivot].abs() <= tolerance {
+                return None;
+            }
+            if pivot != col {
+                for k in 0..4 {
+                    a.elements.swap(k * 4 + col, k * 4 + pivot);
+                    inv.elements.swap(k * 4 + col, k * 4 + pivot);
+                }
+            }
+            let scale = T::one() / a.elements[col * 4 + col];
+            for k in 0..4 {
+                a.elements[k * 4 + col] *= scale;
+                inv.elements[k * 4 + col] *= scale;
+            }
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let factor = a.elements[col * 4 + row];
+                for k in 0..4 {
+                    a.elements[k * 4 + row] -= factor * a.elements[k * 4 + col];
+                    inv.elements[k * 4 + row] -= factor * inv.elements[k * 4 + col];
+                }
+            }
+        }
+        Some(inv)
+    }
+
+    // Fails with `Singular` when the matrix is singular or so close to it
+    // that the inverse would be mostly rounding error, and when the inverse
+    // overflows, as with tiny or subnormal elements. `NotFinite` when an
+    // element is NaN or infinite.
+    //
+    // The pivot test runs on an equilibrated copy, with every column and
+    // then every row scaled to a largest magnitude of one, so it does not
+    // depend on units: a translation of 1e6 or a scale of 0.001 is not
+    // near-singular, while nearly dependent columns still are. A pivot
+    // within a few epsilons of one is treated as zero.
+    pub fn try_inverse(&self) -> Result<Self, MathError> {
+        self.equilibrated_inverse().map(|(inverse, _)| inverse)
+    }
+
+    // `try_inverse` plus the 1-norm condition number of the equilibrated
+    // matrix. Unlike the condition number of the matrix itself this doesn't
+    // grow with the units of a row or column, such as a large translation.
+    pub(crate) fn equilibrated_inverse(&self) -> Result<(Self, T), MathError> {
+        count_op!(inversions);
+        if !self.is_finite() {
+            return Err(MathError::NotFinite);
+        }
+        let largest = |values: [T; 4]| values.into_iter().fold(T::zero(), |m, e| if e.abs() > m { e.abs() } else { m });
+        let mut scaled = *self;
+        let mut col_scales = [T::one(); 4];
+        for (col, scale) in col_scales.iter_mut().enumerate() {
+            *scale = largest(std::array::from_fn(|row| scaled.elements[col * 4 + row]));
+            if *scale == T::zero() {
+                return Err(MathError::Singular);
+            }
+            for row in 0..4 {
+                scaled.elements[col * 4 + row] /= *scale;
+            }
+        }
+        let mut row_scales = [T::one(); 4];
+        for (row, scale) in row_scales.iter_mut().enumerate() {
+            *scale = largest(std::array::from_fn(|col| scaled.elements[col * 4 + row]));
+            for col in 0..4 {
+                scaled.elements[col * 4 + row] /= *scale;
+            }
+        }
+        // scaled = R^-1 A C^-1, so A^-1 = C^-1 scaled^-1 R^-1.
+        let scaled_inverse = scaled.invert_with_tolerance(T::epsilon() * T::from_f64(16.0)).ok_or(MathError::Singular)?;
+        let condition = norm_1(&scaled) * norm_1(&scaled_inverse);
+        let mut inverse = scaled_inverse;
+        for col in 0..4 {
+            for row in 0..4 {
+                inverse.elements[col * 4 + row] = inverse.elements[col * 4 + row] / col_scales[row] / row_scales[col];
+            }
         }
-        self.elements = result;
+        Some((inverse, condition)).filter(|(inverse, _)| inverse.is_finite()).ok_or(MathError::Singular)
+    }
+
+    // Panics if the matrix is singular; see `try_inverse`.
+    pub fn inverse(&self) -> Self {
+        self.try_inverse().expect("Matrix is singular")
+    }
+
+    // Inverse-transpose of the upper-left 3x3, embedded with zero
+    // translation and w = 1. Unlike the matrix itself this keeps normals
+    // perpendicular to surfaces under non-unit and non-uniform scale and
+    // shear; transformed normals still need renormalizing. Fails like
+    // `try_inverse` when the 3x3 part is singular.
+    pub fn normal_matrix(&self) -> Result<Self, MathError> {
+        let mut linear = *self;
+        for i in [3, 7, 11, 12, 13, 14] {
+            linear.elements[i] = T::zero();
+        }
+        linear.elements[15] = T::one();
+        Ok(linear.try_inverse()?.transpose())
     }
 }
 
+// Maximum absolute column sum.
+fn norm_1<T: Float>(m: &Mat4<T>) -> T {
+    let mut out = T::zero();
+    for col in 0..4 {
+        let sum = (0..4).fold(T::zero(), |sum, row| sum + m.elements[col * 4 + row].abs());
+        if sum > out {
+            out = sum;
+        }
+    }
+    out
+}
 
-impl std::ops::Mul<Mat4> for Mat4 {
-    type Output = Mat4;
+impl<T: Float> std::ops::MulAssign<Mat4<T>> for Mat4<T> {
+    fn mul_assign(&mut self, rhs: Mat4<T>) {
+        count_op!(multiplies);
+        self.elements = T::mat4_mul(&self.elements, &rhs.elements);
+    }
+}
+
+
+impl<T: Float> std::ops::Mul<Mat4<T>> for Mat4<T> {
+    type Output = Mat4<T>;
     
-    fn mul(self, rhs: Mat4) -> Self::Output {
-        let mut out = self.clone();
+    fn mul(self, rhs: Mat4<T>) -> Self::Output {
+        let mut out = self;
         out *= rhs;
         out 
     }
 }
 
-impl std::ops::Mul<Vec4> for Mat4 {
-    type Output = Vec4;
+impl<T: Float> std::ops::Mul<Vec4<T>> for Mat4<T> {
+    type Output = Vec4<T>;
     
-    fn mul(self, rhs: Vec4) -> Self::Output {
-        let x = self.elements[0] * rhs.x + self.elements[4] * rhs.y + self.elements[8] * rhs.z + self.elements[12] * rhs.w;
-        let y = self.elements[1] * rhs.x + self.elements[5] * rhs.y + self.elements[9] * rhs.z + self.elements[13] * rhs.w;
-        let z = self.elements[2] * rhs.x + self.elements[6] * rhs.y + self.elements[10] * rhs.z + self.elements[14] * rhs.w;
-        let w = self.elements[3] * rhs.x + self.elements[7] * rhs.y + self.elements[11] * rhs.z + self.elements[15] * rhs.w;
+    fn mul(self, rhs: Vec4<T>) -> Self::Output {
+        let [x, y, z, w] = T::mat4_mul_vec4(&self.elements, [rhs.x, rhs.y, rhs.z, rhs.w]);
         Vec4 { x, y, z, w }
     }
 }
 
-impl std::ops::MulAssign<f32> for Mat4 {
-    fn mul_assign(&mut self, rhs: f32) {
+impl<T: Float> std::ops::MulAssign<T> for Mat4<T> {
+    fn mul_assign(&mut self, rhs: T) {
         for i in 0..16 {
             self.elements[i] *= rhs;
         }
@@ -236,55 +715,72 @@ impl std::ops::MulAssign<f32> for Mat4 {
 }
 
 
-impl std::ops::Mul<f32> for Mat4 {
-    type Output = Mat4;
+impl<T: Float> std::ops::Mul<T> for Mat4<T> {
+    type Output = Mat4<T>;
     
-    fn mul(self, rhs: f32) -> Self::Output {
-        let mut out = self.clone();
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut out = self;
         out *= rhs;
         out 
     }
     
 }
 
-impl std::ops::AddAssign<Mat4> for Mat4 {
-    fn add_assign(&mut self, rhs: Mat4) {
+impl<T: Float> std::ops::AddAssign<Mat4<T>> for Mat4<T> {
+    fn add_assign(&mut self, rhs: Mat4<T>) {
         for i in 0..16 {
             self.elements[i] += rhs.elements[i];
         }
     }
 }
 
-impl std::ops::Add<Mat4> for Mat4 {    
+impl<T: Float> std::ops::Add<Mat4<T>> for Mat4<T> {    
     type Output = Self;
     
-    fn add(self, rhs: Mat4) -> Self::Output {
-        let mut out = self.clone();
+    fn add(self, rhs: Mat4<T>) -> Self::Output {
+        let mut out = self;
         out += rhs;
         out 
     }
 }
 
-impl std::ops::SubAssign<Mat4> for Mat4 {
+impl<T: Float> std::ops::SubAssign<Mat4<T>> for Mat4<T> {
 
-    fn sub_assign(&mut self, rhs: Mat4) {
+    fn sub_assign(&mut self, rhs: Mat4<T>) {
         for i in 0..16 {
             self.elements[i] -= rhs.elements[i];
         }
     }
 }
 
-impl std::ops::Sub<Mat4> for Mat4 {
+impl<T: Float> std::ops::Sub<Mat4<T>> for Mat4<T> {
     type Output = Self;
 
-    fn sub(self, rhs: Mat4) -> Self::Output {
-        let mut out = self.clone();
+    fn sub(self, rhs: Mat4<T>) -> Self::Output {
+        let mut out = self;
         out -= rhs;
         out 
     }
 }
 
-impl std::fmt::Display for Mat4 {
+// Indexed by (row, col); storage is column-major.
+impl<T: Float> std::ops::Index<(usize, usize)> for Mat4<T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        assert!(index.0 < 4 && index.1 < 4, "Index out of bounds");
+        &self.elements[index.1 * 4 + index.0]
+    }
+}
+
+impl<T: Float> std::ops::IndexMut<(usize, usize)> for Mat4<T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        assert!(index.0 < 4 && index.1 < 4, "Index out of bounds");
+        &mut self.elements[index.1 * 4 + index.0]
+    }
+}
+
+impl<T: Float> std::fmt::Display for Mat4<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "[{}, {}, {}, {}]\n[{}, {}, {}, {}]\n[{}, {}, {}, {}]\n[{}, {}, {}, {}]", 
             self.elements[0], self.elements[4], self.elements[8], self.elements[12],
@@ -294,3 +790,382 @@ impl std::fmt::Display for Mat4 {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_round_trips_and_rejects_singular() {
+        let mut m = Mat4::<f64>::identity().translate(&Vec4::new(1.0, -2.0, 3.0, 0.0));
+        m.rotate(Vec4::new(0.3, 1.0, -0.2, 0.0).normalize(), 0.7);
+        let m = m.scale(Vec4::new(2.0, 0.5, 3.0, 1.0));
+        let product = m * m.inverse();
+        let identity = Mat4::<f64>::identity();
+        assert!((0..16).all(|i| (product.elements[i] - identity.elements[i]).abs() < 1e-12));
+
+        let mut flat = Mat4::<f32>::identity();
+        flat.elements[10] = 0.0;
+        assert_eq!(flat.try_inverse().err(), Some(MathError::Singular));
+        // The second and third columns differ by 1e-6: singular to f32.
+        flat.elements[4..12].copy_from_slice(&[0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.000001, 0.0]);
+        assert_eq!(flat.try_inverse().err(), Some(MathError::Singular));
+        // A tiny but independent column is only a change of units.
+        let mut thin = Mat4::<f32>::identity();
+        thin.elements[10] = 1e-9;
+        assert_eq!(thin.try_inverse().unwrap().elements[10], 1e9);
+        // Invertible in exact arithmetic, but the inverse overflows f32.
+        let tiny = Mat4::<f32>::identity() * 1e-39;
+        assert_eq!(tiny.try_inverse().err(), Some(MathError::Singular));
+        assert_eq!(Mat4::<f32>::zeroes().try_inverse().err(), Some(MathError::Singular));
+        flat.elements[10] = f32::NAN;
+        assert_eq!(flat.try_inverse().err(), Some(MathError::NotFinite));
+    }
+
+    #[test]
+    fn inverse_of_affine_matrices_ignores_units() {
+        let close = |m: &Mat4<f64>, expected: &Mat4<f64>, tolerance: f64| {
+            (0..16).all(|i| (m.elements[i] - expected.elements[i]).abs() <= tolerance)
+        };
+        let mut rotation = Mat4::<f64>::identity();
+        rotation.rotate(Vec4::new(1.0, -2.0, 0.5, 0.0).normalize(), 0.9);
+        for (scale, translation) in [(1.0, 1e6), (0.001, 1e4), (1.0, 1000.0)] {
+            let m = Mat4::from_translation(&Vec4::new(translation, -translation, 0.5 * translation, 0.0))
+                * rotation
+                * Mat4::from_scale(Vec4::new(scale, 2.0 * scale, scale, 1.0));
+            let expected = Mat4::from_scale(Vec4::new(1.0 / scale, 0.5 / scale, 1.0 / scale, 1.0))
+                * rotation.transpose()
+                * Mat4::from_translation(&Vec4::new(-translation, translation, -0.5 * translation, 0.0));
+            // Relative to the largest element of the inverse.
+            let magnitude = 1.0 + translation / scale;
+            assert!(close(&m.inverse(), &expected, 1e-14 * magnitude));
+            let inverse = m.cast::<f32>().inverse().cast::<f64>();
+            assert!(close(&inverse, &expected, 1e-6 * magnitude), "{inverse}");
+        }
+        let far = Mat4::<f64>::from_translation(&Vec4::new(1e15, 0.0, -1e15, 0.0));
+        let position = far.inverse().position();
+        assert!((position.x + 1e15).abs() <= 1.0 && (position.z - 1e15).abs() <= 1.0);
+    }
+
+    #[test]
+    fn orthonormalize_removes_drift() {
+        let mut m = Mat4::<f32>::identity().translate(&Vec4::new(1.0, 2.0, 3.0, 0.0));
+        for i in 0..10000 {
+            m.rotate(Vec4::new(1.0, (i % 7) as f32, 2.0, 0.0).normalize(), 0.37);
+        }
+        let fixed = m.orthonormalize();
+        let product = fixed.transpose() * fixed;
+        for col in 0..3 {
+            for row in 0..3 {
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert!((product.elements[col * 4 + row] - expected).abs() < 1e-6);
+            }
+        }
+        assert!((fixed.determinant() - 1.0).abs() < 1e-5);
+        assert_eq!(&fixed.elements[12..], &m.elements[12..]);
+    }
+
+    #[test]
+    fn determinant_and_trace() {
+        let m = Mat4::<f64>::identity().scale(Vec4::new(2.0, 3.0, 4.0, 1.0));
+        assert!((m.determinant() - 24.0).abs() < 1e-12);
+        let mut rotated = m;
+        rotated.rotate(Vec4::new(1.0, 2.0, 3.0, 0.0).normalize(), 1.1);
+        let rotated = rotated.translate(&Vec4::new(5.0, -1.0, 2.0, 0.0));
+        assert!((rotated.determinant() - 24.0).abs() < 1e-12);
+        let mut spin = Mat4::<f64>::identity();
+        spin.rotate(Vec4::new(1.0, 2.0, 3.0, 0.0).normalize(), 1.1);
+        assert!((spin.trace() - (2.0 + 2.0 * 1.1f64.cos())).abs() < 1e-12);
+        assert!(Mat4::<f64>::identity().scale(Vec4::new(-1.0, 1.0, 1.0, 1.0)).determinant() < 0.0);
+
+        let mut m = Mat4::<f64>::zeroes();
+        for i in 0..16 {
+            m.elements[i] = ((i * 7 + 3) % 11) as f64;
+        }
+        assert_eq!(m.trace(), m.elements[0] + m.elements[5] + m.elements[10] + m.elements[15]);
+        let via_adjugate = m.adjugate() * (1.0 / m.determinant());
+        let inverse = m.inverse();
+        assert!((0..16).all(|i| (via_adjugate.elements[i] - inverse.elements[i]).abs() < 1e-12));
+        let cofactors = m.cofactor_matrix();
+        let expansion: f64 = (0..4).map(|row| m.elements[row] * cofactors.elements[row]).sum();
+        assert!((expansion - m.determinant()).abs() < 1e-9);
+        // det(M) det(M^-1) = 1 and det(M^T) = det(M).
+        assert!((m.determinant() * m.inverse().determinant() - 1.0).abs() < 1e-9);
+        assert!((m.transpose().determinant() - m.determinant()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn constructors_match_mutating_builders() {
+        let axis = Vec4::new(1.0, -2.0, 0.5, 0.0).normalize();
+        let mut rotated = Mat4::<f64>::identity();
+        rotated.rotate(axis, 0.9);
+        assert_eq!(Mat4::from_axis_angle(axis, 0.9).elements, rotated.elements);
+
+        let t = Vec4::new(1.0, 2.0, 3.0, 0.0);
+        let s = Vec4::new(2.0, 3.0, 4.0, 1.0);
+        let m = Mat4::<f64>::from_translation(&t) * Mat4::from_scale(s);
+        let p = m * Vec4::new(1.0, 1.0, 1.0, 1.0);
+        assert!((p - Vec4::new(3.0, 5.0, 7.0, 1.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn look_at_centers_target() {
+        let eye = Vec4::new(3.0, 2.0, 5.0, 1.0);
+        let target = Vec4::new(-1.0, 0.5, 0.0, 1.0);
+        let view = Mat4::<f64>::look_at(eye, target, Vec4::new(0.0, 1.0, 0.0, 0.0));
+        let seen = view * target;
+        let distance = (target - eye).length();
+        assert!((seen - Vec4::new(0.0, 0.0, -distance, 1.0)).length() < 1e-12);
+        assert!((view * eye - Vec4::new(0.0, 0.0, 0.0, 1.0)).length() < 1e-12);
+        // World up stays up in view space.
+        assert!((view * Vec4::new(0.0, 1.0, 0.0, 0.0)).y > 0.0);
+    }
+
+    #[test]
+    fn frustum_matches_perspective_and_maps_corners() {
+        let (fov, aspect, near, far) = (1.1f64, 1.6, 0.5, 80.0);
+        let top = near * (fov / 2.0).tan();
+        let right = top * aspect;
+        let symmetric = Mat4::frustum(-right, right, -top, top, near, far);
+        let perspective = Mat4::perspective(fov, aspect, near, far);
+        assert!((0..16).all(|i| (symmetric.elements[i] - perspective.elements[i]).abs() < 1e-12));
+
+        // An off-center near rectangle maps to the corners of clip space.
+        let m = Mat4::frustum(0.1, 0.4, -0.2, 0.3, near, far);
+        for (x, y, expected) in [(0.1, -0.2, (-1.0, -1.0)), (0.4, 0.3, (1.0, 1.0))] {
+            let clip = m * Vec4::new(x, y, -near, 1.0);
+            assert!((clip.x / clip.w - expected.0).abs() < 1e-12 && (clip.y / clip.w - expected.1).abs() < 1e-12);
+            assert!((clip.z / clip.w + 1.0).abs() < 1e-12);
+        }
+        let far_point = m * Vec4::new(0.0, 0.0, -far, 1.0);
+        assert!((far_point.z / far_point.w - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn infinite_perspective_is_far_plane_limit() {
+        let infinite = Mat4::<f64>::perspective_infinite(1.0, 1.5, 0.1);
+        let distant = Mat4::<f64>::perspective(1.0, 1.5, 0.1, 1e12);
+        assert!((0..16).all(|i| (infinite.elements[i] - distant.elements[i]).abs() < 1e-9));
+        let near = infinite * Vec4::new(0.0, 0.0, -0.1, 1.0);
+        assert!((near.z / near.w + 1.0).abs() < 1e-12);
+        let far = infinite * Vec4::new(0.0, 0.0, -1e9, 1.0);
+        assert!(far.z / far.w < 1.0 && far.z / far.w > 1.0 - 1e-9);
+    }
+
+    #[test]
+    fn reversed_projections_map_near_to_one() {
+        let (near, far) = (0.5f64, 200.0);
+        let depth = |m: &Mat4<f64>, z: f64| {
+            let clip = *m * Vec4::new(0.3, -0.2, z, 1.0);
+            clip.z / clip.w
+        };
+        for (reversed, forward) in [
+            (Mat4::perspective_reversed(1.2, 1.5, near, far), Mat4::perspective(1.2, 1.5, near, far)),
+            (Mat4::orthographic_reversed(-2.0, 3.0, -1.0, 1.5, near, far), Mat4::orthographic(-2.0, 3.0, -1.0, 1.5, near, far))
+        ] {
+            assert!((depth(&reversed, -near) - 1.0).abs() < 1e-12);
+            assert!(depth(&reversed, -far).abs() < 1e-12);
+            assert!(depth(&reversed, -10.0) > depth(&reversed, -11.0));
+            // Only depth differs from the forward projection.
+            for i in [0, 1, 4, 5, 8, 9, 11, 12, 13, 15] {
+                assert_eq!(reversed.elements[i], forward.elements[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_to_one_projections_map_depth() {
+        let (near, far) = (0.5f64, 200.0);
+        let depth = |m: &Mat4<f64>, z: f64| {
+            let clip = *m * Vec4::new(0.3, -0.2, z, 1.0);
+            clip.z / clip.w
+        };
+        for (zo, gl) in [
+            (Mat4::perspective_zo(1.2, 1.5, near, far), Mat4::perspective(1.2, 1.5, near, far)),
+            (Mat4::frustum_zo(-0.2, 0.4, -0.3, 0.1, near, far), Mat4::frustum(-0.2, 0.4, -0.3, 0.1, near, far)),
+            (Mat4::orthographic_zo(-2.0, 3.0, -1.0, 1.5, near, far), Mat4::orthographic(-2.0, 3.0, -1.0, 1.5, near, far))
+        ] {
+            assert!(depth(&zo, -near).abs() < 1e-12);
+            assert!((depth(&zo, -far) - 1.0).abs() < 1e-12);
+            // Same as remapping the OpenGL depth from [-1, 1].
+            assert!((depth(&zo, -10.0) - (depth(&gl, -10.0) + 1.0) * 0.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn left_handed_variants_look_down_positive_z() {
+        let (near, far) = (0.5f64, 200.0);
+        let depth = |m: &Mat4<f64>, z: f64| {
+            let clip = *m * Vec4::new(0.3, -0.2, z, 1.0);
+            clip.z / clip.w
+        };
+        for (m, near_depth) in [
+            (Mat4::perspective_lh(1.2, 1.5, near, far), -1.0),
+            (Mat4::perspective_lh_zo(1.2, 1.5, near, far), 0.0),
+            (Mat4::orthographic_lh(-2.0, 3.0, -1.0, 1.5, near, far), -1.0),
+            (Mat4::orthographic_lh_zo(-2.0, 3.0, -1.0, 1.5, near, far), 0.0)
+        ] {
+            assert!((depth(&m, near) - near_depth).abs() < 1e-12);
+            assert!((depth(&m, far) - 1.0).abs() < 1e-12);
+        }
+        let rh = Mat4::perspective(1.2, 1.5, near, far) * Vec4::new(0.3, -0.2, -7.0, 1.0);
+        let lh = Mat4::perspective_lh(1.2, 1.5, near, far) * Vec4::new(0.3, -0.2, 7.0, 1.0);
+        assert!((rh - lh).length() < 1e-12);
+
+        // The target ends up straight ahead on +z, with world up still up.
+        let eye = Vec4::new(3.0, 2.0, 5.0, 1.0);
+        let target = Vec4::new(-1.0, 0.5, 0.0, 1.0);
+        let view = Mat4::<f64>::look_at_lh(eye, target, Vec4::new(0.0, 1.0, 0.0, 0.0));
+        let seen = view * target;
+        assert!((seen - Vec4::new(0.0, 0.0, (target - eye).length(), 1.0)).length() < 1e-12);
+        assert!((view * Vec4::new(0.0, 1.0, 0.0, 0.0)).y > 0.0);
+        assert!((view.determinant() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn shear_moves_along_other_axes() {
+        let m = Mat4::<f64>::from_shear(0.5, 0.0, 0.0, 0.0, 0.0, -2.0);
+        let p = m * Vec4::new(1.0, 2.0, 3.0, 1.0);
+        assert!((p - Vec4::new(2.0, 2.0, -1.0, 1.0)).length() < 1e-12);
+        // A single shear preserves volume; combined ones need not.
+        assert!((Mat4::<f64>::from_shear(0.3, 0.0, 0.0, 0.0, 0.0, 0.0).determinant() - 1.0).abs() < 1e-12);
+        let m = Mat4::<f64>::from_shear(0.3, -0.2, 0.4, 0.1, 0.25, -0.5);
+        let product = m * m.inverse();
+        let identity = Mat4::<f64>::identity();
+        assert!((0..16).all(|i| (product.elements[i] - identity.elements[i]).abs() < 1e-12));
+    }
+
+    #[test]
+    fn reflection_mirrors_across_plane() {
+        // The plane y = 2, given with a non-unit normal.
+        let m = Mat4::<f64>::reflection(Vec4::new(0.0, 3.0, 0.0, 0.0), 6.0);
+        let p = m * Vec4::new(1.0, 5.0, -1.0, 1.0);
+        assert!((p - Vec4::new(1.0, -1.0, -1.0, 1.0)).length() < 1e-12);
+        assert!(((m * Vec4::new(0.0, 1.0, 0.0, 0.0)) - Vec4::new(0.0, -1.0, 0.0, 0.0)).length() < 1e-12);
+
+        let normal = Vec4::new(1.0, -2.0, 0.5, 0.0);
+        let m = Mat4::<f64>::reflection(normal, 1.5);
+        assert!((m.determinant() + 1.0).abs() < 1e-12);
+        let twice = m * m;
+        let identity = Mat4::<f64>::identity();
+        assert!((0..16).all(|i| (twice.elements[i] - identity.elements[i]).abs() < 1e-12));
+        // Points on the plane stay put.
+        let on_plane = Vec4::new(1.5, 0.0, 0.0, 1.0);
+        assert!((m * on_plane - on_plane).length() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "Plane normal is zero")]
+    fn reflection_rejects_zero_normal() {
+        Mat4::<f32>::reflection(Vec4::new(0.0, 0.0, 0.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn normal_matrix_keeps_normals_perpendicular() {
+        let mut m = Mat4::<f64>::from_translation(&Vec4::new(4.0, -1.0, 2.0, 0.0));
+        m.rotate(Vec4::new(1.0, 1.0, 0.0, 0.0).normalize(), 0.6);
+        let m = m.scale(Vec4::new(3.0, 0.5, 1.0, 1.0)) * Mat4::from_shear(0.2, 0.0, 0.0, 0.0, -0.4, 0.0);
+        let tangent = Vec4::new(1.0, 2.0, -1.0, 0.0);
+        let normal = Vec4::new(2.0, -1.0, 0.0, 0.0);
+        let n = m.normal_matrix().unwrap();
+        assert!((m * tangent).dot(&(n * normal)).abs() < 1e-12);
+        assert!((m * tangent).dot(&(m * normal)).abs() > 0.1);
+        assert!((n * normal).w == 0.0 && (n * Vec4::new(0.0, 0.0, 0.0, 1.0) - Vec4::new(0.0, 0.0, 0.0, 1.0)).length() == 0.0);
+        assert_eq!(Mat4::<f64>::from_scale(Vec4::new(1.0, 0.0, 1.0, 1.0)).normal_matrix().err(), Some(MathError::Singular));
+    }
+
+    #[test]
+    fn index_by_row_and_column() {
+        let mut m = Mat4::<f32>::from_translation(&Vec4::new(1.0, 2.0, 3.0, 0.0));
+        assert_eq!([m[(0, 3)], m[(1, 3)], m[(2, 3)], m[(3, 3)]], [1.0, 2.0, 3.0, 1.0]);
+        m[(3, 2)] = -1.0;
+        assert_eq!(m.elements[11], -1.0);
+        assert_eq!((m * Vec4::new(0.0, 0.0, 2.0, 1.0)).w, -1.0);
+    }
+
+    #[test]
+    fn row_and_column_accessors() {
+        let xyzw = |v: Vec4<f32>| [v.x, v.y, v.z, v.w];
+        let mut m = Mat4::<f32>::from_translation(&Vec4::new(1.0, 2.0, 3.0, 0.0));
+        assert_eq!(xyzw(m.column(3)), xyzw(m.position()));
+        assert_eq!(xyzw(m.row(0)), [1.0, 0.0, 0.0, 1.0]);
+
+        m.set_column(1, Vec4::new(4.0, 5.0, 6.0, 7.0));
+        assert_eq!(xyzw(m.y_vector()), [4.0, 5.0, 6.0, 7.0]);
+        m.set_row(2, Vec4::new(-1.0, -2.0, -3.0, -4.0));
+        assert_eq!(xyzw(m.row(2)), [-1.0, -2.0, -3.0, -4.0]);
+        assert_eq!(xyzw(m.column(1)), [4.0, 5.0, -2.0, 7.0]);
+        assert_eq!(xyzw(m.transpose().row(1)), xyzw(m.column(1)));
+    }
+
+    #[test]
+    fn raw_array_conversions() {
+        let m = Mat4::<f32>::from_translation(&Vec4::new(1.0, 2.0, 3.0, 0.0));
+        let array = m.to_cols_array();
+        assert_eq!(&array[12..], &[1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(m.as_slice(), &array);
+        let columns = m.to_cols_array_2d();
+        assert_eq!(columns[3], [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(Mat4::from_cols_array(&array).to_cols_array(), array);
+        assert_eq!(Mat4::from_cols_array_2d(&columns).to_cols_array(), array);
+
+        let v = Vec4::from_array([1.0f32, 2.0, 3.0, 4.0]);
+        assert_eq!(v.as_slice(), &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(v.to_array(), [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn validity_predicates() {
+        let mut m = Mat4::<f32>::identity();
+        assert!(m.is_finite() && !m.is_nan() && m.is_identity(0.0));
+        m.elements[5] = 1.0 + 1e-6;
+        assert!(m.is_identity(1e-5) && !m.is_identity(1e-7));
+        m.elements[12] = f32::INFINITY;
+        assert!(!m.is_finite() && !m.is_nan() && !m.is_identity(1.0));
+        m.elements[12] = f32::NAN;
+        assert!(m.is_nan() && !m.is_identity(f32::MAX));
+
+        let q = Vec4::new(0.0f32, 0.0, 1e-7, -1.0);
+        assert!(q.is_identity_quat(1e-6) && !Vec4::new(0.0f32, 0.0, 0.0, 0.5).is_identity_quat(1e-6));
+        assert!(!Vec4::new(0.0f32, f32::NAN, 0.0, 1.0).is_finite() && Vec4::new(0.0f32, f32::NAN, 0.0, 1.0).is_nan());
+    }
+
+    #[test]
+    fn approximate_and_ulp_equality() {
+        assert_eq!(1.0f32.ulps_between(f32::from_bits(1.0f32.to_bits() + 3)), 3);
+        assert_eq!(0.0f32.ulps_between(-0.0), 0);
+        assert_eq!((-f32::from_bits(1)).ulps_between(f32::from_bits(1)), 2);
+        assert_eq!(f64::MAX.ulps_between(-f64::MAX), 2 * f64::MAX.to_bits());
+        assert_eq!(1.0f32.ulps_between(f32::NAN), u64::MAX);
+
+        let a = Mat4::<f32>::from_axis_angle(Vec4::new(0.0, 0.0, 1.0, 0.0), 0.5);
+        let mut b = a;
+        assert!(a == b);
+        b.elements[0] = f32::from_bits(b.elements[0].to_bits() + 2);
+        assert!(a != b && a.ulps_eq(&b, 2) && !a.ulps_eq(&b, 1));
+        assert!(a.approx_eq(&b, 1e-6) && !a.approx_eq(&b, 0.0));
+
+        // Large values: a fixed epsilon is too strict where ULPs are not.
+        let (u, v) = (Vec4::new(1e6f32, 0.0, 0.0, 0.0), Vec4::new(1e6f32 + 0.0625, 0.0, 0.0, 0.0));
+        assert!(u != v && !u.approx_eq(&v, 1e-3) && u.ulps_eq(&v, 1));
+        assert!(!Vec4::new(f32::NAN, 0.0, 0.0, 0.0).ulps_eq(&Vec4::new(f32::NAN, 0.0, 0.0, 0.0), u64::MAX - 1));
+    }
+
+    #[test]
+    fn axis_angle_rotations_are_right_handed() {
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let x = Vec4::new(1.0f32, 0.0, 0.0, 0.0);
+        let y = Vec4::new(0.0f32, 1.0, 0.0, 0.0);
+        let z = Vec4::new(0.0f32, 0.0, 1.0, 0.0);
+        assert!((Mat4::from_axis_angle(z, quarter) * x).approx_eq(&y, 1e-6));
+        assert!((Mat4::from_axis_angle(x, quarter) * y).approx_eq(&z, 1e-6));
+        assert!((Mat4::from_axis_angle(y, quarter) * z).approx_eq(&x, 1e-6));
+
+        let mut m = Mat4::identity();
+        m.rotate(z, quarter);
+        assert!((m * x).approx_eq(&y, 1e-6));
+        let axis = Vec4::new(0.3f32, -1.0, 0.5, 0.0).normalize();
+        let q = Vec4::new(axis.x * 0.35f32.sin(), axis.y * 0.35f32.sin(), axis.z * 0.35f32.sin(), 0.35f32.cos());
+        assert!(Mat4::from_axis_angle(axis, 0.7).approx_eq(&crate::transform::quat_to_rotation(&q), 1e-6));
+    }
+}
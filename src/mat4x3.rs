@@ -0,0 +1,46 @@
+use crate::float::Float;
+use crate::mat4::Mat4;
+use crate::matrix::Matrix;
+
+// Four rows by three columns: the transpose of a Mat3x4, or a Mat4 with
+// its translation column dropped.
+#[allow(dead_code)]
+pub type Mat4x3<T = f32> = Matrix<T, 4, 3>;
+
+#[allow(dead_code)]
+impl<T: Float> Matrix<T, 4, 3> {
+    // Drops the last column of `m`.
+    pub fn from_mat4(m: &Mat4<T>) -> Self {
+        let mut out = Self::zeroes();
+        for col in 0..3 {
+            for row in 0..4 {
+                out.columns[col][row] = m.elements[col * 4 + row];
+            }
+        }
+        out
+    }
+
+    // Restores the last column as (0, 0, 0, 1).
+    pub fn to_mat4(self) -> Mat4<T> {
+        let mut out = Mat4::identity();
+        for col in 0..3 {
+            for row in 0..4 {
+                out.elements[col * 4 + row] = self.columns[col][row];
+            }
+        }
+        out
+    }
+
+    // Column-major packing: three columns of four.
+    pub fn to_cols_array(self) -> [[T; 4]; 3] {
+        self.columns
+    }
+}
+
+impl<T: Float> std::ops::Mul<Matrix<T, 4, 3>> for Mat4<T> {
+    type Output = Matrix<T, 4, 3>;
+
+    fn mul(self, rhs: Matrix<T, 4, 3>) -> Self::Output {
+        Matrix::<T, 4, 4>::from_mat4(&self) * rhs
+    }
+}
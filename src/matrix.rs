@@ -0,0 +1,322 @@
+use crate::error::MathError;
+use crate::float::Float;
+use crate::instrument::count_op;
+use crate::mat4::Mat4;
+use crate::vector::Vector;
+
+// R x C matrix stored column-major like Mat4: `columns[col][row]`.
+#[derive(Clone, Copy)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    pub(crate) columns: [[T; R]; C],
+}
+
+#[allow(dead_code)]
+impl<T: Float, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn zeroes() -> Self {
+        Self {
+            columns: [[T::zero(); R]; C]
+        }
+    }
+
+    pub fn from_cols(columns: [[T; R]; C]) -> Self {
+        Self {
+            columns
+        }
+    }
+
+    pub fn from_rows(rows: [[T; C]; R]) -> Self {
+        let mut out = Self::zeroes();
+        for row in 0..R {
+            for col in 0..C {
+                out.columns[col][row] = rows[row][col];
+            }
+        }
+        out
+    }
+
+    pub fn rows(&self) -> usize {
+        R
+    }
+
+    pub fn cols(&self) -> usize {
+        C
+    }
+
+    pub fn column(&self, col: usize) -> Vector<T, R> {
+        Vector::new(self.columns[col])
+    }
+
+    pub fn row(&self, row: usize) -> Vector<T, C> {
+        let mut out = Vector::zero();
+        for col in 0..C {
+            out[col] = self.columns[col][row];
+        }
+        out
+    }
+
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        let mut out = Matrix::zeroes();
+        for row in 0..R {
+            for col in 0..C {
+                out.columns[row][col] = self.columns[col][row];
+            }
+        }
+        out
+    }
+
+    // Least-squares solution of self * x = b through the normal equations.
+    // Fails with `Singular` when the columns are linearly dependent.
+    pub fn least_squares(&self, b: &Vector<T, R>) -> Result<Vector<T, C>, MathError> {
+        let transposed = self.transpose();
+        (transposed * *self).solve(&(transposed * *b))
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+    pub fn identity() -> Self {
+        let mut out = Self::zeroes();
+        for i in 0..N {
+            out.columns[i][i] = T::one();
+        }
+        out
+    }
+
+    // Gaussian elimination with partial pivoting. Fails with `Singular`
+    // when the matrix is singular.
+    pub fn solve(&self, b: &Vector<T, N>) -> Result<Vector<T, N>, MathError> {
+        let mut a = *self;
+        let mut x = *b;
+        for col in 0..N {
+            let mut pivot = col;
+            for row in (col + 1)..N {
+                if a.columns[col][row].abs() > a.columns[col][pivot].abs() {
+                    pivot = row;
+                }
+            }
+            if a.columns[col][pivot] == T::zero() {
+                return Err(MathError::Singular);
+            }
+            if pivot != col {
+                for k in 0..N {
+                    a.columns[k].swap(col, pivot);
+                }
+                x.elements.swap(col, pivot);
+            }
+            for row in (col + 1)..N {
+                let factor = a.columns[col][row] / a.columns[col][col];
+                for k in col..N {
+                    let value = a.columns[k][col];
+                    a.columns[k][row] -= factor * value;
+                }
+                let value = x[col];
+                x[row] -= factor * value;
+            }
+        }
+        for row in (0..N).rev() {
+            let mut sum = x[row];
+            for k in (row + 1)..N {
+                sum -= a.columns[k][row] * x[k];
+            }
+            x[row] = sum / a.columns[row][row];
+        }
+        Ok(x)
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> Matrix<T, 4, 4> {
+    pub fn from_mat4(m: &Mat4<T>) -> Self {
+        let mut out = Self::zeroes();
+        for col in 0..4 {
+            for row in 0..4 {
+                out.columns[col][row] = m.elements[col * 4 + row];
+            }
+        }
+        out
+    }
+
+    pub fn to_mat4(self) -> Mat4<T> {
+        let mut out = Mat4::zeroes();
+        for col in 0..4 {
+            for row in 0..4 {
+                out.elements[col * 4 + row] = self.columns[col][row];
+            }
+        }
+        out
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> Default for Matrix<T, R, C> {
+    fn default() -> Self {
+        Self::zeroes()
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::Index<(usize, usize)> for Matrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.columns[index.1][index.0]
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::IndexMut<(usize, usize)> for Matrix<T, R, C> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.columns[index.1][index.0]
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize, const K: usize> std::ops::Mul<Matrix<T, C, K>> for Matrix<T, R, C> {
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Self::Output {
+        count_op!(multiplies);
+        let mut out = Matrix::zeroes();
+        for col in 0..K {
+            for row in 0..R {
+                for k in 0..C {
+                    out.columns[col][row] += self.columns[k][row] * rhs.columns[col][k];
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::Mul<Vector<T, C>> for Matrix<T, R, C> {
+    type Output = Vector<T, R>;
+
+    fn mul(self, rhs: Vector<T, C>) -> Self::Output {
+        let mut out = Vector::zero();
+        for col in 0..C {
+            for row in 0..R {
+                out[row] += self.columns[col][row] * rhs[col];
+            }
+        }
+        out
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::MulAssign<T> for Matrix<T, R, C> {
+    fn mul_assign(&mut self, rhs: T) {
+        for col in 0..C {
+            for row in 0..R {
+                self.columns[col][row] *= rhs;
+            }
+        }
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::Mul<T> for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut out = self;
+        out *= rhs;
+        out
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::AddAssign<Matrix<T, R, C>> for Matrix<T, R, C> {
+    fn add_assign(&mut self, rhs: Matrix<T, R, C>) {
+        for col in 0..C {
+            for row in 0..R {
+                self.columns[col][row] += rhs.columns[col][row];
+            }
+        }
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::Add<Matrix<T, R, C>> for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Matrix<T, R, C>) -> Self::Output {
+        let mut out = self;
+        out += rhs;
+        out
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::SubAssign<Matrix<T, R, C>> for Matrix<T, R, C> {
+    fn sub_assign(&mut self, rhs: Matrix<T, R, C>) {
+        for col in 0..C {
+            for row in 0..R {
+                self.columns[col][row] -= rhs.columns[col][row];
+            }
+        }
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::Sub<Matrix<T, R, C>> for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Matrix<T, R, C>) -> Self::Output {
+        let mut out = self;
+        out -= rhs;
+        out
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::ops::Neg for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -T::one()
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::fmt::Display for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in 0..R {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", self.row(row))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> std::fmt::Debug for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Matrix{}x{}[", R, C)?;
+        for row in 0..R {
+            if row > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", self.row(row))?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangular_product_and_solve() {
+        let a: Matrix<f64, 2, 3> = Matrix::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b: Matrix<f64, 3, 2> = Matrix::from_rows([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+        let c = a * b;
+        assert_eq!(c[(0, 0)], 58.0);
+        assert_eq!(c[(0, 1)], 64.0);
+        assert_eq!(c[(1, 0)], 139.0);
+        assert_eq!(c[(1, 1)], 154.0);
+
+        let m: Matrix<f64, 3, 3> = Matrix::from_rows([[0.0, 2.0, 1.0], [1.0, -1.0, 0.0], [3.0, 0.0, 4.0]]);
+        let x = Vector::new([1.0, -2.0, 0.5]);
+        let solved = m.solve(&(m * x)).unwrap();
+        assert!((solved - x).norm() < 1e-12);
+    }
+
+    #[test]
+    fn least_squares_line_fit() {
+        // y = 2x + 1 sampled exactly, so the fit must recover it.
+        let a: Matrix<f64, 4, 2> = Matrix::from_rows([[0.0, 1.0], [1.0, 1.0], [2.0, 1.0], [3.0, 1.0]]);
+        let b = Vector::new([1.0, 3.0, 5.0, 7.0]);
+        let fit = a.least_squares(&b).unwrap();
+        assert!((fit[0] - 2.0).abs() < 1e-12 && (fit[1] - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use crate::vec4::Vec4;
+
+// Indexed triangle mesh. Positions are points (w = 1), normals are
+// directions (w = 0), and every three indices form a counter-clockwise
+// triangle when seen from outside.
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub positions: Vec<Vec4>,
+    pub normals: Vec<Vec4>,
+    pub indices: Vec<u32>
+}
+
+#[allow(dead_code)]
+impl Mesh {
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    pub fn triangles(&self) -> impl Iterator<Item = [Vec4; 3]> + '_ {
+        self.indices.chunks_exact(3).map(|t| {
+            [self.positions[t[0] as usize], self.positions[t[1] as usize], self.positions[t[2] as usize]]
+        })
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        self.triangles().map(|[a, b, c]| (b - a).cross(&(c - a)).length() * 0.5).sum()
+    }
+
+    // Volume enclosed by a closed mesh by the divergence theorem, as the
+    // sum of signed tetrahedra from the origin to each triangle. Positive
+    // when triangles face outward, negative when the mesh is inside out.
+    // Accumulated in f64 since the terms can cancel heavily.
+    pub fn signed_volume(&self) -> f32 {
+        let volume: f64 = self.triangles().map(|[a, b, c]| {
+            let (a, b, c) = (a.cast::<f64>(), b.cast::<f64>(), c.cast::<f64>());
+            a.cross(&b).dot(&Vec4::new(c.x, c.y, c.z, 0.0))
+        }).sum();
+        (volume / 6.0) as f32
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.signed_volume().abs()
+    }
+
+    // Whether every edge is shared by exactly two triangles that traverse
+    // it in opposite directions. Such a mesh is watertight and consistently
+    // wound, so `signed_volume` is meaningful.
+    pub fn is_closed(&self) -> bool {
+        let mut edges = HashMap::new();
+        for t in self.indices.chunks_exact(3) {
+            for (a, b) in [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+                *edges.entry((a, b)).or_insert(0) += 1;
+            }
+        }
+        edges.iter().all(|(&(a, b), &count)| count == 1 && edges.get(&(b, a)) == Some(&1))
+    }
+
+    // Closed and wound counter-clockwise seen from outside.
+    pub fn is_outward_oriented(&self) -> bool {
+        self.is_closed() && self.signed_volume() > 0.0
+    }
+
+    // Generalized winding number of `p`: the signed solid angle the
+    // triangles subtend at `p` over 4pi. Close to 1 inside and 0 outside a
+    // closed outward mesh, and degrades smoothly for meshes with holes,
+    // overlaps or flipped pieces, so it works for soups too. Each solid
+    // angle uses the Van Oosterom-Strackee formula in f64.
+    pub fn winding_number(&self, p: &Vec4) -> f32 {
+        let p = p.cast::<f64>();
+        let total: f64 = self.triangles().map(|[a, b, c]| {
+            let [a, b, c] = [a, b, c].map(|v| {
+                let d = v.cast::<f64>() - p;
+                Vec4::new(d.x, d.y, d.z, 0.0)
+            });
+            let (la, lb, lc) = (a.length(), b.length(), c.length());
+            let numerator = a.dot(&b.cross(&c));
+            let denominator = la * lb * lc + a.dot(&b) * lc + a.dot(&c) * lb + b.dot(&c) * la;
+            2.0 * numerator.atan2(denominator)
+        }).sum();
+        (total / (4.0 * std::f64::consts::PI)) as f32
+    }
+
+    // Inside when the winding number rounds to at least one.
+    pub fn contains_point(&self, p: &Vec4) -> bool {
+        self.winding_number(p) >= 0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube() -> Mesh {
+        let positions = (0..8)
+            .map(|i| Vec4::new((i & 1) as f32 * 2.0, ((i >> 1) & 1) as f32 * 3.0, ((i >> 2) & 1) as f32 * 4.0, 1.0))
+            .collect();
+        // Counter-clockwise seen from outside.
+        let faces = [
+            [0, 2, 3, 1], [4, 5, 7, 6],
+            [0, 1, 5, 4], [2, 6, 7, 3],
+            [0, 4, 6, 2], [1, 3, 7, 5]
+        ];
+        let indices = faces.iter().flat_map(|f| [f[0], f[1], f[2], f[0], f[2], f[3]]).collect();
+        Mesh {
+            positions,
+            normals: Vec::new(),
+            indices
+        }
+    }
+
+    #[test]
+    fn cube_volume_and_orientation() {
+        let mut mesh = cube();
+        assert!(mesh.is_closed());
+        assert!(mesh.is_outward_oriented());
+        assert!((mesh.signed_volume() - 24.0).abs() < 1e-4);
+        assert!((mesh.surface_area() - 52.0).abs() < 1e-4);
+
+        for t in mesh.indices.chunks_exact_mut(3) {
+            t.swap(1, 2);
+        }
+        assert!(mesh.is_closed() && !mesh.is_outward_oriented());
+        assert!((mesh.signed_volume() + 24.0).abs() < 1e-4);
+
+        mesh.indices.truncate(mesh.indices.len() - 3);
+        assert!(!mesh.is_closed());
+    }
+
+    #[test]
+    fn winding_number_inside_and_outside() {
+        let mut mesh = cube();
+        let inside = Vec4::new(1.0, 1.5, 2.0, 1.0);
+        let outside = Vec4::new(3.0, 1.5, 2.0, 1.0);
+        assert!((mesh.winding_number(&inside) - 1.0).abs() < 1e-5);
+        assert!(mesh.winding_number(&outside).abs() < 1e-5);
+        assert!(mesh.contains_point(&Vec4::new(1.9, 0.1, 3.9, 1.0)));
+        assert!(!mesh.contains_point(&outside));
+
+        // Dropping one face leaves the inside still mostly enclosed.
+        mesh.indices.truncate(mesh.indices.len() - 6);
+        assert!(mesh.contains_point(&inside));
+        assert!(!mesh.contains_point(&outside));
+    }
+}
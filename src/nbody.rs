@@ -0,0 +1,290 @@
+use crate::float::Float;
+use crate::instrument::{trace_event, trace_span};
+use crate::vec4::Vec4;
+
+const MAX_DEPTH: usize = 32;
+const NO_CHILD: usize = usize::MAX;
+
+#[derive(Clone, Copy, Debug)]
+struct Node<T: Float> {
+    center: Vec4<T>,
+    half_size: T,
+    mass: T,
+    center_of_mass: Vec4<T>,
+    children: [usize; 8],
+    // Range into `BarnesHut::order`, only used by leaves.
+    first_body: usize,
+    body_count: usize
+}
+
+impl<T: Float> Node<T> {
+    fn is_leaf(&self) -> bool {
+        self.children.iter().all(|&c| c == NO_CHILD)
+    }
+
+    fn contains(&self, p: &Vec4<T>) -> bool {
+        (p.x - self.center.x).abs() <= self.half_size
+            && (p.y - self.center.y).abs() <= self.half_size
+            && (p.z - self.center.z).abs() <= self.half_size
+    }
+}
+
+// Octree of point masses used to approximate gravitational accelerations in
+// O(n log n). Positions are read from x, y and z; w is ignored.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct BarnesHut<T: Float = f32> {
+    nodes: Vec<Node<T>>,
+    order: Vec<usize>,
+    positions: Vec<Vec4<T>>,
+    masses: Vec<T>
+}
+
+fn octant<T: Float>(center: &Vec4<T>, p: &Vec4<T>) -> usize {
+    (p.x >= center.x) as usize | ((p.y >= center.y) as usize) << 1 | ((p.z >= center.z) as usize) << 2
+}
+
+#[allow(dead_code)]
+impl<T: Float> BarnesHut<T> {
+    pub fn new(positions: &[Vec4<T>], masses: &[T]) -> Self {
+        assert!(positions.len() == masses.len(), "Dimension mismatch");
+        trace_span!("barnes_hut_build", bodies = positions.len());
+        let positions: Vec<Vec4<T>> = positions.iter().map(|p| Vec4::new(p.x, p.y, p.z, T::zero())).collect();
+        let mut tree = Self {
+            nodes: Vec::new(),
+            order: (0..positions.len()).collect(),
+            positions,
+            masses: masses.to_vec()
+        };
+        if tree.positions.is_empty() {
+            return tree;
+        }
+        let mut min = tree.positions[0];
+        let mut max = tree.positions[0];
+        for p in &tree.positions {
+            for axis in 0..3 {
+                if p[axis] < min[axis] {
+                    min[axis] = p[axis];
+                }
+                if p[axis] > max[axis] {
+                    max[axis] = p[axis];
+                }
+            }
+        }
+        let half = T::from_f64(0.5);
+        let center = (min + max) * half;
+        let extent = max - min;
+        let mut half_size = T::zero();
+        for axis in 0..3 {
+            if extent[axis] * half > half_size {
+                half_size = extent[axis] * half;
+            }
+        }
+        // Every body at the same point still needs a cell with some size.
+        if half_size == T::zero() {
+            half_size = T::one();
+        }
+        let count = tree.order.len();
+        tree.build(0, count, center, half_size, 0);
+        trace_event!("barnes_hut_build finished", nodes = tree.nodes.len());
+        tree
+    }
+
+    fn build(&mut self, first: usize, count: usize, center: Vec4<T>, half_size: T, depth: usize) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            center,
+            half_size,
+            mass: T::zero(),
+            center_of_mass: Vec4::zero(),
+            children: [NO_CHILD; 8],
+            first_body: first,
+            body_count: count
+        });
+
+        if count > 1 && depth < MAX_DEPTH {
+            let bodies = &mut self.order[first..first + count];
+            let positions = &self.positions;
+            bodies.sort_unstable_by_key(|&i| octant(&center, &positions[i]));
+            let mut start = first;
+            for child in 0..8 {
+                let mut end = start;
+                while end < first + count && octant(&center, &self.positions[self.order[end]]) == child {
+                    end += 1;
+                }
+                if end > start {
+                    let quarter = half_size * T::from_f64(0.5);
+                    let offset = Vec4::new(
+                        if child & 1 != 0 { quarter } else { -quarter },
+                        if child & 2 != 0 { quarter } else { -quarter },
+                        if child & 4 != 0 { quarter } else { -quarter },
+                        T::zero()
+                    );
+                    let child_index = self.build(start, end - start, center + offset, quarter, depth + 1);
+                    self.nodes[index].children[child] = child_index;
+                }
+                start = end;
+            }
+        }
+
+        let mut mass = T::zero();
+        let mut weighted = Vec4::zero();
+        for &body in &self.order[first..first + count] {
+            mass += self.masses[body];
+            weighted += self.positions[body] * self.masses[body];
+        }
+        let node = &mut self.nodes[index];
+        node.mass = mass;
+        node.center_of_mass = if mass != T::zero() { weighted / mass } else { center };
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    // Acceleration at `point`. Cells whose size over distance is below
+    // `theta` are approximated by their center of mass; theta = 0 gives the
+    // exact direct sum. `exclude` skips one body, typically the one located
+    // at `point`.
+    pub fn acceleration_at(&self, point: &Vec4<T>, g: T, theta: T, softening: T, exclude: Option<usize>) -> Vec4<T> {
+        let point = Vec4::new(point.x, point.y, point.z, T::zero());
+        let mut acceleration = Vec4::zero();
+        if self.nodes.is_empty() {
+            return acceleration;
+        }
+        let softening_sq = softening * softening;
+        let pull = |target: &Vec4<T>, mass: T| {
+            let delta = *target - point;
+            let distance_sq = delta.dot(&delta) + softening_sq;
+            if distance_sq == T::zero() {
+                return Vec4::zero();
+            }
+            delta * (g * mass / (distance_sq * distance_sq.sqrt()))
+        };
+
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if node.is_leaf() {
+                for &body in &self.order[node.first_body..node.first_body + node.body_count] {
+                    if Some(body) != exclude {
+                        acceleration += pull(&self.positions[body], self.masses[body]);
+                    }
+                }
+                continue;
+            }
+            let delta = node.center_of_mass - point;
+            let distance = delta.length();
+            let far = !node.contains(&point) && T::from_f64(2.0) * node.half_size < theta * distance;
+            if far {
+                acceleration += pull(&node.center_of_mass, node.mass);
+            } else {
+                stack.extend(node.children.iter().copied().filter(|&c| c != NO_CHILD));
+            }
+        }
+        acceleration
+    }
+}
+
+// Send + Sync is required with or without the `rayon` feature, so turning it
+// on can't break a build.
+#[allow(dead_code)]
+impl<T: Float + Send + Sync> BarnesHut<T> {
+    // Accelerations of every body due to all the others, in input order.
+    // Runs in parallel when the `rayon` feature is enabled.
+    pub fn accelerations(&self, g: T, theta: T, softening: T) -> Vec<Vec4<T>> {
+        let body = |i: usize| self.acceleration_at(&self.positions[i], g, theta, softening, Some(i));
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            (0..self.len()).into_par_iter().map(body).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            (0..self.len()).map(body).collect()
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub fn gravitational_accelerations<T: Float + Send + Sync>(positions: &[Vec4<T>], masses: &[T], g: T, theta: T, softening: T) -> Vec<Vec4<T>> {
+    BarnesHut::new(positions, masses).accelerations(g, theta, softening)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bodies() -> (Vec<Vec4>, Vec<f32>) {
+        let mut positions = Vec::new();
+        let mut masses = Vec::new();
+        let mut seed = 12345u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0
+        };
+        for _ in 0..200 {
+            positions.push(Vec4::new(next() * 10.0, next() * 10.0, next() * 10.0, 1.0));
+            masses.push(next().abs() + 0.1);
+        }
+        // Coincident bodies must not break the tree.
+        positions.push(positions[0]);
+        masses.push(1.0);
+        (positions, masses)
+    }
+
+    #[test]
+    fn theta_zero_matches_direct_sum() {
+        let (positions, masses) = bodies();
+        let tree = BarnesHut::new(&positions, &masses);
+        let accelerations = tree.accelerations(1.0, 0.0, 0.01);
+        for i in [0, 7, 150] {
+            let mut expected = Vec4::zero();
+            for j in 0..positions.len() {
+                if i != j {
+                    let mut delta = positions[j] - positions[i];
+                    delta.w = 0.0;
+                    let distance_sq = delta.dot(&delta) + 0.0001;
+                    expected += delta * (masses[j] / (distance_sq * distance_sq.sqrt()));
+                }
+            }
+            assert!((accelerations[i] - expected).length() <= 1e-3 * expected.length());
+        }
+    }
+
+    #[test]
+    fn approximation_is_close() {
+        let (positions, masses) = bodies();
+        let tree = BarnesHut::new(&positions, &masses);
+        let exact = tree.accelerations(1.0, 0.0, 0.1);
+        let approx = tree.accelerations(1.0, 0.5, 0.1);
+        for i in 0..positions.len() {
+            assert!((exact[i] - approx[i]).length() <= 0.05 * exact[i].length());
+        }
+    }
+
+    #[test]
+    fn f64_resolves_what_f32_cannot() {
+        // Two bodies 1e-6 apart at 1e3 from the origin collapse to one point
+        // in f32; in f64 they attract along the x axis.
+        let positions = [Vec4::new(1e3, 0.0, 0.0, 0.0), Vec4::new(1e3 + 1e-6, 0.0, 0.0, 0.0)];
+        let accelerations = gravitational_accelerations(&positions, &[1.0, 1.0], 1.0, 0.5, 0.0);
+        assert!((accelerations[0].x - 1e12).abs() < 1e12 * 1e-6);
+        assert_eq!(accelerations[0].x, -accelerations[1].x);
+
+        let (positions, masses) = bodies();
+        let positions: Vec<Vec4<f64>> = positions.iter().map(|p| Vec4::new(p.x as f64, p.y as f64, p.z as f64, 0.0)).collect();
+        let masses: Vec<f64> = masses.iter().map(|&m| m as f64).collect();
+        let tree = BarnesHut::new(&positions, &masses);
+        let exact = tree.accelerations(1.0, 0.0, 0.1);
+        let approx = tree.accelerations(1.0, 0.5, 0.1);
+        for i in 0..positions.len() {
+            assert!((exact[i] - approx[i]).length() <= 0.05 * exact[i].length());
+        }
+    }
+}
@@ -0,0 +1,11 @@
+// Scalar-level building blocks: the float abstraction, number types and
+// angle helpers.
+pub use crate::angle::{lerp_angle, shortest_angle_diff, unwrap_angles, wrap_angle};
+pub use crate::complex::Complex;
+pub use crate::dual::Dual;
+pub use crate::error::MathError;
+pub use crate::f16vec4::{f16_to_f32, f32_to_f16};
+pub use crate::float::Float;
+pub use crate::parse::ParseError;
+#[cfg(feature = "rand")]
+pub use crate::random::{random_in_sphere, random_rotation, random_unit_interval, random_unit_vector};
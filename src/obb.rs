@@ -0,0 +1,93 @@
+use crate::aabb::Aabb;
+use crate::closest_point::{direction, point, ClosestPoint};
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// Oriented bounding box: `axes` are orthonormal and `half_extents` are
+// measured along them.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Obb {
+    pub center: Vec4,
+    pub axes: [Vec4; 3],
+    pub half_extents: Vec4
+}
+
+#[allow(dead_code)]
+impl Obb {
+    pub fn new(center: Vec4, axes: [Vec4; 3], half_extents: Vec4) -> Self {
+        Self {
+            center: point(&center),
+            axes: axes.map(|axis| direction(&axis)),
+            half_extents: direction(&half_extents)
+        }
+    }
+
+    // Box of `half_extents` placed by the rotation and translation of
+    // `transform`, which must not contain scale.
+    pub fn from_mat4(transform: &Mat4, half_extents: Vec4) -> Self {
+        Self::new(
+            transform.position(),
+            [transform.x_vector(), transform.y_vector(), transform.z_vector()],
+            half_extents
+        )
+    }
+
+    pub fn contains_point(&self, p: &Vec4) -> bool {
+        let d = direction(&(*p - self.center));
+        (0..3).all(|i| d.dot(&self.axes[i]).abs() <= self.half_extents[i])
+    }
+
+    fn local(&self, v: &Vec4) -> Vec4 {
+        Vec4::new(v.dot(&self.axes[0]), v.dot(&self.axes[1]), v.dot(&self.axes[2]), 0.0)
+    }
+
+    // Ray parameter of the first hit, zero when the origin is inside. The
+    // ray is moved into the box's frame and slab tested there.
+    pub fn ray_cast(&self, origin: &Vec4, d: &Vec4) -> Option<f32> {
+        let local = Aabb::new(-self.half_extents, self.half_extents);
+        let o = self.local(&direction(&(*origin - self.center)));
+        local.ray_intersection(&o, &self.local(&direction(d))).map(|(entry, _)| entry)
+    }
+
+    pub fn support(&self, d: &Vec4) -> Vec4 {
+        let mut out = self.center;
+        for i in 0..3 {
+            let sign = if self.axes[i].dot(&direction(d)) >= 0.0 { 1.0 } else { -1.0 };
+            out += self.axes[i] * (sign * self.half_extents[i]);
+        }
+        out
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        let mut extent = Vec4::zero();
+        for i in 0..3 {
+            for axis in 0..3 {
+                extent[axis] += self.axes[i][axis].abs() * self.half_extents[i];
+            }
+        }
+        Aabb::from_center_half_extents(&self.center, &extent)
+    }
+
+    pub fn volume(&self) -> f32 {
+        let h = self.half_extents;
+        8.0 * h.x * h.y * h.z
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        let h = self.half_extents;
+        8.0 * (h.x * h.y + h.y * h.z + h.z * h.x)
+    }
+}
+
+impl ClosestPoint for Obb {
+    fn closest_point(&self, p: &Vec4) -> Vec4 {
+        let d = direction(&(*p - self.center));
+        let mut out = self.center;
+        for i in 0..3 {
+            let distance = d.dot(&self.axes[i]).clamp(-self.half_extents[i], self.half_extents[i]);
+            out += self.axes[i] * distance;
+        }
+        out
+    }
+}
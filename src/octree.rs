@@ -0,0 +1,211 @@
+use crate::aabb::Aabb;
+use crate::vec4::Vec4;
+
+#[derive(Clone, Debug)]
+struct OctreeNode {
+    bounds: Aabb,
+    depth: usize,
+    // Index of the first of eight consecutive children.
+    children: Option<usize>,
+    items: Vec<usize>
+}
+
+// Octree over items with bounding boxes. Each item is stored in the deepest
+// node that fully contains it; items outside the root bounds stay in the
+// root so they are still returned by queries.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct Octree<T> {
+    nodes: Vec<OctreeNode>,
+    items: Vec<(Aabb, T)>,
+    max_depth: usize,
+    max_items: usize
+}
+
+#[allow(dead_code)]
+impl<T> Octree<T> {
+    // Nodes split once they hold more than `max_items` items, down to
+    // `max_depth` levels below the root.
+    pub fn new(bounds: Aabb, max_depth: usize, max_items: usize) -> Self {
+        Self {
+            nodes: vec![OctreeNode {
+                bounds,
+                depth: 0,
+                children: None,
+                items: Vec::new()
+            }],
+            items: Vec::new(),
+            max_depth,
+            max_items
+        }
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        self.nodes[0].bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn insert_point(&mut self, point: &Vec4, value: T) {
+        self.insert(Aabb::from_point(point), value);
+    }
+
+    pub fn insert(&mut self, bounds: Aabb, value: T) {
+        let item = self.items.len();
+        self.items.push((bounds, value));
+        let mut node = 0;
+        while let Some(child) = self.child_containing(node, &bounds) {
+            node = child;
+        }
+        self.nodes[node].items.push(item);
+        if self.nodes[node].children.is_none()
+            && self.nodes[node].items.len() > self.max_items
+            && self.nodes[node].depth < self.max_depth
+        {
+            self.split(node);
+        }
+    }
+
+    fn child_containing(&self, node: usize, bounds: &Aabb) -> Option<usize> {
+        let first = self.nodes[node].children?;
+        (first..first + 8).find(|&child| self.nodes[child].bounds.contains(bounds))
+    }
+
+    fn split(&mut self, node: usize) {
+        let bounds = self.nodes[node].bounds;
+        let center = bounds.center();
+        let depth = self.nodes[node].depth + 1;
+        let first = self.nodes.len();
+        for child in 0..8 {
+            let pick = |bit: usize, low: f32, mid: f32, high: f32| {
+                if child & bit != 0 { (mid, high) } else { (low, mid) }
+            };
+            let (x0, x1) = pick(1, bounds.min.x, center.x, bounds.max.x);
+            let (y0, y1) = pick(2, bounds.min.y, center.y, bounds.max.y);
+            let (z0, z1) = pick(4, bounds.min.z, center.z, bounds.max.z);
+            self.nodes.push(OctreeNode {
+                bounds: Aabb::new(Vec4::new(x0, y0, z0, 0.0), Vec4::new(x1, y1, z1, 0.0)),
+                depth,
+                children: None,
+                items: Vec::new()
+            });
+        }
+        self.nodes[node].children = Some(first);
+
+        let items = std::mem::take(&mut self.nodes[node].items);
+        for item in items {
+            match self.child_containing(node, &self.items[item].0) {
+                Some(child) => self.nodes[child].items.push(item),
+                None => self.nodes[node].items.push(item)
+            }
+        }
+        for child in first..first + 8 {
+            if self.nodes[child].items.len() > self.max_items && depth < self.max_depth {
+                self.split(child);
+            }
+        }
+    }
+
+    // Visits every node whose bounds pass `visit_node`, root first, and
+    // returns the items whose bounds pass `keep_item`. The root is always
+    // visited since it may hold items outside its bounds.
+    fn collect<'a>(&'a self, visit_node: impl Fn(&Aabb) -> bool, mut keep_item: impl FnMut(&'a Aabb, &'a T)) {
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            for &item in &node.items {
+                let (bounds, value) = &self.items[item];
+                keep_item(bounds, value);
+            }
+            if let Some(first) = node.children {
+                stack.extend((first..first + 8).filter(|&child| visit_node(&self.nodes[child].bounds)));
+            }
+        }
+    }
+
+    pub fn query_range(&self, region: &Aabb) -> Vec<&T> {
+        let mut out = Vec::new();
+        self.collect(|bounds| bounds.intersects(region), |bounds, value| {
+            if bounds.intersects(region) {
+                out.push(value);
+            }
+        });
+        out
+    }
+
+    // Items whose bounds are hit by the ray within `max_distance`, sorted
+    // by the distance at which the ray enters their bounds.
+    pub fn query_ray(&self, origin: &Vec4, direction: &Vec4, max_distance: f32) -> Vec<(&T, f32)> {
+        let hit = |bounds: &Aabb| match bounds.ray_intersection(origin, direction) {
+            Some((entry, _)) if entry <= max_distance => Some(entry),
+            _ => None
+        };
+        let mut out = Vec::new();
+        self.collect(|bounds| hit(bounds).is_some(), |bounds, value| {
+            if let Some(entry) = hit(bounds) {
+                out.push((value, entry));
+            }
+        });
+        out.sort_by(|a, b| a.1.total_cmp(&b.1));
+        out
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Aabb, &T)> {
+        self.items.iter().map(|(bounds, value)| (bounds, value))
+    }
+
+    // Every node as (bounds, depth, number of items stored directly in it).
+    pub fn nodes(&self) -> impl Iterator<Item = (&Aabb, usize, usize)> {
+        self.nodes.iter().map(|node| (&node.bounds, node.depth, node.items.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Octree<usize> {
+        let mut tree = Octree::new(Aabb::new(Vec4::zero(), Vec4::new(10.0, 10.0, 10.0, 0.0)), 6, 4);
+        let mut id = 0;
+        for x in 0..10 {
+            for y in 0..10 {
+                for z in 0..10 {
+                    tree.insert_point(&Vec4::new(x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5, 0.0), id);
+                    id += 1;
+                }
+            }
+        }
+        tree
+    }
+
+    #[test]
+    fn range_query_matches_brute_force() {
+        let tree = grid();
+        assert!(tree.nodes().count() > 1);
+        let region = Aabb::new(Vec4::new(2.2, 3.1, 0.0, 0.0), Vec4::new(5.9, 4.9, 2.0, 0.0));
+        let mut found: Vec<usize> = tree.query_range(&region).into_iter().copied().collect();
+        found.sort();
+        let expected: Vec<usize> = tree.iter()
+            .filter(|(bounds, _)| region.intersects(bounds))
+            .map(|(_, &id)| id)
+            .collect();
+        assert_eq!(found, expected);
+        assert_eq!(found.len(), 4 * 2 * 2);
+    }
+
+    #[test]
+    fn ray_query_sorted() {
+        let mut tree = grid();
+        tree.insert(Aabb::new(Vec4::new(-5.0, 0.0, 0.0, 0.0), Vec4::new(-4.0, 1.0, 1.0, 0.0)), 9999);
+        let hits = tree.query_ray(&Vec4::new(-10.0, 0.5, 0.5, 0.0), &Vec4::new(1.0, 0.0, 0.0, 0.0), 100.0);
+        assert_eq!(*hits[0].0, 9999);
+        assert_eq!(hits.len(), 11);
+        assert!(hits.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+}
@@ -0,0 +1,163 @@
+// Cross-checks against glam and nalgebra on randomized inputs, enabled with
+// the `oracle-tests` feature. glam pins down the graphics conventions
+// (layout, handedness, clip space); nalgebra the f64 decompositions.
+
+use crate::euler::{EulerAngles, EulerOrder};
+use crate::mat4::Mat4;
+use crate::transform::{quat_to_rotation, rotation_to_quat};
+use crate::vec4::Vec4;
+
+const CASES: usize = 200;
+
+// Xorshift, so runs are reproducible without another dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next() as f32
+    }
+
+    fn mat4(&mut self) -> Mat4<f64> {
+        let mut m = Mat4::zeroes();
+        for i in 0..16 {
+            m.elements[i] = self.next() * 2.0 - 1.0;
+        }
+        m
+    }
+
+    fn unit(&mut self) -> Vec4 {
+        loop {
+            let v = Vec4::new(self.range(-1.0, 1.0), self.range(-1.0, 1.0), self.range(-1.0, 1.0), 0.0);
+            if v.length() > 0.1 && v.length() <= 1.0 {
+                return v.normalize();
+            }
+        }
+    }
+}
+
+fn glam_mat4(m: &Mat4) -> glam::Mat4 {
+    glam::Mat4::from_cols_array(&m.elements)
+}
+
+fn glam_vec3(v: &Vec4) -> glam::Vec3 {
+    glam::Vec3::new(v.x, v.y, v.z)
+}
+
+fn nalgebra_mat4(m: &Mat4<f64>) -> nalgebra::Matrix4<f64> {
+    nalgebra::Matrix4::from_column_slice(&m.elements)
+}
+
+fn assert_close(ours: &[f32], theirs: &[f32], tolerance: f32, what: &str) {
+    let scale = theirs.iter().fold(1.0f32, |m, v| m.max(v.abs()));
+    for (i, (a, b)) in ours.iter().zip(theirs).enumerate() {
+        assert!((a - b).abs() <= tolerance * scale, "{}: element {} is {} but the oracle has {}", what, i, a, b);
+    }
+}
+
+#[test]
+fn products_and_inverses_match_glam() {
+    let mut rng = Rng(0x9e3779b97f4a7c15);
+    for _ in 0..CASES {
+        let (a, b) = (rng.mat4().cast::<f32>(), rng.mat4().cast::<f32>());
+        assert_close(&(a * b).elements, &(glam_mat4(&a) * glam_mat4(&b)).to_cols_array(), 1e-5, "product");
+        assert_close(&a.transpose().elements, &glam_mat4(&a).transpose().to_cols_array(), 0.0, "transpose");
+        let v = Vec4::new(rng.range(-5.0, 5.0), rng.range(-5.0, 5.0), rng.range(-5.0, 5.0), 1.0);
+        let theirs = glam_mat4(&a) * glam::Vec4::new(v.x, v.y, v.z, v.w);
+        let ours = a * v;
+        assert_close(&[ours.x, ours.y, ours.z, ours.w], &theirs.to_array(), 1e-5, "vector product");
+        assert_close(&[a.determinant()], &[glam_mat4(&a).determinant()], 1e-4, "determinant");
+    }
+}
+
+#[test]
+fn decompositions_match_nalgebra() {
+    let mut rng = Rng(0x2545f4914f6cdd1d);
+    for _ in 0..CASES {
+        let m = rng.mat4();
+        let theirs = nalgebra_mat4(&m);
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * (1.0 + b.abs());
+        assert!(close(m.determinant(), theirs.determinant()));
+
+        let inverse = theirs.try_inverse().unwrap();
+        let ours = m.inverse();
+        assert!((0..16).all(|i| (ours.elements[i] - inverse.as_slice()[i]).abs() <= 1e-9 * (1.0 + inverse.amax())));
+
+        let mut singular: Vec<f64> = theirs.singular_values().iter().copied().collect();
+        singular.sort_by(|a, b| b.total_cmp(a));
+        let (_, sigma, _) = m.svd();
+        assert!((0..4).all(|i| close(sigma[i], singular[i])));
+
+        let symmetric = m + m.transpose();
+        let mut eigenvalues: Vec<f64> = nalgebra_mat4(&symmetric).symmetric_eigenvalues().iter().copied().collect();
+        eigenvalues.sort_by(|a, b| b.total_cmp(a));
+        let (values, _) = symmetric.symmetric_eigen(1e-15);
+        assert!((0..4).all(|i| (values[i] - eigenvalues[i]).abs() <= 1e-9));
+
+        // R is unique up to the signs of its rows.
+        let (_, r) = m.qr();
+        let r_theirs = theirs.qr().r();
+        assert!((0..4).all(|row| (row..4).all(|col| (r.elements[col * 4 + row].abs() - r_theirs[(row, col)].abs()).abs() <= 1e-9)));
+    }
+}
+
+#[test]
+fn projections_and_views_match_glam() {
+    let mut rng = Rng(0x853c49e6748fea9b);
+    for _ in 0..CASES {
+        let (fov, aspect) = (rng.range(0.2, 2.5), rng.range(0.5, 2.5));
+        let (near, far) = (rng.range(0.01, 1.0), rng.range(10.0, 1000.0));
+        assert_close(&Mat4::perspective(fov, aspect, near, far).elements, &glam::Mat4::perspective_rh_gl(fov, aspect, near, far).to_cols_array(), 1e-5, "perspective");
+
+        let (left, bottom) = (rng.range(-10.0, -0.1), rng.range(-10.0, -0.1));
+        let (right, top) = (rng.range(0.1, 10.0), rng.range(0.1, 10.0));
+        assert_close(&Mat4::orthographic(left, right, bottom, top, near, far).elements, &glam::Mat4::orthographic_rh_gl(left, right, bottom, top, near, far).to_cols_array(), 1e-5, "orthographic");
+        assert_close(&Mat4::frustum(left, right, bottom, top, near, far).elements, &glam::Mat4::frustum_rh_gl(left, right, bottom, top, near, far).to_cols_array(), 1e-5, "frustum");
+        assert_close(&Mat4::perspective_zo(fov, aspect, near, far).elements, &glam::Mat4::perspective_rh(fov, aspect, near, far).to_cols_array(), 1e-5, "perspective_zo");
+        assert_close(&Mat4::orthographic_zo(left, right, bottom, top, near, far).elements, &glam::Mat4::orthographic_rh(left, right, bottom, top, near, far).to_cols_array(), 1e-5, "orthographic_zo");
+        assert_close(&Mat4::perspective_lh_zo(fov, aspect, near, far).elements, &glam::Mat4::perspective_lh(fov, aspect, near, far).to_cols_array(), 1e-5, "perspective_lh_zo");
+        assert_close(&Mat4::orthographic_lh_zo(left, right, bottom, top, near, far).elements, &glam::Mat4::orthographic_lh(left, right, bottom, top, near, far).to_cols_array(), 1e-5, "orthographic_lh_zo");
+        assert_close(&Mat4::frustum_zo(left, right, bottom, top, near, far).elements, &glam::Mat4::frustum_rh(left, right, bottom, top, near, far).to_cols_array(), 1e-5, "frustum_zo");
+
+        let eye = Vec4::new(rng.range(-10.0, 10.0), rng.range(-10.0, 10.0), rng.range(-10.0, 10.0), 1.0);
+        let target = eye + rng.unit() * rng.range(1.0, 10.0);
+        let up = Vec4::new(0.0, 1.0, 0.0, 0.0);
+        if (target - eye).normalize().dot(&up).abs() > 0.99 {
+            continue;
+        }
+        let theirs = glam::Mat4::look_at_rh(glam_vec3(&eye), glam_vec3(&target), glam_vec3(&up));
+        assert_close(&Mat4::look_at(eye, target, up).elements, &theirs.to_cols_array(), 1e-4, "look_at");
+        let theirs = glam::Mat4::look_at_lh(glam_vec3(&eye), glam_vec3(&target), glam_vec3(&up));
+        assert_close(&Mat4::look_at_lh(eye, target, up).elements, &theirs.to_cols_array(), 1e-4, "look_at_lh");
+    }
+}
+
+#[test]
+fn rotations_match_glam() {
+    let mut rng = Rng(0xda942042e4dd58b5);
+    for _ in 0..CASES {
+        let axis = rng.unit();
+        let angle = rng.range(-3.0, 3.0);
+        assert_close(&Mat4::from_axis_angle(axis, angle).elements, &glam::Mat4::from_axis_angle(glam_vec3(&axis), angle).to_cols_array(), 1e-5, "from_axis_angle");
+
+        let q = glam::Quat::from_axis_angle(glam_vec3(&axis), angle);
+        let ours = quat_to_rotation(&Vec4::new(q.x, q.y, q.z, q.w));
+        assert_close(&ours.elements, &glam::Mat4::from_quat(q).to_cols_array(), 1e-5, "quat_to_rotation");
+        let back = rotation_to_quat(&ours);
+        let theirs = glam::Quat::from_mat4(&glam::Mat4::from_quat(q));
+        let sign = if back.dot(&Vec4::new(theirs.x, theirs.y, theirs.z, theirs.w)) < 0.0 { -1.0 } else { 1.0 };
+        assert_close(&[back.x * sign, back.y * sign, back.z * sign, back.w * sign], &theirs.to_array(), 1e-5, "rotation_to_quat");
+
+        // Our Xyz applies x first about fixed axes, which is glam's
+        // intrinsic ZYX with the angles reversed.
+        let (x, y, z) = (rng.range(-3.0, 3.0), rng.range(-1.5, 1.5), rng.range(-3.0, 3.0));
+        let theirs = glam::Mat4::from_euler(glam::EulerRot::ZYX, z, y, x);
+        assert_close(&EulerAngles::new(x, y, z, EulerOrder::Xyz).to_mat4().elements, &theirs.to_cols_array(), 1e-5, "euler");
+    }
+}
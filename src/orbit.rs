@@ -0,0 +1,223 @@
+use crate::dvec4::DVec4;
+use crate::instrument::trace_event;
+
+const KEPLER_MAX_ITERATIONS: usize = 50;
+const DEGENERATE_EPSILON: f64 = 1e-10;
+
+// Classical orbital elements around a central body. Angles are in radians.
+// Hyperbolic orbits have eccentricity > 1 and a negative semi-major axis;
+// parabolic orbits are not supported.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OrbitalElements {
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub longitude_of_ascending_node: f64,
+    pub argument_of_periapsis: f64,
+    pub true_anomaly: f64
+}
+
+fn xyz(v: &DVec4) -> DVec4 {
+    DVec4::new(v.x, v.y, v.z, 0.0)
+}
+
+// Angle from `a` to `b`, positive when counter-clockwise around `axis`.
+fn signed_angle(a: &DVec4, b: &DVec4, axis: &DVec4) -> f64 {
+    axis.dot(&a.cross(b)).atan2(a.dot(b))
+}
+
+// Solves Kepler's equation for the eccentric anomaly (e < 1, M = E - e sin E)
+// or the hyperbolic anomaly (e > 1, M = e sinh H - H) with Newton's method.
+#[allow(dead_code)]
+pub fn solve_kepler(mean_anomaly: f64, eccentricity: f64, tolerance: f64) -> f64 {
+    let e = eccentricity;
+    if e < 1.0 {
+        let m = (mean_anomaly + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU) - std::f64::consts::PI;
+        let mut anomaly = if e < 0.8 { m } else { std::f64::consts::PI.copysign(m) };
+        let mut iterations = 0;
+        for _ in 0..KEPLER_MAX_ITERATIONS {
+            iterations += 1;
+            let step = (anomaly - e * anomaly.sin() - m) / (1.0 - e * anomaly.cos());
+            anomaly -= step;
+            if step.abs() <= tolerance {
+                break;
+            }
+        }
+        trace_event!("solve_kepler finished", iterations = iterations, residual = anomaly - e * anomaly.sin() - m);
+        anomaly + (mean_anomaly - m)
+    } else {
+        let m = mean_anomaly;
+        let mut anomaly = (m / e).asinh();
+        let mut iterations = 0;
+        for _ in 0..KEPLER_MAX_ITERATIONS {
+            iterations += 1;
+            let step = (e * anomaly.sinh() - anomaly - m) / (e * anomaly.cosh() - 1.0);
+            anomaly -= step;
+            if step.abs() <= tolerance {
+                break;
+            }
+        }
+        trace_event!("solve_kepler finished", iterations = iterations, residual = e * anomaly.sinh() - anomaly - m);
+        anomaly
+    }
+}
+
+#[allow(dead_code)]
+pub fn true_to_mean_anomaly(true_anomaly: f64, eccentricity: f64) -> f64 {
+    let e = eccentricity;
+    let half = true_anomaly / 2.0;
+    if e < 1.0 {
+        let anomaly = 2.0 * ((1.0 - e).sqrt() * half.sin()).atan2((1.0 + e).sqrt() * half.cos());
+        anomaly - e * anomaly.sin()
+    } else {
+        let anomaly = 2.0 * (((e - 1.0) / (e + 1.0)).sqrt() * half.tan()).atanh();
+        e * anomaly.sinh() - anomaly
+    }
+}
+
+#[allow(dead_code)]
+pub fn mean_to_true_anomaly(mean_anomaly: f64, eccentricity: f64, tolerance: f64) -> f64 {
+    let e = eccentricity;
+    let anomaly = solve_kepler(mean_anomaly, e, tolerance);
+    if e < 1.0 {
+        2.0 * ((1.0 + e).sqrt() * (anomaly / 2.0).sin()).atan2((1.0 - e).sqrt() * (anomaly / 2.0).cos())
+    } else {
+        2.0 * (((e + 1.0) / (e - 1.0)).sqrt() * (anomaly / 2.0).tanh()).atan()
+    }
+}
+
+#[allow(dead_code)]
+impl OrbitalElements {
+    // Builds the elements from a position and velocity relative to a body
+    // with gravitational parameter `mu`. The w components are ignored.
+    // For circular orbits the argument of periapsis is zero and the true
+    // anomaly is measured from the ascending node; for equatorial orbits the
+    // ascending node is taken along +x.
+    pub fn from_state(position: &DVec4, velocity: &DVec4, mu: f64) -> Self {
+        let r = xyz(position);
+        let v = xyz(velocity);
+        let r_length = r.length();
+        let h = r.cross(&v);
+        let h_hat = h.normalize();
+        let node = DVec4::new(-h.y, h.x, 0.0, 0.0);
+        let e_vec = (r * (v.dot(&v) - mu / r_length) - v * r.dot(&v)) / mu;
+        let eccentricity = e_vec.length();
+        let energy = v.dot(&v) / 2.0 - mu / r_length;
+
+        let (longitude_of_ascending_node, reference) = if node.length() > DEGENERATE_EPSILON * h.length() {
+            (node.y.atan2(node.x), node.normalize())
+        } else {
+            (0.0, DVec4::new(1.0, 0.0, 0.0, 0.0))
+        };
+        let (argument_of_periapsis, true_anomaly) = if eccentricity > DEGENERATE_EPSILON {
+            (signed_angle(&reference, &e_vec, &h_hat), signed_angle(&e_vec, &r, &h_hat))
+        } else {
+            (0.0, signed_angle(&reference, &r, &h_hat))
+        };
+
+        Self {
+            semi_major_axis: -mu / (2.0 * energy),
+            eccentricity,
+            inclination: (h.z / h.length()).clamp(-1.0, 1.0).acos(),
+            longitude_of_ascending_node: longitude_of_ascending_node.rem_euclid(std::f64::consts::TAU),
+            argument_of_periapsis: argument_of_periapsis.rem_euclid(std::f64::consts::TAU),
+            true_anomaly
+        }
+    }
+
+    // Returns (position, velocity) with w = 0.
+    pub fn to_state(self, mu: f64) -> (DVec4, DVec4) {
+        let e = self.eccentricity;
+        let p = self.semi_major_axis * (1.0 - e * e);
+        let (sin_nu, cos_nu) = self.true_anomaly.sin_cos();
+        let r = p / (1.0 + e * cos_nu);
+        let speed = (mu / p).sqrt();
+        let position = (r * cos_nu, r * sin_nu);
+        let velocity = (-speed * sin_nu, speed * (e + cos_nu));
+
+        let (sin_o, cos_o) = self.longitude_of_ascending_node.sin_cos();
+        let (sin_w, cos_w) = self.argument_of_periapsis.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+        let p_axis = DVec4::new(cos_o * cos_w - sin_o * sin_w * cos_i, sin_o * cos_w + cos_o * sin_w * cos_i, sin_w * sin_i, 0.0);
+        let q_axis = DVec4::new(-cos_o * sin_w - sin_o * cos_w * cos_i, -sin_o * sin_w + cos_o * cos_w * cos_i, cos_w * sin_i, 0.0);
+        (
+            p_axis * position.0 + q_axis * position.1,
+            p_axis * velocity.0 + q_axis * velocity.1
+        )
+    }
+
+    // Orbital period, only meaningful for elliptic orbits.
+    pub fn period(&self, mu: f64) -> f64 {
+        std::f64::consts::TAU * (self.semi_major_axis.powi(3) / mu).sqrt()
+    }
+
+    pub fn mean_motion(&self, mu: f64) -> f64 {
+        (mu / self.semi_major_axis.abs().powi(3)).sqrt()
+    }
+
+    pub fn mean_anomaly(&self) -> f64 {
+        true_to_mean_anomaly(self.true_anomaly, self.eccentricity)
+    }
+
+    // Advances the orbit by `dt` under two-body motion.
+    pub fn propagate(&self, mu: f64, dt: f64) -> Self {
+        let mean_anomaly = self.mean_anomaly() + self.mean_motion(mu) * dt;
+        Self {
+            true_anomaly: mean_to_true_anomaly(mean_anomaly, self.eccentricity, 1e-14),
+            ..*self
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub fn propagate_state(position: &DVec4, velocity: &DVec4, mu: f64, dt: f64) -> (DVec4, DVec4) {
+    OrbitalElements::from_state(position, velocity, mu).propagate(mu, dt).to_state(mu)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MU_EARTH: f64 = 3.986004418e14;
+
+    fn assert_close(a: &DVec4, b: &DVec4, tolerance: f64) {
+        assert!((*a - *b).length() <= tolerance * b.length(), "{} != {}", a, b);
+    }
+
+    #[test]
+    fn state_round_trip() {
+        let cases = [
+            (DVec4::new(7.0e6, 1.0e5, 3.0e5, 0.0), DVec4::new(-100.0, 7.2e3, 1.5e3, 0.0)),
+            (DVec4::new(-6.5e6, 2.0e6, -1.0e6, 0.0), DVec4::new(1.0e3, 9.0e3, 6.0e3, 0.0)),
+            (DVec4::new(8.0e6, 0.0, 0.0, 0.0), DVec4::new(0.0, -7.5e3, 0.0, 0.0))
+        ];
+        for (position, velocity) in cases {
+            let elements = OrbitalElements::from_state(&position, &velocity, MU_EARTH);
+            let (p, v) = elements.to_state(MU_EARTH);
+            assert_close(&p, &position, 1e-9);
+            assert_close(&v, &velocity, 1e-9);
+        }
+    }
+
+    #[test]
+    fn full_period_returns_to_start() {
+        let position = DVec4::new(7.0e6, 1.0e5, 3.0e5, 0.0);
+        let velocity = DVec4::new(-100.0, 7.9e3, 1.5e3, 0.0);
+        let period = OrbitalElements::from_state(&position, &velocity, MU_EARTH).period(MU_EARTH);
+        let (p, v) = propagate_state(&position, &velocity, MU_EARTH, period);
+        assert_close(&p, &position, 1e-8);
+        assert_close(&v, &velocity, 1e-8);
+    }
+
+    #[test]
+    fn kepler_equation() {
+        for &e in &[0.0, 0.3, 0.95, 1.5] {
+            for &m in &[-2.0, 0.1, 1.0, 3.0, 7.0] {
+                let anomaly = solve_kepler(m, e, 1e-14);
+                let residual = if e < 1.0 { anomaly - e * anomaly.sin() } else { e * anomaly.sinh() - anomaly };
+                assert!((residual - m).abs() < 1e-10);
+            }
+        }
+    }
+}
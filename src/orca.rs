@@ -0,0 +1,259 @@
+use crate::vector::Vector;
+
+// Optimal Reciprocal Collision Avoidance (van den Berg et al., "Reciprocal
+// n-Body Collision Avoidance"), following the structure of the RVO2
+// library. Each neighbour constrains the agent's next velocity to a half
+// plane, chosen so both agents take half the responsibility for avoiding
+// each other; a small 2D linear program then finds the allowed velocity
+// closest to the preferred one. Positions and velocities are (x, y).
+
+type Vec2 = Vector<f32, 2>;
+
+const EPSILON: f32 = 1e-5;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OrcaAgent {
+    pub position: (f32, f32),
+    pub velocity: (f32, f32),
+    pub radius: f32
+}
+
+#[allow(dead_code)]
+impl OrcaAgent {
+    pub fn new(position: (f32, f32), velocity: (f32, f32), radius: f32) -> Self {
+        Self {
+            position,
+            velocity,
+            radius
+        }
+    }
+}
+
+// Allowed velocities lie to the left of `direction` through `point`.
+#[derive(Clone, Copy)]
+struct Line {
+    point: Vec2,
+    direction: Vec2
+}
+
+fn vec2(p: (f32, f32)) -> Vec2 {
+    Vector::new([p.0, p.1])
+}
+
+fn det(a: Vec2, b: Vec2) -> f32 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+// Half plane of velocities for `agent` that avoid `other` for at least
+// `time_horizon`, or resolve an existing overlap within one `time_step`.
+fn orca_line(agent: &OrcaAgent, other: &OrcaAgent, time_horizon: f32, time_step: f32) -> Line {
+    let relative_position = vec2(other.position) - vec2(agent.position);
+    let relative_velocity = vec2(agent.velocity) - vec2(other.velocity);
+    let distance_squared = relative_position.norm_squared();
+    let radius = agent.radius + other.radius;
+    let radius_squared = radius * radius;
+
+    let (direction, u) = if distance_squared > radius_squared {
+        // Vector from the cutoff circle's center to the relative velocity.
+        let w = relative_velocity - relative_position * (1.0 / time_horizon);
+        let w_length_squared = w.norm_squared();
+        let dot = w.dot(&relative_position);
+        if dot < 0.0 && dot * dot > radius_squared * w_length_squared {
+            // Closest to the cutoff circle.
+            let w_length = w_length_squared.sqrt();
+            let unit_w = w * (1.0 / w_length);
+            (Vector::new([unit_w[1], -unit_w[0]]), unit_w * (radius / time_horizon - w_length))
+        } else {
+            // Closest to one of the cone's legs.
+            let leg = (distance_squared - radius_squared).sqrt();
+            let (x, y) = (relative_position[0], relative_position[1]);
+            let direction = if det(relative_position, w) > 0.0 {
+                Vector::new([x * leg - y * radius, x * radius + y * leg]) * (1.0 / distance_squared)
+            } else {
+                -Vector::new([x * leg + y * radius, -x * radius + y * leg]) * (1.0 / distance_squared)
+            };
+            (direction, direction * relative_velocity.dot(&direction) - relative_velocity)
+        }
+    } else {
+        // Already overlapping: push apart within one step.
+        let w = relative_velocity - relative_position * (1.0 / time_step);
+        let w_length = w.norm();
+        let unit_w = if w_length > 0.0 { w * (1.0 / w_length) } else { Vector::new([-1.0, 0.0]) };
+        (Vector::new([unit_w[1], -unit_w[0]]), unit_w * (radius / time_step - w_length))
+    };
+    Line {
+        point: vec2(agent.velocity) + u * 0.5,
+        direction
+    }
+}
+
+// Optimizes along line `index` within the speed circle and the half planes
+// before it. False when they leave nothing on the line.
+fn linear_program_1(lines: &[Line], index: usize, radius: f32, optimal: Vec2, direction_only: bool, result: &mut Vec2) -> bool {
+    let line = lines[index];
+    let dot = line.point.dot(&line.direction);
+    let discriminant = dot * dot + radius * radius - line.point.norm_squared();
+    if discriminant < 0.0 {
+        return false;
+    }
+    let root = discriminant.sqrt();
+    let (mut t_left, mut t_right) = (-dot - root, -dot + root);
+    for other in &lines[..index] {
+        let denominator = det(line.direction, other.direction);
+        let numerator = det(other.direction, line.point - other.point);
+        if denominator.abs() <= EPSILON {
+            if numerator < 0.0 {
+                return false;
+            }
+            continue;
+        }
+        let t = numerator / denominator;
+        if denominator >= 0.0 {
+            t_right = t_right.min(t);
+        } else {
+            t_left = t_left.max(t);
+        }
+        if t_left > t_right {
+            return false;
+        }
+    }
+    let t = if direction_only {
+        if optimal.dot(&line.direction) > 0.0 { t_right } else { t_left }
+    } else {
+        line.direction.dot(&(optimal - line.point)).clamp(t_left, t_right)
+    };
+    *result = line.point + line.direction * t;
+    true
+}
+
+// Velocity closest to `optimal` (or furthest along it when `direction_only`)
+// inside the speed circle and all half planes. Returns the index of the
+// first line that made the program infeasible, or `lines.len()`.
+fn linear_program_2(lines: &[Line], radius: f32, optimal: Vec2, direction_only: bool, result: &mut Vec2) -> usize {
+    *result = if direction_only {
+        optimal * radius
+    } else if optimal.norm_squared() > radius * radius {
+        optimal.normalize() * radius
+    } else {
+        optimal
+    };
+    for i in 0..lines.len() {
+        if det(lines[i].direction, lines[i].point - *result) > 0.0 {
+            let previous = *result;
+            if !linear_program_1(lines, i, radius, optimal, direction_only, result) {
+                *result = previous;
+                return i;
+            }
+        }
+    }
+    lines.len()
+}
+
+// Fallback when the half planes leave no velocity: minimizes the largest
+// violation among lines from `first` on, in the 3D sense of RVO2.
+fn linear_program_3(lines: &[Line], first: usize, radius: f32, result: &mut Vec2) {
+    let mut distance = 0.0;
+    for i in first..lines.len() {
+        if det(lines[i].direction, lines[i].point - *result) <= distance {
+            continue;
+        }
+        let mut projected = Vec::with_capacity(i);
+        for j in 0..i {
+            let determinant = det(lines[i].direction, lines[j].direction);
+            let point = if determinant.abs() <= EPSILON {
+                if lines[i].direction.dot(&lines[j].direction) > 0.0 {
+                    continue;
+                }
+                (lines[i].point + lines[j].point) * 0.5
+            } else {
+                lines[i].point + lines[i].direction * (det(lines[j].direction, lines[i].point - lines[j].point) / determinant)
+            };
+            projected.push(Line {
+                point,
+                direction: (lines[j].direction - lines[i].direction).normalize()
+            });
+        }
+        let previous = *result;
+        let inward = Vector::new([-lines[i].direction[1], lines[i].direction[0]]);
+        if linear_program_2(&projected, radius, inward, true, result) < projected.len() {
+            *result = previous;
+        }
+        distance = det(lines[i].direction, lines[i].point - *result);
+    }
+}
+
+// New velocity for `agent`, as close to `preferred` as avoiding every
+// neighbour for `time_horizon` seconds allows, and no faster than
+// `max_speed`. Neighbours are assumed to run the same step, which is what
+// lets each take only half the avoidance. When the agents are too crowded
+// for any velocity to be safe, the one that least violates the constraints
+// is returned.
+#[allow(dead_code)]
+pub fn orca_velocity(agent: &OrcaAgent, preferred: (f32, f32), neighbours: &[OrcaAgent], max_speed: f32, time_horizon: f32, time_step: f32) -> (f32, f32) {
+    let lines: Vec<Line> = neighbours.iter().map(|other| orca_line(agent, other, time_horizon, time_step)).collect();
+    let mut result = Vector::zero();
+    let failed = linear_program_2(&lines, max_speed, vec2(preferred), false, &mut result);
+    if failed < lines.len() {
+        linear_program_3(&lines, failed, max_speed, &mut result);
+    }
+    (result[0], result[1])
+}
+
+// `orca_velocity` for every agent against all the others. Quadratic, so
+// large crowds should gather nearby neighbours with a spatial structure
+// and call `orca_velocity` directly.
+#[allow(dead_code)]
+pub fn orca_step(agents: &[OrcaAgent], preferred: &[(f32, f32)], max_speed: f32, time_horizon: f32, time_step: f32) -> Vec<(f32, f32)> {
+    assert!(preferred.len() == agents.len(), "Dimension mismatch");
+    (0..agents.len()).map(|i| {
+        let neighbours: Vec<OrcaAgent> = agents.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, a)| *a).collect();
+        orca_velocity(&agents[i], preferred[i], &neighbours, max_speed, time_horizon, time_step)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_agent_keeps_preferred_velocity() {
+        let agent = OrcaAgent::new((0.0, 0.0), (0.0, 0.0), 0.5);
+        assert_eq!(orca_velocity(&agent, (1.0, 0.5), &[], 2.0, 2.0, 0.1), (1.0, 0.5));
+        let (x, y) = orca_velocity(&agent, (3.0, 4.0), &[], 2.0, 2.0, 0.1);
+        assert!((x - 1.2).abs() < 1e-6 && (y - 1.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn agents_swap_without_colliding() {
+        // Four agents near a circle each heading for the opposite point,
+        // slightly off so the symmetric deadlock can't happen.
+        let goals = [(5.0, 0.0), (0.0, 5.0), (-5.0, 0.0), (0.0, -5.0)];
+        let mut agents: Vec<OrcaAgent> = goals.iter().enumerate()
+            .map(|(i, &(x, y))| OrcaAgent::new((-x + 0.1 * i as f32, -y + 0.05), (0.0, 0.0), 0.5))
+            .collect();
+        let time_step = 0.1;
+        for _ in 0..300 {
+            let preferred: Vec<(f32, f32)> = agents.iter().zip(&goals).map(|(a, g)| {
+                let (dx, dy) = (g.0 - a.position.0, g.1 - a.position.1);
+                let length = (dx * dx + dy * dy).sqrt();
+                if length > 1.0 { (dx / length, dy / length) } else { (dx, dy) }
+            }).collect();
+            let velocities = orca_step(&agents, &preferred, 1.0, 2.0, time_step);
+            for (agent, velocity) in agents.iter_mut().zip(velocities) {
+                agent.velocity = velocity;
+                agent.position = (agent.position.0 + velocity.0 * time_step, agent.position.1 + velocity.1 * time_step);
+            }
+            for i in 0..agents.len() {
+                for j in (i + 1)..agents.len() {
+                    let (a, b) = (agents[i].position, agents[j].position);
+                    let distance = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+                    assert!(distance > 1.0 - 1e-3, "{} {} {}", i, j, distance);
+                }
+            }
+        }
+        for (agent, goal) in agents.iter().zip(&goals) {
+            assert!((agent.position.0 - goal.0).abs() < 0.1 && (agent.position.1 - goal.1).abs() < 0.1, "{:?}", agent.position);
+        }
+    }
+}
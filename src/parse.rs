@@ -0,0 +1,135 @@
+use std::str::FromStr;
+
+use crate::float::Float;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// Why a string could not be parsed back into a vector or matrix.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    // Missing or unbalanced brackets.
+    Syntax,
+    // A component is not a number.
+    InvalidNumber,
+    // Too few or too many components or rows.
+    WrongLength
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            ParseError::Syntax => "Unbalanced or missing brackets",
+            ParseError::InvalidNumber => "Component is not a number",
+            ParseError::WrongLength => "Wrong number of components"
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Exactly N comma-separated numbers, whitespace around each ignored.
+fn parse_components<T: Float + FromStr, const N: usize>(s: &str) -> Result<[T; N], ParseError> {
+    if s.trim().is_empty() {
+        return Err(ParseError::WrongLength);
+    }
+    let mut out = [T::zero(); N];
+    let mut parts = s.split(',');
+    for value in out.iter_mut() {
+        let part = parts.next().ok_or(ParseError::WrongLength)?;
+        *value = part.trim().parse().map_err(|_| ParseError::InvalidNumber)?;
+    }
+    if parts.next().is_some() {
+        return Err(ParseError::WrongLength);
+    }
+    Ok(out)
+}
+
+// Parses the `Display` form `(x, y, z, w)`. The parentheses are optional.
+impl<T: Float + FromStr> FromStr for Vec4<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let inner = match s.strip_prefix('(') {
+            Some(rest) => rest.strip_suffix(')').ok_or(ParseError::Syntax)?,
+            None if s.ends_with(')') => return Err(ParseError::Syntax),
+            None => s
+        };
+        let [x, y, z, w] = parse_components(inner)?;
+        Ok(Self::new(x, y, z, w))
+    }
+}
+
+// Parses the `Display` form: four bracketed rows `[a, b, c, d]` separated
+// by whitespace.
+impl<T: Float + FromStr> FromStr for Mat4<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut out = Self::zeroes();
+        let mut rest = s.trim();
+        for row in 0..4 {
+            if rest.is_empty() {
+                return Err(ParseError::WrongLength);
+            }
+            let (inner, tail) = rest.strip_prefix('[')
+                .and_then(|r| r.split_once(']'))
+                .ok_or(ParseError::Syntax)?;
+            let values: [T; 4] = parse_components(inner)?;
+            for (col, value) in values.into_iter().enumerate() {
+                out.elements[col * 4 + row] = value;
+            }
+            rest = tail.trim_start();
+        }
+        match rest.chars().next() {
+            None => Ok(out),
+            Some('[') => Err(ParseError::WrongLength),
+            Some(_) => Err(ParseError::Syntax)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_round_trips() {
+        let v = Vec4::new(1.5f32, -0.1, 3e-8, 1e30);
+        let parsed: Vec4 = v.to_string().parse().unwrap();
+        assert_eq!([parsed.x, parsed.y, parsed.z, parsed.w], [v.x, v.y, v.z, v.w]);
+        let parsed: Vec4<f64> = " 1, 2 ,3,4 ".parse().unwrap();
+        assert_eq!([parsed.x, parsed.y, parsed.z, parsed.w], [1.0, 2.0, 3.0, 4.0]);
+
+        let mut m = Mat4::<f64>::zeroes();
+        for (i, e) in m.elements.iter_mut().enumerate() {
+            *e = i as f64 * 0.1 - 0.7;
+        }
+        let parsed: Mat4<f64> = m.to_string().parse().unwrap();
+        assert_eq!(parsed.elements, m.elements);
+    }
+
+    #[test]
+    fn malformed_input_is_an_error() {
+        for s in ["(", ")", "(1, 2, 3, 4", "1, 2, 3, 4)"] {
+            assert_eq!(s.parse::<Vec4>().err(), Some(ParseError::Syntax), "{s:?}");
+        }
+        assert_eq!("".parse::<Vec4>().err(), Some(ParseError::WrongLength));
+        assert_eq!("(1, 2, 3)".parse::<Vec4>().err(), Some(ParseError::WrongLength));
+        assert_eq!("(1, 2, 3, 4, 5)".parse::<Vec4>().err(), Some(ParseError::WrongLength));
+        assert_eq!("(1, 2, x, 4)".parse::<Vec4>().err(), Some(ParseError::InvalidNumber));
+        assert_eq!("(1, 2, , 4)".parse::<Vec4>().err(), Some(ParseError::InvalidNumber));
+        assert_eq!("(\u{e9})".parse::<Vec4>().err(), Some(ParseError::InvalidNumber));
+        assert!("(NaN, inf, -inf, 0)".parse::<Vec4>().unwrap().x.is_nan());
+
+        let rows = "[1, 0, 0, 0]\n[0, 1, 0, 0]\n[0, 0, 1, 0]";
+        assert_eq!(rows.parse::<Mat4>().err(), Some(ParseError::WrongLength));
+        assert_eq!(format!("{rows}\n[0, 0, 0, 1]\n[0, 0, 0, 1]").parse::<Mat4>().err(), Some(ParseError::WrongLength));
+        assert_eq!(format!("{rows}\n[0, 0, 0, 1").parse::<Mat4>().err(), Some(ParseError::Syntax));
+        assert_eq!(format!("{rows}\n[0, 0, 0, 1] x").parse::<Mat4>().err(), Some(ParseError::Syntax));
+        assert_eq!(format!("{rows}\n[0, 0, 1]").parse::<Mat4>().err(), Some(ParseError::WrongLength));
+        assert!(format!("{rows} [0, 0, 0, 1]").parse::<Mat4>().is_ok());
+    }
+}
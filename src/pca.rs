@@ -0,0 +1,42 @@
+use crate::covariance::CovarianceAccumulator;
+use crate::matrix::Matrix;
+use crate::vec4::Vec4;
+use crate::vector::Vector;
+
+// Principal component analysis of a 3D point set. Returns the mean
+// (w = 1), a rotation whose columns are the principal axes ordered by
+// decreasing variance, and the variance along each axis. The axes form a
+// right-handed frame. None for an empty set or one with a NaN or infinite
+// coordinate. Use `CovarianceAccumulator` directly when the points arrive
+// over time.
+#[allow(dead_code)]
+pub fn pca(points: &[Vec4]) -> Option<(Vec4, Matrix<f32, 3, 3>, Vector<f32, 3>)> {
+    if !points.iter().all(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite()) {
+        return None;
+    }
+    CovarianceAccumulator::from_points(points).pca()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_long_axis() {
+        // Points spread along (1, 1, 0) with a little thickness along z.
+        let points: Vec<Vec4> = (0..50)
+            .map(|i| {
+                let t = i as f32 / 49.0 * 2.0 - 1.0;
+                Vec4::new(10.0 + t * 3.0, -5.0 + t * 3.0, if i % 2 == 0 { 0.1 } else { -0.1 }, 1.0)
+            })
+            .collect();
+        let (mean, axes, variances) = pca(&points).unwrap();
+        assert!((mean - Vec4::new(10.0, -5.0, 0.0, 1.0)).length() < 1e-4);
+        let long = axes.column(0);
+        assert!((long[0].abs() - 0.5f32.sqrt()).abs() < 1e-4 && (long[0] - long[1]).abs() < 1e-4);
+        assert!(variances[0] > variances[1] && variances[1] >= variances[2]);
+        // The thickness along z is the second largest spread.
+        assert!((axes.column(1)[2].abs() - 1.0).abs() < 1e-4);
+        assert!(pca(&[]).is_none());
+    }
+}
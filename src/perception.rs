@@ -0,0 +1,88 @@
+use crate::transform::Transform;
+use crate::vec4::Vec4;
+
+// The observer looks down its local -z axis, the same convention as
+// `Mat4::view`, so a camera transform works unchanged.
+fn eye_and_forward(observer: &Transform) -> (Vec4, Vec4) {
+    let forward = observer.transform_vector(&Vec4::new(0.0, 0.0, -1.0, 0.0));
+    let eye = Vec4::new(observer.translation.x, observer.translation.y, observer.translation.z, 1.0);
+    (eye, forward.normalize())
+}
+
+fn in_cone(eye: &Vec4, forward: &Vec4, cos_half_angle: f32, max_distance: f32, target: &Vec4) -> bool {
+    let offset = Vec4::new(target.x - eye.x, target.y - eye.y, target.z - eye.z, 0.0);
+    let distance = offset.length();
+    distance <= max_distance && (distance == 0.0 || offset.dot(forward) >= cos_half_angle * distance)
+}
+
+// Whether `target` lies within `half_angle` radians of the observer's
+// forward direction and no further than `max_distance` from it. A half
+// angle of pi or more sees all around.
+#[allow(dead_code)]
+pub fn in_fov(observer: &Transform, target: &Vec4, half_angle: f32, max_distance: f32) -> bool {
+    let (eye, forward) = eye_and_forward(observer);
+    in_cone(&eye, &forward, half_angle.min(std::f32::consts::PI).cos(), max_distance, target)
+}
+
+// `in_fov` for many targets, sharing the observer setup.
+#[allow(dead_code)]
+pub fn in_fov_batch(observer: &Transform, targets: &[Vec4], half_angle: f32, max_distance: f32) -> Vec<bool> {
+    let (eye, forward) = eye_and_forward(observer);
+    let cos_half_angle = half_angle.min(std::f32::consts::PI).cos();
+    targets.iter().map(|target| in_cone(&eye, &forward, cos_half_angle, max_distance, target)).collect()
+}
+
+// Line of sight: `in_fov`, then `occluded(eye, target)` decides whether
+// anything blocks the ray, typically a physics or BVH raycast. The callback
+// only runs for targets inside the cone, so the cheap test culls first.
+#[allow(dead_code)]
+pub fn can_see(observer: &Transform, target: &Vec4, half_angle: f32, max_distance: f32, mut occluded: impl FnMut(&Vec4, &Vec4) -> bool) -> bool {
+    let (eye, forward) = eye_and_forward(observer);
+    in_cone(&eye, &forward, half_angle.min(std::f32::consts::PI).cos(), max_distance, target) && !occluded(&eye, target)
+}
+
+// Indices of the targets `can_see` accepts, in order.
+#[allow(dead_code)]
+pub fn visible_targets(observer: &Transform, targets: &[Vec4], half_angle: f32, max_distance: f32, mut occluded: impl FnMut(&Vec4, &Vec4) -> bool) -> Vec<usize> {
+    let (eye, forward) = eye_and_forward(observer);
+    let cos_half_angle = half_angle.min(std::f32::consts::PI).cos();
+    (0..targets.len())
+        .filter(|&i| in_cone(&eye, &forward, cos_half_angle, max_distance, &targets[i]) && !occluded(&eye, &targets[i]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mat4::Mat4;
+
+    #[test]
+    fn cone_distance_and_occlusion() {
+        // Standing at x = 10 and turned to face -x.
+        let mut rotation = Mat4::identity();
+        rotation.rotate(Vec4::new(0.0, 1.0, 0.0, 0.0), std::f32::consts::FRAC_PI_2);
+        let observer = Transform::new(Vec4::new(10.0, 0.0, 0.0, 0.0), rotation, Vec4::one());
+        let quarter = std::f32::consts::FRAC_PI_4;
+
+        let targets = [
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+            Vec4::new(5.0, 4.0, 0.0, 1.0),
+            Vec4::new(5.0, 6.0, 0.0, 1.0),
+            Vec4::new(20.0, 0.0, 0.0, 1.0),
+            Vec4::new(-5.0, 0.0, 0.0, 1.0)
+        ];
+        assert_eq!(in_fov_batch(&observer, &targets, quarter, 12.0), vec![true, true, false, false, false]);
+        assert!(in_fov(&observer, &targets[3], std::f32::consts::PI, 12.0));
+        assert!(in_fov(&observer, &Vec4::new(10.0, 0.0, 0.0, 1.0), 0.1, 1.0));
+
+        // A wall at x = 3 blocks everything beyond it.
+        let mut calls = 0;
+        let visible = visible_targets(&observer, &targets, quarter, 100.0, |_, target| {
+            calls += 1;
+            target.x < 3.0
+        });
+        assert_eq!(visible, vec![1]);
+        assert_eq!(calls, 3);
+        assert!(!can_see(&observer, &targets[0], quarter, 100.0, |eye, target| eye.x > 3.0 && target.x < 3.0));
+    }
+}
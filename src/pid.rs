@@ -0,0 +1,169 @@
+use crate::vec4::Vec4;
+
+pub trait PidValue: Copy + std::ops::Add<Output = Self> + std::ops::Sub<Output = Self> + std::ops::Mul<f32, Output = Self> {
+    fn zero() -> Self;
+
+    // Limits the magnitude of the value, used for integral windup clamping.
+    fn clamp_magnitude(self, limit: f32) -> Self;
+}
+
+impl PidValue for f32 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn clamp_magnitude(self, limit: f32) -> Self {
+        self.clamp(-limit, limit)
+    }
+}
+
+impl PidValue for Vec4 {
+    fn zero() -> Self {
+        Vec4::zero()
+    }
+
+    fn clamp_magnitude(self, limit: f32) -> Self {
+        let length = self.length();
+        if length > limit {
+            self * (limit / length)
+        } else {
+            self
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Pid<T: PidValue> {
+    pub kp: f32,
+    pub ki: f32,
+    pub kd: f32,
+    // Maximum magnitude of the accumulated integral term.
+    pub integral_limit: f32,
+    // Time constant of the low-pass filter applied to the derivative term,
+    // zero disables filtering.
+    pub derivative_time_constant: f32,
+    integral: T,
+    derivative: T,
+    previous_error: Option<T>
+}
+
+#[allow(dead_code)]
+impl<T: PidValue> Pid<T> {
+    pub fn new(kp: f32, ki: f32, kd: f32) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            integral_limit: f32::INFINITY,
+            derivative_time_constant: 0.0,
+            integral: T::zero(),
+            derivative: T::zero(),
+            previous_error: None
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.integral = T::zero();
+        self.derivative = T::zero();
+        self.previous_error = None;
+    }
+
+    pub fn integral(&self) -> T {
+        self.integral
+    }
+
+    // The derivative term is zero on the first update after construction or
+    // reset, so a large initial error does not produce a kick.
+    pub fn update(&mut self, error: T, dt: f32) -> T {
+        if dt > 0.0 {
+            self.integral = (self.integral + error * dt).clamp_magnitude(self.integral_limit);
+            if let Some(previous) = self.previous_error {
+                let raw = (error - previous) * (1.0 / dt);
+                let alpha = dt / (self.derivative_time_constant + dt);
+                self.derivative = self.derivative + (raw - self.derivative) * alpha;
+            }
+            self.previous_error = Some(error);
+        }
+        error * self.kp + self.integral * self.ki + self.derivative * self.kd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // First-order plant x' = u - disturbance driven towards x = 1.
+    fn settle(pid: &mut Pid<f32>, disturbance: f32) -> f32 {
+        let dt = 0.01;
+        let mut x = 0.0;
+        for _ in 0..2000 {
+            let u = pid.update(1.0 - x, dt);
+            x += (u - disturbance) * dt;
+        }
+        x
+    }
+
+    #[test]
+    fn step_response() {
+        // Proportional control alone leaves an offset of disturbance / kp.
+        let x = settle(&mut Pid::new(2.0, 0.0, 0.0), 0.5);
+        assert!((x - 0.75).abs() < 1e-3, "{}", x);
+
+        // The integral term removes it.
+        let mut pid = Pid::new(2.0, 1.0, 0.0);
+        let x = settle(&mut pid, 0.5);
+        assert!((x - 1.0).abs() < 1e-3, "{}", x);
+        assert!((pid.integral() * pid.ki - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn integral_saturation_and_anti_windup() {
+        let mut pid = Pid::new(0.0, 1.0, 0.0);
+        pid.integral_limit = 2.0;
+        for _ in 0..5 {
+            assert!(pid.update(10.0, 1.0) <= 2.0);
+        }
+        assert_eq!(pid.integral(), 2.0);
+
+        // The clamped integral unwinds as soon as the error flips sign.
+        assert_eq!(pid.update(-1.0, 1.0), 1.0);
+        assert_eq!(pid.update(-3.0, 1.0), -2.0);
+
+        // A non-positive step leaves the state alone.
+        assert_eq!(pid.update(5.0, 0.0), -2.0);
+        pid.reset();
+        assert_eq!(pid.integral(), 0.0);
+    }
+
+    #[test]
+    fn derivative_has_no_kick_and_is_filtered() {
+        let mut pid = Pid::new(0.0, 0.0, 1.0);
+        assert_eq!(pid.update(5.0, 0.1), 0.0);
+        assert!((pid.update(6.0, 0.1) - 10.0).abs() < 1e-4);
+        pid.reset();
+        assert_eq!(pid.update(-5.0, 0.1), 0.0);
+
+        // With a time constant equal to dt, each update moves halfway
+        // towards the raw derivative.
+        let mut pid = Pid::new(0.0, 0.0, 1.0);
+        pid.derivative_time_constant = 0.1;
+        pid.update(0.0, 0.1);
+        assert!((pid.update(1.0, 0.1) - 5.0).abs() < 1e-4);
+        assert!((pid.update(1.0, 0.1) - 2.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn vec4_values() {
+        let v = Vec4::new(3.0, 4.0, 0.0, 0.0);
+        let clamped = v.clamp_magnitude(2.5);
+        assert!((clamped.x - 1.5).abs() < 1e-6 && (clamped.y - 2.0).abs() < 1e-6);
+        assert_eq!(v.clamp_magnitude(10.0).x, 3.0);
+
+        let mut pid = Pid::<Vec4>::new(1.0, 1.0, 0.0);
+        pid.integral_limit = 1.0;
+        let out = pid.update(Vec4::new(10.0, 0.0, 0.0, 0.0), 1.0);
+        assert!((pid.integral().length() - 1.0).abs() < 1e-6);
+        assert!((out.x - 11.0).abs() < 1e-5 && out.y == 0.0);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::closest_point::{direction, point, ClosestPoint};
+use crate::vec4::Vec4;
+
+// Plane of points p with normal . p = distance, normal of unit length.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane {
+    pub normal: Vec4,
+    pub distance: f32
+}
+
+#[allow(dead_code)]
+impl Plane {
+    // `normal` need not be unit length; both it and `distance` are divided
+    // by its length. Panics if the normal's x, y and z are all zero, since
+    // such a plane has no orientation.
+    pub fn new(normal: Vec4, distance: f32) -> Self {
+        let length = direction(&normal).length();
+        assert!(length > 0.0, "Plane normal is zero");
+        Self {
+            normal: direction(&normal) / length,
+            distance: distance / length
+        }
+    }
+
+    // Panics on a zero normal, like `new`.
+    pub fn from_point_normal(p: &Vec4, normal: &Vec4) -> Self {
+        assert!(direction(normal).length() > 0.0, "Plane normal is zero");
+        let normal = direction(normal).normalize();
+        Self {
+            normal,
+            distance: normal.dot(&direction(p))
+        }
+    }
+
+    // Positive on the side the normal points to.
+    pub fn signed_distance(&self, p: &Vec4) -> f32 {
+        self.normal.dot(&direction(p)) - self.distance
+    }
+}
+
+impl ClosestPoint for Plane {
+    fn closest_point(&self, p: &Vec4) -> Vec4 {
+        point(&(*p - self.normal * self.signed_distance(p)))
+    }
+
+    fn distance(&self, p: &Vec4) -> f32 {
+        self.signed_distance(p).abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_normalizes() {
+        let plane = Plane::new(Vec4::new(0.0, 3.0, 4.0, 7.0), 10.0);
+        assert_eq!(plane.normal, Vec4::new(0.0, 0.6, 0.8, 0.0));
+        assert_eq!(plane.distance, 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Plane normal is zero")]
+    fn zero_normal_panics() {
+        Plane::new(Vec4::new(0.0, 0.0, 0.0, 1.0), 1.0);
+    }
+}
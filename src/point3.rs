@@ -0,0 +1,190 @@
+use crate::float::Float;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// Position in 3D space with an implicit w of 1. Directions stay Vec4 with
+// w = 0: subtracting two points gives a direction, and only directions can
+// be added to a point, so the homogeneous coordinate can't be mixed up.
+#[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point3<T = f32> {
+    pub x: T,
+    pub y: T,
+    pub z: T
+}
+
+#[allow(dead_code)]
+impl<T: Float> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self {
+            x,
+            y,
+            z
+        }
+    }
+
+    pub fn origin() -> Self {
+        Self::new(T::zero(), T::zero(), T::zero())
+    }
+
+    // Drops w without dividing by it.
+    pub fn from_vec4(v: &Vec4<T>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+
+    // Divides by w, for results of projective transforms.
+    pub fn from_homogeneous(v: &Vec4<T>) -> Self {
+        Self::new(v.x / v.w, v.y / v.w, v.z / v.w)
+    }
+
+    pub fn to_vec4(self) -> Vec4<T> {
+        Vec4::new(self.x, self.y, self.z, T::one())
+    }
+
+    pub fn distance(&self, other: &Self) -> T {
+        (*self - *other).length()
+    }
+
+    pub fn lerp(&self, other: &Self, t: T) -> Self {
+        *self + (*other - *self) * t
+    }
+}
+
+impl<T: Float> std::ops::Sub<Point3<T>> for Point3<T> {
+    type Output = Vec4<T>;
+
+    fn sub(self, rhs: Point3<T>) -> Self::Output {
+        Vec4::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z, T::zero())
+    }
+}
+
+impl<T: Float> std::ops::Add<Vec4<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, rhs: Vec4<T>) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Float> std::ops::AddAssign<Vec4<T>> for Point3<T> {
+    fn add_assign(&mut self, rhs: Vec4<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Float> std::ops::Sub<Vec4<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, rhs: Vec4<T>) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Float> std::ops::SubAssign<Vec4<T>> for Point3<T> {
+    fn sub_assign(&mut self, rhs: Vec4<T>) {
+        *self = *self - rhs;
+    }
+}
+
+// Transforms with w = 1, including translation, and divides by the
+// resulting w so projection matrices work too.
+impl<T: Float> std::ops::Mul<Point3<T>> for Mat4<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, rhs: Point3<T>) -> Self::Output {
+        let v = self * rhs.to_vec4();
+        if v.w == T::one() {
+            Point3::from_vec4(&v)
+        } else {
+            Point3::from_homogeneous(&v)
+        }
+    }
+}
+
+impl<T: Float> std::ops::Index<usize> for Point3<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl<T: Float> std::ops::IndexMut<usize> for Point3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl<T: Float> std::fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: Float> std::fmt::Debug for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Point3({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_and_vectors() {
+        let a = Point3::new(1.0f32, 2.0, 3.0);
+        let b = Point3::new(4.0f32, 6.0, 3.0);
+
+        // point - point = vector, with w = 0.
+        let d: Vec4 = b - a;
+        assert_eq!(d, Vec4::new(3.0, 4.0, 0.0, 0.0));
+        // point + vector = point.
+        let moved: Point3 = a + d;
+        assert_eq!(moved, b);
+        assert_eq!(b - d, a);
+        assert_eq!(a.distance(&b), 5.0);
+        assert_eq!(a.lerp(&b, 0.5), Point3::new(2.5, 4.0, 3.0));
+
+        let mut p = Point3::origin();
+        p += Vec4::new(1.0, 1.0, 1.0, 0.0);
+        p -= Vec4::new(0.0, 2.0, 0.0, 0.0);
+        assert_eq!(p, Point3::new(1.0, -1.0, 1.0));
+        p[2] = 9.0;
+        assert_eq!(p[2], 9.0);
+    }
+
+    #[test]
+    fn homogeneous_conversions() {
+        let p = Point3::new(1.0f32, -2.0, 0.5);
+        assert_eq!(p.to_vec4(), Vec4::new(1.0, -2.0, 0.5, 1.0));
+        assert_eq!(Point3::from_vec4(&Vec4::new(1.0, -2.0, 0.5, 4.0)), p);
+        assert_eq!(Point3::from_homogeneous(&Vec4::new(2.0, -4.0, 1.0, 2.0)), p);
+    }
+
+    #[test]
+    fn transforms_translate_points_but_not_vectors() {
+        let t = Mat4::from_translation(&Vec4::new(10.0f32, 0.0, 0.0, 0.0));
+        let p = Point3::new(1.0f32, 2.0, 3.0);
+        let v = Point3::new(2.0f32, 2.0, 3.0) - p;
+        assert_eq!(t * p, Point3::new(11.0, 2.0, 3.0));
+        assert_eq!(t * v, v);
+        assert_eq!((t * p) - (t * (p + v)), -v);
+
+        // Projection divides by w.
+        let projected = Mat4::perspective(1.2f32, 1.0, 0.1, 100.0) * Point3::new(0.5, 0.5, -10.0);
+        let clip = Mat4::perspective(1.2f32, 1.0, 0.1, 100.0) * Vec4::new(0.5, 0.5, -10.0, 1.0);
+        assert!((projected.x - clip.x / clip.w).abs() < 1e-6);
+        assert!((projected.z - clip.z / clip.w).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::aabb::Aabb;
+use crate::plane::Plane;
+use crate::vec4::Vec4;
+
+// Convex polyhedron as the intersection of the half-spaces behind each
+// plane, so every normal points outward.
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default)]
+pub struct ConvexPolyhedron {
+    pub planes: Vec<Plane>
+}
+
+#[allow(dead_code)]
+impl ConvexPolyhedron {
+    pub fn new(planes: Vec<Plane>) -> Self {
+        Self {
+            planes
+        }
+    }
+
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        let mut planes = Vec::with_capacity(6);
+        for axis in 0..3 {
+            let mut normal = Vec4::zero();
+            normal[axis] = 1.0;
+            planes.push(Plane::new(normal, aabb.max[axis]));
+            planes.push(Plane::new(-normal, -aabb.min[axis]));
+        }
+        Self::new(planes)
+    }
+
+    // Largest signed distance to any plane: negative inside, zero on the
+    // boundary and positive outside. Exact on faces, a lower bound on the
+    // true distance past edges and corners.
+    pub fn signed_distance(&self, p: &Vec4) -> f32 {
+        self.planes.iter().map(|plane| plane.signed_distance(p)).fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    // Points on the boundary count as inside. The tolerance scales with
+    // each plane's offset so large coordinates don't flicker from rounding.
+    pub fn contains_point(&self, p: &Vec4) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(p) <= 1e-5 * plane.distance.abs().max(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_and_wedge() {
+        let cube = ConvexPolyhedron::from_aabb(&Aabb::new(Vec4::zero(), Vec4::new(2.0, 2.0, 2.0, 0.0)));
+        assert!(cube.contains_point(&Vec4::new(1.0, 1.0, 1.0, 1.0)));
+        assert!(cube.contains_point(&Vec4::new(2.0, 0.0, 1.0, 1.0)));
+        assert!(!cube.contains_point(&Vec4::new(2.01, 1.0, 1.0, 1.0)));
+        assert!((cube.signed_distance(&Vec4::new(1.0, 1.5, 1.0, 1.0)) + 0.5).abs() < 1e-6);
+
+        let mut wedge = cube.clone();
+        wedge.planes.push(Plane::new(Vec4::new(1.0, 1.0, 0.0, 0.0), 2.0_f32.sqrt()));
+        assert!(wedge.contains_point(&Vec4::new(0.5, 0.5, 1.0, 1.0)));
+        assert!(!wedge.contains_point(&Vec4::new(1.5, 1.5, 1.0, 1.0)));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::dvec4::DVec4;
+
+// Robust geometric predicates after Shewchuk, "Adaptive Precision
+// Floating-Point Arithmetic and Fast Robust Geometric Predicates". Each
+// first evaluates the determinant in plain f64 and returns it when its
+// error bound proves the sign; only near-degenerate input falls back to
+// exact expansion arithmetic. The sign of the result is always exact; the
+// magnitude is an approximation.
+
+// Half an ulp of 1: the relative rounding error of one f64 operation.
+const EPSILON: f64 = f64::EPSILON * 0.5;
+const ORIENT2D_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const ORIENT3D_BOUND: f64 = (7.0 + 56.0 * EPSILON) * EPSILON;
+const INCIRCLE_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+// Positive when a, b, c wind counter-clockwise, negative when clockwise and
+// zero when collinear.
+#[allow(dead_code)]
+pub fn orient2d(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    let left = (a.0 - c.0) * (b.1 - c.1);
+    let right = (a.1 - c.1) * (b.0 - c.0);
+    let det = left - right;
+    if det.abs() >= ORIENT2D_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+    let [acx, acy, bcx, bcy] = [diff(a.0, c.0), diff(a.1, c.1), diff(b.0, c.0), diff(b.1, c.1)];
+    estimate(&sum(&product(&acx, &bcy), &negate(&product(&acy, &bcx))))
+}
+
+// Positive when d lies below the plane through a, b, c, taking "above" as
+// the side from which a, b, c appear counter-clockwise; negative above and
+// zero when the four points are coplanar. This is the sign of
+// (a - d) . ((b - d) x (c - d)). Only x, y and z are read.
+#[allow(dead_code)]
+pub fn orient3d(a: &DVec4, b: &DVec4, c: &DVec4, d: &DVec4) -> f64 {
+    let (adx, ady, adz) = (a.x - d.x, a.y - d.y, a.z - d.z);
+    let (bdx, bdy, bdz) = (b.x - d.x, b.y - d.y, b.z - d.z);
+    let (cdx, cdy, cdz) = (c.x - d.x, c.y - d.y, c.z - d.z);
+    let (bc, ca, ab) = (bdx * cdy - bdy * cdx, cdx * ady - cdy * adx, adx * bdy - ady * bdx);
+    let det = adz * bc + bdz * ca + cdz * ab;
+    let permanent = adz.abs() * ((bdx * cdy).abs() + (bdy * cdx).abs())
+        + bdz.abs() * ((cdx * ady).abs() + (cdy * adx).abs())
+        + cdz.abs() * ((adx * bdy).abs() + (ady * bdx).abs());
+    if det.abs() >= ORIENT3D_BOUND * permanent {
+        return det;
+    }
+    let [adx, ady, adz] = [diff(a.x, d.x), diff(a.y, d.y), diff(a.z, d.z)];
+    let [bdx, bdy, bdz] = [diff(b.x, d.x), diff(b.y, d.y), diff(b.z, d.z)];
+    let [cdx, cdy, cdz] = [diff(c.x, d.x), diff(c.y, d.y), diff(c.z, d.z)];
+    let minor = |px: &[f64], py: &[f64], qx: &[f64], qy: &[f64]| sum(&product(px, qy), &negate(&product(py, qx)));
+    let terms = [
+        product(&adz, &minor(&bdx, &bdy, &cdx, &cdy)),
+        product(&bdz, &minor(&cdx, &cdy, &adx, &ady)),
+        product(&cdz, &minor(&adx, &ady, &bdx, &bdy))
+    ];
+    estimate(&sum(&sum(&terms[0], &terms[1]), &terms[2]))
+}
+
+// Positive when d lies inside the circle through a, b, c, negative outside
+// and zero on it. a, b, c must be counter-clockwise; the sign flips for
+// clockwise input.
+#[allow(dead_code)]
+pub fn incircle(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> f64 {
+    let (adx, ady) = (a.0 - d.0, a.1 - d.1);
+    let (bdx, bdy) = (b.0 - d.0, b.1 - d.1);
+    let (cdx, cdy) = (c.0 - d.0, c.1 - d.1);
+    let (alift, blift, clift) = (adx * adx + ady * ady, bdx * bdx + bdy * bdy, cdx * cdx + cdy * cdy);
+    let det = alift * (bdx * cdy - cdx * bdy) + blift * (cdx * ady - adx * cdy) + clift * (adx * bdy - bdx * ady);
+    let permanent = alift * ((bdx * cdy).abs() + (cdx * bdy).abs())
+        + blift * ((cdx * ady).abs() + (adx * cdy).abs())
+        + clift * ((adx * bdy).abs() + (bdx * ady).abs());
+    if det.abs() >= INCIRCLE_BOUND * permanent {
+        return det;
+    }
+    let [adx, ady, bdx, bdy, cdx, cdy] = [diff(a.0, d.0), diff(a.1, d.1), diff(b.0, d.0), diff(b.1, d.1), diff(c.0, d.0), diff(c.1, d.1)];
+    let lift = |x: &[f64], y: &[f64]| sum(&product(x, x), &product(y, y));
+    let minor = |px: &[f64], py: &[f64], qx: &[f64], qy: &[f64]| sum(&product(px, qy), &negate(&product(py, qx)));
+    let terms = [
+        product(&lift(&adx, &ady), &minor(&bdx, &bdy, &cdx, &cdy)),
+        product(&lift(&bdx, &bdy), &minor(&cdx, &cdy, &adx, &ady)),
+        product(&lift(&cdx, &cdy), &minor(&adx, &ady, &bdx, &bdy))
+    ];
+    estimate(&sum(&sum(&terms[0], &terms[1]), &terms[2]))
+}
+
+// Expansions below are lists of non-overlapping f64 components in order of
+// increasing magnitude whose exact sum is the represented value.
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+// Requires |a| >= |b|.
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+// Exact a - b as a two-component expansion.
+fn diff(a: f64, b: f64) -> Vec<f64> {
+    let (x, y) = two_sum(a, -b);
+    vec![y, x]
+}
+
+fn negate(e: &[f64]) -> Vec<f64> {
+    e.iter().map(|&x| -x).collect()
+}
+
+// Adds one component to an expansion, dropping zeros.
+fn grow(e: &[f64], b: f64) -> Vec<f64> {
+    let mut out = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &component in e {
+        let (sum, error) = two_sum(q, component);
+        if error != 0.0 {
+            out.push(error);
+        }
+        q = sum;
+    }
+    if q != 0.0 || out.is_empty() {
+        out.push(q);
+    }
+    out
+}
+
+fn sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |e, &component| grow(&e, component))
+}
+
+// Multiplies an expansion by one f64, dropping zeros.
+fn scale(e: &[f64], b: f64) -> Vec<f64> {
+    let mut out = Vec::with_capacity(2 * e.len());
+    let Some((&first, rest)) = e.split_first() else {
+        return vec![0.0];
+    };
+    let (mut q, error) = two_product(first, b);
+    if error != 0.0 {
+        out.push(error);
+    }
+    for &component in rest {
+        let (high, low) = two_product(component, b);
+        let (sum, error) = two_sum(q, low);
+        if error != 0.0 {
+            out.push(error);
+        }
+        let (next, error) = fast_two_sum(high, sum);
+        if error != 0.0 {
+            out.push(error);
+        }
+        q = next;
+    }
+    if q != 0.0 || out.is_empty() {
+        out.push(q);
+    }
+    out
+}
+
+fn product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(vec![0.0], |total, &component| sum(&total, &scale(e, component)))
+}
+
+// Components are non-overlapping, so adding from the smallest up yields
+// the value rounded with its sign intact.
+fn estimate(e: &[f64]) -> f64 {
+    e.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orient2d_near_a_line() {
+        // p sits a few ulps off the line y = x; the sign is exactly that of
+        // py - px, which the naive formula gets wrong in places.
+        let ulp = f64::EPSILON;
+        for i in 0..32 {
+            for j in 0..32 {
+                let p = (0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp);
+                let sign = orient2d(p, (12.0, 12.0), (24.0, 24.0)).partial_cmp(&0.0).unwrap();
+                assert_eq!(sign, j.cmp(&i), "{} {}", i, j);
+            }
+        }
+        assert!(orient2d((0.0, 0.0), (1.0, 0.0), (0.0, 1.0)) > 0.0);
+    }
+
+    #[test]
+    fn orient3d_and_incircle_degenerate() {
+        let p = |x: f64, y: f64, z: f64| DVec4::new(x, y, z, 1.0);
+        // Coplanar points far from the origin on the plane x + y + z = 3e8,
+        // with coordinates chosen so the plane is hit exactly.
+        let offset = 1e8;
+        let (a, b, c) = (p(offset, offset, offset), p(offset + 1.0, offset - 1.0, offset), p(offset, offset + 0.5, offset - 0.5));
+        let d = p(offset + 3.0, offset - 1.0, offset - 2.0);
+        assert_eq!(orient3d(&a, &b, &c, &d), 0.0);
+        let above = p(offset + 3.0, offset - 1.0, offset - 2.0 + 1e-8);
+        let below = p(offset + 3.0, offset - 1.0, offset - 2.0 - 1e-8);
+        assert!(orient3d(&a, &b, &c, &above) * orient3d(&a, &b, &c, &below) < 0.0);
+        assert!(orient3d(&p(0.0, 0.0, 0.0), &p(1.0, 0.0, 0.0), &p(0.0, 1.0, 0.0), &p(0.0, 0.0, -1.0)) > 0.0);
+
+        let (a, b, c) = ((1.0, 0.0), (0.0, 1.0), (-1.0, 0.0));
+        assert_eq!(incircle(a, b, c, (0.0, -1.0)), 0.0);
+        assert!(incircle(a, b, c, (0.0, 0.0)) > 0.0);
+        assert!(incircle(a, b, c, (0.0, -1.0 - f64::EPSILON)) < 0.0);
+        assert!(incircle(a, b, c, (0.0, -1.0 + f64::EPSILON)) > 0.0);
+    }
+}
@@ -0,0 +1,21 @@
+// The types and traits most code needs: `use lalg::prelude::*;`.
+#[cfg(feature = "geometry")]
+pub use crate::aabb::Aabb;
+#[cfg(feature = "geometry")]
+pub use crate::closest_point::ClosestPoint;
+pub use crate::error::MathError;
+pub use crate::euler::{EulerAngles, EulerOrder};
+pub use crate::float::Float;
+pub use crate::mat4::Mat4;
+pub use crate::matrix::Matrix;
+#[cfg(feature = "geometry")]
+pub use crate::plane::Plane;
+#[cfg(feature = "geometry")]
+pub use crate::shape::Shape;
+#[cfg(feature = "geometry")]
+pub use crate::sphere::Sphere;
+#[cfg(all(feature = "curves", feature = "geometry"))]
+pub use crate::surface::ParametricSurface;
+pub use crate::transform::Transform;
+pub use crate::vec4::Vec4;
+pub use crate::vector::Vector;
@@ -0,0 +1,103 @@
+use crate::error::MathError;
+use crate::float::Float;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// Window coordinates follow gluProject: `viewport` is (x, y, width, height)
+// with y growing upwards from the bottom-left corner, and window depth runs
+// from 0 at the near plane to 1 at the far plane. For a top-left origin use
+// `height - y`. The plain variants expect GL clip space (z in [-1, 1]), the
+// `_zo` ones the zero-to-one clip space of the `_zo` and reversed
+// projections.
+
+// World point to window (x, y, depth, 1). None when the point is on or
+// behind the eye plane, where the perspective divide would mirror it.
+#[allow(dead_code)]
+pub fn project<T: Float>(world_point: &Vec4<T>, view: &Mat4<T>, proj: &Mat4<T>, viewport: &Vec4<T>) -> Option<Vec4<T>> {
+    project_with_depth(world_point, view, proj, viewport, false)
+}
+
+#[allow(dead_code)]
+pub fn project_zo<T: Float>(world_point: &Vec4<T>, view: &Mat4<T>, proj: &Mat4<T>, viewport: &Vec4<T>) -> Option<Vec4<T>> {
+    project_with_depth(world_point, view, proj, viewport, true)
+}
+
+// Window (x, y, depth) back to a world point with w = 1. Unprojecting the
+// cursor at depth 0 and 1 gives the ends of a picking ray. Fails with
+// `Singular` when proj * view has no inverse.
+#[allow(dead_code)]
+pub fn unproject<T: Float>(screen_point: &Vec4<T>, view: &Mat4<T>, proj: &Mat4<T>, viewport: &Vec4<T>) -> Result<Vec4<T>, MathError> {
+    unproject_with_depth(screen_point, view, proj, viewport, false)
+}
+
+#[allow(dead_code)]
+pub fn unproject_zo<T: Float>(screen_point: &Vec4<T>, view: &Mat4<T>, proj: &Mat4<T>, viewport: &Vec4<T>) -> Result<Vec4<T>, MathError> {
+    unproject_with_depth(screen_point, view, proj, viewport, true)
+}
+
+fn project_with_depth<T: Float>(world_point: &Vec4<T>, view: &Mat4<T>, proj: &Mat4<T>, viewport: &Vec4<T>, zero_to_one: bool) -> Option<Vec4<T>> {
+    let p = Vec4::new(world_point.x, world_point.y, world_point.z, T::one());
+    let clip = *proj * (*view * p);
+    if clip.w <= T::zero() {
+        return None;
+    }
+    let half = T::from_f64(0.5);
+    let ndc = clip / clip.w;
+    let depth = if zero_to_one { ndc.z } else { ndc.z * half + half };
+    Some(Vec4::new(
+        viewport.x + (ndc.x * half + half) * viewport.z,
+        viewport.y + (ndc.y * half + half) * viewport.w,
+        depth,
+        T::one()
+    ))
+}
+
+fn unproject_with_depth<T: Float>(screen_point: &Vec4<T>, view: &Mat4<T>, proj: &Mat4<T>, viewport: &Vec4<T>, zero_to_one: bool) -> Result<Vec4<T>, MathError> {
+    let inverse = (*proj * *view).try_inverse()?;
+    let two = T::from_f64(2.0);
+    let z = if zero_to_one { screen_point.z } else { screen_point.z * two - T::one() };
+    let ndc = Vec4::new(
+        (screen_point.x - viewport.x) / viewport.z * two - T::one(),
+        (screen_point.y - viewport.y) / viewport.w * two - T::one(),
+        z,
+        T::one()
+    );
+    let world = inverse * ndc;
+    if world.w == T::zero() {
+        return Err(MathError::NotFinite);
+    }
+    Ok(world / world.w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_round_trips_through_unproject() {
+        let view = Mat4::<f64>::look_at(Vec4::new(1.0, 2.0, 5.0, 1.0), Vec4::new(0.0, 0.0, 0.0, 1.0), Vec4::new(0.0, 1.0, 0.0, 0.0));
+        let viewport = Vec4::new(10.0, 20.0, 800.0, 600.0);
+        let p = Vec4::new(0.3, -0.4, 0.5, 1.0);
+        let cases = [
+            (Mat4::perspective(1.0, 800.0 / 600.0, 0.1, 100.0), false),
+            (Mat4::perspective_zo(1.0, 800.0 / 600.0, 0.1, 100.0), true),
+            (Mat4::orthographic(-4.0, 4.0, -3.0, 3.0, 0.1, 100.0), false)
+        ];
+        for (proj, zero_to_one) in cases {
+            let screen = if zero_to_one { project_zo(&p, &view, &proj, &viewport) } else { project(&p, &view, &proj, &viewport) }.unwrap();
+            assert!(screen.x > 10.0 && screen.x < 810.0 && screen.y > 20.0 && screen.y < 620.0);
+            assert!(screen.z > 0.0 && screen.z < 1.0);
+            let back = if zero_to_one { unproject_zo(&screen, &view, &proj, &viewport) } else { unproject(&screen, &view, &proj, &viewport) }.unwrap();
+            assert!((back - p).length() < 1e-9);
+        }
+
+        // The look-at target lands in the middle of the viewport, and the
+        // eye cannot be projected.
+        let proj = Mat4::perspective(1.0, 800.0 / 600.0, 0.1, 100.0);
+        let center = project(&Vec4::new(0.0, 0.0, 0.0, 1.0), &view, &proj, &viewport).unwrap();
+        assert!((center.x - 410.0).abs() < 1e-9 && (center.y - 320.0).abs() < 1e-9);
+        assert!(project(&Vec4::new(1.0, 2.0, 6.0, 1.0), &view, &proj, &viewport).is_none());
+        let near = unproject(&Vec4::new(410.0, 320.0, 0.0, 1.0), &view, &proj, &viewport).unwrap();
+        assert!(((near - Vec4::new(1.0, 2.0, 5.0, 1.0)).length() - 0.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::float::Float;
+use crate::mat4::Mat4;
+
+#[allow(dead_code)]
+impl<T: Float> Mat4<T> {
+    // Householder QR: A = Q * R with Q orthogonal and R upper triangular.
+    // R's diagonal is made non-negative, which makes the factorization
+    // unique for invertible A; Q's columns are then A's columns
+    // orthonormalized in order, like Gram-Schmidt but stable.
+    pub fn qr(&self) -> (Self, Self) {
+        let mut r = *self;
+        let mut q = Self::identity();
+        for col in 0..3 {
+            // Reflect column `col` below the diagonal onto the axis.
+            let norm = (col..4).fold(T::zero(), |sum, row| sum + r.elements[col * 4 + row] * r.elements[col * 4 + row]).sqrt();
+            if norm == T::zero() {
+                continue;
+            }
+            let head = r.elements[col * 4 + col];
+            let alpha = if head > T::zero() { -norm } else { norm };
+            let mut v = [T::zero(); 4];
+            for row in col..4 {
+                v[row] = r.elements[col * 4 + row];
+            }
+            v[col] -= alpha;
+            let v_norm_squared = v.iter().fold(T::zero(), |sum, &x| sum + x * x);
+            if v_norm_squared == T::zero() {
+                continue;
+            }
+            let scale = T::from_f64(2.0) / v_norm_squared;
+            // R = H * R and Q = Q * H with H = I - scale * v v^T.
+            for k in 0..4 {
+                let dot = (col..4).fold(T::zero(), |sum, row| sum + v[row] * r.elements[k * 4 + row]);
+                for row in col..4 {
+                    r.elements[k * 4 + row] -= scale * v[row] * dot;
+                }
+            }
+            for row in 0..4 {
+                let dot = (col..4).fold(T::zero(), |sum, k| sum + q.elements[k * 4 + row] * v[k]);
+                for k in col..4 {
+                    q.elements[k * 4 + row] -= scale * dot * v[k];
+                }
+            }
+        }
+        for i in 0..4 {
+            for row in (i + 1)..4 {
+                r.elements[i * 4 + row] = T::zero();
+            }
+            if r.elements[i * 5] < T::zero() {
+                for k in 0..4 {
+                    r.elements[k * 4 + i] = -r.elements[k * 4 + i];
+                    q.elements[i * 4 + k] = -q.elements[i * 4 + k];
+                }
+            }
+        }
+        (q, r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mat4::Mat4;
+
+    #[test]
+    fn orthogonal_times_triangular() {
+        let mut a = Mat4::<f64>::zeroes();
+        for i in 0..16 {
+            a.elements[i] = ((i * 7 + 3) % 11) as f64 - 4.0;
+        }
+        let (q, r) = a.qr();
+        let (product, qtq, identity) = (q * r, q.transpose() * q, Mat4::<f64>::identity());
+        assert!((0..16).all(|i| (product.elements[i] - a.elements[i]).abs() < 1e-12));
+        assert!((0..16).all(|i| (qtq.elements[i] - identity.elements[i]).abs() < 1e-12));
+        for col in 0..4 {
+            assert!(r.elements[col * 5] >= 0.0);
+            for row in (col + 1)..4 {
+                assert_eq!(r.elements[col * 4 + row], 0.0);
+            }
+        }
+        // The first column of Q is the first column of A, normalized.
+        let first = a.x_vector().normalize();
+        assert!((q.x_vector() - first).length() < 1e-12);
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use crate::rect::Rect;
+
+// Handle returned by `LooseQuadtree::insert`.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QuadtreeHandle(usize);
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    rect: Rect,
+    cell: (usize, usize, usize),
+    value: T
+}
+
+// Loose quadtree with a looseness factor of two: every cell's bounds are
+// extended by half its size on each side, so an object is placed by its
+// center and size alone and moving objects rarely change cell. Cells live
+// in per-level hash maps, so only occupied cells cost memory.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct LooseQuadtree<T> {
+    bounds: Rect,
+    max_depth: usize,
+    levels: Vec<HashMap<(usize, usize), Vec<usize>>>,
+    entries: Vec<Option<Entry<T>>>,
+    free: Vec<usize>,
+    len: usize
+}
+
+#[allow(dead_code)]
+impl<T> LooseQuadtree<T> {
+    pub fn new(bounds: Rect, max_depth: usize) -> Self {
+        Self {
+            bounds,
+            max_depth,
+            levels: (0..=max_depth).map(|_| HashMap::new()).collect(),
+            entries: Vec::new(),
+            free: Vec::new(),
+            len: 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn cell_size(&self, depth: usize) -> (f32, f32) {
+        let cells = (1usize << depth) as f32;
+        (self.bounds.width() / cells, self.bounds.height() / cells)
+    }
+
+    fn cell_index(&self, value: f32, origin: f32, size: f32, depth: usize) -> usize {
+        let last = (1usize << depth) - 1;
+        let index = ((value - origin) / size).floor();
+        if index.is_nan() || index < 0.0 {
+            0
+        } else {
+            (index as usize).min(last)
+        }
+    }
+
+    fn cell_for(&self, rect: &Rect) -> (usize, usize, usize) {
+        let mut depth = 0;
+        while depth < self.max_depth {
+            let (w, h) = self.cell_size(depth + 1);
+            if rect.width() > w || rect.height() > h {
+                break;
+            }
+            depth += 1;
+        }
+        let (w, h) = self.cell_size(depth);
+        let (cx, cy) = rect.center();
+        (depth, self.cell_index(cx, self.bounds.min_x, w, depth), self.cell_index(cy, self.bounds.min_y, h, depth))
+    }
+
+    pub fn insert(&mut self, rect: Rect, value: T) -> QuadtreeHandle {
+        let cell = self.cell_for(&rect);
+        let entry = Entry {
+            rect,
+            cell,
+            value
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.entries[index] = Some(entry);
+                index
+            }
+            None => {
+                self.entries.push(Some(entry));
+                self.entries.len() - 1
+            }
+        };
+        self.levels[cell.0].entry((cell.1, cell.2)).or_default().push(index);
+        self.len += 1;
+        QuadtreeHandle(index)
+    }
+
+    fn unlink(&mut self, index: usize, cell: (usize, usize, usize)) {
+        let level = &mut self.levels[cell.0];
+        if let Some(items) = level.get_mut(&(cell.1, cell.2)) {
+            items.retain(|&i| i != index);
+            if items.is_empty() {
+                level.remove(&(cell.1, cell.2));
+            }
+        }
+    }
+
+    // Moves an object, relinking it only when its cell changes. Returns
+    // false if the handle is not live.
+    pub fn update(&mut self, handle: QuadtreeHandle, rect: Rect) -> bool {
+        let cell = self.cell_for(&rect);
+        let old_cell = match self.entries.get_mut(handle.0) {
+            Some(Some(entry)) => {
+                entry.rect = rect;
+                std::mem::replace(&mut entry.cell, cell)
+            }
+            _ => return false
+        };
+        if old_cell != cell {
+            self.unlink(handle.0, old_cell);
+            self.levels[cell.0].entry((cell.1, cell.2)).or_default().push(handle.0);
+        }
+        true
+    }
+
+    pub fn remove(&mut self, handle: QuadtreeHandle) -> Option<T> {
+        let entry = self.entries.get_mut(handle.0)?.take()?;
+        self.unlink(handle.0, entry.cell);
+        self.free.push(handle.0);
+        self.len -= 1;
+        Some(entry.value)
+    }
+
+    pub fn get(&self, handle: QuadtreeHandle) -> Option<(&Rect, &T)> {
+        let entry = self.entries.get(handle.0)?.as_ref()?;
+        Some((&entry.rect, &entry.value))
+    }
+
+    // Calls `f` for every stored object in a cell whose loose bounds overlap
+    // `rect`. Candidates still need an exact overlap test.
+    fn for_each_candidate(&self, rect: &Rect, mut f: impl FnMut(usize)) {
+        for (depth, level) in self.levels.iter().enumerate() {
+            if level.is_empty() {
+                continue;
+            }
+            let (w, h) = self.cell_size(depth);
+            let x0 = self.cell_index(rect.min_x - w * 0.5, self.bounds.min_x, w, depth);
+            let x1 = self.cell_index(rect.max_x + w * 0.5, self.bounds.min_x, w, depth);
+            let y0 = self.cell_index(rect.min_y - h * 0.5, self.bounds.min_y, h, depth);
+            let y1 = self.cell_index(rect.max_y + h * 0.5, self.bounds.min_y, h, depth);
+            if (x1 - x0 + 1) * (y1 - y0 + 1) > level.len() {
+                for (&(x, y), items) in level {
+                    if x >= x0 && x <= x1 && y >= y0 && y <= y1 {
+                        items.iter().for_each(|&i| f(i));
+                    }
+                }
+            } else {
+                for x in x0..=x1 {
+                    for y in y0..=y1 {
+                        if let Some(items) = level.get(&(x, y)) {
+                            items.iter().for_each(|&i| f(i));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn query(&self, rect: &Rect) -> Vec<QuadtreeHandle> {
+        let mut out = Vec::new();
+        self.for_each_candidate(rect, |index| {
+            if let Some(entry) = &self.entries[index] {
+                if entry.rect.intersects(rect) {
+                    out.push(QuadtreeHandle(index));
+                }
+            }
+        });
+        out
+    }
+
+    // Every pair of overlapping objects, each reported once with the
+    // smaller handle first.
+    pub fn overlapping_pairs(&self) -> Vec<(QuadtreeHandle, QuadtreeHandle)> {
+        let mut out = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            let Some(entry) = entry else {
+                continue;
+            };
+            self.for_each_candidate(&entry.rect, |other| {
+                if other > index {
+                    if let Some(candidate) = &self.entries[other] {
+                        if candidate.rect.intersects(&entry.rect) {
+                            out.push((QuadtreeHandle(index), QuadtreeHandle(other)));
+                        }
+                    }
+                }
+            });
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_match_brute_force() {
+        let mut tree = LooseQuadtree::new(Rect::new(0.0, 0.0, 100.0, 100.0), 6);
+        let mut rects = Vec::new();
+        let mut seed = 7u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as f32 / (1u32 << 24) as f32
+        };
+        for i in 0..300 {
+            let size = if i % 50 == 0 { 30.0 } else { 0.5 + next() * 3.0 };
+            let rect = Rect::from_center_half_extents(next() * 110.0 - 5.0, next() * 110.0 - 5.0, size, size * 0.5);
+            rects.push((tree.insert(rect, i), rect));
+        }
+        // Move some objects and drop others.
+        for i in (0..300).step_by(7) {
+            let moved = Rect::from_center_half_extents(next() * 100.0, next() * 100.0, 1.0, 1.0);
+            assert!(tree.update(rects[i].0, moved));
+            rects[i].1 = moved;
+        }
+        for i in (0..300).step_by(11) {
+            assert_eq!(tree.remove(rects[i].0), Some(i));
+        }
+        let live: Vec<_> = rects.iter().enumerate().filter(|(i, _)| i % 11 != 0).map(|(_, r)| *r).collect();
+
+        let mut expected = Vec::new();
+        for a in 0..live.len() {
+            for b in (a + 1)..live.len() {
+                if live[a].1.intersects(&live[b].1) {
+                    let (x, y) = (live[a].0.min(live[b].0), live[a].0.max(live[b].0));
+                    expected.push((x, y));
+                }
+            }
+        }
+        expected.sort();
+        let mut found = tree.overlapping_pairs();
+        found.sort();
+        assert_eq!(found, expected);
+        assert_eq!(tree.len(), live.len());
+    }
+}
@@ -0,0 +1,89 @@
+use rand_core::RngCore;
+
+use crate::mat4::Mat4;
+use crate::transform::quat_to_rotation;
+use crate::vec4::Vec4;
+
+// Uniform in [0, 1) from the top 24 bits, so every value is exact in f32.
+#[allow(dead_code)]
+pub fn random_unit_interval<R: RngCore + ?Sized>(rng: &mut R) -> f32 {
+    (rng.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+}
+
+// Direction (w = 0) uniformly distributed over the unit sphere, from a
+// uniform height and azimuth (Archimedes' hat-box theorem).
+#[allow(dead_code)]
+pub fn random_unit_vector<R: RngCore + ?Sized>(rng: &mut R) -> Vec4 {
+    let z = 1.0 - 2.0 * random_unit_interval(rng);
+    let azimuth = std::f32::consts::TAU * random_unit_interval(rng);
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    Vec4::new(r * azimuth.cos(), r * azimuth.sin(), z, 0.0)
+}
+
+// Point (w = 1) uniformly distributed inside the ball around `center`.
+#[allow(dead_code)]
+pub fn random_in_sphere<R: RngCore + ?Sized>(rng: &mut R, center: &Vec4, radius: f32) -> Vec4 {
+    let offset = random_unit_vector(rng) * (radius * random_unit_interval(rng).cbrt());
+    Vec4::new(center.x + offset.x, center.y + offset.y, center.z + offset.z, 1.0)
+}
+
+// Rotation uniformly distributed over SO(3), through Shoemake's uniform
+// unit quaternion.
+#[allow(dead_code)]
+pub fn random_rotation<R: RngCore + ?Sized>(rng: &mut R) -> Mat4 {
+    let (u1, u2, u3) = (random_unit_interval(rng), random_unit_interval(rng), random_unit_interval(rng));
+    let (a, b) = ((1.0 - u1).sqrt(), u1.sqrt());
+    let (s2, c2) = (std::f32::consts::TAU * u2).sin_cos();
+    let (s3, c3) = (std::f32::consts::TAU * u3).sin_cos();
+    quat_to_rotation(&Vec4::new(a * s2, a * c2, b * s3, b * c3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Xorshift(u64);
+
+    impl RngCore for Xorshift {
+        fn next_u32(&mut self) -> u32 {
+            (self.next_u64() >> 32) as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            rand_core::impls::fill_bytes_via_next(self, dst)
+        }
+    }
+
+    #[test]
+    fn samples_are_unbiased() {
+        let mut rng = Xorshift(0x9e37_79b9_7f4a_7c15);
+        let n = 20000;
+        let mut mean = Vec4::new(0.0, 0.0, 0.0, 0.0);
+        let mut inside = 0;
+        for _ in 0..n {
+            let v = random_unit_vector(&mut rng);
+            assert!((v.length() - 1.0).abs() < 1e-5);
+            mean += v / n as f32;
+            let p = random_in_sphere(&mut rng, &Vec4::new(1.0, 2.0, 3.0, 1.0), 2.0);
+            let d = (p - Vec4::new(1.0, 2.0, 3.0, 1.0)).length();
+            assert!(d <= 2.0 + 1e-5);
+            inside += (d < 2.0 * 0.5f32.cbrt()) as usize;
+        }
+        assert!(mean.length() < 0.03);
+        // Half the volume lies within radius / cbrt(2).
+        assert!((inside as f32 / n as f32 - 0.5).abs() < 0.02);
+
+        let r = random_rotation(&mut rng);
+        assert!((r.determinant() - 1.0).abs() < 1e-5);
+        let t = r * r.transpose();
+        let identity = Mat4::<f32>::identity();
+        assert!((0..16).all(|i| (t.elements[i] - identity.elements[i]).abs() < 1e-5));
+    }
+}
@@ -0,0 +1,56 @@
+// Axis-aligned 2D rectangle.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32
+}
+
+#[allow(dead_code)]
+impl Rect {
+    pub fn new(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Self {
+        Self {
+            min_x,
+            min_y,
+            max_x,
+            max_y
+        }
+    }
+
+    pub fn from_center_half_extents(x: f32, y: f32, half_width: f32, half_height: f32) -> Self {
+        Self::new(x - half_width, y - half_height, x + half_width, y + half_height)
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max_y - self.min_y
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        ((self.min_x + self.max_x) * 0.5, (self.min_y + self.max_y) * 0.5)
+    }
+
+    pub fn contains_point(&self, x: f32, y: f32) -> bool {
+        x >= self.min_x && x <= self.max_x && y >= self.min_y && y <= self.max_y
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min_x <= other.max_x && self.max_x >= other.min_x
+            && self.min_y <= other.max_y && self.max_y >= other.min_y
+    }
+
+    pub fn union(&self, other: &Rect) -> Self {
+        Self::new(
+            self.min_x.min(other.min_x),
+            self.min_y.min(other.min_y),
+            self.max_x.max(other.max_x),
+            self.max_y.max(other.max_y)
+        )
+    }
+}
@@ -0,0 +1,152 @@
+use crate::bivector3::Bivector3;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// Rotor from geometric algebra: a scalar plus a bivector, rotating vectors
+// by the sandwich product R v R~. Rotations happen in a plane rather than
+// about an axis; the plane's dual is the axis a quaternion would use.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rotor3 {
+    pub scalar: f32,
+    pub bivector: Bivector3
+}
+
+#[allow(dead_code)]
+impl Rotor3 {
+    pub fn new(scalar: f32, bivector: Bivector3) -> Self {
+        Self {
+            scalar,
+            bivector
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1.0, Bivector3::default())
+    }
+
+    // Rotation by `angle` radians in `plane`, turning vectors in the plane
+    // from its first towards its second basis direction, so the xy plane
+    // takes x towards y.
+    pub fn from_plane_angle(plane: &Bivector3, angle: f32) -> Self {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        Self::new(cos, plane.normalize() * -sin)
+    }
+
+    // Shortest rotation taking the direction of `from` to that of `to`.
+    // Undefined for opposite vectors.
+    pub fn from_to(from: &Vec4, to: &Vec4) -> Self {
+        let (from, to) = (xyz(from).normalize(), xyz(to).normalize());
+        Self::new(1.0 + to.dot(&from), Bivector3::wedge(&to, &from)).normalize()
+    }
+
+    pub fn length(&self) -> f32 {
+        let b = self.bivector;
+        (self.scalar * self.scalar + b.xy * b.xy + b.xz * b.xz + b.yz * b.yz).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        let length = self.length();
+        Self::new(self.scalar / length, self.bivector * (1.0 / length))
+    }
+
+    // The inverse rotation for a unit rotor.
+    pub fn reverse(&self) -> Self {
+        Self::new(self.scalar, -self.bivector)
+    }
+
+    // Rotates the x, y and z of `v`; w is passed through.
+    pub fn rotate(&self, v: &Vec4) -> Vec4 {
+        let (a, b) = (self.scalar, self.bivector);
+        // R v: a vector part plus a trivector part.
+        let qx = a * v.x + v.y * b.xy + v.z * b.xz;
+        let qy = a * v.y - v.x * b.xy + v.z * b.yz;
+        let qz = a * v.z - v.x * b.xz - v.y * b.yz;
+        let qxyz = v.x * b.yz - v.y * b.xz + v.z * b.xy;
+        // (R v) R~.
+        Vec4::new(
+            a * qx + qy * b.xy + qz * b.xz + qxyz * b.yz,
+            a * qy - qx * b.xy - qxyz * b.xz + qz * b.yz,
+            a * qz + qxyz * b.xy - qx * b.xz - qy * b.yz,
+            v.w
+        )
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        let mut out = Mat4::identity();
+        for col in 0..3 {
+            let mut axis = Vec4::zero();
+            axis[col] = 1.0;
+            let rotated = self.rotate(&axis);
+            for row in 0..3 {
+                out.elements[col * 4 + row] = rotated[row];
+            }
+        }
+        out
+    }
+}
+
+fn xyz(v: &Vec4) -> Vec4 {
+    Vec4::new(v.x, v.y, v.z, 0.0)
+}
+
+// Composition: (p * q) rotates by q first, then by p.
+impl std::ops::Mul<Rotor3> for Rotor3 {
+    type Output = Rotor3;
+
+    fn mul(self, q: Rotor3) -> Self::Output {
+        let (pa, p) = (self.scalar, self.bivector);
+        let (qa, q) = (q.scalar, q.bivector);
+        Rotor3::new(
+            pa * qa - p.xy * q.xy - p.xz * q.xz - p.yz * q.yz,
+            Bivector3::new(
+                p.xy * qa + pa * q.xy + p.yz * q.xz - p.xz * q.yz,
+                p.xz * qa + pa * q.xz - p.yz * q.xy + p.xy * q.yz,
+                p.yz * qa + pa * q.yz + p.xz * q.xy - p.xy * q.xz
+            )
+        )
+    }
+}
+
+impl std::ops::Mul<Vec4> for Rotor3 {
+    type Output = Vec4;
+
+    fn mul(self, rhs: Vec4) -> Self::Output {
+        self.rotate(&rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &Vec4, b: &Vec4) -> bool {
+        (*a - *b).length() < 1e-5
+    }
+
+    #[test]
+    fn plane_rotation_and_composition() {
+        let x = Vec4::new(1.0, 0.0, 0.0, 0.0);
+        let y = Vec4::new(0.0, 1.0, 0.0, 0.0);
+        let z = Vec4::new(0.0, 0.0, 1.0, 0.0);
+        let quarter = std::f32::consts::FRAC_PI_2;
+
+        let xy = Rotor3::from_plane_angle(&Bivector3::wedge(&x, &y), quarter);
+        assert!(close(&xy.rotate(&x), &y));
+        assert!(close(&xy.rotate(&z), &z));
+        let yz = Rotor3::from_plane_angle(&Bivector3::wedge(&y, &z), quarter);
+        assert!(close(&yz.rotate(&y), &z));
+
+        // Composition matches applying in sequence.
+        let v = Vec4::new(0.3, -1.2, 2.0, 1.0);
+        let both = yz * xy;
+        assert!(close(&both.rotate(&v), &yz.rotate(&xy.rotate(&v))));
+        assert!(close(&(both.to_mat4() * v), &both.rotate(&v)));
+        assert!(close(&both.reverse().rotate(&both.rotate(&v)), &v));
+
+        let from = Vec4::new(1.0, 2.0, -0.5, 0.0);
+        let to = Vec4::new(-0.4, 0.1, 3.0, 0.0);
+        assert!(close(&Rotor3::from_to(&from, &to).rotate(&from.normalize()), &to.normalize()));
+        assert!(close(&Bivector3::wedge(&x, &y).dual(), &x.cross(&y)));
+    }
+}
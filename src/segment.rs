@@ -0,0 +1,52 @@
+use crate::aabb::Aabb;
+use crate::closest_point::{direction, point, ClosestPoint};
+use crate::vec4::Vec4;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Segment {
+    pub start: Vec4,
+    pub end: Vec4
+}
+
+#[allow(dead_code)]
+impl Segment {
+    pub fn new(start: Vec4, end: Vec4) -> Self {
+        Self {
+            start: point(&start),
+            end: point(&end)
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        (self.end - self.start).length()
+    }
+
+    // Parameter in [0, 1] of the point on the segment closest to `p`.
+    pub fn closest_parameter(&self, p: &Vec4) -> f32 {
+        let d = self.end - self.start;
+        let length_squared = d.dot(&d);
+        if length_squared == 0.0 {
+            return 0.0;
+        }
+        (direction(&(*p - self.start)).dot(&d) / length_squared).clamp(0.0, 1.0)
+    }
+
+    pub fn at(&self, t: f32) -> Vec4 {
+        self.start + (self.end - self.start) * t
+    }
+
+    pub fn support(&self, d: &Vec4) -> Vec4 {
+        if direction(&(self.end - self.start)).dot(&direction(d)) > 0.0 { self.end } else { self.start }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        Aabb::from_points(&[self.start, self.end]).unwrap()
+    }
+}
+
+impl ClosestPoint for Segment {
+    fn closest_point(&self, p: &Vec4) -> Vec4 {
+        self.at(self.closest_parameter(p))
+    }
+}
@@ -0,0 +1,195 @@
+use crate::aabb::Aabb;
+use crate::capsule::Capsule;
+use crate::closest_point::ClosestPoint;
+use crate::obb::Obb;
+use crate::segment::Segment;
+use crate::sphere::Sphere;
+use crate::triangle::Triangle;
+use crate::vec4::Vec4;
+
+// Any bounded primitive, so colliders of different kinds can share one
+// collection. Planes are left out since they have no bounding box or
+// support point.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub enum Shape {
+    Sphere(Sphere),
+    Aabb(Aabb),
+    Obb(Obb),
+    Capsule(Capsule),
+    Triangle(Triangle),
+    Segment(Segment)
+}
+
+#[allow(dead_code)]
+impl Shape {
+    // Ray parameter of the first hit, zero when the origin is inside.
+    // `max_distance` is measured along the ray in world units, i.e. against
+    // t * |direction|, so it doesn't depend on the direction's length.
+    // Segments are too thin to be hit.
+    pub fn ray_cast(&self, origin: &Vec4, direction: &Vec4, max_distance: f32) -> Option<f32> {
+        let t = match self {
+            Shape::Sphere(s) => s.ray_cast(origin, direction),
+            Shape::Aabb(b) => b.ray_intersection(origin, direction).map(|(entry, _)| entry),
+            Shape::Obb(b) => b.ray_cast(origin, direction),
+            Shape::Capsule(c) => c.ray_cast(origin, direction),
+            Shape::Triangle(t) => t.ray_cast(origin, direction),
+            Shape::Segment(_) => None
+        }?;
+        let speed = Vec4::new(direction.x, direction.y, direction.z, 0.0).length();
+        if t * speed <= max_distance { Some(t) } else { None }
+    }
+
+    // Whether `point` is inside the solid. Always false for triangles and
+    // segments, which have no interior.
+    pub fn contains(&self, point: &Vec4) -> bool {
+        match self {
+            Shape::Sphere(s) => s.contains_point(point),
+            Shape::Aabb(b) => b.contains_point(point),
+            Shape::Obb(b) => b.contains_point(point),
+            Shape::Capsule(c) => c.contains_point(point),
+            Shape::Triangle(_) | Shape::Segment(_) => false
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        match self {
+            Shape::Sphere(s) => s.aabb(),
+            Shape::Aabb(b) => *b,
+            Shape::Obb(b) => b.aabb(),
+            Shape::Capsule(c) => c.aabb(),
+            Shape::Triangle(t) => t.aabb(),
+            Shape::Segment(s) => s.aabb()
+        }
+    }
+
+    // Farthest point of the shape in `direction`, as used by GJK/EPA.
+    pub fn support(&self, direction: &Vec4) -> Vec4 {
+        match self {
+            Shape::Sphere(s) => s.support(direction),
+            Shape::Aabb(b) => b.support(direction),
+            Shape::Obb(b) => b.support(direction),
+            Shape::Capsule(c) => c.support(direction),
+            Shape::Triangle(t) => t.support(direction),
+            Shape::Segment(s) => s.support(direction)
+        }
+    }
+
+    // Zero for triangles and segments.
+    pub fn volume(&self) -> f32 {
+        match self {
+            Shape::Sphere(s) => s.volume(),
+            Shape::Aabb(b) => b.volume(),
+            Shape::Obb(b) => b.volume(),
+            Shape::Capsule(c) => c.volume(),
+            Shape::Triangle(_) | Shape::Segment(_) => 0.0
+        }
+    }
+
+    // A triangle counts one side only.
+    pub fn surface_area(&self) -> f32 {
+        match self {
+            Shape::Sphere(s) => s.surface_area(),
+            Shape::Aabb(b) => b.surface_area(),
+            Shape::Obb(b) => b.surface_area(),
+            Shape::Capsule(c) => c.surface_area(),
+            Shape::Triangle(t) => t.area(),
+            Shape::Segment(_) => 0.0
+        }
+    }
+}
+
+impl ClosestPoint for Shape {
+    fn closest_point(&self, point: &Vec4) -> Vec4 {
+        match self {
+            Shape::Sphere(s) => s.closest_point(point),
+            Shape::Aabb(b) => b.closest_point(point),
+            Shape::Obb(b) => b.closest_point(point),
+            Shape::Capsule(c) => c.closest_point(point),
+            Shape::Triangle(t) => t.closest_point(point),
+            Shape::Segment(s) => s.closest_point(point)
+        }
+    }
+}
+
+impl From<Sphere> for Shape {
+    fn from(shape: Sphere) -> Self {
+        Shape::Sphere(shape)
+    }
+}
+
+impl From<Aabb> for Shape {
+    fn from(shape: Aabb) -> Self {
+        Shape::Aabb(shape)
+    }
+}
+
+impl From<Obb> for Shape {
+    fn from(shape: Obb) -> Self {
+        Shape::Obb(shape)
+    }
+}
+
+impl From<Capsule> for Shape {
+    fn from(shape: Capsule) -> Self {
+        Shape::Capsule(shape)
+    }
+}
+
+impl From<Triangle> for Shape {
+    fn from(shape: Triangle) -> Self {
+        Shape::Triangle(shape)
+    }
+}
+
+impl From<Segment> for Shape {
+    fn from(shape: Segment) -> Self {
+        Shape::Segment(shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f32, y: f32, z: f32) -> Vec4 {
+        Vec4::new(x, y, z, 1.0)
+    }
+
+    #[test]
+    fn heterogeneous_ray_casts() {
+        let s = std::f32::consts::FRAC_1_SQRT_2;
+        let shapes: Vec<Shape> = vec![
+            Sphere::new(p(0.0, 0.0, 10.0), 1.0).into(),
+            Aabb::new(Vec4::new(-1.0, -1.0, 20.0, 0.0), Vec4::new(1.0, 1.0, 22.0, 0.0)).into(),
+            Obb::new(p(0.0, 0.0, 30.0), [Vec4::new(s, s, 0.0, 0.0), Vec4::new(-s, s, 0.0, 0.0), Vec4::new(0.0, 0.0, 1.0, 0.0)], Vec4::new(1.0, 1.0, 1.0, 0.0)).into(),
+            Capsule::new(p(-2.0, 0.0, 40.0), p(2.0, 0.0, 40.0), 0.5).into(),
+            Triangle::new(p(-1.0, -1.0, 50.0), p(1.0, -1.0, 50.0), p(0.0, 1.0, 50.0)).into(),
+            Segment::new(p(0.0, -1.0, 60.0), p(0.0, 1.0, 60.0)).into()
+        ];
+        let origin = p(0.0, 0.0, 0.0);
+        let forward = Vec4::new(0.0, 0.0, 2.0, 0.0);
+        let hits: Vec<Option<f32>> = shapes.iter().map(|shape| shape.ray_cast(&origin, &forward, 100.0)).collect();
+        let expected = [Some(4.5), Some(10.0), Some(14.5), Some(19.75), Some(25.0), None];
+        for (hit, expected) in hits.iter().zip(expected) {
+            assert_eq!(hit.is_some(), expected.is_some());
+            if let (Some(hit), Some(expected)) = (hit, expected) {
+                assert!((hit - expected).abs() < 1e-4);
+            }
+        }
+        // The sphere is hit at t = 4.5, which is 9 units away.
+        assert!(shapes[0].ray_cast(&origin, &forward, 8.5).is_none());
+        assert!(shapes[0].ray_cast(&origin, &forward, 9.5).is_some());
+        assert_eq!(shapes[3].ray_cast(&p(1.9, 0.0, 40.0), &forward, 1.0), Some(0.0));
+
+        // Support points lie on the bounding boxes, which contain the shapes.
+        let up = Vec4::new(0.0, 1.0, 0.0, 0.0);
+        for shape in &shapes {
+            let bounds = shape.aabb();
+            let support = shape.support(&up);
+            assert!((support.y - bounds.max.y).abs() < 1e-5);
+        }
+        assert!(shapes[2].contains(&p(0.0, 1.3, 30.0)) && !shapes[2].contains(&p(1.3, 1.3, 30.0)));
+        assert!((shapes[1].distance(&p(0.0, 3.0, 21.0)) - 2.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,62 @@
+use std::arch::x86_64::*;
+
+// SSE versions of the f32 `Float::mat4_mul` and `Float::mat4_mul_vec4`
+// kernels. They add the products in the same order as the scalar loops, so
+// results are bit-identical with and without the `simd` feature.
+
+pub(crate) fn mat4_mul(lhs: &[f32; 16], rhs: &[f32; 16]) -> [f32; 16] {
+    let mut result = [0.0f32; 16];
+    // SAFETY: SSE is part of the x86_64 baseline, and every unaligned load
+    // and store covers four f32s inside a 16-element array.
+    unsafe {
+        let cols = [0, 1, 2, 3].map(|k| _mm_loadu_ps(lhs.as_ptr().add(k * 4)));
+        for col in 0..4 {
+            let mut acc = _mm_setzero_ps();
+            for k in 0..4 {
+                acc = _mm_add_ps(acc, _mm_mul_ps(cols[k], _mm_set1_ps(rhs[col * 4 + k])));
+            }
+            _mm_storeu_ps(result.as_mut_ptr().add(col * 4), acc);
+        }
+    }
+    result
+}
+
+pub(crate) fn mat4_mul_vec4(m: &[f32; 16], v: [f32; 4]) -> [f32; 4] {
+    let mut result = [0.0f32; 4];
+    // SAFETY: as in `mat4_mul`.
+    unsafe {
+        let mut acc = _mm_mul_ps(_mm_loadu_ps(m.as_ptr()), _mm_set1_ps(v[0]));
+        for k in 1..4 {
+            acc = _mm_add_ps(acc, _mm_mul_ps(_mm_loadu_ps(m.as_ptr().add(k * 4)), _mm_set1_ps(v[k])));
+        }
+        _mm_storeu_ps(result.as_mut_ptr(), acc);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_scalar_bit_for_bit() {
+        let lhs: [f32; 16] = std::array::from_fn(|i| (i as f32 * 0.37).sin() * 10.0);
+        let rhs: [f32; 16] = std::array::from_fn(|i| (i as f32 * 1.3).cos() / 3.0);
+        let v = [0.1f32, -2.5, 7.0, 1.0];
+
+        let mut expected = [0.0f32; 16];
+        for col in 0..4 {
+            for row in 0..4 {
+                for k in 0..4 {
+                    expected[col * 4 + row] += lhs[k * 4 + row] * rhs[col * 4 + k];
+                }
+            }
+        }
+        assert_eq!(mat4_mul(&lhs, &rhs).map(f32::to_bits), expected.map(f32::to_bits));
+
+        let expected: [f32; 4] = std::array::from_fn(|row| {
+            lhs[row] * v[0] + lhs[4 + row] * v[1] + lhs[8 + row] * v[2] + lhs[12 + row] * v[3]
+        });
+        assert_eq!(mat4_mul_vec4(&lhs, v).map(f32::to_bits), expected.map(f32::to_bits));
+    }
+}
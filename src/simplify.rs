@@ -0,0 +1,152 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::closest_point::ClosestPoint;
+use crate::segment::Segment;
+use crate::vec4::Vec4;
+
+// Polyline simplification. Points are positions with x, y and z read and
+// w ignored, so 2D paths just leave z at zero. Both functions keep the
+// first and last points and return the indices of the points kept, in
+// order, so callers can carry timestamps or other attributes along.
+
+// Ramer-Douglas-Peucker: keeps the fewest points such that every dropped
+// point lies within `tolerance` of the simplified path. Good at keeping
+// sharp corners.
+#[allow(dead_code)]
+pub fn simplify_rdp(points: &[Vec4], tolerance: f32) -> Vec<usize> {
+    if points.len() <= 2 {
+        return (0..points.len()).collect();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let segment = Segment::new(points[first], points[last]);
+        let mut farthest = (0.0, first);
+        for i in (first + 1)..last {
+            let d = segment.distance_squared(&points[i]);
+            if d > farthest.0 {
+                farthest = (d, i);
+            }
+        }
+        if farthest.0 > tolerance * tolerance {
+            keep[farthest.1] = true;
+            stack.push((first, farthest.1));
+            stack.push((farthest.1, last));
+        }
+    }
+    (0..points.len()).filter(|&i| keep[i]).collect()
+}
+
+// Area of the triangle a point forms with its neighbours, with the index
+// it belongs to; ordered so the heap pops the smallest area first.
+#[derive(PartialEq)]
+struct Candidate(f32, usize);
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then(other.1.cmp(&self.1))
+    }
+}
+
+fn triangle_area(a: &Vec4, b: &Vec4, c: &Vec4) -> f32 {
+    let (ab, ac) = (*b - *a, *c - *a);
+    Vec4::new(ab.x, ab.y, ab.z, 0.0).cross(&Vec4::new(ac.x, ac.y, ac.z, 0.0)).length() * 0.5
+}
+
+// Visvalingam-Whyatt: repeatedly drops the point whose triangle with its
+// neighbours has the smallest area, until every remaining one is at least
+// `min_area`. Removes small wiggles evenly and tends to look more natural
+// than RDP for outlines and GPS traces.
+#[allow(dead_code)]
+pub fn simplify_visvalingam(points: &[Vec4], min_area: f32) -> Vec<usize> {
+    let n = points.len();
+    if n <= 2 {
+        return (0..n).collect();
+    }
+    let mut previous: Vec<usize> = (0..n).map(|i| i.wrapping_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=n).collect();
+    let mut area = vec![f32::INFINITY; n];
+    let mut heap = BinaryHeap::new();
+    for i in 1..(n - 1) {
+        area[i] = triangle_area(&points[i - 1], &points[i], &points[i + 1]);
+        heap.push(Candidate(area[i], i));
+    }
+    let mut removed = vec![false; n];
+    while let Some(Candidate(smallest, i)) = heap.pop() {
+        // Skip entries made stale by a neighbour's removal.
+        if removed[i] || smallest != area[i] {
+            continue;
+        }
+        if smallest >= min_area {
+            break;
+        }
+        removed[i] = true;
+        let (p, q) = (previous[i], next[i]);
+        next[p] = q;
+        previous[q] = p;
+        for j in [p, q] {
+            if j != 0 && j != n - 1 {
+                // Never let a neighbour's area drop below the one just
+                // removed, so points go in a consistent order.
+                area[j] = triangle_area(&points[previous[j]], &points[j], &points[next[j]]).max(smallest);
+                heap.push(Candidate(area[j], j));
+            }
+        }
+    }
+    (0..n).filter(|&i| !removed[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zigzag() -> Vec<Vec4> {
+        // A square wave with small noise on its flat runs.
+        let mut points = Vec::new();
+        for i in 0..40 {
+            let x = i as f32 * 0.25;
+            let level = if (i / 10) % 2 == 0 { 0.0 } else { 5.0 };
+            let noise = if i % 2 == 0 { 0.01 } else { -0.01 };
+            points.push(Vec4::new(x, level + noise, 0.0, 1.0));
+        }
+        points
+    }
+
+    #[test]
+    fn rdp_keeps_corners_within_tolerance() {
+        let points = zigzag();
+        let kept = simplify_rdp(&points, 0.05);
+        assert_eq!(kept.first(), Some(&0));
+        assert_eq!(kept.last(), Some(&39));
+        assert!(kept.len() <= 10, "{:?}", kept);
+        for window in kept.windows(2) {
+            let segment = Segment::new(points[window[0]], points[window[1]]);
+            assert!((window[0]..window[1]).all(|i| segment.distance(&points[i]) <= 0.05));
+        }
+        assert_eq!(simplify_rdp(&points, 0.0).len(), points.len());
+    }
+
+    #[test]
+    fn visvalingam_drops_small_wiggles() {
+        let points = zigzag();
+        let kept = simplify_visvalingam(&points, 0.1);
+        assert_eq!((kept[0], kept[kept.len() - 1]), (0, 39));
+        assert!(kept.len() <= 10, "{:?}", kept);
+        // Each step of the wave survives.
+        for step in [9, 10, 19, 20, 29, 30] {
+            assert!(kept.contains(&step), "{:?}", kept);
+        }
+        assert_eq!(simplify_visvalingam(&points[..2], 1.0), vec![0, 1]);
+    }
+}
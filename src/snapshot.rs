@@ -0,0 +1,383 @@
+#[cfg(feature = "geometry")]
+use crate::aabb::Aabb;
+use crate::mat4::Mat4;
+use crate::transform::Transform;
+use crate::vec4::Vec4;
+
+// Chunked binary format for persisting math data. Everything is
+// little-endian regardless of the host:
+//
+//   header  "LALG", major u16, minor u16, 8 reserved bytes
+//   chunk   tag [u8; 4], count u32, payload length u32, reserved u32,
+//           then the payload padded with zeros to a multiple of 16
+//
+// Headers and padded payloads are multiples of 16 bytes, so every payload
+// starts 16-byte aligned relative to the start of the buffer. Readers skip
+// chunks with unknown tags by their length, which lets later minor versions
+// add chunk types; a different major version is rejected.
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"LALG";
+pub const SNAPSHOT_MAJOR: u16 = 1;
+pub const SNAPSHOT_MINOR: u16 = 0;
+const HEADER_SIZE: usize = 16;
+const CHUNK_HEADER_SIZE: usize = 16;
+
+// Chunk tags and the f32 count of one element.
+const VEC4_TAG: [u8; 4] = *b"VEC4";
+const MAT4_TAG: [u8; 4] = *b"MAT4";
+const TRANSFORM_TAG: [u8; 4] = *b"XFRM";
+#[cfg(feature = "geometry")]
+const AABB_TAG: [u8; 4] = *b"AABB";
+const TRANSFORM_FLOATS: usize = 24;
+#[cfg(feature = "geometry")]
+const AABB_FLOATS: usize = 8;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SnapshotError {
+    // The buffer does not start with `SNAPSHOT_MAGIC`.
+    BadMagic,
+    // Written with a major version this reader does not understand.
+    UnsupportedVersion(u16),
+    // A header or payload runs past the end of the buffer.
+    Truncated,
+    // A known chunk whose length does not match its element count.
+    MalformedChunk([u8; 4]),
+    // A zero-copy view over a payload that is not aligned for its type.
+    Misaligned,
+    // Zero-copy views need a little-endian host to match the stored data.
+    BigEndianHost
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::BadMagic => f.write_str("Not a lalg snapshot"),
+            SnapshotError::UnsupportedVersion(major) => write!(f, "Unsupported snapshot version {}", major),
+            SnapshotError::Truncated => f.write_str("Snapshot is truncated"),
+            SnapshotError::MalformedChunk(tag) => write!(f, "Malformed {} chunk", String::from_utf8_lossy(tag)),
+            SnapshotError::Misaligned => f.write_str("Snapshot data is misaligned"),
+            SnapshotError::BigEndianHost => f.write_str("Zero-copy snapshot views need a little-endian host")
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+// One decoded chunk. Chunks with unknown tags are skipped rather than
+// returned.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub enum SnapshotChunk {
+    Vec4(Vec<Vec4>),
+    Mat4(Vec<Mat4>),
+    Transform(Vec<Transform>),
+    #[cfg(feature = "geometry")]
+    Aabb(Vec<Aabb>)
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct SnapshotWriter {
+    bytes: Vec<u8>
+}
+
+impl Default for SnapshotWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl SnapshotWriter {
+    pub fn new() -> Self {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&SNAPSHOT_MAGIC);
+        bytes.extend_from_slice(&SNAPSHOT_MAJOR.to_le_bytes());
+        bytes.extend_from_slice(&SNAPSHOT_MINOR.to_le_bytes());
+        bytes.resize(HEADER_SIZE, 0);
+        Self {
+            bytes
+        }
+    }
+
+    // Appends a chunk with a caller-defined tag. Readers that do not know
+    // the tag skip it.
+    pub fn write_chunk(&mut self, tag: [u8; 4], count: u32, payload: &[u8]) -> &mut Self {
+        let length = u32::try_from(payload.len()).expect("Chunk is too large");
+        self.bytes.extend_from_slice(&tag);
+        self.bytes.extend_from_slice(&count.to_le_bytes());
+        self.bytes.extend_from_slice(&length.to_le_bytes());
+        self.bytes.extend_from_slice(&[0; 4]);
+        self.bytes.extend_from_slice(payload);
+        self.bytes.resize(self.bytes.len().next_multiple_of(16), 0);
+        self
+    }
+
+    pub fn write_vec4s(&mut self, values: &[Vec4]) -> &mut Self {
+        self.write_floats(VEC4_TAG, values.len(), values.iter().flat_map(|v| [v.x, v.y, v.z, v.w]))
+    }
+
+    pub fn write_mat4s(&mut self, values: &[Mat4]) -> &mut Self {
+        self.write_floats(MAT4_TAG, values.len(), values.iter().flat_map(|m| m.elements))
+    }
+
+    pub fn write_transforms(&mut self, values: &[Transform]) -> &mut Self {
+        let floats = values.iter().flat_map(|t| {
+            let (p, s) = (t.translation, t.scale);
+            [p.x, p.y, p.z, p.w].into_iter().chain(t.rotation.elements).chain([s.x, s.y, s.z, s.w])
+        });
+        self.write_floats(TRANSFORM_TAG, values.len(), floats)
+    }
+
+    #[cfg(feature = "geometry")]
+    pub fn write_aabbs(&mut self, values: &[Aabb]) -> &mut Self {
+        let floats = values.iter().flat_map(|b| [b.min.x, b.min.y, b.min.z, b.min.w, b.max.x, b.max.y, b.max.z, b.max.w]);
+        self.write_floats(AABB_TAG, values.len(), floats)
+    }
+
+    fn write_floats(&mut self, tag: [u8; 4], count: usize, floats: impl Iterator<Item = f32>) -> &mut Self {
+        let payload: Vec<u8> = floats.flat_map(f32::to_le_bytes).collect();
+        let count = u32::try_from(count).expect("Chunk is too large");
+        self.write_chunk(tag, count, &payload)
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+// A chunk as stored, before decoding.
+#[derive(Clone, Copy, Debug)]
+struct RawChunk<'a> {
+    tag: [u8; 4],
+    count: usize,
+    payload: &'a [u8]
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+// Validates the header and splits the rest of the buffer into chunks.
+fn raw_chunks(bytes: &[u8]) -> Result<Vec<RawChunk<'_>>, SnapshotError> {
+    if !bytes.starts_with(&SNAPSHOT_MAGIC) {
+        return Err(SnapshotError::BadMagic);
+    }
+    if bytes.len() < HEADER_SIZE {
+        return Err(SnapshotError::Truncated);
+    }
+    let major = read_u16(bytes, 4);
+    if major != SNAPSHOT_MAJOR {
+        return Err(SnapshotError::UnsupportedVersion(major));
+    }
+    let mut chunks = Vec::new();
+    let mut at = HEADER_SIZE;
+    while at < bytes.len() {
+        if bytes.len() - at < CHUNK_HEADER_SIZE {
+            return Err(SnapshotError::Truncated);
+        }
+        let tag = [bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]];
+        let count = read_u32(bytes, at + 4) as usize;
+        let length = read_u32(bytes, at + 8) as usize;
+        let offset = at + CHUNK_HEADER_SIZE;
+        if bytes.len() - offset < length {
+            return Err(SnapshotError::Truncated);
+        }
+        chunks.push(RawChunk {
+            tag,
+            count,
+            payload: &bytes[offset..offset + length]
+        });
+        // The final chunk's padding may have been trimmed.
+        at = (offset + length).next_multiple_of(16).min(bytes.len());
+    }
+    Ok(chunks)
+}
+
+// Checks that a chunk holds exactly `count` elements of `floats` f32 each.
+fn expect_floats(chunk: &RawChunk, floats: usize) -> Result<(), SnapshotError> {
+    if chunk.count.checked_mul(floats * 4) != Some(chunk.payload.len()) {
+        return Err(SnapshotError::MalformedChunk(chunk.tag));
+    }
+    Ok(())
+}
+
+fn decode<T>(chunk: &RawChunk, floats: usize, build: impl Fn(&[f32]) -> T) -> Result<Vec<T>, SnapshotError> {
+    expect_floats(chunk, floats)?;
+    let values: Vec<f32> = chunk.payload.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+    Ok(values.chunks_exact(floats).map(build).collect())
+}
+
+fn vec4(f: &[f32]) -> Vec4 {
+    Vec4::new(f[0], f[1], f[2], f[3])
+}
+
+fn mat4(f: &[f32]) -> Mat4 {
+    let mut out = Mat4::zeroes();
+    out.elements.copy_from_slice(&f[..16]);
+    out
+}
+
+// Decodes every chunk this build understands, in file order.
+#[allow(dead_code)]
+pub fn read_snapshot(bytes: &[u8]) -> Result<Vec<SnapshotChunk>, SnapshotError> {
+    let mut out = Vec::new();
+    for chunk in raw_chunks(bytes)? {
+        let decoded = match chunk.tag {
+            VEC4_TAG => SnapshotChunk::Vec4(decode(&chunk, 4, vec4)?),
+            MAT4_TAG => SnapshotChunk::Mat4(decode(&chunk, 16, mat4)?),
+            TRANSFORM_TAG => SnapshotChunk::Transform(decode(&chunk, TRANSFORM_FLOATS, |f| {
+                Transform::new(vec4(&f[0..4]), mat4(&f[4..20]), vec4(&f[20..24]))
+            })?),
+            #[cfg(feature = "geometry")]
+            AABB_TAG => SnapshotChunk::Aabb(decode(&chunk, AABB_FLOATS, |f| Aabb::new(vec4(&f[0..4]), vec4(&f[4..8])))?),
+            _ => continue
+        };
+        out.push(decoded);
+    }
+    Ok(out)
+}
+
+// Read-only, zero-copy access to the Vec4 and Mat4 chunks of a snapshot,
+// for example over a memory-mapped file. The layout is checked once up
+// front: payloads must be aligned for f32, which holds whenever the buffer
+// itself is 4-byte aligned since payloads sit at multiples of 16 (mmap and
+// most allocators give far more). Other chunks are validated but only
+// reachable through `read_snapshot`.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct SnapshotView<'a> {
+    chunks: Vec<RawChunk<'a>>
+}
+
+#[allow(dead_code)]
+impl<'a> SnapshotView<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self, SnapshotError> {
+        if cfg!(target_endian = "big") {
+            return Err(SnapshotError::BigEndianHost);
+        }
+        let chunks = raw_chunks(bytes)?;
+        for chunk in &chunks {
+            let floats = match chunk.tag {
+                VEC4_TAG => 4,
+                MAT4_TAG => 16,
+                _ => continue
+            };
+            expect_floats(chunk, floats)?;
+            if chunk.payload.as_ptr().align_offset(std::mem::align_of::<f32>()) != 0 {
+                return Err(SnapshotError::Misaligned);
+            }
+        }
+        Ok(Self {
+            chunks
+        })
+    }
+
+    // Every Vec4 chunk, in file order.
+    pub fn vec4s(&self) -> impl Iterator<Item = &'a [Vec4]> + '_ {
+        self.chunks.iter().filter(|c| c.tag == VEC4_TAG).map(|c| cast_slice(c))
+    }
+
+    // Every Mat4 chunk, in file order.
+    pub fn mat4s(&self) -> impl Iterator<Item = &'a [Mat4]> + '_ {
+        self.chunks.iter().filter(|c| c.tag == MAT4_TAG).map(|c| cast_slice(c))
+    }
+}
+
+fn cast_slice<'a, T>(chunk: &RawChunk<'a>) -> &'a [T] {
+    debug_assert_eq!(chunk.payload.len(), chunk.count * std::mem::size_of::<T>());
+    // SAFETY: `SnapshotView::new` checked that the payload holds exactly
+    // `count` elements and is aligned for f32. Vec4 and Mat4 are repr(C)
+    // over f32 only, so they have f32 alignment, no padding, and every bit
+    // pattern is valid; on a little-endian host the stored bytes are their
+    // in-memory representation. The slice borrows the payload for 'a.
+    unsafe { std::slice::from_raw_parts(chunk.payload.as_ptr().cast::<T>(), chunk.count) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_and_skip_unknown_chunks() {
+        let mut m = Mat4::identity().translate(&Vec4::new(1.0, 2.0, 3.0, 0.0));
+        m.rotate(Vec4::new(0.0, 1.0, 0.0, 0.0), 0.5);
+        let t = Transform::new(Vec4::new(4.0, 5.0, 6.0, 0.0), Mat4::from_axis_angle(Vec4::new(1.0, 0.0, 0.0, 0.0), 0.25), Vec4::new(2.0, 2.0, 2.0, 0.0));
+        let mut writer = SnapshotWriter::new();
+        writer.write_mat4s(&[m, Mat4::identity()]).write_chunk(*b"NEW!", 1, &[1, 2, 3]).write_transforms(&[t]).write_vec4s(&[]);
+        let bytes = writer.finish();
+        assert_eq!(bytes.len() % 16, 0);
+        // Little-endian on every host: m[0] = cos(0.5).
+        assert_eq!(bytes[HEADER_SIZE + CHUNK_HEADER_SIZE..][..4], 0.5f32.cos().to_le_bytes());
+
+        let chunks = read_snapshot(&bytes).unwrap();
+        assert_eq!(chunks.len(), 3);
+        match &chunks[0] {
+            SnapshotChunk::Mat4(values) => assert!(values.len() == 2 && values[0].elements == m.elements),
+            other => panic!("{:?}", other)
+        }
+        match &chunks[1] {
+            SnapshotChunk::Transform(values) => {
+                assert_eq!(values[0].rotation.elements, t.rotation.elements);
+                assert_eq!([values[0].translation.x, values[0].scale.y], [4.0, 2.0]);
+            }
+            other => panic!("{:?}", other)
+        }
+        assert!(matches!(&chunks[2], SnapshotChunk::Vec4(values) if values.is_empty()));
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let mut writer = SnapshotWriter::new();
+        writer.write_vec4s(&[Vec4::new(1.0, 2.0, 3.0, 4.0)]);
+        let bytes = writer.finish();
+        assert_eq!(read_snapshot(&bytes[..bytes.len() - 1]).err(), Some(SnapshotError::Truncated));
+        assert_eq!(read_snapshot(b"GLTF").err(), Some(SnapshotError::BadMagic));
+
+        let mut newer = bytes.clone();
+        newer[4] = 2;
+        assert_eq!(read_snapshot(&newer).err(), Some(SnapshotError::UnsupportedVersion(2)));
+        let mut wrong_count = bytes.clone();
+        wrong_count[HEADER_SIZE + 4] = 2;
+        assert_eq!(read_snapshot(&wrong_count).err(), Some(SnapshotError::MalformedChunk(VEC4_TAG)));
+        // A newer minor version still reads.
+        let mut minor = bytes;
+        minor[6] = 7;
+        assert_eq!(read_snapshot(&minor).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn zero_copy_views() {
+        let points = [Vec4::new(1.0, 2.0, 3.0, 1.0), Vec4::new(-4.0, 5.0, 6.5, 0.0)];
+        let m = Mat4::from_axis_angle(Vec4::new(0.0, 0.0, 1.0, 0.0), 0.3).translate(&Vec4::new(1.0, 1.0, 1.0, 0.0));
+        let mut writer = SnapshotWriter::new();
+        writer.write_vec4s(&points).write_chunk(*b"SKIP", 0, &[9; 5]).write_mat4s(&[m; 3]);
+        let bytes = writer.finish();
+
+        // Copy into u32 storage so the buffer is aligned like a mapped file.
+        let mut storage = vec![0u32; bytes.len() / 4];
+        for (word, b) in storage.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_ne_bytes([b[0], b[1], b[2], b[3]]);
+        }
+        let aligned: &[u8] = unsafe { std::slice::from_raw_parts(storage.as_ptr().cast::<u8>(), bytes.len()) };
+
+        let view = SnapshotView::new(aligned).unwrap();
+        let vec4s: Vec<&[Vec4]> = view.vec4s().collect();
+        assert_eq!(vec4s.len(), 1);
+        assert_eq!([vec4s[0][1].x, vec4s[0][1].z], [-4.0, 6.5]);
+        let mat4s = view.mat4s().next().unwrap();
+        assert_eq!(mat4s.len(), 3);
+        assert_eq!(mat4s[2].elements, m.elements);
+        assert_eq!(mat4s.as_ptr().cast::<u8>(), aligned[HEADER_SIZE + 3 * CHUNK_HEADER_SIZE + 32 + 16..].as_ptr());
+
+        assert_eq!(SnapshotView::new(&aligned[..aligned.len() - 4]).err(), Some(SnapshotError::Truncated));
+        let mut shifted = vec![0u8; bytes.len() + 4];
+        let start = shifted.as_ptr().align_offset(4) + 1;
+        shifted[start..start + bytes.len()].copy_from_slice(&bytes);
+        assert_eq!(SnapshotView::new(&shifted[start..start + bytes.len()]).err(), Some(SnapshotError::Misaligned));
+    }
+}
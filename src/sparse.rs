@@ -0,0 +1,174 @@
+use crate::dmat::DMat;
+use crate::dvec::DVec;
+use crate::float::Float;
+
+// Sparse matrix in compressed sparse row format. Row `r` holds the entries
+// `values[row_offsets[r]..row_offsets[r + 1]]`, with their columns in the
+// same range of `col_indices`, sorted and without duplicates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMat<T = f64> {
+    rows: usize,
+    cols: usize,
+    row_offsets: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+#[allow(dead_code)]
+impl<T: Float> SparseMat<T> {
+    pub fn zeroes(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            row_offsets: vec![0; rows + 1],
+            col_indices: Vec::new(),
+            values: Vec::new()
+        }
+    }
+
+    pub fn identity(size: usize) -> Self {
+        Self {
+            rows: size,
+            cols: size,
+            row_offsets: (0..=size).collect(),
+            col_indices: (0..size).collect(),
+            values: vec![T::one(); size]
+        }
+    }
+
+    // Builds a matrix from (row, col, value) triplets in any order.
+    // Duplicate positions are summed, which is how finite element and
+    // Laplacian assembly usually accumulates contributions.
+    pub fn from_triplets(rows: usize, cols: usize, triplets: &[(usize, usize, T)]) -> Self {
+        let mut order: Vec<usize> = (0..triplets.len()).collect();
+        for &(row, col, _) in triplets {
+            assert!(row < rows && col < cols, "Index out of bounds");
+        }
+        order.sort_by_key(|&i| (triplets[i].0, triplets[i].1));
+
+        let mut out = Self::zeroes(rows, cols);
+        let mut last = None;
+        for i in order {
+            let (row, col, value) = triplets[i];
+            if last == Some((row, col)) {
+                *out.values.last_mut().unwrap() += value;
+            } else {
+                out.col_indices.push(col);
+                out.values.push(value);
+                out.row_offsets[row + 1] += 1;
+                last = Some((row, col));
+            }
+        }
+        for row in 0..rows {
+            out.row_offsets[row + 1] += out.row_offsets[row];
+        }
+        out
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // Number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        assert!(row < self.rows && col < self.cols, "Index out of bounds");
+        let range = self.row_offsets[row]..self.row_offsets[row + 1];
+        match self.col_indices[range.clone()].binary_search(&col) {
+            Ok(i) => self.values[range.start + i],
+            Err(_) => T::zero()
+        }
+    }
+
+    // Stored entries of one row as (col, value).
+    pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        let range = self.row_offsets[row]..self.row_offsets[row + 1];
+        self.col_indices[range.clone()].iter().copied().zip(self.values[range].iter().copied())
+    }
+
+    // Every stored entry as (row, col, value), in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        (0..self.rows).flat_map(move |row| self.row(row).map(move |(col, value)| (row, col, value)))
+    }
+
+    pub fn mul_vec(&self, v: &DVec<T>) -> DVec<T> {
+        assert_eq!(self.cols, v.len(), "Dimension mismatch");
+        let mut out = DVec::zero(self.rows);
+        for row in 0..self.rows {
+            let mut sum = T::zero();
+            for (col, value) in self.row(row) {
+                sum += value * v[col];
+            }
+            out[row] = sum;
+        }
+        out
+    }
+
+    // Counting sort by column; the result's rows come out already sorted.
+    pub fn transpose(&self) -> Self {
+        let mut out = Self::zeroes(self.cols, self.rows);
+        for &col in &self.col_indices {
+            out.row_offsets[col + 1] += 1;
+        }
+        for col in 0..self.cols {
+            out.row_offsets[col + 1] += out.row_offsets[col];
+        }
+        let mut next = out.row_offsets.clone();
+        out.col_indices = vec![0; self.nnz()];
+        out.values = vec![T::zero(); self.nnz()];
+        for (row, col, value) in self.iter() {
+            out.col_indices[next[col]] = row;
+            out.values[next[col]] = value;
+            next[col] += 1;
+        }
+        out
+    }
+
+    pub fn to_dmat(&self) -> DMat<T> {
+        let mut out = DMat::zeroes(self.rows, self.cols);
+        for (row, col, value) in self.iter() {
+            out[(row, col)] = value;
+        }
+        out
+    }
+}
+
+impl<T: Float> std::ops::Mul<DVec<T>> for SparseMat<T> {
+    type Output = DVec<T>;
+
+    fn mul(self, rhs: DVec<T>) -> Self::Output {
+        self.mul_vec(&rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triplets_spmv_and_transpose() {
+        // Graph Laplacian of the path 0 - 1 - 2 - 3, assembled per edge.
+        let mut triplets = Vec::new();
+        for (a, b) in [(0, 1), (1, 2), (2, 3)] {
+            triplets.extend_from_slice(&[(a, a, 1.0), (b, b, 1.0), (a, b, -1.0), (b, a, -1.0)]);
+        }
+        let laplacian = SparseMat::from_triplets(4, 4, &triplets);
+        assert_eq!(laplacian.nnz(), 10);
+        assert_eq!(laplacian.get(1, 1), 2.0);
+        assert_eq!(laplacian.get(0, 3), 0.0);
+        assert!(laplacian.mul_vec(&DVec::one(4)).norm() == 0.0);
+
+        let a = SparseMat::from_triplets(2, 3, &[(1, 2, 5.0), (0, 0, 1.0), (0, 2, 2.0), (1, 0, 3.0)]);
+        let v = DVec::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(a.mul_vec(&v), a.to_dmat().mul_vec(&v));
+        assert_eq!(a.transpose().to_dmat(), a.to_dmat().transpose());
+        assert_eq!(a.transpose().transpose(), a);
+    }
+}
@@ -0,0 +1,11 @@
+// Grids, spatial partitioning and the algorithms that run over them.
+pub use crate::bitgrid3::BitGrid3;
+pub use crate::bvh::Bvh;
+pub use crate::flood::{connected_components_2d, connected_components_3d, flood_fill_2d, flood_fill_3d, Connectivity2, Connectivity3, Region2, Region3};
+pub use crate::flow::FlowField;
+pub use crate::grid2::Grid2;
+pub use crate::grid3::Grid3;
+pub use crate::nbody::{gravitational_accelerations, BarnesHut};
+pub use crate::octree::Octree;
+pub use crate::quadtree::{LooseQuadtree, QuadtreeHandle};
+pub use crate::summed_area::SummedAreaTable;
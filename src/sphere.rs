@@ -0,0 +1,76 @@
+use crate::aabb::Aabb;
+use crate::closest_point::{direction, point, ClosestPoint};
+use crate::vec4::Vec4;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sphere {
+    pub center: Vec4,
+    pub radius: f32
+}
+
+#[allow(dead_code)]
+impl Sphere {
+    pub fn new(center: Vec4, radius: f32) -> Self {
+        Self {
+            center: point(&center),
+            radius
+        }
+    }
+
+    pub fn contains_point(&self, p: &Vec4) -> bool {
+        let d = direction(&(*p - self.center));
+        d.dot(&d) <= self.radius * self.radius
+    }
+
+    // Ray parameter t of the first hit, so the hit point is origin + d * t.
+    // Zero when the origin is inside.
+    pub fn ray_cast(&self, origin: &Vec4, d: &Vec4) -> Option<f32> {
+        let oc = direction(&(*origin - self.center));
+        let d = direction(d);
+        let a = d.dot(&d);
+        let b = oc.dot(&d);
+        let c = oc.dot(&oc) - self.radius * self.radius;
+        if c <= 0.0 {
+            return Some(0.0);
+        }
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 || a == 0.0 {
+            return None;
+        }
+        let t = (-b - discriminant.sqrt()) / a;
+        if t >= 0.0 { Some(t) } else { None }
+    }
+
+    pub fn support(&self, d: &Vec4) -> Vec4 {
+        let d = direction(d);
+        let length = d.length();
+        if length > 0.0 { self.center + d * (self.radius / length) } else { self.center }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        let r = Vec4::new(self.radius, self.radius, self.radius, 0.0);
+        Aabb::from_center_half_extents(&self.center, &r)
+    }
+
+    pub fn volume(&self) -> f32 {
+        4.0 / 3.0 * std::f32::consts::PI * self.radius.powi(3)
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        4.0 * std::f32::consts::PI * self.radius * self.radius
+    }
+}
+
+impl ClosestPoint for Sphere {
+    fn closest_point(&self, p: &Vec4) -> Vec4 {
+        let d = direction(&(*p - self.center));
+        let length = d.length();
+        if length <= self.radius {
+            point(p)
+        } else {
+            self.center + d * (self.radius / length)
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::cell::Cell;
+
+// Operation counts gathered under the `stats` feature: matrix-matrix
+// products (`Mat4` and `Matrix`), inversions (`Mat4::try_inverse` and
+// everything built on it, `Lu::inverse`) and vector normalizations (`Vec4`
+// and `Vector`).
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpStats {
+    pub multiplies: u64,
+    pub inversions: u64,
+    pub normalizations: u64
+}
+
+// Per thread, so counting never contends; work spread over rayon or other
+// threads is counted on those threads.
+thread_local! {
+    static STATS: Cell<OpStats> = const {
+        Cell::new(OpStats {
+            multiplies: 0,
+            inversions: 0,
+            normalizations: 0
+        })
+    };
+}
+
+pub(crate) fn record(update: impl FnOnce(&mut OpStats)) {
+    STATS.with(|cell| {
+        let mut stats = cell.get();
+        update(&mut stats);
+        cell.set(stats);
+    });
+}
+
+// Counts on this thread since the last call, resetting them to zero. Call
+// once per frame to see how much math the frame did.
+#[allow(dead_code)]
+pub fn take_stats() -> OpStats {
+    STATS.with(Cell::take)
+}
+
+// Counts on this thread so far, without resetting them.
+#[allow(dead_code)]
+pub fn peek_stats() -> OpStats {
+    STATS.with(Cell::get)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mat4::Mat4;
+    use crate::matrix::Matrix;
+    use crate::vec4::Vec4;
+
+    #[test]
+    fn counts_and_resets_per_thread() {
+        take_stats();
+        let m = Mat4::<f32>::from_translation(&Vec4::new(1.0, 2.0, 3.0, 0.0));
+        let _ = m * m * m;
+        let _ = Matrix::<f32, 2, 3>::zeroes() * Matrix::<f32, 3, 2>::zeroes();
+        let _ = m.inverse();
+        let _ = m.lu().inverse();
+        let _ = Vec4::new(1.0f32, 2.0, 2.0, 0.0).normalize();
+        assert_eq!(peek_stats(), OpStats {
+            multiplies: 3,
+            inversions: 2,
+            normalizations: 1
+        });
+        assert_eq!(take_stats().multiplies, 3);
+        assert_eq!(take_stats(), OpStats::default());
+
+        std::thread::spawn(|| {
+            let _ = Mat4::<f32>::identity() * Mat4::identity();
+        }).join().unwrap();
+        assert_eq!(take_stats(), OpStats::default());
+    }
+}
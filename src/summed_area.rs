@@ -0,0 +1,99 @@
+use crate::grid2::Grid2;
+
+// Integral image: each entry holds the sum of all cells above and to the
+// left of it, so any axis-aligned rectangle sums in four lookups. Stored
+// with an extra zero row and column and accumulated in f64, since the
+// corner sums of large grids dwarf the cell values.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct SummedAreaTable {
+    width: usize,
+    height: usize,
+    sums: Vec<f64>
+}
+
+#[allow(dead_code)]
+impl SummedAreaTable {
+    pub fn new(grid: &Grid2<f32>) -> Self {
+        let (width, height) = (grid.width(), grid.height());
+        let stride = width + 1;
+        let mut sums = vec![0.0; stride * (height + 1)];
+        for y in 0..height {
+            let mut row = 0.0;
+            for x in 0..width {
+                row += grid[(x, y)] as f64;
+                sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + row;
+            }
+        }
+        Self {
+            width,
+            height,
+            sums
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Sum over the inclusive rectangle from `min` to `max`, clamped to the
+    // grid. Zero when the rectangle is empty.
+    pub fn sum(&self, min: (usize, usize), max: (usize, usize)) -> f32 {
+        if self.width == 0 || self.height == 0 {
+            return 0.0;
+        }
+        let (x1, y1) = (max.0.min(self.width - 1) + 1, max.1.min(self.height - 1) + 1);
+        let (x0, y0) = (min.0.min(x1), min.1.min(y1));
+        let stride = self.width + 1;
+        let at = |x: usize, y: usize| self.sums[y * stride + x];
+        (at(x1, y1) - at(x0, y1) - at(x1, y0) + at(x0, y0)) as f32
+    }
+
+    // Mean over the inclusive rectangle, clamped to the grid. Zero when the
+    // rectangle is empty.
+    pub fn average(&self, min: (usize, usize), max: (usize, usize)) -> f32 {
+        if self.width == 0 || self.height == 0 {
+            return 0.0;
+        }
+        let (x1, y1) = (max.0.min(self.width - 1) + 1, max.1.min(self.height - 1) + 1);
+        let count = x1.saturating_sub(min.0) * y1.saturating_sub(min.1);
+        if count == 0 { 0.0 } else { self.sum(min, max) / count as f32 }
+    }
+
+    // Box blur with a (2 * radius + 1)^2 window in constant time per cell.
+    // Near the edges the window is cut off and averages fewer cells.
+    pub fn box_blur(&self, radius: usize) -> Grid2<f32> {
+        Grid2::from_fn(self.width, self.height, |x, y| {
+            self.average((x.saturating_sub(radius), y.saturating_sub(radius)), (x + radius, y + radius))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangle_sums_match_brute_force() {
+        let grid = Grid2::from_fn(7, 5, |x, y| ((x * 3 + y * 5) % 7) as f32 - 2.0);
+        let table = SummedAreaTable::new(&grid);
+        for (min, max) in [((0, 0), (6, 4)), ((2, 1), (4, 3)), ((5, 4), (5, 4)), ((3, 2), (100, 100))] {
+            let mut expected = 0.0;
+            for y in min.1..=max.1.min(4) {
+                for x in min.0..=max.0.min(6) {
+                    expected += grid[(x, y)];
+                }
+            }
+            assert!((table.sum(min, max) - expected).abs() < 1e-5);
+        }
+        assert_eq!(table.sum((4, 0), (3, 4)), 0.0);
+        assert_eq!(table.average((2, 2), (2, 2)), grid[(2, 2)]);
+
+        let flat = SummedAreaTable::new(&Grid2::new(6, 4, 2.5));
+        assert!(flat.box_blur(2).as_slice().iter().all(|&v| (v - 2.5).abs() < 1e-6));
+    }
+}
@@ -0,0 +1,312 @@
+use crate::capsule::Capsule;
+use crate::closest_point::ClosestPoint;
+use crate::mesh::Mesh;
+use crate::sweep::stitch;
+use crate::vec4::Vec4;
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+// Surface parametrized over the unit square. `u` goes around the y axis and
+// `v` runs from the bottom to the top, both in [0, 1]. Returns the position
+// (w = 1) and the outward unit normal (w = 0).
+#[allow(dead_code)]
+pub trait ParametricSurface {
+    fn evaluate(&self, u: f32, v: f32) -> (Vec4, Vec4);
+
+    // Samples a (u_segments + 1) x (v_segments + 1) grid and connects it
+    // into counter-clockwise triangles facing outward.
+    fn to_mesh(&self, u_segments: usize, v_segments: usize) -> Mesh {
+        let mut mesh = Mesh::default();
+        let (u_segments, v_segments) = (u_segments.max(1), v_segments.max(1));
+        for i in 0..=u_segments {
+            for j in 0..=v_segments {
+                let (position, normal) = self.evaluate(i as f32 / u_segments as f32, j as f32 / v_segments as f32);
+                mesh.positions.push(position);
+                mesh.normals.push(normal);
+            }
+        }
+        mesh.indices = stitch(u_segments + 1, v_segments + 1, false);
+        mesh
+    }
+}
+
+fn point(x: f32, y: f32, z: f32) -> Vec4 {
+    Vec4::new(x, y, z, 1.0)
+}
+
+fn direction(x: f32, y: f32, z: f32) -> Vec4 {
+    Vec4::new(x, y, z, 0.0)
+}
+
+// Any unit vector perpendicular to the unit vector `v`.
+fn perpendicular(v: &Vec4) -> Vec4 {
+    let axis = if v.x.abs() < 0.9 { direction(1.0, 0.0, 0.0) } else { direction(0.0, 1.0, 0.0) };
+    (axis - *v * v.dot(&axis)).normalize()
+}
+
+// Unit direction at longitude u * 2pi and latitude from -pi/2 to pi/2.
+fn sphere_direction(u: f32, v: f32) -> Vec4 {
+    let (sin_lon, cos_lon) = (u * TAU).sin_cos();
+    let (sin_lat, cos_lat) = ((v - 0.5) * PI).sin_cos();
+    direction(cos_lat * cos_lon, sin_lat, cos_lat * sin_lon)
+}
+
+// Torus around the y axis: `v` winds once around the tube, starting and
+// ending on the outer equator.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Torus {
+    pub major_radius: f32,
+    pub minor_radius: f32
+}
+
+impl ParametricSurface for Torus {
+    fn evaluate(&self, u: f32, v: f32) -> (Vec4, Vec4) {
+        let (sin_u, cos_u) = (u * TAU).sin_cos();
+        let (sin_v, cos_v) = (v * TAU).sin_cos();
+        let ring = self.major_radius + self.minor_radius * cos_v;
+        (
+            point(ring * cos_u, self.minor_radius * sin_v, ring * sin_u),
+            direction(cos_v * cos_u, sin_v, cos_v * sin_u)
+        )
+    }
+}
+
+// The nearest point of the tube's centre circle, then out to the tube. On
+// the y axis every point of the circle is equally near; +x is picked.
+impl ClosestPoint for Torus {
+    fn closest_point(&self, p: &Vec4) -> Vec4 {
+        let radial = (p.x * p.x + p.z * p.z).sqrt();
+        let (cos_u, sin_u) = if radial > 0.0 { (p.x / radial, p.z / radial) } else { (1.0, 0.0) };
+        let center = point(self.major_radius * cos_u, 0.0, self.major_radius * sin_u);
+        let d = point(p.x, p.y, p.z) - center;
+        let length = d.length();
+        if length <= self.minor_radius {
+            point(p.x, p.y, p.z)
+        } else {
+            center + d * (self.minor_radius / length)
+        }
+    }
+}
+
+// Capsule with the local y axis along its segment. `v` is proportional to
+// distance along the profile, so the caps and the side get evenly spaced
+// samples.
+impl ParametricSurface for Capsule {
+    fn evaluate(&self, u: f32, v: f32) -> (Vec4, Vec4) {
+        let axis = self.end - self.start;
+        let half_height = axis.length() * 0.5;
+        let up = if half_height > 0.0 { axis / (2.0 * half_height) } else { direction(0.0, 1.0, 0.0) };
+        let side = perpendicular(&up);
+        let forward = side.cross(&up);
+
+        let (sin_u, cos_u) = (u * TAU).sin_cos();
+        let cap = FRAC_PI_2 * self.radius;
+        let s = v.clamp(0.0, 1.0) * (2.0 * cap + 2.0 * half_height);
+        let (latitude, height) = if s < cap {
+            (s / self.radius - FRAC_PI_2, -half_height)
+        } else if s <= cap + 2.0 * half_height {
+            (0.0, s - cap - half_height)
+        } else {
+            ((s - cap - 2.0 * half_height) / self.radius, half_height)
+        };
+        let (sin_lat, cos_lat) = latitude.sin_cos();
+        let normal = side * (cos_lat * cos_u) + up * sin_lat + forward * (cos_lat * sin_u);
+        let center = (self.start + self.end) * 0.5;
+        (center + up * height + normal * self.radius, normal)
+    }
+}
+
+// Box with `half_extents` whose edges and corners are rounded with
+// `radius`. Points on the sharp box are pulled onto the rounded one by
+// offsetting from the nearest point of the inner box.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct RoundedBox {
+    pub half_extents: Vec4,
+    pub radius: f32
+}
+
+impl ParametricSurface for RoundedBox {
+    fn evaluate(&self, u: f32, v: f32) -> (Vec4, Vec4) {
+        let d = sphere_direction(u, v);
+        let h = self.half_extents;
+        let mut scale = f32::INFINITY;
+        for axis in 0..3 {
+            if d[axis] != 0.0 {
+                scale = scale.min(h[axis] / d[axis].abs());
+            }
+        }
+        let mut inner = d * scale;
+        for axis in 0..3 {
+            let limit = (h[axis] - self.radius).max(0.0);
+            inner[axis] = inner[axis].clamp(-limit, limit);
+        }
+        let offset = d * scale - inner;
+        let normal = if offset.length() > 0.0 { offset.normalize() } else { d };
+        (point(inner.x, inner.y, inner.z) + normal * self.radius, normal)
+    }
+}
+
+// The solid is the inner box grown by `radius`, so the nearest point of
+// the inner box is offset towards the query by up to `radius`.
+impl ClosestPoint for RoundedBox {
+    fn closest_point(&self, p: &Vec4) -> Vec4 {
+        let mut inner = point(p.x, p.y, p.z);
+        for axis in 0..3 {
+            let limit = (self.half_extents[axis] - self.radius).max(0.0);
+            inner[axis] = inner[axis].clamp(-limit, limit);
+        }
+        let d = point(p.x, p.y, p.z) - inner;
+        let length = d.length();
+        if length <= self.radius {
+            point(p.x, p.y, p.z)
+        } else {
+            inner + d * (self.radius / length)
+        }
+    }
+}
+
+// Superellipsoid with semi-axes `radii` (x, y, z). `north_south` shapes
+// the vertical profile and `east_west` the horizontal cross-sections:
+// 1 gives an ellipsoid, values towards 0 get boxier and 2 gives an
+// octahedron-like shape.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Superellipsoid {
+    pub radii: Vec4,
+    pub north_south: f32,
+    pub east_west: f32
+}
+
+fn signed_pow(value: f32, exponent: f32) -> f32 {
+    value.signum() * value.abs().powf(exponent)
+}
+
+impl ParametricSurface for Superellipsoid {
+    fn evaluate(&self, u: f32, v: f32) -> (Vec4, Vec4) {
+        let (sin_lon, cos_lon) = (u * TAU).sin_cos();
+        let (sin_lat, cos_lat) = ((v - 0.5) * PI).sin_cos();
+        // cos(+-pi/2) rounds to a tiny negative, which the signed powers
+        // would turn into a flipped pole.
+        let cos_lat = cos_lat.max(0.0);
+        let (e1, e2) = (self.north_south, self.east_west);
+        let r = self.radii;
+        let position = point(
+            r.x * signed_pow(cos_lat, e1) * signed_pow(cos_lon, e2),
+            r.y * signed_pow(sin_lat, e1),
+            r.z * signed_pow(cos_lat, e1) * signed_pow(sin_lon, e2)
+        );
+        let normal = direction(
+            signed_pow(cos_lat, 2.0 - e1) * signed_pow(cos_lon, 2.0 - e2) / r.x,
+            signed_pow(sin_lat, 2.0 - e1) / r.y,
+            signed_pow(cos_lat, 2.0 - e1) * signed_pow(sin_lon, 2.0 - e2) / r.z
+        );
+        let length = normal.length();
+        (position, if length > 0.0 { normal / length } else { direction(0.0, sin_lat.signum(), 0.0) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every non-degenerate triangle must face the same way as its normals,
+    // and every normal must be unit length.
+    fn check(surface: &impl ParametricSurface) {
+        let mesh = surface.to_mesh(24, 16);
+        assert!(mesh.normals.iter().all(|n| (n.length() - 1.0).abs() < 1e-4));
+        for t in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| mesh.positions[i as usize]);
+            let face = (b - a).cross(&(c - a));
+            if face.length() > 1e-6 {
+                let normal = mesh.normals[t[0] as usize] + mesh.normals[t[1] as usize] + mesh.normals[t[2] as usize];
+                assert!(face.dot(&normal) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn surfaces_face_outward() {
+        let torus = Torus {
+            major_radius: 2.0,
+            minor_radius: 0.5
+        };
+        check(&torus);
+        check(&Capsule::new(point(0.0, -1.0, 0.0), point(0.0, 1.0, 0.0), 0.5));
+        check(&Capsule::new(point(1.0, 2.0, 3.0), point(-1.0, 0.5, 2.0), 0.25));
+        check(&RoundedBox {
+            half_extents: Vec4::new(1.0, 2.0, 0.5, 0.0),
+            radius: 0.2
+        });
+        check(&Superellipsoid {
+            radii: Vec4::new(1.0, 1.5, 2.0, 0.0),
+            north_south: 0.5,
+            east_west: 1.5
+        });
+
+        let (p, n) = torus.evaluate(0.0, 0.25);
+        assert!((p - point(2.0, 0.5, 0.0)).length() < 1e-5 && (n - direction(0.0, 1.0, 0.0)).length() < 1e-5);
+    }
+
+    // Outside queries land on the surface no farther than the nearest of a
+    // dense sample of it; inside ones are their own closest point.
+    fn check_closest(surface: &(impl ParametricSurface + ClosestPoint), queries: &[Vec4]) {
+        let samples = surface.to_mesh(200, 200).positions;
+        for query in queries {
+            let (closest, distance) = (surface.closest_point(query), surface.distance(query));
+            assert_eq!(closest.w, 1.0);
+            if distance == 0.0 {
+                assert_eq!(closest, *query);
+                continue;
+            }
+            let nearest = samples.iter().map(|s| (*s - *query).length()).fold(f32::INFINITY, f32::min);
+            assert!(distance <= nearest + 1e-4 && distance > nearest - 0.05);
+        }
+    }
+
+    #[test]
+    fn closest_points_match_sampling() {
+        let torus = Torus {
+            major_radius: 2.0,
+            minor_radius: 0.5
+        };
+        let queries = [
+            point(4.0, 1.0, -1.0),
+            point(0.0, 3.0, 0.0),
+            point(0.1, 0.0, 0.2),
+            point(-1.0, -2.0, 2.5),
+            point(2.0, 0.1, 0.0)
+        ];
+        check_closest(&torus, &queries);
+        assert_eq!(torus.closest_point(&point(2.0, 0.1, 0.0)), point(2.0, 0.1, 0.0));
+        assert!((torus.closest_point(&Vec4::new(0.0, 0.0, 5.0, 0.0)) - point(0.0, 0.0, 2.5)).length() < 1e-5);
+
+        let shape = RoundedBox {
+            half_extents: Vec4::new(1.0, 2.0, 0.5, 0.0),
+            radius: 0.2
+        };
+        check_closest(&shape, &queries);
+        assert_eq!(shape.closest_point(&point(0.5, -1.0, 0.2)), point(0.5, -1.0, 0.2));
+        // Beyond a corner the nearest point is on its rounding.
+        let corner = shape.closest_point(&point(3.0, 4.0, 2.5));
+        assert!(((corner - point(0.8, 1.8, 0.3)).length() - 0.2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn rounded_box_stays_inside_its_bounds() {
+        let shape = RoundedBox {
+            half_extents: Vec4::new(1.0, 2.0, 0.5, 0.0),
+            radius: 0.2
+        };
+        for i in 0..=20 {
+            for j in 0..=20 {
+                let (p, _) = shape.evaluate(i as f32 / 20.0, j as f32 / 20.0);
+                assert!(p.x.abs() <= 1.0 + 1e-5 && p.y.abs() <= 2.0 + 1e-5 && p.z.abs() <= 0.5 + 1e-5);
+            }
+        }
+        // Face centres lie on the sharp box.
+        let (p, n) = shape.evaluate(0.0, 0.5);
+        assert!((p - point(1.0, 0.0, 0.0)).length() < 1e-5 && (n - direction(1.0, 0.0, 0.0)).length() < 1e-5);
+    }
+}
@@ -0,0 +1,382 @@
+use crate::dmat::DMat;
+use crate::dvec::DVec;
+use crate::float::Float;
+use crate::instrument::{trace_event, trace_span};
+use crate::mat4::Mat4;
+use crate::matrix::Matrix;
+use crate::vec4::Vec4;
+
+const MAX_JACOBI_SWEEPS: usize = 64;
+
+// How `robust_inverse` produced its result.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InverseMethod {
+    // Well-conditioned: the ordinary inverse.
+    Exact,
+    // Singular or ill-conditioned: the pseudo-inverse, which inverts the
+    // well-determined directions and zeroes the rest.
+    PseudoInverse
+}
+
+#[allow(dead_code)]
+impl<T: Float> Mat4<T> {
+    // One-sided Jacobi SVD: A = U * diag(sigma) * V^T with U and V
+    // orthogonal and the singular values in descending order. Rotations
+    // orthogonalize A's columns in place, so small singular values keep
+    // full relative accuracy. The number of singular values above a
+    // tolerance is the numerical rank; U's columns for zero singular values
+    // are filled in to keep it orthogonal.
+    pub fn svd(&self) -> (Self, Vec4<T>, Self) {
+        let (u_columns, sigma_values, v_columns) = decompose(&self.elements, 4, 4);
+        let (mut u, mut sigma, mut v) = (Self::zeroes(), Vec4::zero(), Self::zeroes());
+        u.elements.copy_from_slice(&u_columns);
+        v.elements.copy_from_slice(&v_columns);
+        for i in 0..4 {
+            sigma[i] = sigma_values[i];
+        }
+        complete_orthonormal(&mut u, &sigma);
+        (u, sigma, v)
+    }
+
+    // Moore-Penrose pseudo-inverse, treating singular values at or below
+    // `tolerance` as zero. A tolerance around 4 * epsilon times the largest
+    // singular value only drops what is numerically zero; larger ones trade
+    // accuracy for stability, as in damped least-squares IK.
+    pub fn pinv(&self, tolerance: T) -> Self {
+        let (u, sigma, v) = self.svd();
+        let mut out = Self::zeroes();
+        for i in 0..4 {
+            if sigma[i] <= tolerance {
+                continue;
+            }
+            let inverse = T::one() / sigma[i];
+            for col in 0..4 {
+                for row in 0..4 {
+                    out.elements[col * 4 + row] += v.elements[i * 4 + row] * inverse * u.elements[i * 4 + col];
+                }
+            }
+        }
+        out
+    }
+
+    // Inverse that never blows up. A matrix whose estimated condition
+    // number keeps the relative error of the inverse under about 0.1% is
+    // inverted directly; anything worse, including singular matrices,
+    // gets the pseudo-inverse with singular values below that threshold
+    // dropped. An affine matrix is judged by its linear 3x3 block, since
+    // the translation doesn't affect the accuracy of the inverse; others by
+    // the equilibrated matrix, so units alone never force the
+    // pseudo-inverse.
+    pub fn robust_inverse(&self) -> (Self, InverseMethod) {
+        let max_condition = T::one() / (T::epsilon() * T::from_f64(1000.0));
+        if let Ok((inverse, condition)) = self.equilibrated_inverse() {
+            let affine = [3, 7, 11].iter().all(|&i| self.elements[i] == T::zero()) && self.elements[15] == T::one();
+            let condition = if affine { linear_norm_1(self) * linear_norm_1(&inverse) } else { condition };
+            if condition < max_condition {
+                return (inverse, InverseMethod::Exact);
+            }
+        }
+        let largest = self.svd().1.x;
+        (self.pinv(largest / max_condition), InverseMethod::PseudoInverse)
+    }
+}
+
+// Maximum absolute column sum of the upper-left 3x3 block.
+fn linear_norm_1<T: Float>(m: &Mat4<T>) -> T {
+    let mut out = T::zero();
+    for col in 0..3 {
+        let sum = (0..3).fold(T::zero(), |sum, row| sum + m.elements[col * 4 + row].abs());
+        if sum > out {
+            out = sum;
+        }
+    }
+    out
+}
+
+// Thin SVD of the column-major `rows` x `cols` matrix `a`, rows >= cols:
+// U (rows x cols, zero columns for zero singular values), the singular
+// values in descending order, and V (cols x cols).
+fn decompose<T: Float>(a: &[T], rows: usize, cols: usize) -> (Vec<T>, Vec<T>, Vec<T>) {
+    let mut a = a.to_vec();
+    let mut v = vec![T::zero(); cols * cols];
+    for i in 0..cols {
+        v[i * cols + i] = T::one();
+    }
+    orthogonalize_columns(&mut a, rows, &mut v, cols);
+
+    let norms: Vec<T> = (0..cols).map(|i| (0..rows).fold(T::zero(), |sum, k| sum + a[i * rows + k] * a[i * rows + k]).sqrt()).collect();
+    let mut order: Vec<usize> = (0..cols).collect();
+    order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap_or(std::cmp::Ordering::Equal));
+    let (mut u, mut sigma, mut sorted_v) = (vec![T::zero(); rows * cols], Vec::with_capacity(cols), vec![T::zero(); cols * cols]);
+    for (col, &i) in order.iter().enumerate() {
+        sigma.push(norms[i]);
+        sorted_v[col * cols..(col + 1) * cols].copy_from_slice(&v[i * cols..(i + 1) * cols]);
+        if norms[i] > T::zero() {
+            for k in 0..rows {
+                u[col * rows + k] = a[i * rows + k] / norms[i];
+            }
+        }
+    }
+    (u, sigma, sorted_v)
+}
+
+// Applies Jacobi rotations to pairs of columns of the column-major
+// `rows` x `cols` matrix `a` until they are mutually orthogonal, applying
+// the same rotations to the `cols` x `cols` matrix `v`. Afterwards
+// a_before * v = a, and the column norms of `a` are the singular values.
+fn orthogonalize_columns<T: Float>(a: &mut [T], rows: usize, v: &mut [T], cols: usize) {
+    let column_dot = |m: &[T], i: usize, j: usize, len: usize| (0..len).fold(T::zero(), |sum, k| sum + m[i * len + k] * m[j * len + k]);
+    let rotate = |m: &mut [T], i: usize, j: usize, len: usize, c: T, s: T| {
+        for k in 0..len {
+            let (x, y) = (m[i * len + k], m[j * len + k]);
+            m[i * len + k] = c * x - s * y;
+            m[j * len + k] = s * x + c * y;
+        }
+    };
+    trace_span!("svd", rows = rows, cols = cols);
+    let mut sweeps = 0;
+    for _ in 0..MAX_JACOBI_SWEEPS {
+        sweeps += 1;
+        let mut rotated = false;
+        for i in 0..cols {
+            for j in (i + 1)..cols {
+                let (alpha, beta, gamma) = (column_dot(a, i, i, rows), column_dot(a, j, j, rows), column_dot(a, i, j, rows));
+                if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (T::from_f64(2.0) * gamma);
+                let sign = if zeta < T::zero() { -T::one() } else { T::one() };
+                let t = sign / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = c * t;
+                rotate(a, i, j, rows, c, s);
+                rotate(v, i, j, cols, c, s);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+    trace_event!("svd finished", sweeps = sweeps);
+}
+
+#[allow(dead_code)]
+impl<T: Float> DMat<T> {
+    // Thin SVD: A = U * diag(sigma) * V^T with k = min(rows, cols)
+    // singular values in descending order, U rows x k and V cols x k.
+    pub(crate) fn svd(&self) -> (Self, DVec<T>, Self) {
+        let (rows, cols) = (self.rows(), self.cols());
+        if rows >= cols {
+            let (u, sigma, v) = decompose(self.as_slice(), rows, cols);
+            (DMat::from_col_slice(rows, cols, &u), DVec::from_slice(&sigma), DMat::from_col_slice(cols, cols, &v))
+        } else {
+            // A^T = U' S V'^T, so A = V' S U'^T.
+            let (u, sigma, v) = decompose(self.transpose().as_slice(), cols, rows);
+            (DMat::from_col_slice(rows, rows, &v), DVec::from_slice(&sigma), DMat::from_col_slice(cols, rows, &u))
+        }
+    }
+
+    // Moore-Penrose pseudo-inverse (cols x rows), treating singular values
+    // at or below `tolerance` as zero. For an overdetermined system,
+    // pinv * b is the least-squares solution; for an underdetermined one,
+    // the solution of minimum norm.
+    pub fn pinv(&self, tolerance: T) -> Self {
+        let (u, sigma, v) = self.svd();
+        let mut out = DMat::zeroes(self.cols(), self.rows());
+        for i in 0..sigma.len() {
+            if sigma[i] <= tolerance {
+                continue;
+            }
+            let inverse = T::one() / sigma[i];
+            for col in 0..self.rows() {
+                for row in 0..self.cols() {
+                    out[(row, col)] += v[(row, i)] * inverse * u[(col, i)];
+                }
+            }
+        }
+        out
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float> DMat<T> {
+    // SVD keeping only the `rank` largest singular values: U is rows x k,
+    // V is cols x k, with k = rank clamped to min(rows, cols).
+    pub fn truncated_svd(&self, rank: usize) -> (Self, DVec<T>, Self) {
+        let (u, sigma, v) = self.svd();
+        let k = rank.min(sigma.len());
+        let keep = |m: &Self| DMat::from_col_slice(m.rows(), k, &m.as_slice()[..m.rows() * k]);
+        (keep(&u), DVec::from_slice(&sigma.as_slice()[..k]), keep(&v))
+    }
+
+    // Closest matrix of at most `rank` in the Frobenius and spectral norms
+    // (Eckart-Young): U_k * diag(sigma_k) * V_k^T.
+    pub fn low_rank_approximation(&self, rank: usize) -> Self {
+        let (u, sigma, v) = self.truncated_svd(rank);
+        let mut scaled = u;
+        for col in 0..sigma.len() {
+            for row in 0..scaled.rows() {
+                scaled[(row, col)] *= sigma[col];
+            }
+        }
+        scaled.mul_mat(&v.transpose())
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Float, const R: usize, const C: usize> Matrix<T, R, C> {
+    // Moore-Penrose pseudo-inverse; see `DMat::pinv`.
+    pub fn pinv(&self, tolerance: T) -> Matrix<T, C, R> {
+        let dynamic = DMat::from_fn(R, C, |row, col| self.columns[col][row]);
+        let inverse = dynamic.pinv(tolerance);
+        let mut out = Matrix::zeroes();
+        for col in 0..R {
+            for row in 0..C {
+                out.columns[col][row] = inverse[(row, col)];
+            }
+        }
+        out
+    }
+}
+
+// Fills the columns of `u` whose singular value is zero with unit vectors
+// orthogonal to all the others, by Gram-Schmidt on the coordinate axes.
+fn complete_orthonormal<T: Float>(u: &mut Mat4<T>, sigma: &Vec4<T>) {
+    for col in 0..4 {
+        if sigma[col] > T::zero() {
+            continue;
+        }
+        let mut best = Vec4::zero();
+        let mut best_length = T::zero();
+        for axis in 0..4 {
+            let mut candidate = Vec4::zero();
+            candidate[axis] = T::one();
+            for other in 0..4 {
+                if other == col || (other > col && sigma[other] == T::zero()) {
+                    continue;
+                }
+                let dot = (0..4).fold(T::zero(), |sum, k| sum + u.elements[other * 4 + k] * candidate[k]);
+                for k in 0..4 {
+                    candidate[k] -= dot * u.elements[other * 4 + k];
+                }
+            }
+            let length = candidate.length();
+            if length > best_length {
+                best = candidate / length;
+                best_length = length;
+            }
+        }
+        for k in 0..4 {
+            u.elements[col * 4 + k] = best[k];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &Mat4<f64>, b: &Mat4<f64>, tolerance: f64) -> bool {
+        (0..16).all(|i| (a.elements[i] - b.elements[i]).abs() < tolerance)
+    }
+
+    #[test]
+    fn svd_reconstructs_and_is_orthogonal() {
+        let mut m = Mat4::<f64>::zeroes();
+        for i in 0..16 {
+            m.elements[i] = ((i * 5 + 2) % 9) as f64 - 3.0;
+        }
+        // Make the last column a combination of the others: rank 3.
+        for row in 0..4 {
+            m.elements[12 + row] = m.elements[row] - 2.0 * m.elements[4 + row];
+        }
+        let (u, sigma, v) = m.svd();
+        let identity = Mat4::<f64>::identity();
+        assert!(close(&(u.transpose() * u), &identity, 1e-12));
+        assert!(close(&(v.transpose() * v), &identity, 1e-12));
+        let mut diagonal = Mat4::zeroes();
+        for i in 0..4 {
+            diagonal.elements[i * 5] = sigma[i];
+        }
+        assert!(close(&(u * diagonal * v.transpose()), &m, 1e-12));
+        assert!(sigma.x >= sigma.y && sigma.y >= sigma.z && sigma.z > 1e-6 && sigma.w < 1e-12);
+    }
+
+    #[test]
+    fn low_rank_approximation() {
+        // Outer product plus a small perturbation: rank one dominates.
+        let rank_one = DMat::from_fn(5, 4, |row, col| (row as f64 + 1.0) * (col as f64 - 1.5));
+        let noisy = rank_one.clone() + DMat::from_fn(5, 4, |row, col| if (row + col) % 3 == 0 { 1e-3 } else { 0.0 });
+        let approximation = noisy.low_rank_approximation(1);
+        assert!((approximation - rank_one).as_slice().iter().all(|e| e.abs() < 2e-3));
+
+        let (u, sigma, v) = noisy.truncated_svd(2);
+        assert_eq!((u.rows(), u.cols(), sigma.len(), v.rows(), v.cols()), (5, 2, 2, 4, 2));
+        assert!(sigma[0] > 1e3 * sigma[1]);
+        assert_eq!(noisy.low_rank_approximation(10).rows(), 5);
+        let full = noisy.low_rank_approximation(4);
+        assert!((full - noisy).as_slice().iter().all(|e| e.abs() < 1e-12));
+    }
+
+    #[test]
+    fn robust_inverse_picks_a_path() {
+        let mut m = Mat4::<f64>::identity().translate(&Vec4::new(1.0, 2.0, 3.0, 0.0));
+        m.rotate(Vec4::new(1.0, 1.0, 0.0, 0.0).normalize(), 0.4);
+        let (inverse, method) = m.robust_inverse();
+        assert_eq!(method, InverseMethod::Exact);
+        assert!(close(&inverse, &m.inverse(), 1e-12));
+
+        // Far translations are well posed however large the 1-norm gets.
+        for distance in [100.0f32, 1000.0] {
+            let m = Mat4::<f32>::identity().translate(&Vec4::new(distance, 0.0, 0.0, 0.0));
+            let (inverse, method) = m.robust_inverse();
+            assert_eq!(method, InverseMethod::Exact);
+            let p = inverse * Vec4::new(distance, 2.0, 3.0, 1.0);
+            assert!(p.x.abs() < distance * f32::EPSILON && [p.y, p.z, p.w] == [2.0, 3.0, 1.0]);
+        }
+        let far = Mat4::<f64>::identity().translate(&Vec4::new(1e7, -1e7, 0.0, 0.0));
+        let (inverse, method) = far.robust_inverse();
+        assert_eq!(method, InverseMethod::Exact);
+        assert!(close(&(far * inverse), &Mat4::identity(), 1e-12));
+        // Not affine: a perspective projection with a distant far plane.
+        let projection = Mat4::<f32>::perspective(1.0, 1.5, 0.01, 1e4);
+        let (inverse, method) = projection.robust_inverse();
+        assert_eq!(method, InverseMethod::Exact);
+        assert_eq!(inverse.elements, projection.inverse().elements);
+
+        // Flattened along z: the pseudo-inverse inverts x, y and w and
+        // maps z to zero.
+        let flat = Mat4::<f64>::identity().scale(Vec4::new(2.0, 4.0, 1e-20, 1.0));
+        let (inverse, method) = flat.robust_inverse();
+        assert_eq!(method, InverseMethod::PseudoInverse);
+        assert!(close(&inverse, &Mat4::identity().scale(Vec4::new(0.5, 0.25, 0.0, 1.0)), 1e-12));
+        assert!(close(&(flat * inverse * flat), &flat, 1e-12));
+    }
+
+    #[test]
+    fn pseudo_inverse_solves_least_squares() {
+        // Fit y = a + b x through four points: overdetermined.
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [1.0, 2.9, 5.1, 7.0];
+        let a = DMat::from_fn(4, 2, |row, col| if col == 0 { 1.0 } else { xs[row] });
+        let fit = a.pinv(1e-12).mul_vec(&DVec::from_slice(&ys));
+        let expected = a.least_squares(&DVec::from_slice(&ys)).unwrap();
+        assert!((fit[0] - expected[0]).abs() < 1e-12 && (fit[1] - expected[1]).abs() < 1e-12);
+
+        // Underdetermined: the minimum-norm solution of x + y = 2.
+        let wide = DMat::from_row_slice(1, 2, &[1.0, 1.0]);
+        let x = wide.pinv(1e-12).mul_vec(&DVec::from_slice(&[2.0]));
+        assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 1.0).abs() < 1e-12);
+
+        let fixed = Matrix::<f64, 3, 2>::from_rows([[1.0, 0.0], [0.0, 2.0], [0.0, 0.0]]);
+        let inverse = fixed.pinv(1e-12);
+        assert!((inverse[(0, 0)] - 1.0).abs() < 1e-12 && (inverse[(1, 1)] - 0.5).abs() < 1e-12 && inverse[(0, 2)] == 0.0);
+
+        let singular = Mat4::<f64>::identity().scale(Vec4::new(3.0, 0.0, 1.0, 1.0));
+        let inverse = singular.pinv(1e-12);
+        assert!(close(&(singular * inverse * singular), &singular, 1e-12));
+        assert!(close(&(inverse * singular * inverse), &inverse, 1e-12));
+    }
+}
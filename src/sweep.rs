@@ -0,0 +1,131 @@
+use crate::curve::frames_along_curve;
+use crate::mesh::Mesh;
+use crate::vec4::Vec4;
+
+// Outward normal of every vertex of a 2D polyline, averaged over its
+// neighbouring edges. For a counter-clockwise closed profile, or an open
+// profile walked with the solid on its left, outward is the right-hand side.
+fn profile_normals(profile: &[(f32, f32)], closed: bool) -> Vec<(f32, f32)> {
+    let n = profile.len();
+    let edge_normal = |a: usize, b: usize| {
+        let (dx, dy) = (profile[b].0 - profile[a].0, profile[b].1 - profile[a].1);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length > 0.0 { (dy / length, -dx / length) } else { (0.0, 0.0) }
+    };
+    (0..n).map(|i| {
+        let previous = if i > 0 { Some(edge_normal(i - 1, i)) } else if closed { Some(edge_normal(n - 1, 0)) } else { None };
+        let next = if i + 1 < n { Some(edge_normal(i, i + 1)) } else if closed { Some(edge_normal(n - 1, 0)) } else { None };
+        let (x, y) = match (previous, next) {
+            (Some(a), Some(b)) => (a.0 + b.0, a.1 + b.1),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => (0.0, 0.0)
+        };
+        let length = (x * x + y * y).sqrt();
+        if length > 0.0 { (x / length, y / length) } else { (0.0, 0.0) }
+    }).collect()
+}
+
+// Connects `rings` rings of `ring_size` vertices each. Ring `i` vertex `j`
+// is at index i * ring_size + j; `closed` also joins the last vertex of each
+// ring back to the first.
+pub(crate) fn stitch(rings: usize, ring_size: usize, closed: bool) -> Vec<u32> {
+    let spans = if closed { ring_size } else { ring_size.saturating_sub(1) };
+    let mut indices = Vec::with_capacity(rings.saturating_sub(1) * spans * 6);
+    for i in 0..rings.saturating_sub(1) {
+        for j in 0..spans {
+            let k = (j + 1) % ring_size;
+            let a = (i * ring_size + j) as u32;
+            let b = ((i + 1) * ring_size + j) as u32;
+            let c = (i * ring_size + k) as u32;
+            let d = ((i + 1) * ring_size + k) as u32;
+            indices.extend_from_slice(&[a, c, b, b, c, d]);
+        }
+    }
+    indices
+}
+
+// Revolves a profile of (radius, height) pairs around the y axis. The
+// profile should run upwards for the surface to face away from the axis.
+// The seam ring is duplicated so every ring has its own vertices.
+#[allow(dead_code)]
+pub fn lathe(profile: &[(f32, f32)], segments: usize) -> Mesh {
+    let normals = profile_normals(profile, false);
+    let mut mesh = Mesh::default();
+    if segments == 0 {
+        return mesh;
+    }
+    for i in 0..=segments {
+        let angle = std::f32::consts::TAU * i as f32 / segments as f32;
+        let (sin, cos) = angle.sin_cos();
+        for (j, &(radius, height)) in profile.iter().enumerate() {
+            let (nr, ny) = normals[j];
+            mesh.positions.push(Vec4::new(radius * cos, height, radius * sin, 1.0));
+            mesh.normals.push(Vec4::new(nr * cos, ny, nr * sin, 0.0));
+        }
+    }
+    mesh.indices = stitch(segments + 1, profile.len(), false);
+    mesh
+}
+
+// Sweeps a closed counter-clockwise cross-section of (x, y) pairs along a
+// path. Profile x and y map to the x and y axes of the parallel transport
+// frames, so the surface doesn't twist. The ends are left open.
+#[allow(dead_code)]
+pub fn extrude(profile: &[(f32, f32)], path: &[Vec4]) -> Mesh {
+    let normals = profile_normals(profile, true);
+    let mut mesh = Mesh::default();
+    for frame in frames_along_curve(path) {
+        let (normal, binormal, origin) = (frame.x_vector(), frame.y_vector(), frame.position());
+        for (j, &(x, y)) in profile.iter().enumerate() {
+            let (nx, ny) = normals[j];
+            mesh.positions.push(origin + normal * x + binormal * y);
+            mesh.normals.push(normal * nx + binormal * ny);
+        }
+    }
+    mesh.indices = stitch(path.len(), profile.len(), true);
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every triangle's winding must agree with the stored vertex normals.
+    fn faces_outward(mesh: &Mesh) -> bool {
+        mesh.indices.chunks_exact(3).all(|t| {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| mesh.positions[i as usize]);
+            let face = (b - a).cross(&(c - a));
+            let normal = mesh.normals[t[0] as usize] + mesh.normals[t[1] as usize] + mesh.normals[t[2] as usize];
+            face.dot(&normal) > 0.0
+        })
+    }
+
+    #[test]
+    fn lathe_cylinder() {
+        let mesh = lathe(&[(1.0, 0.0), (1.0, 1.0), (1.0, 2.0)], 16);
+        assert_eq!(mesh.vertex_count(), 17 * 3);
+        assert_eq!(mesh.triangle_count(), 16 * 2 * 2);
+        for (p, n) in mesh.positions.iter().zip(&mesh.normals) {
+            assert!(((p.x * p.x + p.z * p.z).sqrt() - 1.0).abs() < 1e-5);
+            assert!((n.x - p.x).abs() < 1e-5 && n.y.abs() < 1e-5 && (n.z - p.z).abs() < 1e-5);
+        }
+        assert!(faces_outward(&mesh));
+    }
+
+    #[test]
+    fn extrude_tube_along_bend() {
+        let square = [(-0.1, -0.1), (0.1, -0.1), (0.1, 0.1), (-0.1, 0.1)];
+        let path: Vec<Vec4> = (0..=32).map(|i| {
+            let angle = std::f32::consts::PI * i as f32 / 32.0;
+            Vec4::new(angle.cos() * 2.0, angle.sin() * 2.0, i as f32 * 0.05, 1.0)
+        }).collect();
+        let mesh = extrude(&square, &path);
+        assert_eq!(mesh.vertex_count(), 33 * 4);
+        assert_eq!(mesh.triangle_count(), 32 * 4 * 2);
+        assert!(faces_outward(&mesh));
+        // The cross-section keeps its size around the bend.
+        for ring in mesh.positions.chunks_exact(4) {
+            assert!(((ring[0] - ring[2]).length() - 0.2 * 2f32.sqrt()).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use crate::float::Float;
+
+// Symmetric N x N matrix storing only the upper triangle, row by row, in
+// L = N * (N + 1) / 2 elements. Use it through the SymMat3 and SymMat4
+// aliases, which default to f32 and add conversions to the matching dense
+// types.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct SymMat<T, const N: usize, const L: usize> {
+    pub(crate) elements: [T; L],
+}
+
+#[allow(dead_code)]
+impl<T: Float, const N: usize, const L: usize> SymMat<T, N, L> {
+    // Rejects mismatched dimensions such as SymMat<3, 5> at compile time.
+    const PACKED: () = assert!(L == N * (N + 1) / 2, "SymMat<T, N, L> needs L = N * (N + 1) / 2");
+
+    pub fn zeroes() -> Self {
+        let () = Self::PACKED;
+        Self {
+            elements: [T::zero(); L]
+        }
+    }
+
+    pub fn identity() -> Self {
+        let mut out = Self::zeroes();
+        for i in 0..N {
+            out[(i, i)] = T::one();
+        }
+        out
+    }
+
+    fn packed_index(row: usize, col: usize) -> usize {
+        if row >= N || col >= N {
+            panic!("Index out of bounds");
+        }
+        let (row, col) = if row <= col { (row, col) } else { (col, row) };
+        row * N - row * row.saturating_sub(1) / 2 + (col - row)
+    }
+
+    // Reads the upper triangle of `rows`, given as rows[row][col]; the lower
+    // triangle is ignored.
+    pub fn from_rows(rows: &[[T; N]; N]) -> Self {
+        let mut out = Self::zeroes();
+        for row in 0..N {
+            for col in row..N {
+                out[(row, col)] = rows[row][col];
+            }
+        }
+        out
+    }
+
+    pub fn to_rows(self) -> [[T; N]; N] {
+        std::array::from_fn(|row| std::array::from_fn(|col| self[(row, col)]))
+    }
+
+    pub fn trace(&self) -> T {
+        (0..N).fold(T::zero(), |sum, i| sum + self[(i, i)])
+    }
+
+    pub(crate) fn diagonal_array(&self) -> [T; N] {
+        std::array::from_fn(|i| self[(i, i)])
+    }
+
+    // Lower triangular L with self = L * L^T as rows, or None if the matrix
+    // is not positive definite.
+    pub(crate) fn cholesky_rows(&self) -> Option<[[T; N]; N]> {
+        let mut l = [[T::zero(); N]; N];
+        for j in 0..N {
+            let mut d = self[(j, j)];
+            for k in 0..j {
+                d -= l[j][k] * l[j][k];
+            }
+            if d <= T::zero() {
+                return None;
+            }
+            l[j][j] = d.sqrt();
+            for i in (j + 1)..N {
+                let mut s = self[(i, j)];
+                for k in 0..j {
+                    s -= l[i][k] * l[j][k];
+                }
+                l[i][j] = s / l[j][j];
+            }
+        }
+        Some(l)
+    }
+
+    pub(crate) fn solve_cholesky_array(&self, b: [T; N]) -> Option<[T; N]> {
+        let l = self.cholesky_rows()?;
+        let mut y = [T::zero(); N];
+        for i in 0..N {
+            let mut s = b[i];
+            for k in 0..i {
+                s -= l[i][k] * y[k];
+            }
+            y[i] = s / l[i][i];
+        }
+        let mut x = [T::zero(); N];
+        for i in (0..N).rev() {
+            let mut s = y[i];
+            for k in (i + 1)..N {
+                s -= l[k][i] * x[k];
+            }
+            x[i] = s / l[i][i];
+        }
+        Some(x)
+    }
+
+    // Eigenvalues in descending order and the matching unit eigenvectors as
+    // columns, v[row][col], from the shared Jacobi routine.
+    pub(crate) fn eigen_rows(&self, tolerance: T) -> ([T; N], [[T; N]; N]) {
+        crate::eigen::jacobi(&self.to_rows(), tolerance)
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> Default for SymMat<T, N, L> {
+    fn default() -> Self {
+        Self::zeroes()
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::Index<(usize, usize)> for SymMat<T, N, L> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.elements[Self::packed_index(index.0, index.1)]
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::IndexMut<(usize, usize)> for SymMat<T, N, L> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.elements[Self::packed_index(index.0, index.1)]
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::AddAssign<SymMat<T, N, L>> for SymMat<T, N, L> {
+    fn add_assign(&mut self, rhs: SymMat<T, N, L>) {
+        for i in 0..L {
+            self.elements[i] += rhs.elements[i];
+        }
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::Add<SymMat<T, N, L>> for SymMat<T, N, L> {
+    type Output = Self;
+
+    fn add(self, rhs: SymMat<T, N, L>) -> Self::Output {
+        let mut out = self;
+        out += rhs;
+        out
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::SubAssign<SymMat<T, N, L>> for SymMat<T, N, L> {
+    fn sub_assign(&mut self, rhs: SymMat<T, N, L>) {
+        for i in 0..L {
+            self.elements[i] -= rhs.elements[i];
+        }
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::Sub<SymMat<T, N, L>> for SymMat<T, N, L> {
+    type Output = Self;
+
+    fn sub(self, rhs: SymMat<T, N, L>) -> Self::Output {
+        let mut out = self;
+        out -= rhs;
+        out
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::MulAssign<T> for SymMat<T, N, L> {
+    fn mul_assign(&mut self, rhs: T) {
+        for i in 0..L {
+            self.elements[i] *= rhs;
+        }
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::Mul<T> for SymMat<T, N, L> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut out = self;
+        out *= rhs;
+        out
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::ops::Mul<[T; N]> for SymMat<T, N, L> {
+    type Output = [T; N];
+
+    fn mul(self, rhs: [T; N]) -> Self::Output {
+        std::array::from_fn(|row| (0..N).fold(T::zero(), |sum, col| sum + self[(row, col)] * rhs[col]))
+    }
+}
+
+impl<T: Float, const N: usize, const L: usize> std::fmt::Display for SymMat<T, N, L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in 0..N {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for col in 0..N {
+                if col > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", self[(row, col)])?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_layout_is_row_major_upper_triangle() {
+        let mut m = SymMat::<f32, 4, 10>::zeroes();
+        for (i, value) in m.elements.iter_mut().enumerate() {
+            *value = i as f32;
+        }
+        let upper = [(0, 0), (0, 1), (0, 2), (0, 3), (1, 1), (1, 2), (1, 3), (2, 2), (2, 3), (3, 3)];
+        for (i, &(row, col)) in upper.iter().enumerate() {
+            assert_eq!(m[(row, col)], i as f32);
+            assert_eq!(m[(col, row)], i as f32);
+        }
+        assert_eq!(SymMat::<f64, 2, 3>::identity().to_rows(), [[1.0, 0.0], [0.0, 1.0]]);
+        assert_eq!(format!("{}", SymMat::<f64, 2, 3>::identity() * 2.0), "[2, 0]\n[0, 2]");
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds")]
+    fn index_out_of_bounds() {
+        let _ = SymMat::<f32, 3, 6>::zeroes()[(3, 0)];
+    }
+}
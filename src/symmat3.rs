@@ -0,0 +1,120 @@
+use crate::float::Float;
+use crate::mat4::Mat4;
+use crate::symmat::SymMat;
+
+// Only the upper triangle is stored, row by row:
+// (0,0) (0,1) (0,2) (1,1) (1,2) (2,2)
+pub type SymMat3<T = f32> = SymMat<T, 3, 6>;
+
+#[allow(dead_code)]
+impl<T: Float> SymMat3<T> {
+    // Reads the upper triangle of the upper-left 3x3 of `m`.
+    pub fn from_mat4(m: &Mat4<T>) -> Self {
+        Self::from_rows(&std::array::from_fn(|row| std::array::from_fn(|col| m.elements[col * 4 + row])))
+    }
+
+    // Embedded in the upper-left 3x3 of an identity.
+    pub fn to_mat4(self) -> Mat4<T> {
+        let mut out = Mat4::zeroes();
+        for row in 0..3 {
+            for col in 0..3 {
+                out.elements[col * 4 + row] = self[(row, col)];
+            }
+        }
+        out.elements[15] = T::one();
+        out
+    }
+
+    pub fn diagonal(&self) -> [T; 3] {
+        self.diagonal_array()
+    }
+
+    // Returns the rows of the lower triangular L with self = L * L^T, or None
+    // if the matrix is not positive definite.
+    pub fn cholesky(&self) -> Option<[[T; 3]; 3]> {
+        self.cholesky_rows()
+    }
+
+    // Solves self * x = b through the Cholesky factorization.
+    pub fn solve_cholesky(&self, b: [T; 3]) -> Option<[T; 3]> {
+        self.solve_cholesky_array(b)
+    }
+
+    // Cyclic Jacobi eigendecomposition. Returns the eigenvalues in descending
+    // order and the matching unit eigenvectors as columns, v[row][col].
+    // Iteration stops once the off-diagonal norm falls below `tolerance`.
+    pub fn eigen(&self, tolerance: T) -> ([T; 3], [[T; 3]; 3]) {
+        self.eigen_rows(tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> SymMat3 {
+        let mut m = SymMat3::zeroes();
+        m.elements.copy_from_slice(&[4.0, 1.0, 0.5, 3.0, 0.3, 2.0]);
+        m
+    }
+
+    #[test]
+    fn packing_and_conversions() {
+        let m = sample();
+        assert_eq!(m[(2, 1)], 0.3);
+        assert_eq!(m[(1, 2)], 0.3);
+        let rows = m.to_rows();
+        assert_eq!(rows[0], [4.0, 1.0, 0.5]);
+        assert_eq!(SymMat3::from_rows(&rows).elements, m.elements);
+        let embedded = m.to_mat4();
+        assert_eq!(embedded.elements[15], 1.0);
+        assert_eq!(embedded.elements[4 + 2], 0.3);
+        assert_eq!(SymMat3::from_mat4(&embedded).elements, m.elements);
+        assert_eq!(m.diagonal(), [4.0, 3.0, 2.0]);
+        assert_eq!(m.trace(), 9.0);
+    }
+
+    #[test]
+    fn cholesky_reconstructs() {
+        let m = sample();
+        let l = m.cholesky().unwrap();
+        for row in 0..3 {
+            for col in 0..3 {
+                let product: f32 = (0..3).map(|k| l[row][k] * l[col][k]).sum();
+                assert!((product - m[(row, col)]).abs() < 1e-5);
+            }
+        }
+        let x = [1.0, -2.0, 0.5];
+        let solved = m.solve_cholesky(m * x).unwrap();
+        for i in 0..3 {
+            assert!((solved[i] - x[i]).abs() < 1e-5);
+        }
+        assert!((SymMat3::identity() * -1.0).cholesky().is_none());
+    }
+
+    #[test]
+    fn eigen_reconstructs() {
+        let m = sample();
+        let (values, vectors) = m.eigen(1e-7);
+        assert!(values[0] >= values[1] && values[1] >= values[2]);
+        for col in 0..3 {
+            let v = [vectors[0][col], vectors[1][col], vectors[2][col]];
+            let mv = m * v;
+            for i in 0..3 {
+                assert!((mv[i] - values[col] * v[i]).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn f64_keeps_full_precision() {
+        let mut m = SymMat3::<f64>::zeroes();
+        m.elements.copy_from_slice(&[4.0, 1.0, 0.5, 3.0, 0.3, 2.0]);
+        let x = [1.0, -2.0, 1e-9];
+        let solved = m.solve_cholesky(m * x).unwrap();
+        assert!((0..3).all(|i| (solved[i] - x[i]).abs() < 1e-14));
+        let (values, _) = m.eigen(1e-15);
+        assert!((values.iter().sum::<f64>() - m.trace()).abs() < 1e-13);
+        assert_eq!(SymMat3::from_mat4(&m.to_mat4()).elements, m.elements);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::float::Float;
+use crate::mat4::Mat4;
+use crate::symmat::SymMat;
+use crate::vec4::Vec4;
+
+// Only the upper triangle is stored, row by row:
+// (0,0) (0,1) (0,2) (0,3) (1,1) (1,2) (1,3) (2,2) (2,3) (3,3)
+pub type SymMat4<T = f32> = SymMat<T, 4, 10>;
+
+fn mat4_from_rows<T: Float>(rows: &[[T; 4]; 4]) -> Mat4<T> {
+    let mut out = Mat4::zeroes();
+    for row in 0..4 {
+        for col in 0..4 {
+            out.elements[col * 4 + row] = rows[row][col];
+        }
+    }
+    out
+}
+
+#[allow(dead_code)]
+impl<T: Float> SymMat4<T> {
+    // Reads the upper triangle of `m`, the lower triangle is ignored.
+    pub fn from_mat4(m: &Mat4<T>) -> Self {
+        Self::from_rows(&std::array::from_fn(|row| std::array::from_fn(|col| m.elements[col * 4 + row])))
+    }
+
+    pub fn to_mat4(self) -> Mat4<T> {
+        mat4_from_rows(&self.to_rows())
+    }
+
+    pub fn diagonal(&self) -> Vec4<T> {
+        let [x, y, z, w] = self.diagonal_array();
+        Vec4::new(x, y, z, w)
+    }
+
+    // Returns the lower triangular L with self = L * L^T, or None if the
+    // matrix is not positive definite.
+    pub fn cholesky(&self) -> Option<Mat4<T>> {
+        self.cholesky_rows().map(|l| mat4_from_rows(&l))
+    }
+
+    // Solves self * x = b through the Cholesky factorization.
+    pub fn solve_cholesky(&self, b: Vec4<T>) -> Option<Vec4<T>> {
+        let [x, y, z, w] = self.solve_cholesky_array([b.x, b.y, b.z, b.w])?;
+        Some(Vec4::new(x, y, z, w))
+    }
+
+    // Cyclic Jacobi eigendecomposition. Returns the eigenvalues in descending
+    // order and a matrix whose columns are the matching unit eigenvectors.
+    // Iteration stops once the off-diagonal norm falls below `tolerance`.
+    pub fn eigen(&self, tolerance: T) -> (Vec4<T>, Mat4<T>) {
+        let ([x, y, z, w], vectors) = self.eigen_rows(tolerance);
+        (Vec4::new(x, y, z, w), mat4_from_rows(&vectors))
+    }
+}
+
+impl<T: Float> std::ops::Mul<Vec4<T>> for SymMat4<T> {
+    type Output = Vec4<T>;
+
+    fn mul(self, rhs: Vec4<T>) -> Self::Output {
+        let [x, y, z, w] = self * [rhs.x, rhs.y, rhs.z, rhs.w];
+        Vec4::new(x, y, z, w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> SymMat4 {
+        let mut m = SymMat4::zeroes();
+        let values = [4.0, 1.0, 0.5, 0.2, 3.0, 0.3, 0.1, 2.0, 0.4, 1.5];
+        m.elements.copy_from_slice(&values);
+        m
+    }
+
+    #[test]
+    fn cholesky_reconstructs() {
+        let m = sample();
+        let l = m.cholesky().unwrap();
+        let product = l * l.transpose();
+        for row in 0..4 {
+            for col in 0..4 {
+                assert!((product.elements[col * 4 + row] - m[(row, col)]).abs() < 1e-5);
+            }
+        }
+        let x = Vec4::new(1.0, -2.0, 0.5, 3.0);
+        let solved = m.solve_cholesky(m * x).unwrap();
+        for i in 0..4 {
+            assert!((solved[i] - x[i]).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn eigen_reconstructs() {
+        let m = sample();
+        let (values, vectors) = m.eigen(1e-7);
+        assert!(values[0] >= values[1] && values[1] >= values[2] && values[2] >= values[3]);
+        for col in 0..4 {
+            let v = Vec4::new(
+                vectors.elements[col * 4],
+                vectors.elements[col * 4 + 1],
+                vectors.elements[col * 4 + 2],
+                vectors.elements[col * 4 + 3]
+            );
+            let mv = m * v;
+            for i in 0..4 {
+                assert!((mv[i] - values[col] * v[i]).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn not_positive_definite() {
+        let m = SymMat4::identity() * -1.0;
+        assert!(m.cholesky().is_none());
+    }
+}
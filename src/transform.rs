@@ -0,0 +1,331 @@
+// Rigid and affine transforms, rotation representations and the helpers
+// converting between them.
+pub use crate::affine3::Affine3;
+pub use crate::bivector3::Bivector3;
+pub use crate::euler::{EulerAngles, EulerOrder};
+pub use crate::project::{project, project_zo, unproject, unproject_zo};
+pub use crate::rotor3::Rotor3;
+use crate::mat4::Mat4;
+use crate::vec4::Vec4;
+
+// Translation, rotation and scale applied as T * R * S. `rotation` must be a
+// pure rotation matrix with no translation; the w components of
+// `translation` and `scale` are ignored.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    pub translation: Vec4,
+    pub rotation: Mat4,
+    pub scale: Vec4
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[allow(dead_code)]
+impl Transform {
+    pub fn new(translation: Vec4, rotation: Mat4, scale: Vec4) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self {
+            translation: Vec4::zero(),
+            rotation: Mat4::identity(),
+            scale: Vec4::one()
+        }
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        let mut out = self.rotation;
+        for col in 0..3 {
+            for row in 0..3 {
+                out.elements[col * 4 + row] *= self.scale[col];
+            }
+            out.elements[col * 4 + 3] = 0.0;
+        }
+        out.elements[12] = self.translation.x;
+        out.elements[13] = self.translation.y;
+        out.elements[14] = self.translation.z;
+        out.elements[15] = 1.0;
+        out
+    }
+
+    // Splits an affine matrix into TRS; see `Mat4::decompose`.
+    pub fn from_mat4(m: &Mat4) -> Self {
+        let (translation, rotation, scale) = m.decompose();
+        Self {
+            translation,
+            rotation,
+            scale
+        }
+    }
+
+    pub fn transform_point(&self, point: &Vec4) -> Vec4 {
+        let scaled = Vec4::new(point.x * self.scale.x, point.y * self.scale.y, point.z * self.scale.z, 0.0);
+        let mut out = self.rotation * scaled + self.translation;
+        out.w = 1.0;
+        out
+    }
+
+    pub fn transform_vector(&self, vector: &Vec4) -> Vec4 {
+        let scaled = Vec4::new(vector.x * self.scale.x, vector.y * self.scale.y, vector.z * self.scale.z, 0.0);
+        self.rotation * scaled
+    }
+
+    // Exact when `self` has uniform scale; otherwise the shear that the
+    // matrix product would produce cannot be represented and is dropped.
+    pub fn compose(&self, other: &Transform) -> Self {
+        let mut translation = self.transform_point(&other.translation);
+        translation.w = 0.0;
+        Self {
+            translation,
+            rotation: self.rotation * other.rotation,
+            scale: Vec4::new(
+                self.scale.x * other.scale.x,
+                self.scale.y * other.scale.y,
+                self.scale.z * other.scale.z,
+                1.0
+            )
+        }
+    }
+
+    // Exact for uniform scale, see `compose`.
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.transpose();
+        let scale = Vec4::new(1.0 / self.scale.x, 1.0 / self.scale.y, 1.0 / self.scale.z, 1.0);
+        let t = rotation * Vec4::new(self.translation.x, self.translation.y, self.translation.z, 0.0);
+        Self {
+            translation: Vec4::new(-t.x * scale.x, -t.y * scale.y, -t.z * scale.z, 0.0),
+            rotation,
+            scale
+        }
+    }
+
+    // Lerps translation and scale and slerps the rotation.
+    pub fn interpolate(&self, other: &Transform, t: f32) -> Self {
+        let from = rotation_to_quat(&self.rotation);
+        let to = rotation_to_quat(&other.rotation);
+        Self {
+            translation: self.translation + (other.translation - self.translation) * t,
+            rotation: quat_to_rotation(&slerp(from, to, t)),
+            scale: self.scale + (other.scale - self.scale) * t
+        }
+    }
+}
+
+impl std::ops::Mul<Transform> for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+#[allow(dead_code)]
+impl Mat4 {
+    // Splits an affine matrix into translation (w = 0), rotation and
+    // per-axis scale (w = 1), so that T * R * S rebuilds it:
+    // - scale is the length of each basis column;
+    // - a mirroring matrix gets a negative x scale, keeping R a proper
+    //   rotation;
+    // - shear can't be represented: R is the basis orthonormalized in
+    //   x, y, z order, so x keeps its direction and T * R * S differs from
+    //   a sheared input;
+    // - a zero-length column gets a zero scale and R fills in an axis
+    //   perpendicular to the others.
+    // The bottom row is assumed to be (0, 0, 0, 1).
+    pub fn decompose(&self) -> (Vec4, Mat4, Vec4) {
+        let e = &self.elements;
+        let mut columns = [0, 1, 2].map(|col| Vec4::new(e[col * 4], e[col * 4 + 1], e[col * 4 + 2], 0.0));
+        let mut scale = Vec4::new(columns[0].length(), columns[1].length(), columns[2].length(), 1.0);
+        let det = columns[0].dot(&columns[1].cross(&columns[2]));
+        if det < 0.0 {
+            scale.x = -scale.x;
+            columns[0] = -columns[0];
+        }
+
+        let mut axes: Vec<Vec4> = Vec::with_capacity(3);
+        let mut filled = None;
+        for col in 0..3 {
+            let reject = |v: Vec4, axes: &[Vec4]| axes.iter().fold(v, |v, a| v - *a * a.dot(&v));
+            let residual = reject(columns[col], &axes);
+            let length = residual.length();
+            if scale[col] != 0.0 && length > 1e-6 * scale[col].abs() {
+                axes.push(residual / length);
+            } else {
+                let candidates = [0, 1, 2].map(|axis| {
+                    let mut unit = Vec4::zero();
+                    unit[axis] = 1.0;
+                    reject(unit, &axes)
+                });
+                let best = candidates.iter().fold(Vec4::zero(), |best, c| if c.length() > best.length() { *c } else { best });
+                axes.push(best.normalize());
+                filled = Some(col);
+            }
+        }
+        if let Some(col) = filled {
+            if axes[0].dot(&axes[1].cross(&axes[2])) < 0.0 {
+                axes[col] = -axes[col];
+            }
+        }
+
+        let mut rotation = Mat4::identity();
+        for (col, axis) in axes.iter().enumerate() {
+            for row in 0..3 {
+                rotation.elements[col * 4 + row] = axis[row];
+            }
+        }
+        (Vec4::new(e[12], e[13], e[14], 0.0), rotation, scale)
+    }
+
+    // Decomposes both affine matrices (see `decompose`), slerps the
+    // rotations and lerps translation and scale, so a blend between two
+    // rotated poses stays rigid instead of collapsing like `lerp`. Shear
+    // in either input is dropped.
+    pub fn interpolate(&self, other: &Mat4, t: f32) -> Mat4 {
+        Transform::from_mat4(self).interpolate(&Transform::from_mat4(other), t).to_mat4()
+    }
+}
+
+// Quaternions below are stored as (x, y, z, w) in a Vec4.
+pub(crate) fn rotation_to_quat(m: &Mat4) -> Vec4 {
+    let at = |row: usize, col: usize| m.elements[col * 4 + row];
+    let trace = at(0, 0) + at(1, 1) + at(2, 2);
+    let q = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        Vec4::new((at(2, 1) - at(1, 2)) / s, (at(0, 2) - at(2, 0)) / s, (at(1, 0) - at(0, 1)) / s, 0.25 * s)
+    } else if at(0, 0) > at(1, 1) && at(0, 0) > at(2, 2) {
+        let s = (1.0 + at(0, 0) - at(1, 1) - at(2, 2)).sqrt() * 2.0;
+        Vec4::new(0.25 * s, (at(0, 1) + at(1, 0)) / s, (at(0, 2) + at(2, 0)) / s, (at(2, 1) - at(1, 2)) / s)
+    } else if at(1, 1) > at(2, 2) {
+        let s = (1.0 + at(1, 1) - at(0, 0) - at(2, 2)).sqrt() * 2.0;
+        Vec4::new((at(0, 1) + at(1, 0)) / s, 0.25 * s, (at(1, 2) + at(2, 1)) / s, (at(0, 2) - at(2, 0)) / s)
+    } else {
+        let s = (1.0 + at(2, 2) - at(0, 0) - at(1, 1)).sqrt() * 2.0;
+        Vec4::new((at(0, 2) + at(2, 0)) / s, (at(1, 2) + at(2, 1)) / s, 0.25 * s, (at(1, 0) - at(0, 1)) / s)
+    };
+    q.normalize()
+}
+
+pub(crate) fn quat_to_rotation(q: &Vec4) -> Mat4 {
+    let (x, y, z, w) = (q.x, q.y, q.z, q.w);
+    let mut out = Mat4::identity();
+    out.elements[0] = 1.0 - 2.0 * (y * y + z * z);
+    out.elements[1] = 2.0 * (x * y + w * z);
+    out.elements[2] = 2.0 * (x * z - w * y);
+    out.elements[4] = 2.0 * (x * y - w * z);
+    out.elements[5] = 1.0 - 2.0 * (x * x + z * z);
+    out.elements[6] = 2.0 * (y * z + w * x);
+    out.elements[8] = 2.0 * (x * z + w * y);
+    out.elements[9] = 2.0 * (y * z - w * x);
+    out.elements[10] = 1.0 - 2.0 * (x * x + y * y);
+    out
+}
+
+pub(crate) fn slerp(from: Vec4, to: Vec4, t: f32) -> Vec4 {
+    let mut to = to;
+    let mut cos_theta = from.dot(&to);
+    if cos_theta < 0.0 {
+        to = -to;
+        cos_theta = -cos_theta;
+    }
+    if cos_theta > 0.9995 {
+        return (from + (to - from) * t).normalize();
+    }
+    let theta = cos_theta.acos();
+    let sin_theta = theta.sin();
+    (from * ((1.0 - t) * theta).sin() + to * (t * theta).sin()) / sin_theta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_mat_eq(a: &Mat4, b: &Mat4) {
+        for i in 0..16 {
+            assert!((a.elements[i] - b.elements[i]).abs() < 1e-5, "{}\n!=\n{}", a, b);
+        }
+    }
+
+    fn sample() -> Transform {
+        let mut rotation = Mat4::identity();
+        rotation.rotate(Vec4::new(1.0, 2.0, 3.0, 0.0).normalize(), 0.7);
+        Transform::new(Vec4::new(1.0, -2.0, 3.0, 0.0), rotation, Vec4::new(2.0, 0.5, 1.5, 1.0))
+    }
+
+    #[test]
+    fn mat4_round_trip() {
+        let t = sample();
+        let back = Transform::from_mat4(&t.to_mat4());
+        assert_mat_eq(&back.to_mat4(), &t.to_mat4());
+        assert_mat_eq(&back.rotation, &t.rotation);
+    }
+
+    #[test]
+    fn decompose_mirror_shear_and_flat() {
+        let t = sample();
+        let mut mirrored = t;
+        mirrored.scale = Vec4::new(2.0, -0.5, 1.5, 1.0);
+        let (translation, rotation, scale) = mirrored.to_mat4().decompose();
+        assert!((translation - Vec4::new(1.0, -2.0, 3.0, 0.0)).length() < 1e-6);
+        assert!(scale.x < 0.0 && (rotation.determinant() - 1.0).abs() < 1e-5);
+        assert_mat_eq(&Transform::new(translation, rotation, scale).to_mat4(), &mirrored.to_mat4());
+
+        // Shear: x keeps its direction and the rotation stays orthonormal.
+        let sheared = Mat4::from_shear(0.5, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let (_, rotation, scale) = sheared.decompose();
+        assert_mat_eq(&rotation, &Mat4::identity());
+        assert!((scale.y - 1.25f32.sqrt()).abs() < 1e-6);
+
+        // Flattened along z: zero scale, rotation still proper.
+        let mut flat = t;
+        flat.scale = Vec4::new(2.0, 0.5, 0.0, 1.0);
+        let (_, rotation, scale) = flat.to_mat4().decompose();
+        assert_eq!(scale.z, 0.0);
+        assert_mat_eq(&rotation, &t.rotation);
+    }
+
+    #[test]
+    fn compose_and_inverse_uniform_scale() {
+        let mut a = sample();
+        a.scale = Vec4::new(2.0, 2.0, 2.0, 1.0);
+        let b = sample();
+        assert_mat_eq(&(a * b).to_mat4(), &(a.to_mat4() * b.to_mat4()));
+        assert_mat_eq(&(a * a.inverse()).to_mat4(), &Mat4::identity());
+    }
+
+    #[test]
+    fn interpolate_endpoints() {
+        let a = Transform::identity();
+        let b = sample();
+        assert_mat_eq(&a.interpolate(&b, 0.0).to_mat4(), &a.to_mat4());
+        assert_mat_eq(&a.interpolate(&b, 1.0).to_mat4(), &b.to_mat4());
+    }
+
+    #[test]
+    fn matrix_interpolation_stays_rigid() {
+        let a = Mat4::identity();
+        let b = Mat4::from_translation(&Vec4::new(2.0, 0.0, 0.0, 0.0)) * Mat4::from_axis_angle(Vec4::new(0.0, 0.0, 1.0, 0.0), std::f32::consts::PI * 0.9);
+        let raw = a.lerp(&b, 0.5);
+        let blended = a.interpolate(&b, 0.5);
+        assert_mat_eq(&a.interpolate(&b, 1.0), &b);
+        assert!((raw.elements[0] - (1.0 + b.elements[0]) * 0.5).abs() < 1e-6 && raw.elements[12] == 1.0);
+        // Lerping nearly opposite rotations nearly collapses the basis; the
+        // decomposed blend keeps unit scale and rotates halfway.
+        assert!(raw.determinant() < 0.05);
+        assert!((blended.determinant() - 1.0).abs() < 1e-5);
+        let x = blended * Vec4::new(1.0, 0.0, 0.0, 0.0);
+        let expected = Mat4::from_axis_angle(Vec4::new(0.0, 0.0, 1.0, 0.0), std::f32::consts::PI * 0.45) * Vec4::new(1.0, 0.0, 0.0, 0.0);
+        assert!((x - expected).length() < 1e-5);
+        assert!((blended.elements[12] - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::aabb::Aabb;
+use crate::closest_point::{point, ClosestPoint};
+use crate::vec4::Vec4;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct Triangle {
+    pub a: Vec4,
+    pub b: Vec4,
+    pub c: Vec4
+}
+
+#[allow(dead_code)]
+impl Triangle {
+    pub fn new(a: Vec4, b: Vec4, c: Vec4) -> Self {
+        Self {
+            a: point(&a),
+            b: point(&b),
+            c: point(&c)
+        }
+    }
+
+    // Unnormalized normal, counter-clockwise winding facing the viewer.
+    pub fn normal(&self) -> Vec4 {
+        (self.b - self.a).cross(&(self.c - self.a))
+    }
+
+    pub fn area(&self) -> f32 {
+        self.normal().length() * 0.5
+    }
+
+    // Two-sided Moller-Trumbore intersection. Returns t with the hit at
+    // origin + d * t.
+    pub fn ray_cast(&self, origin: &Vec4, d: &Vec4) -> Option<f32> {
+        let d = Vec4::new(d.x, d.y, d.z, 0.0);
+        let e1 = self.b - self.a;
+        let e2 = self.c - self.a;
+        let p = d.cross(&e2);
+        let det = e1.dot(&p);
+        if det.abs() <= f32::EPSILON * e1.length() * e2.length() * d.length() {
+            return None;
+        }
+        let inv = 1.0 / det;
+        let s = point(origin) - self.a;
+        let u = s.dot(&p) * inv;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(&e1);
+        let v = d.dot(&q) * inv;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = e2.dot(&q) * inv;
+        if t >= 0.0 { Some(t) } else { None }
+    }
+
+    pub fn support(&self, d: &Vec4) -> Vec4 {
+        let d = Vec4::new(d.x, d.y, d.z, 0.0);
+        let mut best = self.a;
+        for v in [self.b, self.c] {
+            if v.dot(&d) > best.dot(&d) {
+                best = v;
+            }
+        }
+        best
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        Aabb::from_points(&[self.a, self.b, self.c]).unwrap()
+    }
+}
+
+// Voronoi region walk from Ericson, Real-Time Collision Detection 5.1.5.
+impl ClosestPoint for Triangle {
+    fn closest_point(&self, p: &Vec4) -> Vec4 {
+        let p = point(p);
+        let (a, b, c) = (self.a, self.b, self.c);
+        let ab = b - a;
+        let ac = c - a;
+        let ap = p - a;
+        let d1 = ab.dot(&ap);
+        let d2 = ac.dot(&ap);
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        let bp = p - b;
+        let d3 = ab.dot(&bp);
+        let d4 = ac.dot(&bp);
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = p - c;
+        let d5 = ab.dot(&cp);
+        let d6 = ac.dot(&cp);
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        let denominator = 1.0 / (va + vb + vc);
+        a + ab * (vb * denominator) + ac * (vc * denominator)
+    }
+}
@@ -0,0 +1,99 @@
+use crate::predicates::orient2d;
+
+// Triangulates a simple polygon, given by its vertices in order with either
+// winding, by ear clipping. Returns counter-clockwise triangles as indices
+// into `polygon`. Collinear and repeated vertices are clipped without
+// emitting a triangle, so a polygon without area, including one with fewer
+// than three vertices, gives no triangles. Returns None when a vertex has a
+// NaN or infinite coordinate, or when clipping gets stuck, which only
+// happens for self-intersecting polygons.
+#[allow(dead_code)]
+pub fn triangulate_polygon(polygon: &[(f32, f32)]) -> Option<Vec<[usize; 3]>> {
+    if !polygon.iter().all(|p| p.0.is_finite() && p.1.is_finite()) {
+        return None;
+    }
+    let n = polygon.len();
+    let point = |i: usize| (polygon[i].0 as f64, polygon[i].1 as f64);
+    let twice_area: f64 = (0..n)
+        .map(|i| {
+            let (a, b) = (point(i), point((i + 1) % n));
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum();
+    let mut remaining: Vec<usize> = (0..n).collect();
+    if twice_area < 0.0 {
+        remaining.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(n.saturating_sub(2));
+    let mut i = 0;
+    // Vertices visited since the last clip; a full lap without a clip means
+    // no ear is left.
+    let mut visited = 0;
+    while remaining.len() >= 3 {
+        if visited >= remaining.len() {
+            return None;
+        }
+        let len = remaining.len();
+        let (prev, current, next) = (remaining[(i + len - 1) % len], remaining[i], remaining[(i + 1) % len]);
+        let (a, b, c) = (point(prev), point(current), point(next));
+        let turn = orient2d(a, b, c);
+        let is_ear = turn > 0.0 && !remaining.iter().any(|&k| {
+            let p = point(k);
+            p != a && p != b && p != c && orient2d(a, b, p) >= 0.0 && orient2d(b, c, p) >= 0.0 && orient2d(c, a, p) >= 0.0
+        });
+        if turn == 0.0 || is_ear {
+            if is_ear {
+                triangles.push([prev, current, next]);
+            }
+            remaining.remove(i);
+            visited = 0;
+            if i == remaining.len() {
+                i = 0;
+            }
+        } else {
+            i = (i + 1) % len;
+            visited += 1;
+        }
+    }
+    Some(triangles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn twice_area(polygon: &[(f32, f32)], triangle: &[usize; 3]) -> f64 {
+        let [a, b, c] = triangle.map(|i| (polygon[i].0 as f64, polygon[i].1 as f64));
+        orient2d(a, b, c)
+    }
+
+    #[test]
+    fn concave_polygon_either_winding() {
+        // L shape of area 3 with a collinear vertex on the bottom edge.
+        let l_shape = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)];
+        let mut clockwise = l_shape;
+        clockwise.reverse();
+        for polygon in [l_shape, clockwise] {
+            let triangles = triangulate_polygon(&polygon).unwrap();
+            assert_eq!(triangles.len(), 4);
+            assert!(triangles.iter().all(|t| twice_area(&polygon, t) > 0.0));
+            assert_eq!(triangles.iter().map(|t| twice_area(&polygon, t)).sum::<f64>(), 6.0);
+        }
+    }
+
+    #[test]
+    fn degenerate_polygons() {
+        assert_eq!(triangulate_polygon(&[]), Some(vec![]));
+        assert_eq!(triangulate_polygon(&[(0.0, 0.0), (1.0, 1.0)]), Some(vec![]));
+        assert_eq!(triangulate_polygon(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]), Some(vec![]));
+        assert_eq!(triangulate_polygon(&[(1.0, 1.0); 5]), Some(vec![]));
+        assert_eq!(triangulate_polygon(&[(0.0, 0.0), (1.0, 0.0), (f32::NAN, 1.0)]), None);
+        assert_eq!(triangulate_polygon(&[(0.0, 0.0), (f32::INFINITY, 0.0), (0.0, 1.0)]), None);
+        // Repeated vertices around a square.
+        let square = [(0.0, 0.0), (0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (1.0, 1.0), (0.0, 1.0)];
+        assert_eq!(triangulate_polygon(&square).unwrap().len(), 2);
+        // A bow tie intersects itself; it must terminate either way.
+        let _ = triangulate_polygon(&[(0.0, 0.0), (1.0, 1.0), (1.0, 0.0), (0.0, 1.0)]);
+    }
+}
@@ -0,0 +1,344 @@
+use crate::bvec2::BVec2;
+use crate::ivec2::IVec2;
+use crate::vec4::Vec4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UVec2 {
+    pub x: u32,
+    pub y: u32
+}
+
+#[allow(dead_code)]
+impl UVec2 {
+    pub fn new(x: u32, y: u32) -> Self {
+        Self {
+            x,
+            y
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: 0,
+            y: 0
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            x: 1,
+            y: 1
+        }
+    }
+
+    pub fn splat(value: u32) -> Self {
+        Self {
+            x: value,
+            y: value
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> u32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y)
+        }
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y)
+        }
+    }
+
+    pub fn cmpeq(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x == other.x,
+            y: self.y == other.y
+        }
+    }
+
+    pub fn cmpne(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x != other.x,
+            y: self.y != other.y
+        }
+    }
+
+    pub fn cmplt(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x < other.x,
+            y: self.y < other.y
+        }
+    }
+
+    pub fn cmple(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x <= other.x,
+            y: self.y <= other.y
+        }
+    }
+
+    pub fn cmpgt(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x > other.x,
+            y: self.y > other.y
+        }
+    }
+
+    pub fn cmpge(&self, other: &Self) -> BVec2 {
+        BVec2 {
+            x: self.x >= other.x,
+            y: self.y >= other.y
+        }
+    }
+
+    // Picks each lane from `a` where `mask` is set and from `b` otherwise.
+    pub fn select(mask: &BVec2, a: &Self, b: &Self) -> Self {
+        Self {
+            x: if mask.x { a.x } else { b.x },
+            y: if mask.y { a.y } else { b.y }
+        }
+    }
+
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_add(other.x),
+            y: self.y.wrapping_add(other.y)
+        }
+    }
+
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_sub(other.x),
+            y: self.y.wrapping_sub(other.y)
+        }
+    }
+
+    pub fn wrapping_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_mul(other.x),
+            y: self.y.wrapping_mul(other.y)
+        }
+    }
+
+    pub fn saturating_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y)
+        }
+    }
+
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y)
+        }
+    }
+
+    pub fn saturating_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_mul(other.x),
+            y: self.y.saturating_mul(other.y)
+        }
+    }
+
+    pub fn as_vec4(&self) -> Vec4 {
+        Vec4::new(self.x as f32, self.y as f32, 0.0, 0.0)
+    }
+
+    // Truncates toward zero. Returns None if a component is not finite or
+    // its truncated value does not fit in a u32.
+    pub fn try_from_vec4(v: &Vec4) -> Option<Self> {
+        let convert = |value: f32| {
+            if value.is_finite() && value > -1.0 && value < 4294967296.0 {
+                Some(value as u32)
+            } else {
+                None
+            }
+        };
+        Some(Self {
+            x: convert(v.x)?,
+            y: convert(v.y)?
+        })
+    }
+
+    pub fn try_from_ivec2(v: &IVec2) -> Option<Self> {
+        Some(Self {
+            x: u32::try_from(v.x).ok()?,
+            y: u32::try_from(v.y).ok()?
+        })
+    }
+
+    pub fn try_as_ivec2(&self) -> Option<IVec2> {
+        Some(IVec2 {
+            x: i32::try_from(self.x).ok()?,
+            y: i32::try_from(self.y).ok()?
+        })
+    }
+}
+
+impl std::ops::Add<UVec2> for UVec2 {
+    type Output = UVec2;
+
+    fn add(self, rhs: UVec2) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y
+        }
+    }
+}
+
+impl std::ops::AddAssign<UVec2> for UVec2 {
+    fn add_assign(&mut self, rhs: UVec2) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl std::ops::Sub<UVec2> for UVec2 {
+    type Output = UVec2;
+
+    fn sub(self, rhs: UVec2) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y
+        }
+    }
+}
+
+impl std::ops::SubAssign<UVec2> for UVec2 {
+    fn sub_assign(&mut self, rhs: UVec2) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl std::ops::Mul<u32> for UVec2 {
+    type Output = UVec2;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs
+        }
+    }
+}
+
+impl std::ops::MulAssign<u32> for UVec2 {
+    fn mul_assign(&mut self, rhs: u32) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl std::ops::Div<u32> for UVec2 {
+    type Output = UVec2;
+
+    fn div(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs
+        }
+    }
+}
+
+impl std::ops::DivAssign<u32> for UVec2 {
+    fn div_assign(&mut self, rhs: u32) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
+impl std::ops::Rem<u32> for UVec2 {
+    type Output = UVec2;
+
+    fn rem(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x % rhs,
+            y: self.y % rhs
+        }
+    }
+}
+
+impl std::ops::RemAssign<u32> for UVec2 {
+    fn rem_assign(&mut self, rhs: u32) {
+        self.x %= rhs;
+        self.y %= rhs;
+    }
+}
+
+impl std::ops::Index<usize> for UVec2 {
+    type Output = u32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for UVec2 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for UVec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl std::fmt::Debug for UVec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "UVec2({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_conversions() {
+        assert_eq!(UVec2::try_from_vec4(&Vec4::new(3.9, 0.0, -7.0, 1e30)), Some(UVec2::new(3, 0)));
+        // Anything above -1 truncates to zero.
+        assert_eq!(UVec2::try_from_vec4(&Vec4::new(-0.5, 1.0, 0.0, 0.0)), Some(UVec2::new(0, 1)));
+        assert_eq!(UVec2::try_from_vec4(&Vec4::new(-1.0, 1.0, 0.0, 0.0)), None);
+        assert_eq!(UVec2::try_from_vec4(&Vec4::new(1.0, f32::NAN, 0.0, 0.0)), None);
+        assert_eq!(UVec2::try_from_vec4(&Vec4::new(f32::INFINITY, 1.0, 0.0, 0.0)), None);
+        assert_eq!(UVec2::try_from_vec4(&Vec4::new(4294967040.0, 0.0, 0.0, 0.0)), Some(UVec2::new(4294967040, 0)));
+        assert_eq!(UVec2::try_from_vec4(&Vec4::new(4294967296.0, 0.0, 0.0, 0.0)), None);
+        assert_eq!(UVec2::new(5, 6).as_vec4(), Vec4::new(5.0, 6.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn signed_conversions() {
+        assert_eq!(UVec2::try_from_ivec2(&IVec2::new(0, i32::MAX)), Some(UVec2::new(0, i32::MAX as u32)));
+        assert_eq!(UVec2::try_from_ivec2(&IVec2::new(4, -1)), None);
+        assert_eq!(UVec2::new(7, i32::MAX as u32).try_as_ivec2(), Some(IVec2::new(7, i32::MAX)));
+        assert_eq!(UVec2::new(7, i32::MAX as u32 + 1).try_as_ivec2(), None);
+    }
+
+    #[test]
+    fn wrapping_and_saturating() {
+        let a = UVec2::new(0, u32::MAX);
+        assert_eq!(a.wrapping_sub(&UVec2::one()), UVec2::new(u32::MAX, u32::MAX - 1));
+        assert_eq!(a.wrapping_add(&UVec2::one()), UVec2::new(1, 0));
+        assert_eq!(a.saturating_sub(&UVec2::one()), UVec2::new(0, u32::MAX - 1));
+        assert_eq!(a.saturating_mul(&UVec2::splat(2)), UVec2::new(0, u32::MAX));
+    }
+}
@@ -0,0 +1,368 @@
+use crate::bvec3::BVec3;
+use crate::ivec3::IVec3;
+use crate::vec4::Vec4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UVec3 {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32
+}
+
+#[allow(dead_code)]
+impl UVec3 {
+    pub fn new(x: u32, y: u32, z: u32) -> Self {
+        Self {
+            x,
+            y,
+            z
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            z: 0
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            x: 1,
+            y: 1,
+            z: 1
+        }
+    }
+
+    pub fn splat(value: u32) -> Self {
+        Self {
+            x: value,
+            y: value,
+            z: value
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> u32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z)
+        }
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z)
+        }
+    }
+
+    pub fn cmpeq(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x == other.x,
+            y: self.y == other.y,
+            z: self.z == other.z
+        }
+    }
+
+    pub fn cmpne(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x != other.x,
+            y: self.y != other.y,
+            z: self.z != other.z
+        }
+    }
+
+    pub fn cmplt(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x < other.x,
+            y: self.y < other.y,
+            z: self.z < other.z
+        }
+    }
+
+    pub fn cmple(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x <= other.x,
+            y: self.y <= other.y,
+            z: self.z <= other.z
+        }
+    }
+
+    pub fn cmpgt(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x > other.x,
+            y: self.y > other.y,
+            z: self.z > other.z
+        }
+    }
+
+    pub fn cmpge(&self, other: &Self) -> BVec3 {
+        BVec3 {
+            x: self.x >= other.x,
+            y: self.y >= other.y,
+            z: self.z >= other.z
+        }
+    }
+
+    // Picks each lane from `a` where `mask` is set and from `b` otherwise.
+    pub fn select(mask: &BVec3, a: &Self, b: &Self) -> Self {
+        Self {
+            x: if mask.x { a.x } else { b.x },
+            y: if mask.y { a.y } else { b.y },
+            z: if mask.z { a.z } else { b.z }
+        }
+    }
+
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_add(other.x),
+            y: self.y.wrapping_add(other.y),
+            z: self.z.wrapping_add(other.z)
+        }
+    }
+
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_sub(other.x),
+            y: self.y.wrapping_sub(other.y),
+            z: self.z.wrapping_sub(other.z)
+        }
+    }
+
+    pub fn wrapping_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_mul(other.x),
+            y: self.y.wrapping_mul(other.y),
+            z: self.z.wrapping_mul(other.z)
+        }
+    }
+
+    pub fn saturating_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+            z: self.z.saturating_add(other.z)
+        }
+    }
+
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y),
+            z: self.z.saturating_sub(other.z)
+        }
+    }
+
+    pub fn saturating_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_mul(other.x),
+            y: self.y.saturating_mul(other.y),
+            z: self.z.saturating_mul(other.z)
+        }
+    }
+
+    pub fn as_vec4(&self) -> Vec4 {
+        Vec4::new(self.x as f32, self.y as f32, self.z as f32, 0.0)
+    }
+
+    // Truncates toward zero. Returns None if a component is not finite or
+    // its truncated value does not fit in a u32.
+    pub fn try_from_vec4(v: &Vec4) -> Option<Self> {
+        let convert = |value: f32| {
+            if value.is_finite() && value > -1.0 && value < 4294967296.0 {
+                Some(value as u32)
+            } else {
+                None
+            }
+        };
+        Some(Self {
+            x: convert(v.x)?,
+            y: convert(v.y)?,
+            z: convert(v.z)?
+        })
+    }
+
+    pub fn try_from_ivec3(v: &IVec3) -> Option<Self> {
+        Some(Self {
+            x: u32::try_from(v.x).ok()?,
+            y: u32::try_from(v.y).ok()?,
+            z: u32::try_from(v.z).ok()?
+        })
+    }
+
+    pub fn try_as_ivec3(&self) -> Option<IVec3> {
+        Some(IVec3 {
+            x: i32::try_from(self.x).ok()?,
+            y: i32::try_from(self.y).ok()?,
+            z: i32::try_from(self.z).ok()?
+        })
+    }
+}
+
+impl std::ops::Add<UVec3> for UVec3 {
+    type Output = UVec3;
+
+    fn add(self, rhs: UVec3) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z
+        }
+    }
+}
+
+impl std::ops::AddAssign<UVec3> for UVec3 {
+    fn add_assign(&mut self, rhs: UVec3) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl std::ops::Sub<UVec3> for UVec3 {
+    type Output = UVec3;
+
+    fn sub(self, rhs: UVec3) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z
+        }
+    }
+}
+
+impl std::ops::SubAssign<UVec3> for UVec3 {
+    fn sub_assign(&mut self, rhs: UVec3) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl std::ops::Mul<u32> for UVec3 {
+    type Output = UVec3;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs
+        }
+    }
+}
+
+impl std::ops::MulAssign<u32> for UVec3 {
+    fn mul_assign(&mut self, rhs: u32) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+
+impl std::ops::Div<u32> for UVec3 {
+    type Output = UVec3;
+
+    fn div(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs
+        }
+    }
+}
+
+impl std::ops::DivAssign<u32> for UVec3 {
+    fn div_assign(&mut self, rhs: u32) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+    }
+}
+
+impl std::ops::Rem<u32> for UVec3 {
+    type Output = UVec3;
+
+    fn rem(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x % rhs,
+            y: self.y % rhs,
+            z: self.z % rhs
+        }
+    }
+}
+
+impl std::ops::RemAssign<u32> for UVec3 {
+    fn rem_assign(&mut self, rhs: u32) {
+        self.x %= rhs;
+        self.y %= rhs;
+        self.z %= rhs;
+    }
+}
+
+impl std::ops::Index<usize> for UVec3 {
+    type Output = u32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for UVec3 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for UVec3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl std::fmt::Debug for UVec3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "UVec3({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_conversions() {
+        assert_eq!(UVec3::try_from_vec4(&Vec4::new(1.5, 2.5, 3.5, -9.0)), Some(UVec3::new(1, 2, 3)));
+        assert_eq!(UVec3::try_from_vec4(&Vec4::new(1.0, 2.0, -3.0, 0.0)), None);
+        assert_eq!(UVec3::try_from_vec4(&Vec4::new(1.0, 2.0, f32::NAN, 0.0)), None);
+        assert_eq!(UVec3::try_from_vec4(&Vec4::new(1.0, f32::NEG_INFINITY, 3.0, 0.0)), None);
+        assert_eq!(UVec3::try_from_vec4(&Vec4::new(5e9, 0.0, 0.0, 0.0)), None);
+        let v = UVec3::new(1, 2, 16_777_216);
+        assert_eq!(UVec3::try_from_vec4(&v.as_vec4()), Some(v));
+    }
+
+    #[test]
+    fn signed_conversions() {
+        assert_eq!(UVec3::try_from_ivec3(&IVec3::new(1, 2, 3)), Some(UVec3::new(1, 2, 3)));
+        assert_eq!(UVec3::try_from_ivec3(&IVec3::new(1, i32::MIN, 3)), None);
+        assert_eq!(UVec3::new(u32::MAX, 0, 0).try_as_ivec3(), None);
+        assert_eq!(UVec3::new(0, 1, 2).try_as_ivec3(), Some(IVec3::new(0, 1, 2)));
+    }
+}
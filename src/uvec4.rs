@@ -0,0 +1,402 @@
+use crate::bvec4::BVec4;
+use crate::ivec4::IVec4;
+use crate::vec4::Vec4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UVec4 {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+    pub w: u32
+}
+
+#[allow(dead_code)]
+impl UVec4 {
+    pub fn new(x: u32, y: u32, z: u32, w: u32) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            w
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            z: 0,
+            w: 0
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            x: 1,
+            y: 1,
+            z: 1,
+            w: 1
+        }
+    }
+
+    pub fn splat(value: u32) -> Self {
+        Self {
+            x: value,
+            y: value,
+            z: value,
+            w: value
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> u32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z),
+            w: self.w.min(other.w)
+        }
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+            w: self.w.max(other.w)
+        }
+    }
+
+    pub fn cmpeq(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x == other.x,
+            y: self.y == other.y,
+            z: self.z == other.z,
+            w: self.w == other.w
+        }
+    }
+
+    pub fn cmpne(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x != other.x,
+            y: self.y != other.y,
+            z: self.z != other.z,
+            w: self.w != other.w
+        }
+    }
+
+    pub fn cmplt(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x < other.x,
+            y: self.y < other.y,
+            z: self.z < other.z,
+            w: self.w < other.w
+        }
+    }
+
+    pub fn cmple(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x <= other.x,
+            y: self.y <= other.y,
+            z: self.z <= other.z,
+            w: self.w <= other.w
+        }
+    }
+
+    pub fn cmpgt(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x > other.x,
+            y: self.y > other.y,
+            z: self.z > other.z,
+            w: self.w > other.w
+        }
+    }
+
+    pub fn cmpge(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x >= other.x,
+            y: self.y >= other.y,
+            z: self.z >= other.z,
+            w: self.w >= other.w
+        }
+    }
+
+    // Picks each lane from `a` where `mask` is set and from `b` otherwise.
+    pub fn select(mask: &BVec4, a: &Self, b: &Self) -> Self {
+        Self {
+            x: if mask.x { a.x } else { b.x },
+            y: if mask.y { a.y } else { b.y },
+            z: if mask.z { a.z } else { b.z },
+            w: if mask.w { a.w } else { b.w }
+        }
+    }
+
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_add(other.x),
+            y: self.y.wrapping_add(other.y),
+            z: self.z.wrapping_add(other.z),
+            w: self.w.wrapping_add(other.w)
+        }
+    }
+
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_sub(other.x),
+            y: self.y.wrapping_sub(other.y),
+            z: self.z.wrapping_sub(other.z),
+            w: self.w.wrapping_sub(other.w)
+        }
+    }
+
+    pub fn wrapping_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_mul(other.x),
+            y: self.y.wrapping_mul(other.y),
+            z: self.z.wrapping_mul(other.z),
+            w: self.w.wrapping_mul(other.w)
+        }
+    }
+
+    pub fn saturating_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+            z: self.z.saturating_add(other.z),
+            w: self.w.saturating_add(other.w)
+        }
+    }
+
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y),
+            z: self.z.saturating_sub(other.z),
+            w: self.w.saturating_sub(other.w)
+        }
+    }
+
+    pub fn saturating_mul(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.saturating_mul(other.x),
+            y: self.y.saturating_mul(other.y),
+            z: self.z.saturating_mul(other.z),
+            w: self.w.saturating_mul(other.w)
+        }
+    }
+
+    pub fn as_vec4(&self) -> Vec4 {
+        Vec4::new(self.x as f32, self.y as f32, self.z as f32, self.w as f32)
+    }
+
+    // Truncates toward zero. Returns None if a component is not finite or
+    // its truncated value does not fit in a u32.
+    pub fn try_from_vec4(v: &Vec4) -> Option<Self> {
+        let convert = |value: f32| {
+            if value.is_finite() && value > -1.0 && value < 4294967296.0 {
+                Some(value as u32)
+            } else {
+                None
+            }
+        };
+        Some(Self {
+            x: convert(v.x)?,
+            y: convert(v.y)?,
+            z: convert(v.z)?,
+            w: convert(v.w)?
+        })
+    }
+
+    pub fn try_from_ivec4(v: &IVec4) -> Option<Self> {
+        Some(Self {
+            x: u32::try_from(v.x).ok()?,
+            y: u32::try_from(v.y).ok()?,
+            z: u32::try_from(v.z).ok()?,
+            w: u32::try_from(v.w).ok()?
+        })
+    }
+
+    pub fn try_as_ivec4(&self) -> Option<IVec4> {
+        Some(IVec4 {
+            x: i32::try_from(self.x).ok()?,
+            y: i32::try_from(self.y).ok()?,
+            z: i32::try_from(self.z).ok()?,
+            w: i32::try_from(self.w).ok()?
+        })
+    }
+}
+
+impl std::ops::Add<UVec4> for UVec4 {
+    type Output = UVec4;
+
+    fn add(self, rhs: UVec4) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+            w: self.w + rhs.w
+        }
+    }
+}
+
+impl std::ops::AddAssign<UVec4> for UVec4 {
+    fn add_assign(&mut self, rhs: UVec4) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+        self.w += rhs.w;
+    }
+}
+
+impl std::ops::Sub<UVec4> for UVec4 {
+    type Output = UVec4;
+
+    fn sub(self, rhs: UVec4) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+            w: self.w - rhs.w
+        }
+    }
+}
+
+impl std::ops::SubAssign<UVec4> for UVec4 {
+    fn sub_assign(&mut self, rhs: UVec4) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+        self.w -= rhs.w;
+    }
+}
+
+impl std::ops::Mul<u32> for UVec4 {
+    type Output = UVec4;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+            w: self.w * rhs
+        }
+    }
+}
+
+impl std::ops::MulAssign<u32> for UVec4 {
+    fn mul_assign(&mut self, rhs: u32) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+        self.w *= rhs;
+    }
+}
+
+impl std::ops::Div<u32> for UVec4 {
+    type Output = UVec4;
+
+    fn div(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+            w: self.w / rhs
+        }
+    }
+}
+
+impl std::ops::DivAssign<u32> for UVec4 {
+    fn div_assign(&mut self, rhs: u32) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+        self.w /= rhs;
+    }
+}
+
+impl std::ops::Rem<u32> for UVec4 {
+    type Output = UVec4;
+
+    fn rem(self, rhs: u32) -> Self::Output {
+        Self {
+            x: self.x % rhs,
+            y: self.y % rhs,
+            z: self.z % rhs,
+            w: self.w % rhs
+        }
+    }
+}
+
+impl std::ops::RemAssign<u32> for UVec4 {
+    fn rem_assign(&mut self, rhs: u32) {
+        self.x %= rhs;
+        self.y %= rhs;
+        self.z %= rhs;
+        self.w %= rhs;
+    }
+}
+
+impl std::ops::Index<usize> for UVec4 {
+    type Output = u32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for UVec4 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Index out of bounds")
+        }
+    }
+}
+
+impl std::fmt::Display for UVec4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+impl std::fmt::Debug for UVec4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "UVec4({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_conversions() {
+        assert_eq!(UVec4::try_from_vec4(&Vec4::new(0.0, 0.99, 100.1, 7.0)), Some(UVec4::new(0, 0, 100, 7)));
+        assert_eq!(UVec4::try_from_vec4(&Vec4::new(0.0, 0.0, 0.0, -1.5)), None);
+        assert_eq!(UVec4::try_from_vec4(&Vec4::new(0.0, 0.0, 0.0, f32::NAN)), None);
+        assert_eq!(UVec4::try_from_vec4(&Vec4::new(0.0, 0.0, 0.0, f32::MAX)), None);
+        assert_eq!(UVec4::new(1, 2, 3, 4).as_vec4(), Vec4::new(1.0, 2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn signed_conversions() {
+        assert_eq!(UVec4::try_from_ivec4(&IVec4::new(0, 1, 2, 3)), Some(UVec4::new(0, 1, 2, 3)));
+        assert_eq!(UVec4::try_from_ivec4(&IVec4::new(0, 1, 2, -3)), None);
+        assert_eq!(UVec4::new(0, 0, 0, 1 << 31).try_as_ivec4(), None);
+        let v = UVec4::new(9, 8, 7, i32::MAX as u32);
+        assert_eq!(v.try_as_ivec4().and_then(|i| UVec4::try_from_ivec4(&i)), Some(v));
+    }
+}
@@ -1,14 +1,20 @@
-#[derive(Clone, Copy)]
-pub struct Vec4 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
-    pub w: f32
+use crate::bvec4::BVec4;
+use crate::float::Float;
+use crate::instrument::count_op;
+
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Vec4<T = f32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T
 }
 
 #[allow(dead_code)]
-impl Vec4 {
-    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+impl<T: Float> Vec4<T> {
+    pub fn new(x: T, y: T, z: T, w: T) -> Self {
         Self {
             x,
             y,
@@ -19,31 +25,32 @@ impl Vec4 {
 
     pub fn zero() -> Self {
         Self {
-            x: 0.0,
-            y: 0.0,
-            z: 0.0,
-            w: 0.0
+            x: T::zero(),
+            y: T::zero(),
+            z: T::zero(),
+            w: T::zero()
         }
     }
 
     pub fn one() -> Self {
         Self {
-            x: 1.0,
-            y: 1.0,
-            z: 1.0,
-            w: 1.0
+            x: T::one(),
+            y: T::one(),
+            z: T::one(),
+            w: T::one()
         }
     }
 
-    pub fn dot(&self, other: &Self) -> f32 {
+    pub fn dot(&self, other: &Self) -> T {
         self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
     }
 
-    pub fn length(&self) -> f32 {
+    pub fn length(&self) -> T {
         self.dot(self).sqrt()
     }
 
     pub fn normalize(&self) -> Self {
+        count_op!(normalizations);
         let length = self.length();
         Self {
             x: self.x / length,
@@ -58,15 +65,132 @@ impl Vec4 {
             x: self.y * other.z - self.z * other.y,
             y: self.z * other.x - self.x * other.z,
             z: self.x * other.y - self.y * other.x,
-            w: 0.0
+            w: T::zero()
+        }
+    }
+
+    // Converts every component to another scalar type through f64.
+    pub fn cast<U: Float>(&self) -> Vec4<U> {
+        Vec4 {
+            x: U::from_f64(self.x.to_f64()),
+            y: U::from_f64(self.y.to_f64()),
+            z: U::from_f64(self.z.to_f64()),
+            w: U::from_f64(self.w.to_f64())
+        }
+    }
+
+    pub fn from_array(array: [T; 4]) -> Self {
+        let [x, y, z, w] = array;
+        Self::new(x, y, z, w)
+    }
+
+    pub fn to_array(self) -> [T; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    // The components in x, y, z, w order, without copying.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: `Vec4` is `repr(C)` with four fields of type T, so it is
+        // laid out as [T; 4] with no padding.
+        unsafe { std::slice::from_raw_parts((self as *const Self).cast::<T>(), 4) }
+    }
+
+    pub fn is_finite(&self) -> bool {
+        [self.x, self.y, self.z, self.w].iter().all(|c| c.to_f64().is_finite())
+    }
+
+    pub fn is_nan(&self) -> bool {
+        [self.x, self.y, self.z, self.w].iter().any(|c| c.to_f64().is_nan())
+    }
+
+    // Treats the vector as a quaternion (x, y, z, w): within `epsilon` per
+    // component of the identity rotation, (0, 0, 0, 1) or its negation.
+    pub fn is_identity_quat(&self, epsilon: T) -> bool {
+        let near = |w: T| self.x.abs() <= epsilon && self.y.abs() <= epsilon && self.z.abs() <= epsilon && (self.w - w).abs() <= epsilon;
+        near(T::one()) || near(-T::one())
+    }
+
+    // Every component within `epsilon` of the other's. Exact comparison is
+    // `==`.
+    pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        (*self - *other).to_array().iter().all(|d| d.abs() <= epsilon)
+    }
+
+    // Every component at most `max_ulps` representable values away; scales
+    // with magnitude where `approx_eq` does not.
+    pub fn ulps_eq(&self, other: &Self, max_ulps: u64) -> bool {
+        self.to_array().iter().zip(other.to_array()).all(|(&a, b)| a.ulps_between(b) <= max_ulps)
+    }
+
+    pub fn cmpeq(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x == other.x,
+            y: self.y == other.y,
+            z: self.z == other.z,
+            w: self.w == other.w
+        }
+    }
+
+    pub fn cmpne(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x != other.x,
+            y: self.y != other.y,
+            z: self.z != other.z,
+            w: self.w != other.w
+        }
+    }
+
+    pub fn cmplt(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x < other.x,
+            y: self.y < other.y,
+            z: self.z < other.z,
+            w: self.w < other.w
+        }
+    }
+
+    pub fn cmple(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x <= other.x,
+            y: self.y <= other.y,
+            z: self.z <= other.z,
+            w: self.w <= other.w
+        }
+    }
+
+    pub fn cmpgt(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x > other.x,
+            y: self.y > other.y,
+            z: self.z > other.z,
+            w: self.w > other.w
+        }
+    }
+
+    pub fn cmpge(&self, other: &Self) -> BVec4 {
+        BVec4 {
+            x: self.x >= other.x,
+            y: self.y >= other.y,
+            z: self.z >= other.z,
+            w: self.w >= other.w
+        }
+    }
+
+    // Picks each lane from `a` where `mask` is set and from `b` otherwise.
+    pub fn select(mask: &BVec4, a: &Self, b: &Self) -> Self {
+        Self {
+            x: if mask.x { a.x } else { b.x },
+            y: if mask.y { a.y } else { b.y },
+            z: if mask.z { a.z } else { b.z },
+            w: if mask.w { a.w } else { b.w }
         }
     }
 }
 
-impl std::ops::Add<Vec4> for Vec4 {
-    type Output = Vec4;
+impl<T: Float> std::ops::Add<Vec4<T>> for Vec4<T> {
+    type Output = Vec4<T>;
 
-    fn add(self, rhs: Vec4) -> Self::Output {
+    fn add(self, rhs: Vec4<T>) -> Self::Output {
         Self {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
@@ -76,8 +200,8 @@ impl std::ops::Add<Vec4> for Vec4 {
     }
 }
 
-impl std::ops::AddAssign<Vec4> for Vec4 {
-    fn add_assign(&mut self, rhs: Vec4) {
+impl<T: Float> std::ops::AddAssign<Vec4<T>> for Vec4<T> {
+    fn add_assign(&mut self, rhs: Vec4<T>) {
         self.x += rhs.x;
         self.y += rhs.y;
         self.z += rhs.z;
@@ -85,10 +209,10 @@ impl std::ops::AddAssign<Vec4> for Vec4 {
     }
 }
 
-impl std::ops::Sub<Vec4> for Vec4 {
-    type Output = Vec4;
+impl<T: Float> std::ops::Sub<Vec4<T>> for Vec4<T> {
+    type Output = Vec4<T>;
 
-    fn sub(self, rhs: Vec4) -> Self::Output {
+    fn sub(self, rhs: Vec4<T>) -> Self::Output {
         Self {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
@@ -98,8 +222,8 @@ impl std::ops::Sub<Vec4> for Vec4 {
     }
 }
 
-impl std::ops::SubAssign<Vec4> for Vec4 {
-    fn sub_assign(&mut self, rhs: Vec4) {
+impl<T: Float> std::ops::SubAssign<Vec4<T>> for Vec4<T> {
+    fn sub_assign(&mut self, rhs: Vec4<T>) {
         self.x -= rhs.x;
         self.y -= rhs.y;
         self.z -= rhs.z;
@@ -107,10 +231,10 @@ impl std::ops::SubAssign<Vec4> for Vec4 {
     }
 }
 
-impl std::ops::Mul<f32> for Vec4 {
-    type Output = Vec4;
+impl<T: Float> std::ops::Mul<T> for Vec4<T> {
+    type Output = Vec4<T>;
 
-    fn mul(self, rhs: f32) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Self {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -120,8 +244,8 @@ impl std::ops::Mul<f32> for Vec4 {
     }
 }
 
-impl std::ops::MulAssign<f32> for Vec4 {
-    fn mul_assign(&mut self, rhs: f32) {
+impl<T: Float> std::ops::MulAssign<T> for Vec4<T> {
+    fn mul_assign(&mut self, rhs: T) {
         self.x *= rhs;
         self.y *= rhs;
         self.z *= rhs;
@@ -129,30 +253,30 @@ impl std::ops::MulAssign<f32> for Vec4 {
     }
 }
 
-impl std::ops::Mul<Vec4> for Vec4 {
-    type Output = Vec4;
+impl<T: Float> std::ops::Mul<Vec4<T>> for Vec4<T> {
+    type Output = Vec4<T>;
 
-    fn mul(self, rhs: Vec4) -> Self::Output {
+    fn mul(self, rhs: Vec4<T>) -> Self::Output {
         // cross product
         Self {
             x: self.y * rhs.z - self.z * rhs.y,
             y: self.z * rhs.x - self.x * rhs.z,
             z: self.x * rhs.y - self.y * rhs.x,
-            w: 0.0
+            w: T::zero()
         }
     }
 }
 
-impl std::ops::MulAssign<Vec4> for Vec4 {
-    fn mul_assign(&mut self, rhs: Vec4) {
+impl<T: Float> std::ops::MulAssign<Vec4<T>> for Vec4<T> {
+    fn mul_assign(&mut self, rhs: Vec4<T>) {
         *self = *self * rhs;
     }
 }
 
-impl std::ops::Div<f32> for Vec4 {
-    type Output = Vec4;
+impl<T: Float> std::ops::Div<T> for Vec4<T> {
+    type Output = Vec4<T>;
 
-    fn div(self, rhs: f32) -> Self::Output {
+    fn div(self, rhs: T) -> Self::Output {
         Self {
             x: self.x / rhs,
             y: self.y / rhs,
@@ -162,8 +286,8 @@ impl std::ops::Div<f32> for Vec4 {
     }
 }
 
-impl std::ops::DivAssign<f32> for Vec4 {
-    fn div_assign(&mut self, rhs: f32) {
+impl<T: Float> std::ops::DivAssign<T> for Vec4<T> {
+    fn div_assign(&mut self, rhs: T) {
         self.x /= rhs;
         self.y /= rhs;
         self.z /= rhs;
@@ -171,8 +295,8 @@ impl std::ops::DivAssign<f32> for Vec4 {
     }
 }
 
-impl std::ops::Neg for Vec4 {
-    type Output = Vec4;
+impl<T: Float> std::ops::Neg for Vec4<T> {
+    type Output = Vec4<T>;
 
     fn neg(self) -> Self::Output {
         Self {
@@ -184,8 +308,8 @@ impl std::ops::Neg for Vec4 {
     }
 }
 
-impl std::ops::Index<usize> for Vec4 {
-    type Output = f32;
+impl<T: Float> std::ops::Index<usize> for Vec4<T> {
+    type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
         match index {
@@ -198,7 +322,7 @@ impl std::ops::Index<usize> for Vec4 {
     }
 }
 
-impl std::ops::IndexMut<usize> for Vec4 {
+impl<T: Float> std::ops::IndexMut<usize> for Vec4<T> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         match index {
             0 => &mut self.x,
@@ -210,13 +334,13 @@ impl std::ops::IndexMut<usize> for Vec4 {
     }
 }
 
-impl std::fmt::Display for Vec4 {
+impl<T: Float> std::fmt::Display for Vec4<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
     }
 }
 
-impl std::fmt::Debug for Vec4 {
+impl<T: Float> std::fmt::Debug for Vec4<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "Vec4({}, {}, {}, {})", self.x, self.y, self.z, self.w)
     }
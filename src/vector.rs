@@ -0,0 +1,266 @@
+use crate::float::Float;
+use crate::instrument::count_op;
+
+#[derive(Clone, Copy)]
+pub struct Vector<T, const N: usize> {
+    pub(crate) elements: [T; N],
+}
+
+#[allow(dead_code)]
+impl<T: Float, const N: usize> Vector<T, N> {
+    pub fn new(elements: [T; N]) -> Self {
+        Self {
+            elements
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            elements: [T::zero(); N]
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            elements: [T::one(); N]
+        }
+    }
+
+    pub fn splat(value: T) -> Self {
+        Self {
+            elements: [value; N]
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    pub fn to_array(self) -> [T; N] {
+        self.elements
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        let mut out = T::zero();
+        for i in 0..N {
+            out += self.elements[i] * other.elements[i];
+        }
+        out
+    }
+
+    pub fn norm_squared(&self) -> T {
+        self.dot(self)
+    }
+
+    pub fn norm(&self) -> T {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        count_op!(normalizations);
+        *self / self.norm()
+    }
+
+    pub fn component_mul(&self, other: &Self) -> Self {
+        let mut out = *self;
+        for i in 0..N {
+            out.elements[i] *= other.elements[i];
+        }
+        out
+    }
+
+    pub fn component_div(&self, other: &Self) -> Self {
+        let mut out = *self;
+        for i in 0..N {
+            out.elements[i] /= other.elements[i];
+        }
+        out
+    }
+
+    pub fn map(&self, f: impl Fn(T) -> T) -> Self {
+        let mut out = *self;
+        for i in 0..N {
+            out.elements[i] = f(out.elements[i]);
+        }
+        out
+    }
+}
+
+impl<T: Float, const N: usize> Default for Vector<T, N> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::Add<Vector<T, N>> for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn add(self, rhs: Vector<T, N>) -> Self::Output {
+        let mut out = self;
+        out += rhs;
+        out
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::AddAssign<Vector<T, N>> for Vector<T, N> {
+    fn add_assign(&mut self, rhs: Vector<T, N>) {
+        for i in 0..N {
+            self.elements[i] += rhs.elements[i];
+        }
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::Sub<Vector<T, N>> for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn sub(self, rhs: Vector<T, N>) -> Self::Output {
+        let mut out = self;
+        out -= rhs;
+        out
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::SubAssign<Vector<T, N>> for Vector<T, N> {
+    fn sub_assign(&mut self, rhs: Vector<T, N>) {
+        for i in 0..N {
+            self.elements[i] -= rhs.elements[i];
+        }
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::Mul<T> for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut out = self;
+        out *= rhs;
+        out
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::MulAssign<T> for Vector<T, N> {
+    fn mul_assign(&mut self, rhs: T) {
+        for i in 0..N {
+            self.elements[i] *= rhs;
+        }
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::Div<T> for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        let mut out = self;
+        out /= rhs;
+        out
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::DivAssign<T> for Vector<T, N> {
+    fn div_assign(&mut self, rhs: T) {
+        for i in 0..N {
+            self.elements[i] /= rhs;
+        }
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::Neg for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn neg(self) -> Self::Output {
+        let mut out = self;
+        for i in 0..N {
+            out.elements[i] = -out.elements[i];
+        }
+        out
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.elements[index]
+    }
+}
+
+impl<T: Float, const N: usize> std::ops::IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.elements[index]
+    }
+}
+
+impl<T: Float, const N: usize> std::fmt::Display for Vector<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "(")?;
+        for i in 0..N {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", self.elements[i])?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T: Float, const N: usize> std::fmt::Debug for Vector<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Vector{}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let a = Vector::new([1.0f32, -2.0, 3.0]);
+        let b = Vector::new([4.0f32, 0.5, -1.0]);
+        assert_eq!((a + b).to_array(), [5.0, -1.5, 2.0]);
+        assert_eq!((a - b).to_array(), [-3.0, -2.5, 4.0]);
+        assert_eq!((a * 2.0).to_array(), [2.0, -4.0, 6.0]);
+        assert_eq!((a / 2.0).to_array(), [0.5, -1.0, 1.5]);
+        assert_eq!((-a).to_array(), [-1.0, 2.0, -3.0]);
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 4.0;
+        c /= 2.0;
+        assert_eq!(c.to_array(), [8.0, 1.0, -2.0]);
+        c[1] = 7.0;
+        assert_eq!(c[1], 7.0);
+        assert_eq!(format!("{:?}", a), "Vector(1, -2, 3)");
+    }
+
+    #[test]
+    fn products_and_norms() {
+        let a = Vector::new([3.0f64, 4.0, 0.0, 12.0]);
+        let b = Vector::new([1.0f64, 2.0, 5.0, -1.0]);
+        assert_eq!(a.dot(&b), -1.0);
+        assert_eq!(a.norm_squared(), 169.0);
+        assert_eq!(a.norm(), 13.0);
+        assert!((a.normalize().norm() - 1.0).abs() < 1e-15);
+        assert_eq!(a.component_mul(&b).to_array(), [3.0, 8.0, 0.0, -12.0]);
+        assert_eq!(a.component_div(&b).to_array(), [3.0, 2.0, 0.0, -12.0]);
+        assert_eq!(b.map(f64::abs).to_array(), [1.0, 2.0, 5.0, 1.0]);
+        assert_eq!(Vector::<f64, 4>::default().to_array(), [0.0; 4]);
+        assert_eq!(Vector::<f64, 2>::splat(2.5).as_slice(), &[2.5, 2.5]);
+        assert!(Vector::<f32, 0>::zero().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn index_out_of_bounds() {
+        let v = Vector::<f32, 2>::zero();
+        let _ = v[v.len()];
+    }
+}
@@ -0,0 +1,101 @@
+use crate::rect::Rect;
+
+// Occluding segment between two 2D points.
+pub type Occluder = ((f32, f32), (f32, f32));
+
+// Angle offset of the extra rays cast either side of each endpoint, so the
+// sweep sees past corners as well as onto them.
+const CORNER_OFFSET: f32 = 1e-4;
+
+// Region visible from `origin` through the occluders, clipped to `bounds`,
+// as a polygon of points sorted counter-clockwise by angle around `origin`.
+// Drawn as a triangle fan centered on `origin` it covers exactly the lit
+// area. Rays are cast at every occluder endpoint and just either side of
+// it, so this is quadratic in the number of segments; fine for the few
+// hundred walls of a lighting or line-of-sight query.
+#[allow(dead_code)]
+pub fn visibility_polygon(origin: (f32, f32), occluders: &[Occluder], bounds: &Rect) -> Vec<(f32, f32)> {
+    let corners = [(bounds.min_x, bounds.min_y), (bounds.max_x, bounds.min_y), (bounds.max_x, bounds.max_y), (bounds.min_x, bounds.max_y)];
+    let mut walls = occluders.to_vec();
+    walls.extend((0..4).map(|i| (corners[i], corners[(i + 1) % 4])));
+
+    let mut angles = Vec::with_capacity(walls.len() * 6);
+    for &(a, b) in &walls {
+        for p in [a, b] {
+            let angle = (p.1 - origin.1).atan2(p.0 - origin.0);
+            angles.extend([angle - CORNER_OFFSET, angle, angle + CORNER_OFFSET]);
+        }
+    }
+    angles.sort_by(f32::total_cmp);
+
+    let mut polygon: Vec<(f32, f32)> = Vec::with_capacity(angles.len());
+    for angle in angles {
+        let direction = (angle.cos(), angle.sin());
+        let nearest = walls.iter()
+            .filter_map(|wall| ray_hit(origin, direction, wall))
+            .fold(f32::INFINITY, f32::min);
+        if !nearest.is_finite() {
+            continue;
+        }
+        let hit = (origin.0 + direction.0 * nearest, origin.1 + direction.1 * nearest);
+        let duplicate = polygon.last().is_some_and(|last| (last.0 - hit.0).abs() < 1e-5 && (last.1 - hit.1).abs() < 1e-5);
+        if !duplicate {
+            polygon.push(hit);
+        }
+    }
+    polygon
+}
+
+// Distance along the ray to where it crosses `wall`, if it does.
+fn ray_hit(origin: (f32, f32), direction: (f32, f32), wall: &Occluder) -> Option<f32> {
+    let (a, b) = *wall;
+    let edge = (b.0 - a.0, b.1 - a.1);
+    let denominator = direction.0 * edge.1 - direction.1 * edge.0;
+    if denominator.abs() < 1e-12 {
+        return None;
+    }
+    let to_start = (a.0 - origin.0, a.1 - origin.1);
+    let t = (to_start.0 * edge.1 - to_start.1 * edge.0) / denominator;
+    let u = (to_start.0 * direction.1 - to_start.1 * direction.0) / denominator;
+    (t >= 0.0 && (0.0..=1.0).contains(&u)).then_some(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(polygon: &[(f32, f32)]) -> f32 {
+        (0..polygon.len()).map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            a.0 * b.1 - b.0 * a.1
+        }).sum::<f32>() * 0.5
+    }
+
+    fn contains(polygon: &[(f32, f32)], p: (f32, f32)) -> bool {
+        let mut inside = false;
+        for i in 0..polygon.len() {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            if (a.1 > p.1) != (b.1 > p.1) && p.0 < a.0 + (p.1 - a.1) / (b.1 - a.1) * (b.0 - a.0) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    #[test]
+    fn wall_casts_shadow() {
+        let bounds = Rect::new(-10.0, -10.0, 10.0, 10.0);
+        let open = visibility_polygon((0.0, 0.0), &[], &bounds);
+        assert!((area(&open) - 400.0).abs() < 1e-2);
+
+        // The wall hides a trapezoid from x = 2 to the boundary.
+        let wall = ((2.0, -1.0), (2.0, 1.0));
+        let lit = visibility_polygon((0.0, 0.0), &[wall], &bounds);
+        assert!((area(&lit) - 352.0).abs() < 0.1, "{}", area(&lit));
+        assert!(!contains(&lit, (5.0, 0.0)));
+        assert!(!contains(&lit, (9.0, 4.0)));
+        assert!(contains(&lit, (1.0, 0.0)));
+        assert!(contains(&lit, (5.0, 3.0)));
+        assert!(contains(&lit, (-9.0, -9.0)));
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::VecDeque;
+
+use crate::aabb::Aabb;
+use crate::closest_point::ClosestPoint;
+use crate::grid3::Grid3;
+use crate::mesh::Mesh;
+use crate::triangle::Triangle;
+use crate::vec4::Vec4;
+
+// Cubic cells of `cell_size` laid over `bounds`, starting at its minimum
+// corner. The grid covers the whole box, so the last cell on each axis may
+// stick out past the maximum.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub struct VoxelLayout {
+    pub origin: Vec4,
+    pub cell_size: f32,
+    pub dims: [usize; 3]
+}
+
+#[allow(dead_code)]
+impl VoxelLayout {
+    pub fn new(bounds: &Aabb, cell_size: f32) -> Self {
+        let extent = bounds.max - bounds.min;
+        let dims = [0, 1, 2].map(|axis| ((extent[axis] / cell_size).ceil() as usize).max(1));
+        Self {
+            origin: bounds.min,
+            cell_size,
+            dims
+        }
+    }
+
+    pub fn cell_center(&self, x: usize, y: usize, z: usize) -> Vec4 {
+        let h = self.cell_size;
+        Vec4::new(
+            self.origin.x + (x as f32 + 0.5) * h,
+            self.origin.y + (y as f32 + 0.5) * h,
+            self.origin.z + (z as f32 + 0.5) * h,
+            1.0
+        )
+    }
+
+    // Inclusive range of cells touched by `aabb` along each axis, or None
+    // when it misses the grid.
+    pub(crate) fn cell_range(&self, aabb: &Aabb) -> Option<[(usize, usize); 3]> {
+        let mut out = [(0, 0); 3];
+        for axis in 0..3 {
+            let lo = ((aabb.min[axis] - self.origin[axis]) / self.cell_size).floor();
+            let hi = ((aabb.max[axis] - self.origin[axis]) / self.cell_size).floor();
+            if hi < 0.0 || lo >= self.dims[axis] as f32 {
+                return None;
+            }
+            out[axis] = (lo.max(0.0) as usize, (hi as usize).min(self.dims[axis] - 1));
+        }
+        Some(out)
+    }
+
+    fn grid<T: Clone>(&self, fill: T) -> Grid3<T> {
+        Grid3::new(self.dims[0], self.dims[1], self.dims[2], fill)
+    }
+}
+
+// Marks every cell the surface touches, including cells it only grazes, so
+// thin features never fall through the grid. Uses the separating axis test
+// between each triangle and the cells under its bounding box.
+#[allow(dead_code)]
+pub fn voxelize(mesh: &Mesh, layout: &VoxelLayout) -> Grid3<bool> {
+    let mut grid = layout.grid(false);
+    let half = layout.cell_size * 0.5;
+    for triangle in mesh.triangles() {
+        let Some(range) = layout.cell_range(&triangle_aabb(&triangle)) else {
+            continue;
+        };
+        for z in range[2].0..=range[2].1 {
+            for y in range[1].0..=range[1].1 {
+                for x in range[0].0..=range[0].1 {
+                    if !grid[(x, y, z)] && triangle_overlaps_cube(&triangle, &layout.cell_center(x, y, z), half) {
+                        grid[(x, y, z)] = true;
+                    }
+                }
+            }
+        }
+    }
+    grid
+}
+
+// Signed distance to the mesh at each cell centre, negative inside. Exact
+// distances are only computed within `band` of the surface; farther cells
+// are clamped to +-band. Signs come from the winding number near the
+// surface and from a flood fill through the empty cells elsewhere, so the
+// mesh should be closed and `layout` should leave a margin around it.
+#[allow(dead_code)]
+pub fn signed_distance_field(mesh: &Mesh, layout: &VoxelLayout, band: f32) -> Grid3<f32> {
+    let mut distance = layout.grid(f32::INFINITY);
+    for triangle in mesh.triangles() {
+        let mut aabb = triangle_aabb(&triangle);
+        aabb.min -= Vec4::new(band, band, band, 0.0);
+        aabb.max += Vec4::new(band, band, band, 0.0);
+        let Some(range) = layout.cell_range(&aabb) else {
+            continue;
+        };
+        let shape = Triangle::new(triangle[0], triangle[1], triangle[2]);
+        for z in range[2].0..=range[2].1 {
+            for y in range[1].0..=range[1].1 {
+                for x in range[0].0..=range[0].1 {
+                    let d = shape.distance(&layout.cell_center(x, y, z));
+                    let cell = &mut distance[(x, y, z)];
+                    *cell = cell.min(d);
+                }
+            }
+        }
+    }
+
+    let in_band = distance.map(|&d| d <= band);
+    let outside = flood_outside(&in_band);
+    let mut out = layout.grid(band);
+    for index in 0..out.len() {
+        let (x, y, z) = out.coords_of(index);
+        out.cells[index] = if in_band.cells[index] {
+            let d = distance.cells[index];
+            if mesh.contains_point(&layout.cell_center(x, y, z)) { -d } else { d }
+        } else if outside.cells[index] {
+            band
+        } else {
+            -band
+        };
+    }
+    out
+}
+
+// Cells reachable from the grid border without crossing a blocked cell,
+// moving through faces only.
+fn flood_outside(blocked: &Grid3<bool>) -> Grid3<bool> {
+    let (w, h, d) = (blocked.width(), blocked.height(), blocked.depth());
+    let mut reached = Grid3::new(w, h, d, false);
+    let mut queue = VecDeque::new();
+    for index in 0..blocked.len() {
+        let (x, y, z) = blocked.coords_of(index);
+        let border = x == 0 || y == 0 || z == 0 || x + 1 == w || y + 1 == h || z + 1 == d;
+        if border && !blocked.cells[index] {
+            reached.cells[index] = true;
+            queue.push_back((x, y, z));
+        }
+    }
+    while let Some((x, y, z)) = queue.pop_front() {
+        let neighbours = [
+            (x.wrapping_sub(1), y, z), (x + 1, y, z),
+            (x, y.wrapping_sub(1), z), (x, y + 1, z),
+            (x, y, z.wrapping_sub(1)), (x, y, z + 1)
+        ];
+        for (nx, ny, nz) in neighbours {
+            if blocked.get(nx, ny, nz) == Some(&false) && !reached[(nx, ny, nz)] {
+                reached[(nx, ny, nz)] = true;
+                queue.push_back((nx, ny, nz));
+            }
+        }
+    }
+    reached
+}
+
+fn triangle_aabb(triangle: &[Vec4; 3]) -> Aabb {
+    Aabb::new(triangle[0], triangle[0]).union(&Aabb::new(triangle[1], triangle[1])).union(&Aabb::new(triangle[2], triangle[2]))
+}
+
+// Akenine-Moller triangle/box overlap: the three box axes are covered by
+// the bounding box culling, leaving the face normal and the nine edge
+// cross products.
+fn triangle_overlaps_cube(triangle: &[Vec4; 3], center: &Vec4, half: f32) -> bool {
+    let v = triangle.map(|p| Vec4::new(p.x - center.x, p.y - center.y, p.z - center.z, 0.0));
+    let edges = [v[1] - v[0], v[2] - v[1], v[0] - v[2]];
+    let mut axes = vec![edges[0].cross(&edges[1])];
+    for edge in edges {
+        for axis in 0..3 {
+            let mut unit = Vec4::zero();
+            unit[axis] = 1.0;
+            axes.push(unit.cross(&edge));
+        }
+    }
+    axes.iter().all(|axis| {
+        let projections = v.map(|p| p.dot(axis));
+        let min = projections[0].min(projections[1]).min(projections[2]);
+        let max = projections[0].max(projections[1]).max(projections[2]);
+        let radius = half * (axis.x.abs() + axis.y.abs() + axis.z.abs());
+        min <= radius && max >= -radius
+    })
+}
+
+// Both tests build their input from a parametric torus.
+#[cfg(all(test, feature = "curves"))]
+mod tests {
+    use super::*;
+    use crate::surface::ParametricSurface;
+    use crate::surface::Torus;
+
+    #[test]
+    fn voxelized_torus_is_a_ring() {
+        let torus = Torus {
+            major_radius: 2.0,
+            minor_radius: 0.5
+        };
+        let layout = VoxelLayout::new(&Aabb::new(Vec4::new(-3.0, -1.0, -3.0, 0.0), Vec4::new(3.0, 1.0, 3.0, 0.0)), 0.125);
+        let grid = voxelize(&torus.to_mesh(48, 24), &layout);
+        assert_eq!([grid.width(), grid.height(), grid.depth()], [48, 16, 48]);
+        // Cells on the tube surface are set, the hole and the far corner
+        // are not.
+        let at = |p: Vec4| {
+            let [x, y, z] = [0, 1, 2].map(|axis| ((p[axis] - layout.origin[axis]) / layout.cell_size) as usize);
+            grid[(x, y, z)]
+        };
+        assert!(at(Vec4::new(2.5, 0.0, 0.0, 1.0)));
+        assert!(at(Vec4::new(0.0, 0.5, 2.0, 1.0)));
+        assert!(!at(Vec4::new(0.0, 0.0, 0.0, 1.0)));
+        assert!(!at(Vec4::new(2.9, 0.9, 2.9, 1.0)));
+    }
+
+    #[test]
+    fn torus_distance_field() {
+        let torus = Torus {
+            major_radius: 2.0,
+            minor_radius: 0.5
+        };
+        let layout = VoxelLayout::new(&Aabb::new(Vec4::new(-3.0, -1.0, -3.0, 0.0), Vec4::new(3.0, 1.0, 3.0, 0.0)), 0.25);
+        let sdf = signed_distance_field(&torus.to_mesh(48, 24), &layout, 0.4);
+        for index in 0..sdf.len() {
+            let (x, y, z) = sdf.coords_of(index);
+            let p = layout.cell_center(x, y, z);
+            let ring = (p.x * p.x + p.z * p.z).sqrt() - 2.0;
+            let exact = (ring * ring + p.y * p.y).sqrt() - 0.5;
+            assert!((sdf.cells[index] - exact.clamp(-0.4, 0.4)).abs() < 0.02, "{} {}", sdf.cells[index], exact);
+        }
+    }
+}